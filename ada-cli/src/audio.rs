@@ -1,5 +1,6 @@
 //! Audio utilities for preprocessing voice recordings.
 
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -8,19 +9,80 @@ use anyhow::{bail, Context, Result};
 /// Target sample rate for whisper-cli input.
 pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
 
+/// Target channel count for whisper-cli input.
+pub const WHISPER_CHANNELS: u32 = 1;
+
+/// Audio stream properties reported by ffprobe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioProbe {
+    /// Sample rate in Hz (0 if ffprobe did not report one).
+    pub sample_rate: u32,
+    /// Channel count (0 if ffprobe did not report one).
+    pub channels: u32,
+}
+
+impl AudioProbe {
+    /// Parse `key=value` lines emitted by
+    /// `ffprobe -show_entries stream=sample_rate,channels -of default=noprint_wrappers=1`.
+    pub fn parse(output: &str) -> Self {
+        let mut probe = AudioProbe {
+            sample_rate: 0,
+            channels: 0,
+        };
+
+        for line in output.lines() {
+            let Some((key, value)) = line.trim().split_once('=') else {
+                continue;
+            };
+            let value = value.trim().parse().unwrap_or(0);
+            match key.trim() {
+                "sample_rate" => probe.sample_rate = value,
+                "channels" => probe.channels = value,
+                _ => {}
+            }
+        }
+
+        probe
+    }
+
+    /// Whether the stream is already 16 kHz mono and can be fed to whisper-cli as-is.
+    pub fn is_whisper_ready(&self) -> bool {
+        self.sample_rate == WHISPER_SAMPLE_RATE && self.channels == WHISPER_CHANNELS
+    }
+}
+
+/// Build the ffmpeg conversion arguments (placed between `-i <input>` and
+/// the output path) needed to make `probe` whisper-ready.
+///
+/// Only the conversions that are actually required are emitted, so both the
+/// resample and the down-mix happen in a single ffmpeg invocation.
+pub fn conversion_args(probe: &AudioProbe) -> Vec<String> {
+    let mut args = Vec::new();
+    if probe.sample_rate != WHISPER_SAMPLE_RATE {
+        args.push("-ar".to_string());
+        args.push(WHISPER_SAMPLE_RATE.to_string());
+    }
+    if probe.channels != WHISPER_CHANNELS {
+        args.push("-ac".to_string());
+        args.push(WHISPER_CHANNELS.to_string());
+    }
+    args
+}
+
 /// Ensure a voice file is 16 kHz mono WAV for whisper-cli.
 ///
-/// Returns the original path if already 16 kHz, otherwise resamples into
-/// `temp_dir` and returns the resampled path.
+/// Returns the original path if already a 16 kHz mono WAV, otherwise
+/// converts (e.g. from FLAC), resamples and/or down-mixes into `temp_dir` and
+/// returns the converted path.
 pub fn ensure_16khz(voice_path: &Path, temp_dir: &Path) -> Result<PathBuf> {
     let ffprobe = crate::binary_resolver::resolve(crate::binary_resolver::Tool::Ffprobe)
         .map_err(|_| anyhow::anyhow!("ffprobe not available. Run: ./utils/init_media_tools.sh"))?;
 
-    // Probe the sample rate
+    // Probe the sample rate and channel count
     let probe_output = Command::new(&ffprobe)
         .args(["-v", "error", "-select_streams", "a:0"])
-        .args(["-show_entries", "stream=sample_rate"])
-        .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+        .args(["-show_entries", "stream=sample_rate,channels"])
+        .args(["-of", "default=noprint_wrappers=1"])
         .arg(voice_path)
         .output()
         .with_context(|| "Failed to run ffprobe")?;
@@ -32,14 +94,16 @@ pub fn ensure_16khz(voice_path: &Path, temp_dir: &Path) -> Result<PathBuf> {
         );
     }
 
-    let rate_str = String::from_utf8_lossy(&probe_output.stdout);
-    let sample_rate: u32 = rate_str.trim().parse().unwrap_or(0);
+    let probe = AudioProbe::parse(&String::from_utf8_lossy(&probe_output.stdout));
 
-    if sample_rate == WHISPER_SAMPLE_RATE {
+    let is_wav = voice_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if probe.is_whisper_ready() && is_wav {
         return Ok(voice_path.to_path_buf());
     }
 
-    // Resample to 16 kHz mono WAV
+    // Resample and/or down-mix to 16 kHz mono WAV
     let ffmpeg = crate::binary_resolver::resolve(crate::binary_resolver::Tool::Ffmpeg)
         .map_err(|_| anyhow::anyhow!("ffmpeg not available. Run: ./utils/init_media_tools.sh"))?;
 
@@ -47,7 +111,7 @@ pub fn ensure_16khz(voice_path: &Path, temp_dir: &Path) -> Result<PathBuf> {
     let output = Command::new(&ffmpeg)
         .args(["-y", "-i"])
         .arg(voice_path)
-        .args(["-ar", "16000", "-ac", "1"])
+        .args(conversion_args(&probe))
         .arg(&resampled)
         .output()
         .with_context(|| "Failed to resample audio with ffmpeg")?;
@@ -61,3 +125,126 @@ pub fn ensure_16khz(voice_path: &Path, temp_dir: &Path) -> Result<PathBuf> {
 
     Ok(resampled)
 }
+
+/// EBU R128 loudness normalization filter applied by [`normalize_loudness`].
+pub const LOUDNORM_FILTER: &str = "loudnorm=I=-16:TP=-1.5:LRA=11";
+
+/// Build the ffmpeg arguments that loudness-normalize `input` into `output`.
+///
+/// The output is written as 16 kHz mono so it can be fed straight to
+/// whisper-cli (`loudnorm` otherwise upsamples to 192 kHz).
+pub fn loudnorm_args(input: &Path, output: &Path) -> Vec<OsString> {
+    vec![
+        "-y".into(),
+        "-i".into(),
+        input.as_os_str().to_os_string(),
+        "-af".into(),
+        LOUDNORM_FILTER.into(),
+        "-ar".into(),
+        WHISPER_SAMPLE_RATE.to_string().into(),
+        "-ac".into(),
+        WHISPER_CHANNELS.to_string().into(),
+        output.as_os_str().to_os_string(),
+    ]
+}
+
+/// Apply ffmpeg's `loudnorm` (EBU R128) filter to `input`, writing
+/// `voice_loudnorm.wav` into `temp_dir`.
+///
+/// Returns the normalized file path.
+pub fn normalize_loudness(input: &Path, temp_dir: &Path) -> Result<PathBuf> {
+    let ffmpeg = crate::binary_resolver::resolve(crate::binary_resolver::Tool::Ffmpeg)
+        .map_err(|_| anyhow::anyhow!("ffmpeg not available. Run: ./utils/init_media_tools.sh"))?;
+
+    let normalized = temp_dir.join("voice_loudnorm.wav");
+    let output = Command::new(&ffmpeg)
+        .args(loudnorm_args(input, &normalized))
+        .output()
+        .with_context(|| "Failed to normalize audio with ffmpeg")?;
+
+    if !output.status.success() {
+        bail!(
+            "ffmpeg loudnorm failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_probe_parse__sample_rate_and_channels__then_reads_both() {
+        let probe = AudioProbe::parse("sample_rate=48000\nchannels=2\n");
+        assert_eq!(
+            probe,
+            AudioProbe {
+                sample_rate: 48_000,
+                channels: 2
+            }
+        );
+    }
+
+    #[test]
+    fn audio_probe_parse__garbage__then_zeroes() {
+        let probe = AudioProbe::parse("not a probe\nsample_rate=N/A\n");
+        assert_eq!(probe.sample_rate, 0);
+        assert_eq!(probe.channels, 0);
+    }
+
+    #[test]
+    fn conversion_args__stereo_16khz__then_downmixes_only() {
+        let probe = AudioProbe {
+            sample_rate: 16_000,
+            channels: 2,
+        };
+        assert_eq!(conversion_args(&probe), vec!["-ac", "1"]);
+    }
+
+    #[test]
+    fn conversion_args__mono_48khz__then_resamples_without_downmix() {
+        let probe = AudioProbe {
+            sample_rate: 48_000,
+            channels: 1,
+        };
+        let args = conversion_args(&probe);
+        assert_eq!(args, vec!["-ar", "16000"]);
+        assert!(!args.contains(&"-ac".to_string()));
+    }
+
+    #[test]
+    fn conversion_args__stereo_48khz__then_resamples_and_downmixes() {
+        let probe = AudioProbe {
+            sample_rate: 48_000,
+            channels: 2,
+        };
+        assert_eq!(conversion_args(&probe), vec!["-ar", "16000", "-ac", "1"]);
+    }
+
+    #[test]
+    fn conversion_args__mono_16khz__then_empty_and_ready() {
+        let probe = AudioProbe {
+            sample_rate: 16_000,
+            channels: 1,
+        };
+        assert!(probe.is_whisper_ready());
+        assert!(conversion_args(&probe).is_empty());
+    }
+
+    #[test]
+    fn loudnorm_args__any_input__then_applies_loudnorm_filter() {
+        let args = loudnorm_args(Path::new("/in/voice.wav"), Path::new("/tmp/out.wav"));
+        let args: Vec<String> = args
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+
+        let af = args.iter().position(|a| a == "-af").expect("has -af");
+        assert!(args[af + 1].starts_with("loudnorm"));
+        assert_eq!(args[2], "/in/voice.wav");
+        assert_eq!(args.last().map(String::as_str), Some("/tmp/out.wav"));
+    }
+}
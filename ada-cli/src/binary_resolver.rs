@@ -17,6 +17,9 @@ pub enum Tool {
 }
 
 impl Tool {
+    /// Every tool, in the order `ada doctor` reports them
+    pub const ALL: [Tool; 3] = [Tool::WhisperCpp, Tool::Ffmpeg, Tool::Ffprobe];
+
     /// Environment variable name for overriding this tool's path
     fn env_var(self) -> &'static str {
         match self {
@@ -35,8 +38,8 @@ impl Tool {
         }
     }
 
-    /// Human-readable name for error messages
-    fn display_name(self) -> &'static str {
+    /// Human-readable name for error messages and health reports
+    pub fn display_name(self) -> &'static str {
         match self {
             Tool::Ffmpeg => "ffmpeg",
             Tool::Ffprobe => "ffprobe",
@@ -75,39 +78,60 @@ pub fn resolve(tool: Tool) -> Result<PathBuf> {
     )
 }
 
+/// Resolve every tool in [`Tool::ALL`], keeping failures alongside successes
+pub fn resolve_all() -> Vec<(Tool, Result<PathBuf>)> {
+    Tool::ALL
+        .iter()
+        .map(|&tool| (tool, resolve(tool)))
+        .collect()
+}
+
 /// Check if a tool is available (without erroring)
 pub fn is_available(tool: Tool) -> bool {
     resolve(tool).is_ok()
 }
 
-/// Search for a bundled binary relative to the current executable.
+/// Every location [`resolve`] probes for `tool`, in resolution order
 ///
-/// Checks:
+/// Starts with the environment override when it is set.
+pub fn search_paths(tool: Tool) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::env::var_os(tool.env_var())
+        .map(PathBuf::from)
+        .into_iter()
+        .collect();
+    paths.extend(bundled_candidates(tool));
+    paths
+}
+
+/// Search for a bundled binary relative to the current executable.
+fn find_bundled(tool: Tool) -> Option<PathBuf> {
+    bundled_candidates(tool)
+        .into_iter()
+        .find(|candidate| candidate.exists())
+}
+
+/// Bundled binary locations relative to the current executable:
 /// - Same directory as executable
 /// - `../bin/` (plugin layout: bin/ada, bin/ffmpeg)
 /// - `media_tools/` subdirectory
-fn find_bundled(tool: Tool) -> Option<PathBuf> {
-    let exe_path = std::env::current_exe().ok()?;
-    let exe_dir = exe_path.parent()?;
+fn bundled_candidates(tool: Tool) -> Vec<PathBuf> {
+    let Ok(exe_path) = std::env::current_exe() else {
+        return Vec::new();
+    };
+    let Some(exe_dir) = exe_path.parent() else {
+        return Vec::new();
+    };
 
     let name = tool.bundled_name();
 
-    let candidates: Vec<PathBuf> = [
+    [
         Some(exe_dir.join(name)),
         exe_dir.parent().map(|p| p.join("bin").join(name)),
         Some(exe_dir.join("media_tools").join(name)),
     ]
     .into_iter()
     .flatten()
-    .collect();
-
-    for candidate in candidates {
-        if candidate.exists() {
-            return Some(candidate);
-        }
-    }
-
-    None
+    .collect()
 }
 
 #[cfg(test)]
@@ -191,6 +215,27 @@ mod tests {
         let _ = is_available(Tool::WhisperCpp);
     }
 
+    #[test]
+    fn resolve_all__every_tool_variant__then_one_entry_each() {
+        // Exhaustive on purpose: a new variant fails to compile here until
+        // it is added to Tool::ALL and this test
+        fn index(tool: Tool) -> usize {
+            match tool {
+                Tool::Ffmpeg => 0,
+                Tool::Ffprobe => 1,
+                Tool::WhisperCpp => 2,
+            }
+        }
+        let mut seen = [false; 3];
+
+        for (tool, _) in resolve_all() {
+            assert!(!seen[index(tool)], "{:?} listed twice", tool);
+            seen[index(tool)] = true;
+        }
+
+        assert_eq!(seen, [true; 3]);
+    }
+
     #[test]
     fn resolve__no_env_no_bundled__then_error() {
         let result = with_env(
@@ -209,4 +254,15 @@ mod tests {
         assert!(err.contains("not found"), "Error should say not found: {err}");
         assert!(err.contains("init_media_tools"), "Error should mention init script: {err}");
     }
+
+    #[test]
+    fn search_paths__env_override_set__then_listed_before_bundled() {
+        let paths = with_env("ADA_FFPROBE_PATH", Some("/opt/tools/ffprobe"), || {
+            search_paths(Tool::Ffprobe)
+        });
+
+        assert_eq!(paths[0], PathBuf::from("/opt/tools/ffprobe"));
+        assert!(paths.len() > 1, "bundled locations follow: {:?}", paths);
+        assert!(paths[1..].iter().all(|p| p.ends_with("ffprobe")));
+    }
 }
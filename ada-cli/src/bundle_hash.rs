@@ -0,0 +1,175 @@
+//! Content hash for capture bundles.
+//!
+//! Capture records the hash in `manifest.json` as `bundle_hash` when it
+//! finalizes a bundle, and `ada query <bundle> validate --check-hash`
+//! recomputes it to catch files corrupted or edited after the fact.
+//!
+//! Files are hashed in order of their `/`-separated path relative to the
+//! bundle, so the result doesn't depend on the platform's path separator or
+//! the order the filesystem lists directory entries in.
+
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+/// Whether a file (given by its `/`-separated path inside the bundle) is
+/// left out of the hash
+///
+/// Excluded are the manifest that stores the hash, the session record that
+/// is updated after finalization, caches that queries write into the bundle
+/// (transcripts, screenshots), and Finder metadata.
+pub fn is_excluded(relative: &str) -> bool {
+    if relative.rsplit('/').next() == Some(".DS_Store") {
+        return true;
+    }
+    if relative.contains('/') {
+        return false;
+    }
+    relative == "manifest.json"
+        || relative == "session.json"
+        || (relative.starts_with("transcript") && relative.ends_with(".json"))
+        || (relative.starts_with("screenshot_") && relative.ends_with(".png"))
+}
+
+/// SHA-256 over every file in `bundle_dir` that isn't excluded, as lowercase hex
+pub fn compute(bundle_dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(bundle_dir, "", &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let path = bundle_dir.join(&relative);
+        let mut file =
+            File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let len = file
+            .metadata()
+            .with_context(|| format!("Failed to stat {}", path.display()))?
+            .len();
+
+        // Name and length first, so file boundaries are unambiguous
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(len.to_le_bytes());
+        io::copy(&mut file, &mut hasher)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Add the relative paths of files under `dir` (itself at `prefix`) to `files`
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let relative = format!("{}{}", prefix, name.to_string_lossy());
+
+        if path.is_dir() {
+            collect_files(&path, &format!("{}/", relative), files)?;
+        } else if !is_excluded(&relative) {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, relative: &str, content: &str) {
+        let path = dir.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn bundle(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (relative, content) in files {
+            write(dir.path(), relative, content);
+        }
+        dir
+    }
+
+    const FILES: &[(&str, &str)] = &[
+        ("screen.mp4", "video"),
+        ("tracer_stats.json", "{}"),
+        ("trace/session_1/pid_1/manifest.json", "{\"symbols\":[]}"),
+        ("trace/session_1/pid_1/thread_0/index.atf", "events"),
+    ];
+
+    #[test]
+    fn compute__file_modified__then_hash_changes() {
+        let dir = bundle(FILES);
+        let before = compute(dir.path()).unwrap();
+
+        write(
+            dir.path(),
+            "trace/session_1/pid_1/thread_0/index.atf",
+            "evento",
+        );
+        let after = compute(dir.path()).unwrap();
+
+        assert_eq!(before.len(), 64);
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn compute__same_files_created_in_other_order__then_same_hash() {
+        let forward = bundle(FILES);
+        let reversed: Vec<_> = FILES.iter().rev().copied().collect();
+        let backward = bundle(&reversed);
+
+        assert_eq!(
+            compute(forward.path()).unwrap(),
+            compute(backward.path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn compute__file_renamed__then_hash_changes() {
+        let dir = bundle(FILES);
+        let before = compute(dir.path()).unwrap();
+
+        fs::rename(
+            dir.path().join("screen.mp4"),
+            dir.path().join("screen2.mp4"),
+        )
+        .unwrap();
+
+        assert_ne!(before, compute(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn compute__excluded_files_change__then_hash_unchanged() {
+        let dir = bundle(FILES);
+        let before = compute(dir.path()).unwrap();
+
+        write(dir.path(), "manifest.json", "{\"bundle_hash\":\"...\"}");
+        write(dir.path(), "session.json", "{\"status\":\"complete\"}");
+        write(dir.path(), "transcript_normalized.json", "{}");
+        write(dir.path(), "screenshot_3s.png", "png");
+        write(dir.path(), "trace/.DS_Store", "finder");
+
+        assert_eq!(before, compute(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn is_excluded__nested_names__then_only_root_caches_excluded() {
+        assert!(is_excluded("manifest.json"));
+        assert!(!is_excluded("trace/session_1/pid_1/manifest.json"));
+        assert!(!is_excluded("trace/transcript.json"));
+        assert!(!is_excluded("voice.wav"));
+    }
+}
@@ -5,27 +5,49 @@
 
 use anyhow::{bail, Context};
 use clap::Subcommand;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
 use std::fs;
+use std::io::IsTerminal;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracer_backend::ffi::TracerStats;
 use tracer_backend::TracerController;
 
+use crate::flight_rpc::{FlightRpcServer, SharedSession};
 use crate::session_state::{self, SessionState, SessionStatus};
 
 #[derive(Subcommand)]
 pub enum CaptureCommands {
     /// Start a multimodal capture session
     ///
-    /// Session data is stored in ~/.ada/sessions/<session_id>/
+    /// Session data is stored in ~/.ada/sessions/<session_id>/ unless
+    /// --output-dir is given, in which case the bundle is written to
+    /// <output_dir>/<session_id>/ and only session.json stays in ~/.ada/sessions.
     /// The session directory IS the bundle (contains manifest.json, trace/, etc.)
     Start {
-        /// Path to the binary to trace
-        binary: String,
+        /// Path to the binary or `.app` bundle to trace
+        #[arg(required_unless_present = "bundle_id")]
+        binary: Option<String>,
+
+        /// Trace the installed app with this bundle identifier instead of a path
+        ///
+        /// With --bundle-id every positional argument is passed to the app.
+        #[arg(long, value_name = "ID")]
+        bundle_id: Option<String>,
+
+        /// Write the session bundle under this directory instead of ~/.ada/sessions
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+
+        /// Free-text note describing what this capture is about
+        #[arg(long, value_name = "TEXT")]
+        note: Option<String>,
 
         /// Disable screen recording (enabled by default)
         #[arg(long = "no-screen")]
@@ -43,11 +65,97 @@ pub enum CaptureCommands {
         #[arg(long, default_value_t = 0)]
         post_roll_ms: u32,
 
+        /// Re-encode the screen recording at this frame rate
+        #[arg(long, value_name = "N")]
+        screen_fps: Option<u32>,
+
+        /// Re-encode the screen recording at this size (`W:H`) or percentage (`50%`)
+        #[arg(long, value_name = "W:H|PERCENT")]
+        screen_scale: Option<ScreenScale>,
+
+        /// Keep the lossless voice track as `wav` or `flac`
+        ///
+        /// FLAC is lossless and smaller; transcription converts either to WAV.
+        #[arg(long, value_name = "wav|flac", default_value_t = VoiceFormat::Wav)]
+        voice_format: VoiceFormat,
+
+        /// Bitrate of the compressed voice track (voice.m4a) in kbit/s
+        #[arg(
+            long,
+            value_name = "K",
+            default_value_t = VOICE_BITRATE_K,
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        voice_bitrate: u32,
+
+        /// Keep only the lossless voice track, skipping the AAC voice.m4a
+        #[arg(long)]
+        no_aac: bool,
+
+        /// Abort the whole capture if the screen/voice recorder exits early
+        #[arg(long)]
+        strict: bool,
+
+        /// Arm the detail lane but fire it only on a marker (Enter or SIGUSR1)
+        ///
+        /// Each marker keeps --pre-roll-ms of detail before it and records
+        /// --post-roll-ms after it; the trigger is then re-armed for the next
+        /// marker. Markers arriving inside an open window are coalesced.
+        #[arg(long)]
+        trigger_on_marker: bool,
+
+        /// Serve `trace.flight.arm`/`fire`/`disarm` JSON-RPC on this address
+        ///
+        /// Lets another tool arm, fire and disarm the detail trigger while the
+        /// capture runs, and read live counters through `trace.stats`. Port 0
+        /// picks a free port, printed at startup.
+        #[arg(long, value_name = "ADDR")]
+        rpc_address: Option<SocketAddr>,
+
+        /// Stop or throttle the capture once trace data exceeds this many MiB
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        max_trace_mb: Option<u64>,
+
+        /// What to do when --max-trace-mb is exceeded: `stop` ends the capture
+        /// (writing the bundle as usual), `throttle` disables the detail lane
+        #[arg(long, value_name = "stop|throttle", default_value_t = BudgetPolicy::Stop)]
+        on_budget: BudgetPolicy,
+
+        /// Attempts at spawning and attaching the tracer before giving up
+        ///
+        /// Failed attempts are retried with exponential backoff starting at 200ms.
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = SPAWN_ATTEMPTS,
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        spawn_retries: u32,
+
+        /// Check prerequisites and print the plan without starting a capture
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print a single JSON summary on completion instead of the text report
+        ///
+        /// Progress messages go to stderr so stdout only carries the summary.
+        #[arg(long)]
+        json: bool,
+
         /// Arguments to pass to the binary
         #[arg(trailing_var_arg = true)]
         args: Vec<String>,
     },
 
+    /// Re-attach to a session whose capture crashed while the app kept running
+    ///
+    /// Tracing continues into the same bundle; screen and voice recording
+    /// are not restarted.
+    Resume {
+        /// Session ID to resume
+        session_id: String,
+    },
+
     /// Stop a running capture session
     Stop {
         /// Session ID to stop (defaults to latest running session)
@@ -61,18 +169,346 @@ pub fn run(cmd: CaptureCommands) -> anyhow::Result<()> {
     match cmd {
         CaptureCommands::Start {
             binary,
+            bundle_id,
+            output_dir,
+            note,
             no_screen,
             no_voice,
             pre_roll_ms,
             post_roll_ms,
+            screen_fps,
+            screen_scale,
+            voice_format,
+            voice_bitrate,
+            no_aac,
+            strict,
+            trigger_on_marker,
+            rpc_address,
+            max_trace_mb,
+            on_budget,
+            spawn_retries,
+            dry_run,
+            json,
             args,
-        } => start_capture(&binary, !no_screen, !no_voice, pre_roll_ms, post_roll_ms, &args),
+        } => {
+            let options = StartOptions {
+                output_dir,
+                note,
+                screen: !no_screen,
+                voice: !no_voice,
+                pre_roll_ms,
+                post_roll_ms,
+                screen_fps,
+                screen_scale,
+                voice_format,
+                voice_bitrate_k: voice_bitrate,
+                aac: !no_aac,
+                strict,
+                trigger_on_marker,
+                rpc_address,
+                max_trace_mb,
+                on_budget,
+                spawn_attempts: spawn_retries,
+                json,
+            };
+            let (binary, args) = match bundle_id {
+                Some(bundle_id) => {
+                    let app = resolve_bundle_id(&bundle_id)?;
+                    (app, binary.into_iter().chain(args).collect())
+                }
+                None => (
+                    binary.expect("clap requires binary without --bundle-id"),
+                    args,
+                ),
+            };
+            if dry_run {
+                dry_run_capture(&binary, &options, &args)
+            } else {
+                start_capture(&binary, &options, &args)
+            }
+        }
+        CaptureCommands::Resume { session_id } => resume_capture(&session_id),
         CaptureCommands::Stop { session_id } => stop_capture(session_id),
     }
 }
 // LCOV_EXCL_STOP
 
-#[derive(Serialize)]
+/// Options for `capture start`, collected from the CLI flags
+struct StartOptions {
+    output_dir: Option<PathBuf>,
+    note: Option<String>,
+    screen: bool,
+    voice: bool,
+    pre_roll_ms: u32,
+    post_roll_ms: u32,
+    screen_fps: Option<u32>,
+    screen_scale: Option<ScreenScale>,
+    voice_format: VoiceFormat,
+    voice_bitrate_k: u32,
+    /// Encode the compressed voice.m4a alongside the lossless track
+    aac: bool,
+    strict: bool,
+    trigger_on_marker: bool,
+    /// Where to serve the flight recorder RPC; `None` disables it
+    rpc_address: Option<SocketAddr>,
+    /// Trace data budget in MiB; `None` is unlimited
+    max_trace_mb: Option<u64>,
+    on_budget: BudgetPolicy,
+    spawn_attempts: u32,
+    json: bool,
+}
+
+/// Default attempts at spawning/attaching before a capture is abandoned
+const SPAWN_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled after each further failure
+const SPAWN_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Call `f` up to `attempts` times (at least once) until it succeeds
+///
+/// Sleeps `backoff` before the first retry and doubles it for each one after.
+/// Returns the last error if every attempt fails.
+fn retry<T, E: std::fmt::Display>(
+    attempts: u32,
+    backoff: Duration,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let attempts = attempts.max(1);
+    let mut delay = backoff;
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                eprintln!(
+                    "Warning: attempt {}/{} failed ({}); retrying in {} ms",
+                    attempt,
+                    attempts,
+                    e,
+                    delay.as_millis()
+                );
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Requested output size for the screen recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenScale {
+    /// Explicit width and height in pixels
+    Size { width: u32, height: u32 },
+    /// Percentage of the native resolution
+    Percent(u32),
+}
+
+impl ScreenScale {
+    /// ffmpeg `scale` filter expression for this size.
+    ///
+    /// Percent scaling is rounded down to even dimensions, which H.264 requires.
+    fn to_filter(self) -> String {
+        match self {
+            ScreenScale::Size { width, height } => format!("scale={}:{}", width, height),
+            ScreenScale::Percent(pct) => format!(
+                "scale=trunc(iw*{pct}/200)*2:trunc(ih*{pct}/200)*2",
+                pct = pct
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for ScreenScale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some((w, h)) = s.split_once(':') {
+            let width: u32 = w
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid width: {}", w))?;
+            let height: u32 = h
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid height: {}", h))?;
+            if width == 0 || height == 0 {
+                return Err("width and height must be greater than zero".to_string());
+            }
+            return Ok(ScreenScale::Size { width, height });
+        }
+
+        let pct = s.strip_suffix('%').unwrap_or(s);
+        let pct: u32 = pct
+            .trim()
+            .parse()
+            .map_err(|_| format!("expected W:H or a percentage, got: {}", s))?;
+        if pct == 0 || pct > 100 {
+            return Err("percentage must be between 1 and 100".to_string());
+        }
+        Ok(ScreenScale::Percent(pct))
+    }
+}
+
+impl std::fmt::Display for ScreenScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScreenScale::Size { width, height } => write!(f, "{}:{}", width, height),
+            ScreenScale::Percent(pct) => write!(f, "{}%", pct),
+        }
+    }
+}
+
+/// Default bitrate of the compressed voice track in kbit/s
+const VOICE_BITRATE_K: u32 = 128;
+
+/// Container for the lossless voice track kept in the bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceFormat {
+    /// 16-bit PCM WAV, as ada-recorder writes it
+    Wav,
+    /// FLAC, transcoded from the recorded WAV
+    Flac,
+}
+
+impl VoiceFormat {
+    /// File name of the lossless voice track in the bundle
+    fn file_name(self) -> &'static str {
+        match self {
+            VoiceFormat::Wav => "voice.wav",
+            VoiceFormat::Flac => "voice.flac",
+        }
+    }
+}
+
+impl std::str::FromStr for VoiceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "wav" => Ok(VoiceFormat::Wav),
+            "flac" => Ok(VoiceFormat::Flac),
+            other => Err(format!("expected wav or flac, got: {}", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for VoiceFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoiceFormat::Wav => write!(f, "wav"),
+            VoiceFormat::Flac => write!(f, "flac"),
+        }
+    }
+}
+
+/// What the capture loop does once the trace data outgrows `--max-trace-mb`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetPolicy {
+    /// End the capture as if it had been interrupted
+    Stop,
+    /// Keep capturing with the detail lane disabled
+    Throttle,
+}
+
+impl std::str::FromStr for BudgetPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "stop" => Ok(BudgetPolicy::Stop),
+            "throttle" => Ok(BudgetPolicy::Throttle),
+            other => Err(format!("expected stop or throttle, got: {}", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for BudgetPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetPolicy::Stop => write!(f, "stop"),
+            BudgetPolicy::Throttle => write!(f, "throttle"),
+        }
+    }
+}
+
+/// How often the capture loop measures the trace directory against the budget
+const TRACE_BUDGET_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Action to take for a trace directory of `trace_bytes` under a budget of
+/// `max_mb` MiB
+///
+/// Stopping is returned on every check past the budget; throttling only once,
+/// since the detail lane stays disabled afterwards.
+fn budget_action(
+    trace_bytes: u64,
+    max_mb: u64,
+    policy: BudgetPolicy,
+    throttled: bool,
+) -> Option<BudgetPolicy> {
+    if trace_bytes <= max_mb.saturating_mul(1024 * 1024) {
+        return None;
+    }
+    match policy {
+        BudgetPolicy::Throttle if throttled => None,
+        policy => Some(policy),
+    }
+}
+
+/// Target of a voice encode from the recorded voice.wav
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VoiceEncoding {
+    /// Compressed AAC track (voice.m4a) at this bitrate in kbit/s
+    Aac { bitrate_k: u32 },
+    /// Lossless track in this format
+    Lossless(VoiceFormat),
+}
+
+impl VoiceEncoding {
+    fn file_name(self) -> &'static str {
+        match self {
+            VoiceEncoding::Aac { .. } => "voice.m4a",
+            VoiceEncoding::Lossless(format) => format.file_name(),
+        }
+    }
+}
+
+/// Tracer statistics snapshot taken at the end of a capture.
+///
+/// Same shape as `tracer_stats.json` written by the standalone tracer, so the
+/// file persisted into the bundle can be read by the same tooling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TraceStats {
+    #[serde(default)]
+    pub events_captured: u64,
+    #[serde(default)]
+    pub events_dropped: u64,
+    #[serde(default)]
+    pub bytes_written: u64,
+    #[serde(default)]
+    pub hooks_installed: u32,
+    #[serde(default)]
+    pub fallback_events: u64,
+    #[serde(default)]
+    pub preroll_evicted: u64,
+}
+
+impl From<TracerStats> for TraceStats {
+    fn from(stats: TracerStats) -> Self {
+        Self {
+            events_captured: stats.events_captured,
+            events_dropped: stats.events_dropped,
+            bytes_written: stats.bytes_written,
+            hooks_installed: stats.hooks_installed,
+            fallback_events: stats.fallback_events,
+            preroll_evicted: stats.preroll_evicted,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 struct BundleManifest {
     version: u32,
     created_at_ms: u64,
@@ -80,10 +516,57 @@ struct BundleManifest {
     session_name: String,
     trace_root: String,
     trace_session: Option<String>,
+    /// Every trace session, oldest first; only written when there are several
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    trace_sessions: Vec<String>,
     screen_path: Option<String>,
     voice_path: Option<String>,
     voice_lossless_path: Option<String>,
     detail_when_voice: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    screen_fps: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    screen_scale: Option<String>,
+    /// Format of `voice_lossless_path` ("wav" or "flac")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    voice_format: Option<String>,
+    /// Bitrate of `voice_path` in kbit/s
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    voice_bitrate_k: Option<u32>,
+    /// Length of `screen_path` as reported by ffprobe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    screen_duration_sec: Option<f64>,
+    /// Length of `voice_lossless_path` as reported by ffprobe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    voice_duration_sec: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trace_stats: Option<TraceStats>,
+    /// Artifacts ("screen", "voice") whose recorder exited early
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    failed_artifacts: Vec<String>,
+    /// Content hash of the finalized bundle, for `query validate --check-hash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bundle_hash: Option<String>,
+    /// Exit code of the traced process, if it exited during the capture
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+}
+
+impl BundleManifest {
+    /// Fill in the voice tracks `capture start` left in `bundle_dir`
+    ///
+    /// A stray voice.m4a is ignored when `--no-aac` was given.
+    fn record_voice(&mut self, bundle_dir: &Path, options: &StartOptions) {
+        let compressed = options.voice && options.aac && bundle_dir.join("voice.m4a").exists();
+        let lossless = lossless_voice(bundle_dir).filter(|_| options.voice);
+
+        self.voice_path = compressed.then(|| "voice.m4a".to_string());
+        self.voice_lossless_path = lossless.map(|format| format.file_name().to_string());
+        self.voice_format = lossless.map(|format| format.to_string());
+        self.voice_bitrate_k = compressed.then_some(options.voice_bitrate_k);
+    }
 }
 
 // LCOV_EXCL_START - macOS app bundle resolution and agent path setup
@@ -111,7 +594,7 @@ fn resolve_executable_path(path: &str) -> anyhow::Result<String> {
         }
 
         // Read CFBundleExecutable from Info.plist
-        let executable_name = read_plist_key(&info_plist, "CFBundleExecutable")
+        let executable_name = ada_cli::info_plist::read_string_key(&info_plist, "CFBundleExecutable")
             .with_context(|| format!("Failed to read executable name from {}", info_plist.display()))?;
 
         let executable_path = p.join("Contents/MacOS").join(&executable_name);
@@ -132,20 +615,29 @@ fn resolve_executable_path(path: &str) -> anyhow::Result<String> {
     }
 }
 
-/// Read a key from Info.plist using PlistBuddy
-fn read_plist_key(plist_path: &Path, key: &str) -> anyhow::Result<String> {
-    let output = Command::new("/usr/libexec/PlistBuddy")
-        .arg("-c")
-        .arg(format!("Print :{}", key))
-        .arg(plist_path)
+/// Find the installed `.app` for a bundle identifier using Spotlight
+fn resolve_bundle_id(bundle_id: &str) -> anyhow::Result<String> {
+    if !is_valid_bundle_id(bundle_id) {
+        bail!(
+            "Invalid bundle id '{}': expected reverse-DNS like com.example.app",
+            bundle_id
+        );
+    }
+
+    let output = Command::new("mdfind")
+        .arg(format!("kMDItemCFBundleIdentifier == '{}'", bundle_id))
         .output()
-        .context("Failed to execute PlistBuddy")?;
+        .context("Failed to execute mdfind")?;
 
     if !output.status.success() {
-        bail!("PlistBuddy failed: {}", String::from_utf8_lossy(&output.stderr));
+        bail!("mdfind failed: {}", String::from_utf8_lossy(&output.stderr));
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    let candidates = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    select_bundle_match(bundle_id, candidates)
 }
 
 /// Ensure ADA_AGENT_RPATH_SEARCH_PATHS is set so the tracer can find libfrida_agent.dylib
@@ -200,7 +692,83 @@ fn ensure_agent_rpath() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Create `dir` if needed and verify that files can be written into it
+fn ensure_writable_dir(dir: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory {}", dir.display()))?;
+
+    tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Output directory is not writable: {}", dir.display()))?;
+
+    Ok(())
+}
+
+/// Recorder output log, written into the bundle directory
+const RECORDER_LOG: &str = "recorder.log";
+
+/// Number of log lines shown when the recorder exits early
+const RECORDER_LOG_TAIL_LINES: usize = 20;
+
+/// A recorder subprocess with its stdout/stderr redirected to a log file
+struct RecorderChild {
+    child: Child,
+    log_path: PathBuf,
+}
+
+/// A recorder that exited unsuccessfully while capture was running
+#[derive(Debug)]
+struct RecorderFailure {
+    status: ExitStatus,
+    log_tail: String,
+}
+
+impl RecorderChild {
+    fn spawn(mut cmd: Command, log_path: &Path) -> anyhow::Result<Self> {
+        let log = fs::File::create(log_path)
+            .with_context(|| format!("Failed to create {}", log_path.display()))?;
+        let child = cmd
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .spawn()?;
+
+        Ok(Self {
+            child,
+            log_path: log_path.to_path_buf(),
+        })
+    }
+
+    /// Non-blocking check for an unsuccessful exit
+    ///
+    /// Returns `None` while the recorder is running or if it exited cleanly.
+    fn poll_failure(&mut self) -> Option<RecorderFailure> {
+        match self.child.try_wait() {
+            Ok(Some(status)) if !status.success() => Some(RecorderFailure {
+                status,
+                log_tail: fs::read_to_string(&self.log_path)
+                    .map(|log| tail_lines(&log, RECORDER_LOG_TAIL_LINES))
+                    .unwrap_or_default(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Last `count` non-empty lines of `text`
+fn tail_lines(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+/// Error for screen/voice capture on platforms without ada-recorder
+#[cfg(not(target_os = "macos"))]
+const MEDIA_CAPTURE_UNSUPPORTED: &str = "screen/voice capture is only supported on macOS";
+
 /// Find the ada-recorder binary
+#[cfg(target_os = "macos")]
 fn find_ada_recorder() -> anyhow::Result<PathBuf> {
     // 1. Same directory as ada binary
     if let Ok(exe) = std::env::current_exe() {
@@ -225,23 +793,238 @@ fn find_ada_recorder() -> anyhow::Result<PathBuf> {
     )
 }
 
+/// ada-recorder is built on ScreenCaptureKit/AVFoundation and only exists
+/// on macOS, so screen and voice capture fail up front elsewhere
+#[cfg(not(target_os = "macos"))]
+fn find_ada_recorder() -> anyhow::Result<PathBuf> {
+    bail!(MEDIA_CAPTURE_UNSUPPORTED)
+}
+
 // LCOV_EXCL_STOP
 
-// LCOV_EXCL_START - Integration code requires live tracer and capture hardware
+/// Bundle identifiers are reverse-DNS: letters, digits, `-` and `.`
+fn is_valid_bundle_id(bundle_id: &str) -> bool {
+    !bundle_id.is_empty()
+        && !bundle_id.starts_with('.')
+        && !bundle_id.ends_with('.')
+        && bundle_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
+
+/// Pick the single `.app` among Spotlight matches for `bundle_id`
+fn select_bundle_match(bundle_id: &str, candidates: Vec<String>) -> anyhow::Result<String> {
+    let mut apps: Vec<String> = candidates
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| line.ends_with(".app"))
+        .collect();
+    apps.sort();
+    apps.dedup();
+
+    match apps.len() {
+        0 => bail!("No installed app found with bundle id '{}'", bundle_id),
+        1 => Ok(apps.remove(0)),
+        _ => bail!(
+            "Bundle id '{}' matches {} apps; pass the app path instead:\n  {}",
+            bundle_id,
+            apps.len(),
+            apps.join("\n  ")
+        ),
+    }
+}
+
+/// Outcome of one `--dry-run` prerequisite check
+#[derive(Debug)]
+struct Prerequisite {
+    name: &'static str,
+    ok: bool,
+    /// Resolved path on success, or what went wrong
+    detail: String,
+}
 
-fn start_capture(
+/// Lookups used by the dry-run checks, replaceable in tests
+struct PrerequisiteResolvers<'a> {
+    executable: &'a dyn Fn(&str) -> anyhow::Result<String>,
+    agent: &'a dyn Fn() -> anyhow::Result<String>,
+    recorder: &'a dyn Fn() -> anyhow::Result<PathBuf>,
+    ffmpeg: &'a dyn Fn() -> anyhow::Result<PathBuf>,
+}
+
+/// Run the prerequisite checks that apply to the requested capture modes
+///
+/// The recorder is only needed for screen or voice, and ffmpeg only for voice
+/// encoding (AAC or FLAC) or screen re-encoding.
+fn collect_prerequisites(
     binary: &str,
-    screen: bool,
-    voice: bool,
-    pre_roll_ms: u32,
-    post_roll_ms: u32,
-    args: &[String],
-) -> anyhow::Result<()> {
+    options: &StartOptions,
+    resolvers: &PrerequisiteResolvers,
+) -> Vec<Prerequisite> {
+    fn check<T>(
+        name: &'static str,
+        result: anyhow::Result<T>,
+        show: impl Fn(T) -> String,
+    ) -> Prerequisite {
+        match result {
+            Ok(value) => Prerequisite {
+                name,
+                ok: true,
+                detail: show(value),
+            },
+            Err(e) => Prerequisite {
+                name,
+                ok: false,
+                detail: format!("{:#}", e),
+            },
+        }
+    }
+
+    let mut checks = vec![
+        check("binary", (resolvers.executable)(binary), |path| path),
+        check("frida agent", (resolvers.agent)(), |paths| paths),
+    ];
+
+    if options.screen || options.voice {
+        checks.push(check("ada-recorder", (resolvers.recorder)(), |path| {
+            path.display().to_string()
+        }));
+    }
+
+    let reencode_screen =
+        options.screen && (options.screen_fps.is_some() || options.screen_scale.is_some());
+    let encode_voice = options.voice && (options.aac || options.voice_format != VoiceFormat::Wav);
+    if encode_voice || reencode_screen {
+        checks.push(check("ffmpeg", (resolvers.ffmpeg)(), |path| {
+            path.display().to_string()
+        }));
+    }
+
+    checks
+}
+
+// LCOV_EXCL_START - Dry run wires the checks to the real system
+
+fn dry_run_capture(binary: &str, options: &StartOptions, args: &[String]) -> anyhow::Result<()> {
+    let resolvers = PrerequisiteResolvers {
+        executable: &resolve_executable_path,
+        agent: &|| {
+            ensure_agent_rpath()?;
+            Ok(std::env::var("ADA_AGENT_RPATH_SEARCH_PATHS").unwrap_or_default())
+        },
+        recorder: &find_ada_recorder,
+        ffmpeg: &|| {
+            let result = ada_cli::doctor::check_ffmpeg();
+            match (result.ok, result.path) {
+                (true, Some(path)) => Ok(PathBuf::from(path)),
+                _ => bail!(
+                    "not found ({})",
+                    result.fix.unwrap_or_else(|| "install ffmpeg".to_string())
+                ),
+            }
+        },
+    };
+    let checks = collect_prerequisites(binary, options, &resolvers);
+
+    println!("ADA Capture Dry Run (no session created):");
+    for check in &checks {
+        let mark = if check.ok { "\u{2713}" } else { "\u{2717}" };
+        // Indent multi-line hints (e.g. install steps) under their check
+        let detail = check.detail.replace('\n', "\n      ");
+        println!("  {} {}: {}", mark, check.name, detail);
+    }
+
+    let mut modes = vec!["trace"];
+    if options.screen {
+        modes.push("screen");
+    }
+    if options.voice {
+        modes.push("voice");
+    }
+    println!();
+    println!("Would capture: {}", modes.join(", "));
+    let mut command = vec![binary.to_string()];
+    command.extend_from_slice(args);
+    println!("  Command: {}", command.join(" "));
+    if let Some(address) = options.rpc_address {
+        println!("  Flight recorder RPC: {}", address);
+    }
+    if let Some(max_mb) = options.max_trace_mb {
+        println!(
+            "  Trace budget: {} MiB (then {})",
+            max_mb, options.on_budget
+        );
+    }
+    match &options.output_dir {
+        Some(dir) => println!("  Bundle under: {}", dir.display()),
+        None => match session_state::sessions_dir() {
+            Ok(dir) => println!("  Bundle under: {}", dir.display()),
+            Err(e) => println!("  Bundle under: unavailable ({})", e),
+        },
+    }
+
+    let missing = checks.iter().filter(|check| !check.ok).count();
+    if missing > 0 {
+        bail!("{} prerequisite(s) missing", missing);
+    }
+    Ok(())
+}
+
+// LCOV_EXCL_STOP
+
+/// Final `capture start --json` output
+#[derive(Debug, Serialize)]
+struct CaptureSummary<'a> {
+    session_id: &'a str,
+    bundle_path: &'a Path,
+    manifest_path: &'a Path,
+    status: SessionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+}
+
+impl CaptureSummary<'_> {
+    fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Print a progress line; with `--json` it goes to stderr so stdout only
+/// carries the summary
+fn progress(json: bool, line: &str) {
+    if json {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+// LCOV_EXCL_START - Integration code requires live tracer and capture hardware
+
+fn start_capture(binary: &str, options: &StartOptions, args: &[String]) -> anyhow::Result<()> {
+    let screen = options.screen;
+    let voice = options.voice;
+
+    // Fail fast if there is nowhere to register the session
+    let sessions_dir = session_state::sessions_dir()?;
+
     // Clean up any orphaned sessions first
     if let Err(e) = session_state::cleanup_orphaned() {
         tracing::warn!("Failed to cleanup orphaned sessions: {}", e);
     }
 
+    // Fail fast if a custom output directory can't hold the bundle. Store it
+    // absolute so the registered session_path resolves from any directory.
+    let output_dir = match &options.output_dir {
+        Some(dir) => {
+            ensure_writable_dir(dir)?;
+            Some(
+                fs::canonicalize(dir)
+                    .with_context(|| format!("Failed to resolve {}", dir.display()))?,
+            )
+        }
+        None => None,
+    };
+
     // Ensure agent library can be found
     ensure_agent_rpath()?;
 
@@ -254,7 +1037,10 @@ fn start_capture(
     let session_id = session_state::generate_session_id(&app_info.name);
 
     // Session directory IS the bundle directory
-    let bundle_dir = session_state::session_dir(&session_id)?;
+    let bundle_dir = match &output_dir {
+        Some(output_dir) => output_dir.join(&session_id),
+        None => sessions_dir.join(&session_id),
+    };
     let trace_root = bundle_dir.join("trace");
     let session_name = session_id.clone();
 
@@ -271,6 +1057,9 @@ fn start_capture(
         status: SessionStatus::Running,
         pid: None, // Will be set after spawn
         capture_pid: Some(std::process::id()),
+        note: options.note.clone(),
+        exit_code: None,
+        tags: Vec::new(),
     };
 
     if let Err(e) = session_state::register(&session) {
@@ -278,22 +1067,26 @@ fn start_capture(
     }
 
     // Output session info for Claude context
-    println!("ADA Session Started:");
-    println!("  ID: {}", session_id);
-    println!(
-        "  App: {} ({})",
-        app_info.name,
-        app_info.bundle_id.as_deref().unwrap_or("no bundle id")
-    );
-    println!("  Binary: {}", binary);
-    println!("  Bundle: {}", bundle_dir.display());
-    println!("  Time: {}", session.start_time);
+    if !options.json {
+        println!("ADA Session Started:");
+        println!("  ID: {}", session_id);
+        println!(
+            "  App: {} ({})",
+            app_info.name,
+            app_info.bundle_id.as_deref().unwrap_or("no bundle id")
+        );
+        println!("  Binary: {}", binary);
+        println!("  Bundle: {}", bundle_dir.display());
+        println!("  Time: {}", session.start_time);
+    }
 
     let mut controller = map_tracer_result(TracerController::new(&trace_root))?;
 
     let mut spawn_args = vec![binary.to_string()];
     spawn_args.extend_from_slice(args);
-    let target_pid = map_tracer_result(controller.spawn_suspended(binary, &spawn_args))?;
+    let target_pid = map_tracer_result(retry(options.spawn_attempts, SPAWN_BACKOFF, || {
+        controller.spawn_suspended(binary, &spawn_args)
+    }))?;
 
     // Update session with target PID
     if let Ok(Some(mut session)) = session_state::get(&session_id) {
@@ -301,16 +1094,31 @@ fn start_capture(
         let _ = session_state::update(&session_id, &session);
     }
 
-    map_tracer_result(controller.attach(target_pid))?;
-    map_tracer_result(controller.install_hooks())?;
-
-    // Always arm and fire trigger to start recording events
-    // (voice mode only affects pre/post roll timing, but tracing needs the trigger)
-    map_tracer_result(controller.arm_trigger(pre_roll_ms, post_roll_ms))?;
-    map_tracer_result(controller.fire_trigger())?;
-
-    map_tracer_result(controller.set_detail_enabled(voice))?;
-    map_tracer_result(controller.resume())?;
+    map_tracer_result(retry(options.spawn_attempts, SPAWN_BACKOFF, || {
+        controller.attach(target_pid)
+    }))?;
+    // Shared with the flight recorder RPC server. Stops and detaches on drop,
+    // including early returns below
+    let controller = Arc::new(Mutex::new(controller));
+    let tracer = SharedSession::new(controller.clone());
+    map_tracer_result(tracer.lock().install_hooks())?;
+
+    map_tracer_result(
+        tracer
+            .lock()
+            .arm_trigger(options.pre_roll_ms, options.post_roll_ms),
+    )?;
+    if options.trigger_on_marker {
+        // Leave the trigger armed; markers fire it during the main loop
+        install_marker_sources();
+        map_tracer_result(tracer.lock().set_detail_enabled(true))?;
+    } else {
+        // Always fire trigger to start recording events
+        // (voice mode only affects pre/post roll timing, but tracing needs the trigger)
+        map_tracer_result(tracer.lock().fire_trigger())?;
+        map_tracer_result(tracer.lock().set_detail_enabled(voice))?;
+    }
+    map_tracer_result(tracer.lock().resume())?;
 
     // Start ada-recorder for screen/voice recording
     let mut recorder_child = None;
@@ -318,7 +1126,28 @@ fn start_capture(
         recorder_child = Some(start_ada_recorder(&bundle_dir, screen, voice)?);
     }
 
-    println!("Capture running. Press Ctrl+C to stop.");
+    progress(options.json, "Capture running. Press Ctrl+C to stop.");
+    if options.trigger_on_marker {
+        progress(
+            options.json,
+            &format!(
+                "Detail trigger armed. Press Enter or run `kill -USR1 {}` to mark a moment.",
+                std::process::id()
+            ),
+        );
+    }
+
+    let rpc_server = match options.rpc_address {
+        Some(address) => {
+            let server = FlightRpcServer::start(address, controller.clone())?;
+            progress(
+                options.json,
+                &format!("Flight recorder RPC listening on {}", server.address()),
+            );
+            Some(server)
+        }
+        None => None,
+    };
 
     let running = Arc::new(AtomicBool::new(true));
     let running_flag = running.clone();
@@ -326,40 +1155,211 @@ fn start_capture(
         running_flag.store(false, Ordering::SeqCst);
     })?;
 
-    // Main loop: monitor both Ctrl+C and target process
-    let exit_reason = wait_for_termination(&running, target_pid);
+    let mut markers = options
+        .trigger_on_marker
+        .then(|| MarkerTriggers::new(Duration::from_millis(options.post_roll_ms.into())));
+    let mut budget_stop = None;
+    let mut throttled = false;
+    let mut next_budget_check = Instant::now() + TRACE_BUDGET_CHECK_INTERVAL;
+    let stop_flag = running.clone();
+    let mut on_tick = || {
+        if let Some(max_mb) = options.max_trace_mb {
+            if budget_stop.is_none() && Instant::now() >= next_budget_check {
+                next_budget_check = Instant::now() + TRACE_BUDGET_CHECK_INTERVAL;
+                let trace_bytes = session_state::dir_size(&trace_root).unwrap_or_default();
+                let exceeded = format!(
+                    "trace data reached {} MiB (budget {} MiB)",
+                    trace_bytes / (1024 * 1024),
+                    max_mb
+                );
+                match budget_action(trace_bytes, max_mb, options.on_budget, throttled) {
+                    Some(BudgetPolicy::Stop) => {
+                        eprintln!("Warning: {}; stopping capture", exceeded);
+                        budget_stop = Some(format!(
+                            "Capture stopped after trace data exceeded {} MiB",
+                            max_mb
+                        ));
+                        stop_flag.store(false, Ordering::SeqCst);
+                    }
+                    Some(BudgetPolicy::Throttle) => {
+                        eprintln!("Warning: {}; disabling the detail lane", exceeded);
+                        throttled = true;
+                        if let Err(e) = map_tracer_result(tracer.lock().set_detail_enabled(false)) {
+                            eprintln!("Warning: failed to disable detail lane: {}", e);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
 
-    println!("\n{}", exit_reason);
+        let Some(markers) = markers.as_mut() else {
+            return;
+        };
+        markers.record(MARKER_COUNT.swap(0, Ordering::SeqCst));
+        match markers.poll(Instant::now()) {
+            Some(TriggerAction::Fire) => match map_tracer_result(tracer.lock().fire_trigger()) {
+                Ok(()) => progress(
+                    options.json,
+                    &format!("Marker {}: detail trigger fired", markers.fired),
+                ),
+                Err(e) => eprintln!("Warning: failed to fire detail trigger: {}", e),
+            },
+            Some(TriggerAction::Rearm) => {
+                let mut controller = tracer.lock();
+                let rearmed = map_tracer_result(controller.disarm_trigger()).and_then(|_| {
+                    map_tracer_result(
+                        controller.arm_trigger(options.pre_roll_ms, options.post_roll_ms),
+                    )
+                });
+                if let Err(e) = rearmed {
+                    eprintln!("Warning: failed to re-arm detail trigger: {}", e);
+                }
+            }
+            None => {}
+        }
+    };
+
+    // Main loop: monitor Ctrl+C, target process and recorder health
+    let (mut exit_reason, recorder_failure, target_exit) = wait_for_termination(
+        &running,
+        target_pid,
+        recorder_child.as_mut(),
+        options.strict,
+        parse_poll_interval(std::env::var(POLL_INTERVAL_ENV).ok().as_deref()),
+        MAX_CAPTURE_DURATION,
+        &mut on_tick,
+        &mut || {
+            tracer
+                .lock()
+                .wait_for_exit(Some(EXIT_STATUS_WAIT))
+                .ok()
+                .flatten()
+        },
+    );
+    let exit_code = target_exit.code();
+    if let Some(reason) = budget_stop {
+        exit_reason = reason;
+    }
+    // Stop taking trigger requests before teardown; this also drops the
+    // server's handle on the controller
+    if let Some(server) = rpc_server {
+        server.stop();
+    }
+
+    progress(options.json, &format!("\n{}", exit_reason));
+    if let Some(markers) = &markers {
+        progress(
+            options.json,
+            &format!(
+                "Detail triggers fired: {} ({} markers coalesced)",
+                markers.fired, markers.coalesced
+            ),
+        );
+    }
 
     // Stop recorder first (sends SIGTERM)
-    if let Some(mut child) = recorder_child {
-        stop_ada_recorder(&mut child)?;
+    if let Some(mut recorder) = recorder_child {
+        stop_ada_recorder(&mut recorder.child)?;
     }
 
     // Cleanup tracer
-    if voice {
-        let _ = map_tracer_result(controller.disarm_trigger());
-        let _ = map_tracer_result(controller.set_detail_enabled(false));
+    if voice || options.trigger_on_marker || options.rpc_address.is_some() {
+        let _ = map_tracer_result(tracer.lock().disarm_trigger());
+        let _ = map_tracer_result(tracer.lock().set_detail_enabled(false));
     }
 
-    if let Err(err) = controller.detach() {
+    if let Err(err) = tracer.finish() {
         eprintln!("Warning: failed to detach tracer ({err})");
     }
+
+    // Snapshot stats after detach (drain totals are final) but before teardown
+    let trace_stats = TraceStats::from(
+        controller
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_stats(),
+    );
     drop(controller);
 
-    // Encode voice to AAC if we have a WAV file
+    let stats_path = bundle_dir.join("tracer_stats.json");
+    let stats_json = serde_json::to_string_pretty(&trace_stats)?;
+    if let Err(e) = fs::write(&stats_path, stats_json) {
+        eprintln!("Warning: could not write {}: {}", stats_path.display(), e);
+    }
+
+    // Re-encode screen recording if a frame rate or size was requested
+    let screen_mp4 = bundle_dir.join("screen.mp4");
+    if screen
+        && screen_mp4.exists()
+        && (options.screen_fps.is_some() || options.screen_scale.is_some())
+    {
+        if let Err(e) = reencode_screen(&bundle_dir, options.screen_fps, options.screen_scale) {
+            eprintln!("Warning: Failed to re-encode screen recording: {}", e);
+        }
+    }
+
+    // Encode voice to AAC, and to the requested lossless format, if we have a WAV file
     let voice_wav = bundle_dir.join("voice.wav");
     if voice_wav.exists() {
-        if let Err(e) = encode_voice_to_aac(&bundle_dir) {
-            eprintln!("Warning: Failed to encode voice to AAC: {}", e);
+        let aac = VoiceEncoding::Aac {
+            bitrate_k: options.voice_bitrate_k,
+        };
+        if options.aac {
+            if let Err(e) = encode_voice(&bundle_dir, aac) {
+                eprintln!("Warning: Failed to encode voice to AAC: {}", e);
+            }
+        }
+        if options.voice_format != VoiceFormat::Wav {
+            match encode_voice(&bundle_dir, VoiceEncoding::Lossless(options.voice_format)) {
+                Ok(_) => {
+                    if let Err(e) = fs::remove_file(&voice_wav) {
+                        eprintln!("Warning: could not remove {}: {}", voice_wav.display(), e);
+                    }
+                }
+                Err(e) => eprintln!(
+                    "Warning: Failed to encode voice to {}, keeping voice.wav: {}",
+                    options.voice_format, e
+                ),
+            }
         }
     }
+    let voice_lossless = lossless_voice(&bundle_dir).filter(|_| voice);
+    let screen_recorded = screen && screen_mp4.exists();
 
     let finished_at_ms = current_time_ms();
-    let trace_session = find_latest_trace_session(&trace_root);
+    let mut trace_sessions = match find_trace_sessions(&trace_root) {
+        Ok(sessions) => {
+            if sessions.is_empty() {
+                eprintln!(
+                    "Warning: No trace session was recorded under {}",
+                    trace_root.display()
+                );
+            }
+            sessions
+        }
+        Err(e) => {
+            eprintln!("Warning: {:#}", e);
+            Vec::new()
+        }
+    };
+    let trace_session = trace_sessions.last().cloned();
+    if trace_sessions.len() < 2 {
+        trace_sessions.clear();
+    }
+
+    let mut failed_artifacts = Vec::new();
+    if recorder_failure.is_some() {
+        if screen {
+            failed_artifacts.push("screen".to_string());
+        }
+        if voice {
+            failed_artifacts.push("voice".to_string());
+        }
+    }
 
     // Write manifest
-    let manifest = BundleManifest {
+    let mut manifest = BundleManifest {
         version: 1,
         created_at_ms: now_ms,
         finished_at_ms,
@@ -368,90 +1368,352 @@ fn start_capture(
         trace_session: trace_session
             .as_ref()
             .map(|path| path_as_string(&bundle_dir, path)),
-        screen_path: if screen && bundle_dir.join("screen.mp4").exists() {
-            Some("screen.mp4".to_string())
-        } else {
-            None
-        },
-        voice_path: if voice && bundle_dir.join("voice.m4a").exists() {
-            Some("voice.m4a".to_string())
-        } else {
-            None
-        },
-        voice_lossless_path: if voice && voice_wav.exists() {
-            Some("voice.wav".to_string())
-        } else {
-            None
-        },
+        trace_sessions: trace_sessions
+            .iter()
+            .map(|path| path_as_string(&bundle_dir, path))
+            .collect(),
+        screen_path: screen_recorded.then(|| "screen.mp4".to_string()),
+        voice_path: None,
+        voice_lossless_path: None,
         detail_when_voice: voice,
+        screen_fps: options.screen_fps.filter(|_| screen),
+        screen_scale: options
+            .screen_scale
+            .filter(|_| screen)
+            .map(|scale| scale.to_string()),
+        voice_format: None,
+        voice_bitrate_k: None,
+        screen_duration_sec: screen_recorded
+            .then(|| duration_or_warn(&screen_mp4))
+            .flatten(),
+        voice_duration_sec: voice_lossless
+            .and_then(|format| duration_or_warn(&bundle_dir.join(format.file_name()))),
+        note: options.note.clone(),
+        trace_stats: Some(trace_stats),
+        failed_artifacts,
+        bundle_hash: bundle_hash_or_warn(&bundle_dir),
+        exit_code,
     };
+    manifest.record_voice(&bundle_dir, options);
 
     let manifest_path = bundle_dir.join("manifest.json");
     let manifest_json = serde_json::to_string_pretty(&manifest)?;
     fs::write(&manifest_path, manifest_json)
         .with_context(|| format!("Failed to write manifest at {}", manifest_path.display()))?;
 
+    if options.strict {
+        if let Some(failure) = &recorder_failure {
+            if let Ok(Some(mut session)) = session_state::get(&session_id) {
+                session.status = SessionStatus::Failed;
+                session.end_time = Some(chrono::Utc::now().to_rfc3339());
+                session.exit_code = exit_code;
+                let _ = session_state::update(&session_id, &session);
+            }
+            if options.json {
+                let summary = CaptureSummary {
+                    session_id: &session_id,
+                    bundle_path: &bundle_dir,
+                    manifest_path: &manifest_path,
+                    status: SessionStatus::Failed,
+                    exit_code,
+                };
+                println!("{}", summary.to_json()?);
+            }
+            bail!(
+                "Capture aborted: ada-recorder exited early ({}). Partial bundle at {}",
+                failure.status,
+                bundle_dir.display()
+            );
+        }
+    }
+
     notify_ready(&bundle_dir);
 
     // Mark session as complete
     if let Ok(Some(mut session)) = session_state::get(&session_id) {
         session.status = SessionStatus::Complete;
         session.end_time = Some(chrono::Utc::now().to_rfc3339());
+        session.exit_code = exit_code;
         let _ = session_state::update(&session_id, &session);
     }
 
-    println!("ADA Session Complete:");
-    println!("  ID: {}", session_id);
-    println!("  Bundle: {}", bundle_dir.display());
-    println!("  Manifest: {}", manifest_path.display());
+    if options.json {
+        let summary = CaptureSummary {
+            session_id: &session_id,
+            bundle_path: &bundle_dir,
+            manifest_path: &manifest_path,
+            status: SessionStatus::Complete,
+            exit_code,
+        };
+        println!("{}", summary.to_json()?);
+    } else {
+        println!("ADA Session Complete:");
+        println!("  ID: {}", session_id);
+        println!("  Bundle: {}", bundle_dir.display());
+        println!("  Manifest: {}", manifest_path.display());
+        println!("  Target: {}", describe_exit(target_exit));
+    }
     Ok(())
 }
 
-/// Wait for either Ctrl+C or target process termination
-fn wait_for_termination(running: &Arc<AtomicBool>, target_pid: u32) -> String {
+/// Hash the finalized bundle; failing only costs tamper detection, so warn
+fn bundle_hash_or_warn(bundle_dir: &Path) -> Option<String> {
+    match ada_cli::bundle_hash::compute(bundle_dir) {
+        Ok(hash) => Some(hash),
+        Err(e) => {
+            eprintln!("Warning: could not hash bundle: {:#}", e);
+            None
+        }
+    }
+}
+
+/// Probe a recording's length; the manifest just omits it if ffprobe can't
+fn duration_or_warn(path: &Path) -> Option<f64> {
+    match ada_cli::media_probe::duration_sec(path) {
+        Ok(seconds) => Some(seconds),
+        Err(e) => {
+            eprintln!("Warning: could not read duration: {:#}", e);
+            None
+        }
+    }
+}
+
+/// Environment variable overriding the capture loop's poll interval (ms)
+const POLL_INTERVAL_ENV: &str = "ADA_CAPTURE_POLL_MS";
+
+/// Default capture loop poll interval
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Bounds for `ADA_CAPTURE_POLL_MS`; the upper one keeps Ctrl+C responsive
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Hard ceiling on a capture's length, so a forgotten session can't run forever
+const MAX_CAPTURE_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Poll interval from a raw `ADA_CAPTURE_POLL_MS` value
+///
+/// Missing or unparsable values use the default; others are clamped to
+/// `MIN_POLL_INTERVAL..=MAX_POLL_INTERVAL`.
+fn parse_poll_interval(raw: Option<&str>) -> Duration {
+    match raw.map(|v| v.trim().parse::<u64>()) {
+        Some(Ok(ms)) => Duration::from_millis(ms).clamp(MIN_POLL_INTERVAL, MAX_POLL_INTERVAL),
+        Some(Err(_)) => {
+            eprintln!(
+                "Warning: ignoring invalid {} (expected milliseconds)",
+                POLL_INTERVAL_ENV
+            );
+            POLL_INTERVAL
+        }
+        None => POLL_INTERVAL,
+    }
+}
+
+/// Wait for Ctrl+C, target process termination, or (with `strict`) an early
+/// recorder exit
+///
+/// `on_tick` runs once per `poll_interval`. After `max_duration` the capture
+/// is force-stopped. Returns the exit reason, the recorder failure, if the
+/// recorder died while capture was still running, and how the target ended.
+///
+/// When the target exits without being our child, `waitpid` can't reap it
+/// and `collect_exit_code` is asked for the code instead.
+#[allow(clippy::too_many_arguments)]
+fn wait_for_termination(
+    running: &Arc<AtomicBool>,
+    target_pid: u32,
+    mut recorder: Option<&mut RecorderChild>,
+    strict: bool,
+    poll_interval: Duration,
+    max_duration: Duration,
+    on_tick: &mut dyn FnMut(),
+    collect_exit_code: &mut dyn FnMut() -> Option<i32>,
+) -> (String, Option<RecorderFailure>, TargetExit) {
+    let mut recorder_failure = None;
+    let started = Instant::now();
+
     loop {
+        on_tick();
+
         // Check Ctrl+C
         if !running.load(Ordering::SeqCst) {
-            return "User interrupted (Ctrl+C)".to_string();
-        }
-
-        // Check if target process is still alive using waitpid with WNOHANG
-        let mut status: i32 = 0;
-        let result = unsafe { libc::waitpid(target_pid as i32, &mut status, libc::WNOHANG) };
-
-        if result > 0 {
-            // Process state changed
-            if libc::WIFEXITED(status) {
-                let exit_code = libc::WEXITSTATUS(status);
-                return format!("Target process exited with code {}", exit_code);
-            } else if libc::WIFSIGNALED(status) {
-                let signal = libc::WTERMSIG(status);
-                let signal_name = match signal {
-                    libc::SIGTERM => "SIGTERM",
-                    libc::SIGKILL => "SIGKILL",
-                    libc::SIGSEGV => "SIGSEGV (crash)",
-                    libc::SIGABRT => "SIGABRT (abort)",
-                    libc::SIGBUS => "SIGBUS",
-                    libc::SIGFPE => "SIGFPE",
-                    libc::SIGILL => "SIGILL",
-                    _ => "unknown signal",
-                };
-                return format!("Target process killed by {} ({})", signal_name, signal);
+            return (
+                "User interrupted (Ctrl+C)".to_string(),
+                recorder_failure,
+                TargetExit::Running,
+            );
+        }
+
+        if started.elapsed() >= max_duration {
+            eprintln!(
+                "Warning: capture reached the {}s safety limit; stopping",
+                max_duration.as_secs()
+            );
+            return (
+                format!(
+                    "Capture stopped after reaching the {}s limit",
+                    max_duration.as_secs()
+                ),
+                recorder_failure,
+                TargetExit::Running,
+            );
+        }
+
+        // Check the recorder until it has failed once
+        if recorder_failure.is_none() {
+            if let Some(failure) = recorder
+                .as_deref_mut()
+                .and_then(RecorderChild::poll_failure)
+            {
+                warn_recorder_failure(&failure);
+                recorder_failure = Some(failure);
+                if strict {
+                    return (
+                        "ada-recorder exited early, aborting capture (--strict)".to_string(),
+                        recorder_failure,
+                        TargetExit::Running,
+                    );
+                }
             }
-        } else if result == -1 {
-            // Error - process might not be our child, use kill(0) to check
-            let alive = unsafe { libc::kill(target_pid as i32, 0) };
-            if alive != 0 {
-                return "Target process terminated".to_string();
+        }
+
+        if let Some((reason, exit)) = poll_target_exit(target_pid) {
+            if exit == TargetExit::Unknown {
+                if let Some(code) = collect_exit_code() {
+                    let reason = format!("Target process exited with code {}", code);
+                    return (reason, recorder_failure, TargetExit::Exited(code));
+                }
             }
+            return (reason, recorder_failure, exit);
         }
 
-        thread::sleep(Duration::from_millis(100));
+        thread::sleep(poll_interval);
+    }
+}
+
+/// Exit code recorded for a `waitpid` status: the exit status, or
+/// 128 + signal number (as a shell reports it) for a killed process
+///
+/// `None` for statuses that don't end the process (stopped, continued).
+fn status_exit_code(status: i32) -> Option<i32> {
+    if libc::WIFEXITED(status) {
+        Some(libc::WEXITSTATUS(status))
+    } else if libc::WIFSIGNALED(status) {
+        Some(128 + libc::WTERMSIG(status))
+    } else {
+        None
+    }
+}
+
+/// How the traced process ended, as far as the capture could tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetExit {
+    /// Still running when the capture stopped; the tracer detached from it
+    Running,
+    /// Exited with this code (128 + signal number for a killed process)
+    Exited(i32),
+    /// Exited, but its status couldn't be collected
+    Unknown,
+}
+
+impl TargetExit {
+    /// Exit code recorded in the session and manifest
+    fn code(self) -> Option<i32> {
+        match self {
+            TargetExit::Exited(code) => Some(code),
+            TargetExit::Running | TargetExit::Unknown => None,
+        }
+    }
+}
+
+/// How the traced process ended, for the completion summary
+fn describe_exit(exit: TargetExit) -> String {
+    match exit {
+        TargetExit::Running => "still running (detached)".to_string(),
+        TargetExit::Exited(code) => format!("exited {}", code),
+        TargetExit::Unknown => "terminated (exit code unknown)".to_string(),
+    }
+}
+
+/// How long to wait for the tracer to report a non-child target's exit code
+const EXIT_STATUS_WAIT: Duration = Duration::from_millis(500);
+
+/// Non-blocking check whether the target process has exited
+///
+/// Returns the exit reason and how the process ended; the exit code is
+/// unknown unless the process could be reaped.
+fn poll_target_exit(target_pid: u32) -> Option<(String, TargetExit)> {
+    // Check if target process is still alive using waitpid with WNOHANG
+    let mut status: i32 = 0;
+    let result = unsafe { libc::waitpid(target_pid as i32, &mut status, libc::WNOHANG) };
+
+    if result > 0 {
+        // Process state changed
+        let exit = status_exit_code(status).map_or(TargetExit::Unknown, TargetExit::Exited);
+        if libc::WIFEXITED(status) {
+            let code = libc::WEXITSTATUS(status);
+            return Some((format!("Target process exited with code {}", code), exit));
+        } else if libc::WIFSIGNALED(status) {
+            let signal = libc::WTERMSIG(status);
+            let signal_name = match signal {
+                libc::SIGTERM => "SIGTERM",
+                libc::SIGKILL => "SIGKILL",
+                libc::SIGSEGV => "SIGSEGV (crash)",
+                libc::SIGABRT => "SIGABRT (abort)",
+                libc::SIGBUS => "SIGBUS",
+                libc::SIGFPE => "SIGFPE",
+                libc::SIGILL => "SIGILL",
+                _ => "unknown signal",
+            };
+            return Some((
+                format!("Target process killed by {} ({})", signal_name, signal),
+                exit,
+            ));
+        }
+    } else if result == -1 {
+        // Error - process might not be our child, check it still exists
+        if !session_state::is_process_alive(target_pid) {
+            return Some(("Target process terminated".to_string(), TargetExit::Unknown));
+        }
+    }
+
+    None
+}
+
+/// Markers received since the main loop last checked
+static MARKER_COUNT: AtomicU64 = AtomicU64::new(0);
+
+extern "C" fn on_marker_signal(_signal: libc::c_int) {
+    MARKER_COUNT.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Count SIGUSR1 and, when stdin is a terminal, each Enter keypress as a marker
+fn install_marker_sources() {
+    unsafe {
+        libc::signal(
+            libc::SIGUSR1,
+            on_marker_signal as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
+    if std::io::stdin().is_terminal() {
+        thread::spawn(|| {
+            for line in std::io::stdin().lines() {
+                if line.is_err() {
+                    break;
+                }
+                MARKER_COUNT.fetch_add(1, Ordering::SeqCst);
+            }
+        });
     }
 }
 
 /// Start ada-recorder subprocess for screen and voice recording
-fn start_ada_recorder(bundle_dir: &Path, screen: bool, voice: bool) -> anyhow::Result<Child> {
+fn start_ada_recorder(
+    bundle_dir: &Path,
+    screen: bool,
+    voice: bool,
+) -> anyhow::Result<RecorderChild> {
     let recorder_path = find_ada_recorder()?;
 
     let mut cmd = Command::new(&recorder_path);
@@ -466,17 +1728,29 @@ fn start_ada_recorder(bundle_dir: &Path, screen: bool, voice: bool) -> anyhow::R
         cmd.arg("--no-voice");
     }
 
-    let child = cmd
-        .stdin(Stdio::null())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .with_context(|| format!("Failed to start ada-recorder at {}", recorder_path.display()))?;
+    let recorder =
+        RecorderChild::spawn(cmd, &bundle_dir.join(RECORDER_LOG)).with_context(|| {
+            format!(
+                "Failed to start ada-recorder at {}",
+                recorder_path.display()
+            )
+        })?;
 
     // Give recorder time to initialize
     thread::sleep(Duration::from_millis(500));
 
-    Ok(child)
+    Ok(recorder)
+}
+
+fn warn_recorder_failure(failure: &RecorderFailure) {
+    eprintln!(
+        "Warning: ada-recorder exited early ({}); screen/voice recording stopped",
+        failure.status
+    );
+    if !failure.log_tail.is_empty() {
+        eprintln!("--- last lines of {} ---", RECORDER_LOG);
+        eprintln!("{}", failure.log_tail);
+    }
 }
 
 /// Stop ada-recorder gracefully
@@ -510,6 +1784,132 @@ fn stop_ada_recorder(child: &mut Child) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Re-attach to a session's still-running target after a crashed capture
+fn resume_capture(session_id: &str) -> anyhow::Result<()> {
+    let mut session = session_state::get(session_id)?
+        .ok_or_else(|| anyhow::anyhow!("Session {} not found", session_id))?;
+    let target_pid = session.resume_target(session_state::is_process_alive)?;
+
+    ensure_agent_rpath()?;
+
+    let bundle_dir = session.session_path.clone();
+    let trace_root = bundle_dir.join("trace");
+    fs::create_dir_all(&trace_root).with_context(|| {
+        format!(
+            "Failed to create trace directory at {}",
+            trace_root.display()
+        )
+    })?;
+
+    session.status = SessionStatus::Running;
+    session.end_time = None;
+    session.capture_pid = Some(std::process::id());
+    session_state::update(session_id, &session)?;
+
+    println!("ADA Session Resumed:");
+    println!("  ID: {}", session_id);
+    println!("  Target PID: {}", target_pid);
+    println!("  Bundle: {}", bundle_dir.display());
+
+    let mut controller = map_tracer_result(TracerController::new(&trace_root))?;
+    map_tracer_result(retry(SPAWN_ATTEMPTS, SPAWN_BACKOFF, || {
+        controller.attach(target_pid)
+    }))?;
+    // The target is already running, so there is nothing to resume
+    let mut tracer = controller.session();
+    map_tracer_result(tracer.install_hooks())?;
+    map_tracer_result(tracer.arm_trigger(0, 0))?;
+    map_tracer_result(tracer.fire_trigger())?;
+    map_tracer_result(tracer.set_detail_enabled(false))?;
+
+    println!("Capture running. Press Ctrl+C to stop.");
+
+    let running = Arc::new(AtomicBool::new(true));
+    let running_flag = running.clone();
+    ctrlc::set_handler(move || {
+        running_flag.store(false, Ordering::SeqCst);
+    })?;
+
+    let (exit_reason, _, target_exit) = wait_for_termination(
+        &running,
+        target_pid,
+        None,
+        false,
+        parse_poll_interval(std::env::var(POLL_INTERVAL_ENV).ok().as_deref()),
+        MAX_CAPTURE_DURATION,
+        &mut || {},
+        &mut || tracer.wait_for_exit(Some(EXIT_STATUS_WAIT)).ok().flatten(),
+    );
+    let exit_code = target_exit.code();
+    println!("\n{}", exit_reason);
+
+    if let Err(err) = tracer.finish() {
+        eprintln!("Warning: failed to detach tracer ({err})");
+    }
+    let trace_stats = TraceStats::from(controller.get_stats());
+    drop(controller);
+
+    let mut trace_sessions = find_trace_sessions(&trace_root).unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}", e);
+        Vec::new()
+    });
+    let trace_session = trace_sessions.last().cloned();
+    if trace_sessions.len() < 2 {
+        trace_sessions.clear();
+    }
+
+    // Keep whatever the crashed capture's recorder left behind
+    let existing = |name: &str| bundle_dir.join(name).exists().then(|| name.to_string());
+    let voice_lossless = lossless_voice(&bundle_dir);
+    let manifest = BundleManifest {
+        version: 1,
+        created_at_ms: chrono::DateTime::parse_from_rfc3339(&session.start_time)
+            .map(|t| t.timestamp_millis() as u64)
+            .unwrap_or_else(|_| current_time_ms()),
+        finished_at_ms: current_time_ms(),
+        session_name: session_id.to_string(),
+        trace_root: path_as_string(&bundle_dir, &trace_root),
+        trace_session: trace_session
+            .as_ref()
+            .map(|path| path_as_string(&bundle_dir, path)),
+        trace_sessions: trace_sessions
+            .iter()
+            .map(|path| path_as_string(&bundle_dir, path))
+            .collect(),
+        screen_path: existing("screen.mp4"),
+        voice_path: existing("voice.m4a"),
+        voice_lossless_path: voice_lossless.map(|format| format.file_name().to_string()),
+        detail_when_voice: false,
+        screen_fps: None,
+        screen_scale: None,
+        voice_format: voice_lossless.map(|format| format.to_string()),
+        voice_bitrate_k: None,
+        screen_duration_sec: existing("screen.mp4")
+            .and_then(|name| duration_or_warn(&bundle_dir.join(name))),
+        voice_duration_sec: voice_lossless
+            .and_then(|format| duration_or_warn(&bundle_dir.join(format.file_name()))),
+        note: session.note.clone(),
+        trace_stats: Some(trace_stats),
+        failed_artifacts: Vec::new(),
+        bundle_hash: bundle_hash_or_warn(&bundle_dir),
+        exit_code,
+    };
+    let manifest_path = bundle_dir.join("manifest.json");
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write manifest at {}", manifest_path.display()))?;
+
+    session.status = SessionStatus::Complete;
+    session.end_time = Some(chrono::Utc::now().to_rfc3339());
+    session.exit_code = exit_code;
+    let _ = session_state::update(session_id, &session);
+
+    println!("ADA Session Complete:");
+    println!("  ID: {}", session_id);
+    println!("  Manifest: {}", manifest_path.display());
+    println!("  Target: {}", describe_exit(target_exit));
+    Ok(())
+}
+
 /// Stop a running capture session
 fn stop_capture(session_id: Option<String>) -> anyhow::Result<()> {
     // Find the session to stop
@@ -553,6 +1953,69 @@ fn stop_capture(session_id: Option<String>) -> anyhow::Result<()> {
 
 // LCOV_EXCL_STOP
 
+/// What the main loop should do with the flight recorder trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TriggerAction {
+    /// Fire the armed trigger for a new marker
+    Fire,
+    /// Post-roll elapsed: disarm and re-arm for the next marker
+    Rearm,
+}
+
+/// Trigger accounting for `--trigger-on-marker`
+///
+/// A marker fires the armed trigger; the flight recorder keeps the pre-roll
+/// already buffered and records for `post_roll` afterward. Once the post-roll
+/// has elapsed the trigger is re-armed. Markers received while a window is
+/// open are coalesced into it rather than queued.
+#[derive(Debug)]
+struct MarkerTriggers {
+    post_roll: Duration,
+    pending: u64,
+    window_end: Option<Instant>,
+    fired: u32,
+    coalesced: u64,
+}
+
+impl MarkerTriggers {
+    fn new(post_roll: Duration) -> Self {
+        Self {
+            post_roll,
+            pending: 0,
+            window_end: None,
+            fired: 0,
+            coalesced: 0,
+        }
+    }
+
+    /// Add markers received since the last call
+    fn record(&mut self, markers: u64) {
+        self.pending += markers;
+    }
+
+    /// Decide the next trigger action at `now`
+    fn poll(&mut self, now: Instant) -> Option<TriggerAction> {
+        if let Some(window_end) = self.window_end {
+            if now < window_end {
+                self.coalesced += std::mem::take(&mut self.pending);
+                return None;
+            }
+            self.window_end = None;
+            return Some(TriggerAction::Rearm);
+        }
+
+        if self.pending == 0 {
+            return None;
+        }
+
+        self.coalesced += self.pending - 1;
+        self.pending = 0;
+        self.fired += 1;
+        self.window_end = Some(now + self.post_roll);
+        Some(TriggerAction::Fire)
+    }
+}
+
 fn map_tracer_result<T, E>(result: Result<T, E>) -> anyhow::Result<T>
 where
     E: std::fmt::Display,
@@ -562,7 +2025,196 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{map_tracer_result, resolve_executable_path};
+    use super::{
+        budget_action, collect_prerequisites, describe_exit, ensure_writable_dir,
+        find_trace_sessions, map_tracer_result, notification_script, parse_poll_interval,
+        resolve_bundle_id, resolve_executable_path, retry, screen_reencode_args,
+        select_bundle_match, should_notify, status_exit_code, tail_lines, voice_encode_args,
+        wait_for_termination, BudgetPolicy, BundleManifest, CaptureSummary, MarkerTriggers,
+        PrerequisiteResolvers, RecorderChild, ScreenScale, SessionStatus, StartOptions, TargetExit,
+        TraceStats, TriggerAction, VoiceEncoding, VoiceFormat, MAX_POLL_INTERVAL,
+        MIN_POLL_INTERVAL, NO_NOTIFY_ENV, POLL_INTERVAL, VOICE_BITRATE_K,
+    };
+    use std::path::Path;
+    use std::process::Command;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Run `f` with `CI` and `ADA_NO_NOTIFY` set as given, restoring them afterwards
+    fn with_notify_env<R>(ci: Option<&str>, no_notify: Option<&str>, f: impl FnOnce() -> R) -> R {
+        let _guard = ada_cli::test_utils::ENV_MUTEX.lock().unwrap();
+        let vars = [("CI", ci), (NO_NOTIFY_ENV, no_notify)];
+        let originals: Vec<_> = vars
+            .iter()
+            .map(|(key, _)| (*key, std::env::var(key).ok()))
+            .collect();
+        for (key, value) in vars {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+
+        let result = f();
+
+        for (key, value) in originals {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn should_notify__interactive_without_overrides__then_true() {
+        assert!(with_notify_env(None, None, || should_notify(true)));
+    }
+
+    #[test]
+    fn should_notify__no_tty__then_false() {
+        assert!(!with_notify_env(None, None, || should_notify(false)));
+    }
+
+    #[test]
+    fn should_notify__ci_set__then_false() {
+        assert!(!with_notify_env(Some("true"), None, || should_notify(true)));
+    }
+
+    #[test]
+    fn should_notify__no_notify_set__then_false() {
+        assert!(!with_notify_env(None, Some("1"), || should_notify(true)));
+    }
+
+    #[test]
+    fn should_notify__empty_env_values__then_ignored() {
+        assert!(with_notify_env(Some(""), Some(""), || should_notify(true)));
+    }
+
+    #[test]
+    fn notification_script__quotes_in_path__then_escaped_and_path_is_body() {
+        let script = notification_script(Path::new("/tmp/my \"app\"/session_1"));
+        assert_eq!(
+            script,
+            r#"display notification "/tmp/my \"app\"/session_1" with title "ADA Capture" subtitle "Bundle ready""#
+        );
+    }
+
+    /// Closure that fails `failures` times before returning the call count
+    fn failing(failures: u32, calls: &mut u32) -> impl FnMut() -> Result<u32, String> + '_ {
+        move || {
+            *calls += 1;
+            if *calls <= failures {
+                Err(format!("transient failure {}", calls))
+            } else {
+                Ok(*calls)
+            }
+        }
+    }
+
+    #[test]
+    fn retry__fails_then_succeeds__then_returns_value() {
+        let mut calls = 0;
+
+        let result = retry(3, Duration::ZERO, failing(2, &mut calls));
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn retry__always_fails__then_last_error_after_all_attempts() {
+        let mut calls = 0;
+
+        let result = retry(3, Duration::ZERO, failing(u32::MAX, &mut calls));
+
+        assert_eq!(result, Err("transient failure 3".to_string()));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn retry__first_attempt_succeeds__then_no_delay() {
+        let mut calls = 0;
+        let start = Instant::now();
+
+        let result = retry(3, Duration::from_secs(10), failing(0, &mut calls));
+
+        assert_eq!(result, Ok(1));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn retry__backoff__then_doubles_between_attempts() {
+        let mut calls = 0;
+        let start = Instant::now();
+
+        let result = retry(3, Duration::from_millis(20), failing(2, &mut calls));
+
+        // 20ms before the second attempt, 40ms before the third
+        assert_eq!(result, Ok(3));
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[test]
+    fn retry__zero_attempts__then_tries_once() {
+        let mut calls = 0;
+
+        let result = retry(0, Duration::ZERO, failing(1, &mut calls));
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn parse_poll_interval__unset__then_default() {
+        assert_eq!(parse_poll_interval(None), POLL_INTERVAL);
+    }
+
+    #[test]
+    fn parse_poll_interval__valid_ms__then_used() {
+        assert_eq!(parse_poll_interval(Some("50")), Duration::from_millis(50));
+        assert_eq!(
+            parse_poll_interval(Some(" 250 ")),
+            Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn parse_poll_interval__out_of_range__then_clamped() {
+        assert_eq!(parse_poll_interval(Some("0")), MIN_POLL_INTERVAL);
+        assert_eq!(parse_poll_interval(Some("60000")), MAX_POLL_INTERVAL);
+    }
+
+    #[test]
+    fn parse_poll_interval__invalid__then_default() {
+        assert_eq!(parse_poll_interval(Some("fast")), POLL_INTERVAL);
+        assert_eq!(parse_poll_interval(Some("-5")), POLL_INTERVAL);
+    }
+
+    #[test]
+    fn wait_for_termination__max_duration_reached__then_force_stopped() {
+        let running = Arc::new(AtomicBool::new(true));
+        let mut ticks = 0;
+
+        // Our own pid never exits, so only the ceiling can end the loop
+        let (reason, failure, exit) = wait_for_termination(
+            &running,
+            std::process::id(),
+            None,
+            false,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+            &mut || ticks += 1,
+            &mut || None,
+        );
+
+        assert!(reason.contains("limit"), "{}", reason);
+        assert!(failure.is_none());
+        assert_eq!(exit, TargetExit::Running);
+        assert!(ticks >= 2);
+    }
 
     #[test]
     fn map_tracer_result_ok() {
@@ -583,6 +2235,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(target_os = "macos")]
     fn resolve_executable_path__app_bundle__then_resolved() {
         // Use Calculator.app as it exists on all macOS systems
         let result = resolve_executable_path("/System/Applications/Calculator.app").unwrap();
@@ -593,49 +2246,946 @@ mod tests {
     }
 
     #[test]
-    fn resolve_executable_path__nonexistent_app__then_error() {
-        let result = resolve_executable_path("/nonexistent/Fake.app");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Info.plist"));
+    #[cfg(target_os = "macos")]
+    fn resolve_bundle_id__calculator__then_app_path() {
+        let app = resolve_bundle_id("com.apple.calculator").unwrap();
+        assert!(app.ends_with("Calculator.app"), "{}", app);
     }
-}
 
-fn encode_voice_to_aac(bundle_dir: &Path) -> anyhow::Result<PathBuf> {
-    let ffmpeg = ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::Ffmpeg)
-        .context("ffmpeg not found")?;
-    let input = bundle_dir.join("voice.wav");
-    let output = bundle_dir.join("voice.m4a");
-
-    if !input.exists() {
-        anyhow::bail!("voice.wav not found at {}", input.display());
+    #[test]
+    fn resolve_bundle_id__malformed_id__then_error_without_searching() {
+        let err = resolve_bundle_id("com.example' || true").unwrap_err();
+        assert!(err.to_string().contains("Invalid bundle id"));
     }
 
-    let status = Command::new(ffmpeg)
-        .args([
-            "-y",
-            "-i",
-            input.to_str().unwrap_or_default(),
-            "-c:a",
-            "aac",
-            "-b:a",
-            "128k",
-        ])
-        .arg(&output)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .context("Failed to encode voice.m4a")?;
+    #[test]
+    fn select_bundle_match__single_app__then_returned() {
+        let candidates = vec![
+            "/Applications/Example.app".to_string(),
+            "/Applications/Example.app".to_string(),
+            "/Users/me/Library/Preferences/com.example.app.plist".to_string(),
+        ];
 
-    if !status.success() {
-        anyhow::bail!("ffmpeg failed while encoding voice.m4a");
+        let app = select_bundle_match("com.example.app", candidates).unwrap();
+
+        assert_eq!(app, "/Applications/Example.app");
     }
 
-    Ok(output)
-}
+    #[test]
+    fn select_bundle_match__no_or_multiple_apps__then_error() {
+        let err = select_bundle_match("com.example.app", Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("No installed app"));
+
+        let err = select_bundle_match(
+            "com.example.app",
+            vec![
+                "/Applications/Example.app".to_string(),
+                "/Users/me/Build/Example.app".to_string(),
+            ],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("matches 2 apps"));
+        assert!(err.to_string().contains("/Users/me/Build/Example.app"));
+    }
+
+    #[test]
+    fn resolve_executable_path__nonexistent_app__then_error() {
+        let result = resolve_executable_path("/nonexistent/Fake.app");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Info.plist"));
+    }
+
+    fn start_options(screen: bool, voice: bool) -> StartOptions {
+        StartOptions {
+            output_dir: None,
+            note: None,
+            screen,
+            voice,
+            pre_roll_ms: 0,
+            post_roll_ms: 0,
+            screen_fps: None,
+            screen_scale: None,
+            voice_format: VoiceFormat::Wav,
+            voice_bitrate_k: VOICE_BITRATE_K,
+            aac: true,
+            strict: false,
+            trigger_on_marker: false,
+            rpc_address: None,
+            max_trace_mb: None,
+            on_budget: BudgetPolicy::Stop,
+            spawn_attempts: 1,
+            json: false,
+        }
+    }
+
+    fn found(path: &str) -> anyhow::Result<std::path::PathBuf> {
+        Ok(std::path::PathBuf::from(path))
+    }
+
+    fn names(checks: &[super::Prerequisite]) -> Vec<&str> {
+        checks.iter().map(|check| check.name).collect()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn find_ada_recorder__not_macos__then_unsupported_error() {
+        let err = super::find_ada_recorder().unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "screen/voice capture is only supported on macOS"
+        );
+    }
+
+    #[test]
+    fn collect_prerequisites__trace_only__then_no_recorder_or_ffmpeg() {
+        let resolvers = PrerequisiteResolvers {
+            executable: &|binary| Ok(binary.to_string()),
+            agent: &|| Ok("/opt/ada/lib".to_string()),
+            recorder: &|| panic!("recorder not needed"),
+            ffmpeg: &|| panic!("ffmpeg not needed"),
+        };
+
+        let checks =
+            collect_prerequisites("/usr/bin/true", &start_options(false, false), &resolvers);
+
+        assert_eq!(names(&checks), vec!["binary", "frida agent"]);
+        assert!(checks.iter().all(|check| check.ok));
+        assert_eq!(checks[0].detail, "/usr/bin/true");
+    }
+
+    #[test]
+    fn collect_prerequisites__screen_and_voice__then_recorder_and_ffmpeg_checked() {
+        let resolvers = PrerequisiteResolvers {
+            executable: &|binary| Ok(binary.to_string()),
+            agent: &|| Ok("/opt/ada/lib".to_string()),
+            recorder: &|| found("/opt/ada/bin/ada-recorder"),
+            ffmpeg: &|| found("/opt/homebrew/bin/ffmpeg"),
+        };
+
+        let checks = collect_prerequisites("/usr/bin/true", &start_options(true, true), &resolvers);
+
+        assert_eq!(
+            names(&checks),
+            vec!["binary", "frida agent", "ada-recorder", "ffmpeg"]
+        );
+        assert_eq!(checks[3].detail, "/opt/homebrew/bin/ffmpeg");
+    }
+
+    #[test]
+    fn collect_prerequisites__voice_without_aac__then_no_ffmpeg() {
+        let resolvers = PrerequisiteResolvers {
+            executable: &|binary| Ok(binary.to_string()),
+            agent: &|| Ok("/opt/ada/lib".to_string()),
+            recorder: &|| found("/opt/ada/bin/ada-recorder"),
+            ffmpeg: &|| panic!("ffmpeg not needed"),
+        };
+        let mut options = start_options(false, true);
+        options.aac = false;
+
+        let checks = collect_prerequisites("/usr/bin/true", &options, &resolvers);
+
+        assert_eq!(
+            names(&checks),
+            vec!["binary", "frida agent", "ada-recorder"]
+        );
+    }
+
+    #[test]
+    fn bundle_manifest__record_voice_no_aac__then_only_lossless_path() {
+        let bundle = tempfile::tempdir().unwrap();
+        std::fs::write(bundle.path().join("voice.wav"), b"RIFF").unwrap();
+        std::fs::write(bundle.path().join("voice.m4a"), b"stale").unwrap();
+        let mut options = start_options(true, true);
+        let mut manifest = sample_manifest();
+
+        manifest.record_voice(bundle.path(), &options);
+        assert_eq!(manifest.voice_path.as_deref(), Some("voice.m4a"));
+        assert_eq!(manifest.voice_bitrate_k, Some(VOICE_BITRATE_K));
+
+        options.aac = false;
+        manifest.record_voice(bundle.path(), &options);
+        let json = serde_json::to_value(&manifest).unwrap();
+
+        assert!(json["voice_path"].is_null());
+        assert_eq!(json["voice_lossless_path"], "voice.wav");
+        assert_eq!(json["voice_format"], "wav");
+        assert!(json.get("voice_bitrate_k").is_none());
+    }
+
+    #[test]
+    fn collect_prerequisites__screen_reencode__then_ffmpeg_checked() {
+        let resolvers = PrerequisiteResolvers {
+            executable: &|binary| Ok(binary.to_string()),
+            agent: &|| Ok("/opt/ada/lib".to_string()),
+            recorder: &|| found("/opt/ada/bin/ada-recorder"),
+            ffmpeg: &|| found("/opt/homebrew/bin/ffmpeg"),
+        };
+        let mut options = start_options(true, false);
+
+        let without = collect_prerequisites("/usr/bin/true", &options, &resolvers);
+        options.screen_fps = Some(10);
+        let with = collect_prerequisites("/usr/bin/true", &options, &resolvers);
+
+        assert_eq!(
+            names(&without),
+            vec!["binary", "frida agent", "ada-recorder"]
+        );
+        assert_eq!(
+            names(&with),
+            vec!["binary", "frida agent", "ada-recorder", "ffmpeg"]
+        );
+    }
+
+    #[test]
+    fn collect_prerequisites__missing_tools__then_failures_reported() {
+        let resolvers = PrerequisiteResolvers {
+            executable: &|_| anyhow::bail!("Info.plist not found"),
+            agent: &|| Ok("/opt/ada/lib".to_string()),
+            recorder: &|| anyhow::bail!("ada-recorder not found"),
+            ffmpeg: &|| found("/opt/homebrew/bin/ffmpeg"),
+        };
+
+        let checks =
+            collect_prerequisites("/Apps/Fake.app", &start_options(true, true), &resolvers);
+
+        let failed: Vec<_> = checks.iter().filter(|check| !check.ok).collect();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].name, "binary");
+        assert!(failed[0].detail.contains("Info.plist"));
+        assert_eq!(failed[1].name, "ada-recorder");
+    }
+
+    fn sample_manifest() -> BundleManifest {
+        BundleManifest {
+            version: 1,
+            created_at_ms: 1_000,
+            finished_at_ms: 2_000,
+            session_name: "session_test".to_string(),
+            trace_root: "trace".to_string(),
+            trace_session: Some("trace/session_1/pid_1".to_string()),
+            trace_sessions: Vec::new(),
+            screen_path: Some("screen.mp4".to_string()),
+            voice_path: None,
+            voice_lossless_path: None,
+            detail_when_voice: false,
+            screen_fps: None,
+            screen_scale: None,
+            voice_format: None,
+            voice_bitrate_k: None,
+            screen_duration_sec: None,
+            voice_duration_sec: None,
+            note: None,
+            trace_stats: None,
+            failed_artifacts: Vec::new(),
+            bundle_hash: None,
+            exit_code: None,
+        }
+    }
+
+    #[test]
+    fn marker_triggers__single_marker__then_fire_then_rearm_after_post_roll() {
+        let start = Instant::now();
+        let mut markers = MarkerTriggers::new(Duration::from_millis(500));
+
+        assert_eq!(markers.poll(start), None);
+
+        markers.record(1);
+        assert_eq!(markers.poll(start), Some(TriggerAction::Fire));
+        assert_eq!(markers.poll(start + Duration::from_millis(100)), None);
+        assert_eq!(
+            markers.poll(start + Duration::from_millis(500)),
+            Some(TriggerAction::Rearm)
+        );
+        assert_eq!(markers.poll(start + Duration::from_millis(600)), None);
+        assert_eq!(markers.fired, 1);
+        assert_eq!(markers.coalesced, 0);
+    }
+
+    #[test]
+    fn marker_triggers__markers_inside_window__then_coalesced() {
+        let start = Instant::now();
+        let mut markers = MarkerTriggers::new(Duration::from_millis(500));
+
+        // Two markers in the same tick fire once
+        markers.record(2);
+        assert_eq!(markers.poll(start), Some(TriggerAction::Fire));
+
+        markers.record(3);
+        assert_eq!(markers.poll(start + Duration::from_millis(200)), None);
+
+        assert_eq!(markers.fired, 1);
+        assert_eq!(markers.coalesced, 4);
+    }
+
+    #[test]
+    fn marker_triggers__marker_after_rearm__then_fires_again() {
+        let start = Instant::now();
+        let mut markers = MarkerTriggers::new(Duration::ZERO);
+
+        markers.record(1);
+        assert_eq!(markers.poll(start), Some(TriggerAction::Fire));
+
+        // A marker queued during the re-arm tick waits for the next tick
+        markers.record(1);
+        assert_eq!(markers.poll(start), Some(TriggerAction::Rearm));
+        assert_eq!(markers.poll(start), Some(TriggerAction::Fire));
+
+        assert_eq!(markers.fired, 2);
+        assert_eq!(markers.coalesced, 0);
+    }
+
+    #[test]
+    fn recorder_child__exits_immediately__then_failure_with_log_tail() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let log_path = temp_dir.path().join("recorder.log");
+        let mut cmd = Command::new("sh");
+        cmd.args([
+            "-c",
+            "echo 'Error: screen recording permission denied' >&2; exit 3",
+        ]);
+
+        let mut recorder = RecorderChild::spawn(cmd, &log_path).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let failure = loop {
+            if let Some(failure) = recorder.poll_failure() {
+                break failure;
+            }
+            assert!(Instant::now() < deadline, "recorder exit not detected");
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        assert_eq!(failure.status.code(), Some(3));
+        assert!(failure.log_tail.contains("permission denied"));
+    }
+
+    #[test]
+    fn recorder_child__exits_cleanly__then_no_failure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut recorder =
+            RecorderChild::spawn(Command::new("true"), &temp_dir.path().join("recorder.log"))
+                .unwrap();
+
+        recorder.child.wait().unwrap();
+        assert!(recorder.poll_failure().is_none());
+    }
+
+    #[test]
+    fn capture_summary__complete_session__then_single_json_object() {
+        let summary = CaptureSummary {
+            session_id: "session_2026_01_24_14_56_19_a1b2c3",
+            bundle_path: Path::new("/tmp/ada/session_2026_01_24_14_56_19_a1b2c3"),
+            manifest_path: Path::new("/tmp/ada/session_2026_01_24_14_56_19_a1b2c3/manifest.json"),
+            status: SessionStatus::Complete,
+            exit_code: None,
+        };
+
+        let json = summary.to_json().unwrap();
+
+        assert!(!json.contains('\n'), "one line: {}", json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "session_id": "session_2026_01_24_14_56_19_a1b2c3",
+                "bundle_path": "/tmp/ada/session_2026_01_24_14_56_19_a1b2c3",
+                "manifest_path": "/tmp/ada/session_2026_01_24_14_56_19_a1b2c3/manifest.json",
+                "status": "complete",
+            })
+        );
+    }
+
+    #[test]
+    fn tail_lines__more_than_count__then_last_lines_only() {
+        let text = "one\ntwo\n\nthree\nfour\n";
+        assert_eq!(tail_lines(text, 2), "three\nfour");
+        assert_eq!(tail_lines(text, 10), "one\ntwo\nthree\nfour");
+    }
+
+    #[test]
+    fn bundle_manifest__failed_artifacts__then_serialized_only_when_present() {
+        let mut manifest = sample_manifest();
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert!(json.get("failed_artifacts").is_none());
+
+        manifest.failed_artifacts = vec!["screen".to_string(), "voice".to_string()];
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            json["failed_artifacts"],
+            serde_json::json!(["screen", "voice"])
+        );
+    }
+
+    #[test]
+    fn find_trace_sessions__two_sessions__then_all_oldest_first() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let trace_root = temp.path().join("trace");
+        for dir in [
+            "session_2/pid_7",
+            "session_1/pid_7",
+            "session_1/pid_9",
+            "other",
+        ] {
+            std::fs::create_dir_all(trace_root.join(dir)).unwrap();
+        }
+
+        let sessions = find_trace_sessions(&trace_root).unwrap();
+
+        assert_eq!(
+            sessions,
+            vec![
+                trace_root.join("session_1/pid_7"),
+                trace_root.join("session_1/pid_9"),
+                trace_root.join("session_2/pid_7"),
+            ]
+        );
+        assert!(find_trace_sessions(&temp.path().join("missing"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn find_trace_sessions__empty_root__then_ok_empty() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let sessions = find_trace_sessions(temp.path()).unwrap();
+
+        assert!(sessions.is_empty());
+    }
+
+    #[test]
+    fn find_trace_sessions__one_session_without_pid_dirs__then_session_itself() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("session_1")).unwrap();
+
+        let sessions = find_trace_sessions(temp.path()).unwrap();
+
+        assert_eq!(sessions, vec![temp.path().join("session_1")]);
+    }
+
+    #[test]
+    fn find_trace_sessions__root_unreadable__then_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let not_a_dir = temp.path().join("trace");
+        std::fs::write(&not_a_dir, b"").unwrap();
+
+        let err = find_trace_sessions(&not_a_dir).unwrap_err();
+
+        assert!(err.to_string().contains("Failed to read trace root"));
+    }
+
+    #[test]
+    fn bundle_manifest__trace_sessions__then_serialized_only_when_present() {
+        let mut manifest = sample_manifest();
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert!(json.get("trace_sessions").is_none());
+
+        manifest.trace_sessions = vec![
+            "trace/session_1/pid_1".to_string(),
+            "trace/session_2/pid_1".to_string(),
+        ];
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            json["trace_sessions"],
+            serde_json::json!(["trace/session_1/pid_1", "trace/session_2/pid_1"])
+        );
+    }
+
+    #[test]
+    fn bundle_manifest__trace_stats__then_serialized_as_object() {
+        let mut manifest = sample_manifest();
+        manifest.trace_stats = Some(TraceStats {
+            events_captured: 1200,
+            events_dropped: 3,
+            bytes_written: 65_536,
+            hooks_installed: 42,
+            fallback_events: 7,
+            preroll_evicted: 2,
+        });
+
+        let value = serde_json::to_value(&manifest).unwrap();
+        let stats = &value["trace_stats"];
+        assert_eq!(stats["events_captured"], 1200);
+        assert_eq!(stats["events_dropped"], 3);
+        assert_eq!(stats["hooks_installed"], 42);
+        assert_eq!(stats["fallback_events"], 7);
+        assert_eq!(stats["preroll_evicted"], 2);
+
+        let loaded: BundleManifest = serde_json::from_value(value).unwrap();
+        assert_eq!(loaded.trace_stats, manifest.trace_stats);
+    }
+
+    #[test]
+    fn trace_stats__tracer_stats_file_shape__then_parses() {
+        // Shape written by the standalone tracer's tracer_stats.json
+        let json = r#"{
+  "events_captured": 10,
+  "events_dropped": 0,
+  "bytes_written": 512,
+  "hooks_installed": 4,
+  "fallback_events": 0
+}"#;
+        let stats: TraceStats = serde_json::from_str(json).unwrap();
+        assert_eq!(stats.events_captured, 10);
+        assert_eq!(stats.bytes_written, 512);
+        assert_eq!(stats.hooks_installed, 4);
+    }
+
+    #[test]
+    fn bundle_manifest__note_roundtrip__then_preserved() {
+        let mut manifest = sample_manifest();
+        manifest.note = Some("checkout flow, second attempt".to_string());
+
+        let json = serde_json::to_string_pretty(&manifest).unwrap();
+        assert!(json.contains("\"note\": \"checkout flow, second attempt\""));
+
+        let loaded: BundleManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.note.as_deref(),
+            Some("checkout flow, second attempt")
+        );
+        assert_eq!(loaded.session_name, "session_test");
+    }
+
+    #[test]
+    fn bundle_manifest__no_note__then_field_omitted() {
+        let json = serde_json::to_string(&sample_manifest()).unwrap();
+        assert!(!json.contains("note"));
+        assert!(!json.contains("screen_fps"));
+
+        let loaded: BundleManifest = serde_json::from_str(&json).unwrap();
+        assert!(loaded.note.is_none());
+    }
+
+    #[test]
+    fn bundle_manifest__exit_code__then_serialized_only_when_known() {
+        let mut manifest = sample_manifest();
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert!(json.get("exit_code").is_none());
+
+        manifest.exit_code = Some(139);
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(json.contains("\"exit_code\":139"));
+        let loaded: BundleManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.exit_code, Some(139));
+    }
+
+    #[test]
+    fn status_exit_code__wait_statuses__then_exit_or_signal_code() {
+        // waitpid encodes an exit status in the second byte and a fatal
+        // signal in the low seven bits
+        assert_eq!(status_exit_code(0), Some(0));
+        assert_eq!(status_exit_code(3 << 8), Some(3));
+        assert_eq!(status_exit_code(libc::SIGSEGV), Some(128 + libc::SIGSEGV));
+        // Stopped by SIGSTOP: still alive
+        assert_eq!(status_exit_code((libc::SIGSTOP << 8) | 0x7f), None);
+    }
+
+    #[test]
+    fn describe_exit__each_outcome__then_distinct_label() {
+        assert_eq!(describe_exit(TargetExit::Exited(0)), "exited 0");
+        assert_eq!(describe_exit(TargetExit::Exited(139)), "exited 139");
+        assert_eq!(
+            describe_exit(TargetExit::Running),
+            "still running (detached)"
+        );
+        assert_eq!(
+            describe_exit(TargetExit::Unknown),
+            "terminated (exit code unknown)"
+        );
+    }
+
+    #[test]
+    fn target_exit__code__then_only_known_exits_recorded() {
+        assert_eq!(TargetExit::Exited(2).code(), Some(2));
+        assert_eq!(TargetExit::Running.code(), None);
+        assert_eq!(TargetExit::Unknown.code(), None);
+    }
 
-fn find_latest_trace_session(trace_root: &Path) -> Option<PathBuf> {
-    let mut sessions: Vec<PathBuf> = fs::read_dir(trace_root)
-        .ok()?
+    /// Pid of a process that has exited and was already reaped, so
+    /// `waitpid` fails for it like for a target that isn't our child
+    fn reaped_pid() -> u32 {
+        let mut child = Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+        child.id()
+    }
+
+    #[test]
+    fn wait_for_termination__unreapable_target_exited__then_code_from_collector() {
+        let running = Arc::new(AtomicBool::new(true));
+
+        let (reason, _, exit) = wait_for_termination(
+            &running,
+            reaped_pid(),
+            None,
+            false,
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+            &mut || {},
+            &mut || Some(3),
+        );
+
+        assert_eq!(exit, TargetExit::Exited(3));
+        assert_eq!(reason, "Target process exited with code 3");
+    }
+
+    #[test]
+    fn wait_for_termination__unreapable_target_code_unavailable__then_unknown_not_running() {
+        let running = Arc::new(AtomicBool::new(true));
+
+        let (reason, _, exit) = wait_for_termination(
+            &running,
+            reaped_pid(),
+            None,
+            false,
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+            &mut || {},
+            &mut || None,
+        );
+
+        assert_eq!(exit, TargetExit::Unknown);
+        assert_eq!(reason, "Target process terminated");
+    }
+
+    #[test]
+    fn capture_summary__exit_code__then_included_in_json() {
+        let summary = CaptureSummary {
+            session_id: "session_exit",
+            bundle_path: Path::new("/tmp/ada/session_exit"),
+            manifest_path: Path::new("/tmp/ada/session_exit/manifest.json"),
+            status: SessionStatus::Complete,
+            exit_code: Some(2),
+        };
+
+        let value: serde_json::Value = serde_json::from_str(&summary.to_json().unwrap()).unwrap();
+        assert_eq!(value["exit_code"], 2);
+    }
+
+    #[test]
+    fn bundle_manifest__durations__then_serialized_only_when_probed() {
+        let mut manifest = sample_manifest();
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert!(json.get("screen_duration_sec").is_none());
+        assert!(json.get("voice_duration_sec").is_none());
+
+        manifest.screen_duration_sec = Some(12.5);
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["screen_duration_sec"], 12.5);
+        assert!(json.get("voice_duration_sec").is_none());
+    }
+
+    #[test]
+    fn ensure_writable_dir__missing_dir__then_created() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("artifacts/capture");
+        ensure_writable_dir(&dir).unwrap();
+        assert!(dir.is_dir());
+        // Probe file must not be left behind
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn ensure_writable_dir__path_is_file__then_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("not_a_dir");
+        std::fs::write(&file, b"x").unwrap();
+        assert!(ensure_writable_dir(&file).is_err());
+    }
+
+    #[test]
+    fn screen_scale__parse_size__then_width_height() {
+        let scale: ScreenScale = "1280:720".parse().unwrap();
+        assert_eq!(
+            scale,
+            ScreenScale::Size {
+                width: 1280,
+                height: 720
+            }
+        );
+        assert_eq!(scale.to_string(), "1280:720");
+    }
+
+    #[test]
+    fn screen_scale__parse_percent__then_percent() {
+        assert_eq!(
+            "50%".parse::<ScreenScale>().unwrap(),
+            ScreenScale::Percent(50)
+        );
+        assert_eq!(
+            "25".parse::<ScreenScale>().unwrap(),
+            ScreenScale::Percent(25)
+        );
+    }
+
+    #[test]
+    fn screen_scale__parse_invalid__then_error() {
+        assert!("0:720".parse::<ScreenScale>().is_err());
+        assert!("150%".parse::<ScreenScale>().is_err());
+        assert!("wide".parse::<ScreenScale>().is_err());
+    }
+
+    #[test]
+    fn screen_reencode_args__fps_and_scale__then_single_filter_chain() {
+        let args = screen_reencode_args(
+            Path::new("/b/screen.mp4"),
+            Path::new("/b/screen.reencode.mp4"),
+            Some(10),
+            Some(ScreenScale::Size {
+                width: 1280,
+                height: 720,
+            }),
+        );
+        let args: Vec<String> = args
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+
+        let vf = args.iter().position(|a| a == "-vf").expect("has -vf");
+        assert_eq!(args[vf + 1], "fps=10,scale=1280:720");
+        assert_eq!(args[2], "/b/screen.mp4");
+        assert_eq!(
+            args.last().map(String::as_str),
+            Some("/b/screen.reencode.mp4")
+        );
+    }
+
+    #[test]
+    fn screen_reencode_args__fps_only__then_no_scale_filter() {
+        let args = screen_reencode_args(Path::new("in.mp4"), Path::new("out.mp4"), Some(5), None);
+        let vf = args.iter().position(|a| a == "-vf").expect("has -vf");
+        assert_eq!(args[vf + 1], "fps=5");
+    }
+
+    #[test]
+    fn screen_reencode_args__percent_scale__then_even_dimensions() {
+        let args = screen_reencode_args(
+            Path::new("in.mp4"),
+            Path::new("out.mp4"),
+            None,
+            Some(ScreenScale::Percent(50)),
+        );
+        let vf = args.iter().position(|a| a == "-vf").expect("has -vf");
+        assert_eq!(args[vf + 1], "scale=trunc(iw*50/200)*2:trunc(ih*50/200)*2");
+    }
+
+    #[test]
+    fn voice_encode_args__each_encoding__then_matching_codec_and_output() {
+        let cases = [
+            (
+                VoiceEncoding::Aac { bitrate_k: 64 },
+                "/b/voice.m4a",
+                &["-c:a", "aac", "-b:a", "64k"][..],
+            ),
+            (
+                VoiceEncoding::Lossless(VoiceFormat::Wav),
+                "/b/voice.wav",
+                &["-c:a", "pcm_s16le"][..],
+            ),
+            (
+                VoiceEncoding::Lossless(VoiceFormat::Flac),
+                "/b/voice.flac",
+                &["-c:a", "flac"][..],
+            ),
+        ];
+
+        for (encoding, output, codec) in cases {
+            let args = voice_encode_args(Path::new("/b/voice.wav"), Path::new(output), encoding);
+            let args: Vec<String> = args
+                .iter()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect();
+
+            assert_eq!(args[..3], ["-y", "-i", "/b/voice.wav"], "{:?}", encoding);
+            assert_eq!(args[3..args.len() - 1], *codec, "{:?}", encoding);
+            assert_eq!(args.last().map(String::as_str), Some(output));
+        }
+    }
+
+    #[test]
+    fn voice_format__parse_and_display__then_round_trips() {
+        assert_eq!("FLAC".parse::<VoiceFormat>(), Ok(VoiceFormat::Flac));
+        assert_eq!("wav".parse::<VoiceFormat>(), Ok(VoiceFormat::Wav));
+        assert!("mp3".parse::<VoiceFormat>().is_err());
+        assert_eq!(VoiceFormat::Flac.to_string(), "flac");
+    }
+
+    #[test]
+    fn budget_policy__parse_and_display__then_round_trips() {
+        assert_eq!("Stop".parse::<BudgetPolicy>(), Ok(BudgetPolicy::Stop));
+        assert_eq!(
+            "throttle".parse::<BudgetPolicy>(),
+            Ok(BudgetPolicy::Throttle)
+        );
+        assert!("pause".parse::<BudgetPolicy>().is_err());
+        assert_eq!(BudgetPolicy::Throttle.to_string(), "throttle");
+    }
+
+    #[test]
+    fn budget_action__within_budget__then_none() {
+        let mib = 1024 * 1024;
+
+        assert_eq!(budget_action(0, 10, BudgetPolicy::Stop, false), None);
+        assert_eq!(budget_action(10 * mib, 10, BudgetPolicy::Stop, false), None);
+        assert_eq!(
+            budget_action(10 * mib, 10, BudgetPolicy::Throttle, false),
+            None
+        );
+    }
+
+    #[test]
+    fn budget_action__over_budget__then_policy_action() {
+        let over = 10 * 1024 * 1024 + 1;
+
+        assert_eq!(
+            budget_action(over, 10, BudgetPolicy::Stop, false),
+            Some(BudgetPolicy::Stop)
+        );
+        assert_eq!(
+            budget_action(over, 10, BudgetPolicy::Throttle, false),
+            Some(BudgetPolicy::Throttle)
+        );
+    }
+
+    #[test]
+    fn budget_action__already_throttled__then_not_repeated() {
+        let over = 20 * 1024 * 1024;
+
+        assert_eq!(budget_action(over, 10, BudgetPolicy::Throttle, true), None);
+        assert_eq!(
+            budget_action(over, 10, BudgetPolicy::Stop, true),
+            Some(BudgetPolicy::Stop)
+        );
+    }
+}
+
+/// The lossless voice track present in `bundle_dir`, if any
+fn lossless_voice(bundle_dir: &Path) -> Option<VoiceFormat> {
+    [VoiceFormat::Flac, VoiceFormat::Wav]
+        .into_iter()
+        .find(|format| bundle_dir.join(format.file_name()).exists())
+}
+
+/// Build ffmpeg arguments that encode the recorded voice track into `output`.
+fn voice_encode_args(input: &Path, output: &Path, encoding: VoiceEncoding) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec!["-y".into(), "-i".into(), input.into()];
+    match encoding {
+        VoiceEncoding::Aac { bitrate_k } => {
+            args.extend(["-c:a", "aac", "-b:a"].map(OsString::from));
+            args.push(format!("{}k", bitrate_k).into());
+        }
+        VoiceEncoding::Lossless(VoiceFormat::Wav) => {
+            args.extend(["-c:a", "pcm_s16le"].map(OsString::from));
+        }
+        VoiceEncoding::Lossless(VoiceFormat::Flac) => {
+            args.extend(["-c:a", "flac"].map(OsString::from));
+        }
+    }
+    args.push(output.into());
+    args
+}
+
+// LCOV_EXCL_START - Requires ffmpeg and a real voice recording
+fn encode_voice(bundle_dir: &Path, encoding: VoiceEncoding) -> anyhow::Result<PathBuf> {
+    let ffmpeg = ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::Ffmpeg)
+        .context("ffmpeg not found")?;
+    let input = bundle_dir.join("voice.wav");
+    let output = bundle_dir.join(encoding.file_name());
+
+    if !input.exists() {
+        anyhow::bail!("voice.wav not found at {}", input.display());
+    }
+
+    let status = Command::new(ffmpeg)
+        .args(voice_encode_args(&input, &output, encoding))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .with_context(|| format!("Failed to encode {}", encoding.file_name()))?;
+
+    if !status.success() {
+        let _ = fs::remove_file(&output);
+        anyhow::bail!("ffmpeg failed while encoding {}", encoding.file_name());
+    }
+
+    Ok(output)
+}
+// LCOV_EXCL_STOP
+
+/// Build ffmpeg arguments that re-encode a screen recording at the requested
+/// frame rate and/or size.
+fn screen_reencode_args(
+    input: &Path,
+    output: &Path,
+    fps: Option<u32>,
+    scale: Option<ScreenScale>,
+) -> Vec<OsString> {
+    let mut filters = Vec::new();
+    if let Some(fps) = fps {
+        filters.push(format!("fps={}", fps));
+    }
+    if let Some(scale) = scale {
+        filters.push(scale.to_filter());
+    }
+
+    let mut args: Vec<OsString> = vec!["-y".into(), "-i".into(), input.into()];
+    if !filters.is_empty() {
+        args.push("-vf".into());
+        args.push(filters.join(",").into());
+    }
+    args.extend(["-c:v", "libx264", "-pix_fmt", "yuv420p", "-an"].map(OsString::from));
+    args.push(output.into());
+    args
+}
+
+// LCOV_EXCL_START - Requires ffmpeg and a real screen recording
+fn reencode_screen(
+    bundle_dir: &Path,
+    fps: Option<u32>,
+    scale: Option<ScreenScale>,
+) -> anyhow::Result<PathBuf> {
+    let ffmpeg = ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::Ffmpeg)
+        .context("ffmpeg not found")?;
+    let input = bundle_dir.join("screen.mp4");
+    let temp = bundle_dir.join("screen.reencode.mp4");
+
+    let output = Command::new(ffmpeg)
+        .args(screen_reencode_args(&input, &temp, fps, scale))
+        .stdout(Stdio::null())
+        .output()
+        .context("Failed to re-encode screen.mp4")?;
+
+    if !output.status.success() {
+        let _ = fs::remove_file(&temp);
+        anyhow::bail!(
+            "ffmpeg failed while re-encoding screen.mp4: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fs::rename(&temp, &input).with_context(|| format!("Failed to replace {}", input.display()))?;
+    Ok(input)
+}
+// LCOV_EXCL_STOP
+
+/// All trace session directories under `trace_root`, oldest first
+///
+/// Each `session_*` directory contributes its PID subdirectories, or itself
+/// when it has none. The last entry is the primary (latest) session.
+///
+/// A missing trace root means no session has been written yet and yields an
+/// empty list; any other failure to read it is an error.
+fn find_trace_sessions(trace_root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(trace_root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read trace root {}", trace_root.display()))
+        }
+    };
+    let mut sessions: Vec<PathBuf> = entries
         .filter_map(|entry| entry.ok())
         .map(|entry| entry.path())
         .filter(|path| {
@@ -645,28 +3195,44 @@ fn find_latest_trace_session(trace_root: &Path) -> Option<PathBuf> {
                 .unwrap_or(false)
         })
         .collect();
-
     sessions.sort();
-    let session_dir = sessions.pop()?;
-
-    let mut pid_dirs: Vec<PathBuf> = fs::read_dir(&session_dir)
-        .ok()?
-        .filter_map(|entry| entry.ok())
-        .map(|entry| entry.path())
-        .filter(|path| path.is_dir())
-        .collect();
 
-    pid_dirs.sort();
-    pid_dirs.pop().or(Some(session_dir))
+    let mut found = Vec::new();
+    for session_dir in sessions {
+        let mut pid_dirs: Vec<PathBuf> = fs::read_dir(&session_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default();
+        pid_dirs.sort();
+
+        if pid_dirs.is_empty() {
+            found.push(session_dir);
+        } else {
+            found.extend(pid_dirs);
+        }
+    }
+    Ok(found)
 }
 
+/// Environment variable that disables the desktop notification when set
+const NO_NOTIFY_ENV: &str = "ADA_NO_NOTIFY";
+
 fn notify_ready(bundle_dir: &Path) {
-    let message = format!("Bundle ready: {}", bundle_dir.display());
-    let script = format!(
-        "display notification \"{}\" with title \"ADA Capture\"",
-        message.replace('"', "'")
-    );
+    let has_tty = fs::File::open("/dev/tty").is_ok();
+    if !should_notify(has_tty) {
+        return;
+    }
+
+    post_notification(&notification_script(bundle_dir));
+}
 
+#[cfg(target_os = "macos")]
+fn post_notification(script: &str) {
     let _ = Command::new("osascript")
         .arg("-e")
         .arg(script)
@@ -675,6 +3241,27 @@ fn notify_ready(bundle_dir: &Path) {
         .status();
 }
 
+/// No desktop notifier to drive; the bundle path is printed on completion
+#[cfg(not(target_os = "macos"))]
+fn post_notification(_script: &str) {}
+
+/// Whether a desktop notification is wanted: only with a controlling
+/// terminal, and never under CI or when `ADA_NO_NOTIFY` is set
+fn should_notify(has_tty: bool) -> bool {
+    let env_set = |key: &str| std::env::var_os(key).is_some_and(|v| !v.is_empty());
+    has_tty && !env_set("CI") && !env_set(NO_NOTIFY_ENV)
+}
+
+/// AppleScript posting the bundle path as the notification body on its own,
+/// so it can be copied verbatim (the path is also printed on completion)
+fn notification_script(bundle_dir: &Path) -> String {
+    let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        "display notification \"{}\" with title \"ADA Capture\" subtitle \"Bundle ready\"",
+        escape(&bundle_dir.display().to_string())
+    )
+}
+
 fn path_as_string(bundle_dir: &Path, path: &Path) -> String {
     path.strip_prefix(bundle_dir)
         .unwrap_or(path)
@@ -9,6 +9,7 @@
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
 use std::fs;
 use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
@@ -96,10 +97,79 @@ struct BundleManifest {
     segment_start_ms: u64,
     segment_end_ms: u64,
     segment_index: u32,
+    /// Recorder that produced `screen_path` ("screencapture" or "ffmpeg")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    screen_backend: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bundle_hash: Option<String>,
 }
 
 struct RecorderChild {
     child: Child,
+    backend: ScreenBackend,
+}
+
+/// Program used to record the screen
+#[derive(Debug, Clone, PartialEq)]
+enum ScreenBackend {
+    /// macOS `screencapture -v`
+    Screencapture(PathBuf),
+    /// ffmpeg grabbing the screen device (avfoundation on macOS, x11grab elsewhere)
+    Ffmpeg(PathBuf),
+}
+
+impl ScreenBackend {
+    /// Name recorded in the manifest
+    fn name(&self) -> &'static str {
+        match self {
+            ScreenBackend::Screencapture(_) => "screencapture",
+            ScreenBackend::Ffmpeg(_) => "ffmpeg",
+        }
+    }
+
+    fn program(&self) -> &Path {
+        match self {
+            ScreenBackend::Screencapture(path) | ScreenBackend::Ffmpeg(path) => path,
+        }
+    }
+
+    /// Arguments recording the main display into `output`
+    fn args(&self, output: &Path) -> Vec<OsString> {
+        let mut args: Vec<OsString> = match self {
+            ScreenBackend::Screencapture(_) => ["-v", "-D", "1"].map(OsString::from).to_vec(),
+            ScreenBackend::Ffmpeg(_) => {
+                let mut args: Vec<OsString> = ["-y", "-f"].map(OsString::from).to_vec();
+                if cfg!(target_os = "macos") {
+                    args.extend(
+                        ["avfoundation", "-i", "Capture screen 0:none"].map(OsString::from),
+                    );
+                } else {
+                    let display = std::env::var_os("DISPLAY").unwrap_or_else(|| ":0".into());
+                    args.extend(["x11grab".into(), "-i".into(), display]);
+                }
+                args.extend(["-c:v", "libx264", "-pix_fmt", "yuv420p"].map(OsString::from));
+                args
+            }
+        };
+        args.push(output.into());
+        args
+    }
+}
+
+/// Prefer `screencapture`, fall back to ffmpeg, else explain what to install
+fn select_screen_backend(
+    screencapture: Option<PathBuf>,
+    ffmpeg: Option<PathBuf>,
+) -> anyhow::Result<ScreenBackend> {
+    match (screencapture, ffmpeg) {
+        (Some(path), _) => Ok(ScreenBackend::Screencapture(path)),
+        (None, Some(path)) => Ok(ScreenBackend::Ffmpeg(path)),
+        (None, None) => anyhow::bail!(
+            "No screen recorder available: screencapture was not found (it ships with macOS) \
+             and neither was ffmpeg.\n\
+             Fix: Run ./utils/init_media_tools.sh to install the bundled ffmpeg"
+        ),
+    }
 }
 
 struct CaptureSession {
@@ -112,6 +182,8 @@ struct CaptureSession {
     segment_start_ms: Option<u64>,
     active_segment_dir: Option<PathBuf>,
     is_voice_active: bool,
+    /// Backend of the last screen recording, kept for its segment's manifest
+    screen_backend: Option<ScreenBackend>,
 }
 
 impl CaptureSession {
@@ -181,6 +253,7 @@ impl CaptureSession {
             segment_start_ms: None,
             active_segment_dir: None,
             is_voice_active: false,
+            screen_backend: None,
         })
     }
 
@@ -234,6 +307,7 @@ impl CaptureSession {
 
         if let Some(recorder) = self.screen_recorder.as_mut() {
             stop_recorder(recorder)?;
+            self.screen_backend = Some(recorder.backend.clone());
         }
 
         self.screen_recorder = None;
@@ -313,6 +387,17 @@ impl CaptureSession {
             segment_start_ms,
             segment_end_ms,
             segment_index,
+            screen_backend: screen_path
+                .as_ref()
+                .and(self.screen_backend.as_ref())
+                .map(|backend| backend.name().to_string()),
+            bundle_hash: match ada_cli::bundle_hash::compute(&bundle_dir) {
+                Ok(hash) => Some(hash),
+                Err(err) => {
+                    eprintln!("Warning: could not hash bundle: {err:#}");
+                    None
+                }
+            },
         };
 
         let manifest_path = bundle_dir.join("manifest.json");
@@ -383,7 +468,8 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::map_tracer_result;
+    use super::{map_tracer_result, select_screen_backend, ScreenBackend};
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn map_tracer_result_ok() {
@@ -396,6 +482,51 @@ mod tests {
         let err = map_tracer_result::<(), &str>(Err("boom")).expect_err("err result");
         assert!(err.to_string().contains("boom"));
     }
+
+    #[test]
+    fn select_screen_backend__screencapture_available__then_preferred() {
+        let backend = select_screen_backend(
+            Some(PathBuf::from("/usr/sbin/screencapture")),
+            Some(PathBuf::from("/opt/ada/bin/ffmpeg")),
+        )
+        .expect("backend");
+
+        assert_eq!(
+            backend,
+            ScreenBackend::Screencapture(PathBuf::from("/usr/sbin/screencapture"))
+        );
+        assert_eq!(backend.name(), "screencapture");
+    }
+
+    #[test]
+    fn select_screen_backend__only_ffmpeg__then_ffmpeg_grabs_screen() {
+        let backend = select_screen_backend(None, Some(PathBuf::from("/opt/ada/bin/ffmpeg")))
+            .expect("backend");
+
+        assert_eq!(backend.name(), "ffmpeg");
+        assert_eq!(backend.program(), Path::new("/opt/ada/bin/ffmpeg"));
+        let args = backend.args(Path::new("/seg/screen.mp4"));
+        let grab = if cfg!(target_os = "macos") {
+            "avfoundation"
+        } else {
+            "x11grab"
+        };
+        let f = args.iter().position(|a| a == "-f").expect("has -f");
+        assert_eq!(args[f + 1], grab);
+        assert_eq!(
+            args.last().map(|a| a.as_os_str()),
+            Some("/seg/screen.mp4".as_ref())
+        );
+    }
+
+    #[test]
+    fn select_screen_backend__nothing_available__then_actionable_error() {
+        let err = select_screen_backend(None, None).expect_err("no recorder");
+
+        let message = err.to_string();
+        assert!(message.contains("screencapture"), "{}", message);
+        assert!(message.contains("init_media_tools.sh"), "{}", message);
+    }
 }
 
 fn handle_command(
@@ -552,20 +683,20 @@ fn start_screen_recording(segment_dir: &Path) -> anyhow::Result<RecorderChild> {
     let output = segment_dir.join("screen.mp4");
     let log_path = segment_dir.join("screen_ffmpeg.log");
 
-    let mut cmd = Command::new("screencapture");
-    cmd.arg("-v").arg("-D").arg("1");
-    cmd.arg(&output);
+    let backend = select_screen_backend(
+        which::which("screencapture").ok(),
+        ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::Ffmpeg).ok(),
+    )?;
 
-    let child = cmd
+    let child = Command::new(backend.program())
+        .args(backend.args(&output))
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(open_log_file(&log_path)?)
         .spawn()
-        .context("Failed to start screencapture")?;
+        .with_context(|| format!("Failed to start {}", backend.name()))?;
 
-    Ok(RecorderChild {
-        child,
-    })
+    Ok(RecorderChild { child, backend })
 }
 
 fn stop_recorder(recorder: &mut RecorderChild) -> anyhow::Result<()> {
@@ -1,10 +1,20 @@
 //! Doctor command for system health checks.
 //!
 //! Provides CLI commands for verifying ADA dependencies and system configuration.
+//!
+//! Each check implements [`HealthCheck`]. `ada doctor` runs [`builtin_checks`];
+//! tools embedding this crate can append their own checks and pass the list
+//! to [`run_with_checks`].
 
+use crate::binary_resolver::{self, Tool};
+use ada_sessions::SESSIONS_DIR_ENV;
+use anyhow::bail;
 use clap::Subcommand;
-use serde::Serialize;
-use std::path::PathBuf;
+use serde::{Serialize, Serializer};
+use std::cell::Cell;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 #[derive(Subcommand)]
 pub enum DoctorCommands {
@@ -13,320 +23,1724 @@ pub enum DoctorCommands {
         /// Output format (text or json)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Run the known fix command of each failed check, after confirmation
+        #[arg(long)]
+        fix: bool,
     },
 }
 
 /// Result of a single health check
-#[derive(Serialize, Clone)]
-struct CheckResult {
-    ok: bool,
+#[derive(Serialize, Clone, Default)]
+pub struct CheckResult {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+    /// Version the tool reports about itself, where it could be detected
     #[serde(skip_serializing_if = "Option::is_none")]
-    path: Option<String>,
+    pub version: Option<String>,
+    /// Locations probed, in search order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub search_paths: Vec<String>,
+    /// Non-fatal problem, e.g. a tool older than the supported minimum
     #[serde(skip_serializing_if = "Option::is_none")]
-    fix: Option<String>,
+    pub warning: Option<String>,
+    /// Program and arguments `--fix` may run to remediate a failure
+    ///
+    /// Set only by checks whose fix is a vetted command; `fix` is free text
+    /// for humans and is never executed.
+    #[serde(skip)]
+    pub fix_command: Option<Vec<String>>,
+}
+
+/// Text report section a check is listed under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Core,
+    /// Listed as granted / not granted rather than by path
+    Permissions,
+    Analysis,
+    Custom,
+}
+
+impl Section {
+    /// Every section, in report order
+    const ALL: [Section; 4] = [
+        Section::Core,
+        Section::Permissions,
+        Section::Analysis,
+        Section::Custom,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Section::Core => "Core",
+            Section::Permissions => "Permissions",
+            Section::Analysis => "Analysis",
+            Section::Custom => "Custom",
+        }
+    }
+}
+
+/// A single `ada doctor` check
+pub trait HealthCheck {
+    /// Name shown in the text report; the JSON key is the name with spaces
+    /// and dashes replaced by `_`
+    fn name(&self) -> &str;
+
+    fn run(&self) -> CheckResult;
+
+    fn section(&self) -> Section {
+        Section::Custom
+    }
+}
+
+/// Frida agent library is available
+pub struct FridaAgentCheck;
+
+impl HealthCheck for FridaAgentCheck {
+    fn name(&self) -> &str {
+        "frida agent"
+    }
+
+    fn run(&self) -> CheckResult {
+        check_frida_agent()
+    }
+
+    fn section(&self) -> Section {
+        Section::Core
+    }
+}
+
+/// Terminal may record the screen
+pub struct ScreenRecordingCheck;
+
+impl HealthCheck for ScreenRecordingCheck {
+    fn name(&self) -> &str {
+        "screen recording"
+    }
+
+    fn run(&self) -> CheckResult {
+        check_screen_recording()
+    }
+
+    fn section(&self) -> Section {
+        Section::Permissions
+    }
+}
+
+/// Terminal may use the microphone
+pub struct MicrophoneCheck;
+
+impl HealthCheck for MicrophoneCheck {
+    fn name(&self) -> &str {
+        "microphone"
+    }
+
+    fn run(&self) -> CheckResult {
+        check_microphone()
+    }
+
+    fn section(&self) -> Section {
+        Section::Permissions
+    }
+}
+
+/// Bundled or system media tool is installed
+pub struct ToolCheck {
+    tool: Tool,
+    /// Resolution taken by the first run; later runs (e.g. after `--fix`)
+    /// resolve again
+    resolved: Cell<Option<anyhow::Result<PathBuf>>>,
+}
+
+impl ToolCheck {
+    pub fn new(tool: Tool) -> Self {
+        Self::resolved(tool, None)
+    }
+
+    fn resolved(tool: Tool, resolved: Option<anyhow::Result<PathBuf>>) -> Self {
+        Self {
+            tool,
+            resolved: Cell::new(resolved),
+        }
+    }
+}
+
+impl HealthCheck for ToolCheck {
+    fn name(&self) -> &str {
+        self.tool.display_name()
+    }
+
+    fn run(&self) -> CheckResult {
+        let resolved = self
+            .resolved
+            .take()
+            .unwrap_or_else(|| binary_resolver::resolve(self.tool));
+        tool_check(self.tool, resolved)
+    }
+
+    fn section(&self) -> Section {
+        Section::Analysis
+    }
+}
+
+/// Volume backing the sessions directory has room for another capture
+pub struct DiskSpaceCheck;
+
+impl HealthCheck for DiskSpaceCheck {
+    fn name(&self) -> &str {
+        "disk space"
+    }
+
+    fn run(&self) -> CheckResult {
+        check_disk_space()
+    }
+
+    fn section(&self) -> Section {
+        Section::Analysis
+    }
+}
+
+/// Checks run by `ada doctor`, in report order
+pub fn builtin_checks() -> Vec<Box<dyn HealthCheck>> {
+    let mut checks: Vec<Box<dyn HealthCheck>> = vec![
+        Box::new(FridaAgentCheck),
+        Box::new(ScreenRecordingCheck),
+        Box::new(MicrophoneCheck),
+    ];
+    checks.extend(
+        binary_resolver::resolve_all()
+            .into_iter()
+            .map(|(tool, resolved)| {
+                Box::new(ToolCheck::resolved(tool, Some(resolved))) as Box<dyn HealthCheck>
+            }),
+    );
+    checks.push(Box::new(DiskSpaceCheck));
+    checks
 }
 
 /// All check results
 #[derive(Serialize)]
 struct DoctorReport {
     status: String,
-    checks: CheckResults,
+    /// Serialized as an object keyed by check name, in run order
+    #[serde(serialize_with = "serialize_checks")]
+    checks: Vec<NamedResult>,
     issues_count: usize,
 }
 
-#[derive(Serialize)]
-struct CheckResults {
-    frida_agent: CheckResult,
-    whisper: CheckResult,
-    ffmpeg: CheckResult,
+/// Outcome of one [`HealthCheck`]
+struct NamedResult {
+    name: String,
+    section: Section,
+    result: CheckResult,
+}
+
+impl DoctorReport {
+    fn collect(checks: &[Box<dyn HealthCheck>]) -> Self {
+        let checks: Vec<NamedResult> = checks
+            .iter()
+            .map(|check| NamedResult {
+                name: check.name().to_string(),
+                section: check.section(),
+                result: check.run(),
+            })
+            .collect();
+
+        let mut report = DoctorReport {
+            status: String::new(),
+            checks,
+            issues_count: 0,
+        };
+        report.recount();
+        report
+    }
+
+    /// Refresh `issues_count` and `status` after results changed
+    fn recount(&mut self) {
+        self.issues_count = self.checks.iter().filter(|c| !c.result.ok).count();
+        self.status = if self.issues_count == 0 {
+            "ok".to_string()
+        } else {
+            "issues_found".to_string()
+        };
+    }
+}
+
+/// What `--fix` did about one failed check
+#[derive(Debug, PartialEq)]
+enum FixOutcome {
+    /// Fix command ran and the check now passes
+    Fixed,
+    /// Fix command ran but the check still fails
+    StillFailing,
+    /// User declined to run the fix command
+    Declined,
+    /// Fix command could not be run or exited with an error
+    CommandFailed(String),
+    /// No vetted fix command; needs manual attention
+    Manual,
+}
+
+/// Offer each failed check's fix command, then re-run the check
+///
+/// Commands shared by several checks (e.g. `init_media_tools.sh` for every
+/// media tool) are confirmed and run once. Returns the outcome per failed
+/// check, in report order.
+fn apply_fixes(
+    checks: &[Box<dyn HealthCheck>],
+    report: &mut DoctorReport,
+    confirm: &mut dyn FnMut(&[String]) -> bool,
+    execute: &mut dyn FnMut(&[String]) -> anyhow::Result<()>,
+) -> Vec<(String, FixOutcome)> {
+    let mut ran: Vec<(Vec<String>, Result<(), String>)> = Vec::new();
+    let mut declined: Vec<Vec<String>> = Vec::new();
+    let mut outcomes = Vec::new();
+
+    for (check, entry) in checks.iter().zip(report.checks.iter_mut()) {
+        if entry.result.ok {
+            continue;
+        }
+        let Some(command) = entry.result.fix_command.clone() else {
+            outcomes.push((entry.name.clone(), FixOutcome::Manual));
+            continue;
+        };
+
+        let previous = ran.iter().find(|(cmd, _)| *cmd == command);
+        let result = match previous {
+            Some((_, result)) => result.clone(),
+            None if declined.contains(&command) => {
+                outcomes.push((entry.name.clone(), FixOutcome::Declined));
+                continue;
+            }
+            None if !confirm(&command) => {
+                declined.push(command);
+                outcomes.push((entry.name.clone(), FixOutcome::Declined));
+                continue;
+            }
+            None => {
+                let result = execute(&command).map_err(|e| format!("{:#}", e));
+                ran.push((command, result.clone()));
+                result
+            }
+        };
+
+        let outcome = match result {
+            Err(error) => FixOutcome::CommandFailed(error),
+            Ok(()) => {
+                entry.result = check.run();
+                if entry.result.ok {
+                    FixOutcome::Fixed
+                } else {
+                    FixOutcome::StillFailing
+                }
+            }
+        };
+        outcomes.push((entry.name.clone(), outcome));
+    }
+
+    report.recount();
+    outcomes
+}
+
+/// Text summary of `--fix`: what was fixed and what still needs attention
+fn fix_summary(report: &DoctorReport, outcomes: &[(String, FixOutcome)]) -> String {
+    let mut out = String::from("Fixes:\n");
+
+    for (name, outcome) in outcomes {
+        let detail = match outcome {
+            FixOutcome::Fixed => {
+                out.push_str(&format!("  \u{2713} {}: fixed\n", name));
+                continue;
+            }
+            FixOutcome::StillFailing => "still failing after fix command".to_string(),
+            FixOutcome::Declined => "fix skipped".to_string(),
+            FixOutcome::CommandFailed(error) => format!("fix command failed: {}", error),
+            FixOutcome::Manual => "needs manual attention".to_string(),
+        };
+        out.push_str(&format!("  \u{2717} {}: {}\n", name, detail));
+
+        let fix = report
+            .checks
+            .iter()
+            .find(|check| check.name == *name)
+            .and_then(|check| check.result.fix.as_ref());
+        if let Some(fix) = fix {
+            out.push_str(&format!("    \u{2192} {}\n", fix));
+        }
+    }
+
+    let fixed = outcomes
+        .iter()
+        .filter(|(_, outcome)| *outcome == FixOutcome::Fixed)
+        .count();
+    out.push_str(&format!(
+        "\nFixed {} of {}; {} issue{} remaining\n",
+        fixed,
+        outcomes.len(),
+        report.issues_count,
+        if report.issues_count == 1 { "" } else { "s" }
+    ));
+    out
+}
+
+// LCOV_EXCL_START - Interactive prompt and real process execution
+
+/// Ask on the terminal whether to run `command`; anything but y/yes declines
+fn confirm_fix(command: &[String]) -> bool {
+    print!("Run `{}`? [y/N] ", command.join(" "));
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+fn run_fix_command(command: &[String]) -> anyhow::Result<()> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("empty fix command"))?;
+    let status = Command::new(program).args(args).status()?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+// LCOV_EXCL_STOP
+
+fn serialize_checks<S: Serializer>(
+    checks: &[NamedResult],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        checks
+            .iter()
+            .map(|check| (check.name.replace([' ', '-'], "_"), &check.result)),
+    )
 }
 
 pub fn run(cmd: DoctorCommands) -> anyhow::Result<()> {
+    run_with_checks(cmd, &builtin_checks())
+}
+
+/// Run a doctor command with `checks` in place of [`builtin_checks`]
+pub fn run_with_checks(cmd: DoctorCommands, checks: &[Box<dyn HealthCheck>]) -> anyhow::Result<()> {
     match cmd {
-        DoctorCommands::Check { format } => run_checks(&format),
+        DoctorCommands::Check { format, fix } => run_checks(checks, &format, fix),
+    }
+}
+
+fn run_checks(checks: &[Box<dyn HealthCheck>], format: &str, fix: bool) -> anyhow::Result<()> {
+    if fix && format == "json" {
+        bail!("--fix prompts for confirmation and cannot be combined with --format json");
+    }
+
+    let mut report = DoctorReport::collect(checks);
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", text_report(&report));
+    }
+
+    if fix && report.issues_count > 0 {
+        let outcomes = apply_fixes(checks, &mut report, &mut confirm_fix, &mut run_fix_command);
+        print!("\n{}", fix_summary(&report, &outcomes));
+    }
+
+    if report.issues_count > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn text_report(report: &DoctorReport) -> String {
+    let mut out = String::from("ADA Doctor\n==========\n\n");
+
+    for section in Section::ALL {
+        let mut checks = report
+            .checks
+            .iter()
+            .filter(|check| check.section == section)
+            .peekable();
+        if checks.peek().is_none() {
+            continue;
+        }
+
+        out.push_str(&format!("{}:\n", section.title()));
+        for check in checks {
+            if section == Section::Permissions {
+                out.push_str(&permission_lines(&check.name, &check.result));
+            } else {
+                out.push_str(&check_lines(&check.name, &check.result));
+            }
+        }
+        out.push('\n');
+    }
+
+    let issues_count = report.issues_count;
+    if issues_count == 0 {
+        out.push_str("Status: All checks passed\n");
+    } else {
+        out.push_str(&format!(
+            "Status: {} issue{} found\n",
+            issues_count,
+            if issues_count == 1 { "" } else { "s" }
+        ));
+    }
+    out
+}
+
+/// Text report lines for one check, e.g. `  ✓ ffmpeg: /path (6.1.1)`
+fn check_lines(name: &str, result: &CheckResult) -> String {
+    let mut out = String::new();
+    if result.ok {
+        let location = result.path.as_deref().unwrap_or("valid");
+        match &result.version {
+            Some(version) => out.push_str(&format!(
+                "  \u{2713} {}: {} ({})\n",
+                name, location, version
+            )),
+            None => out.push_str(&format!("  \u{2713} {}: {}\n", name, location)),
+        }
+        if let Some(warning) = &result.warning {
+            out.push_str(&format!("    ! {}\n", warning));
+        }
+    } else {
+        let problem = result.path.as_deref().unwrap_or("not found");
+        out.push_str(&format!("  \u{2717} {}: {}\n", name, problem));
+        if let Some(fix) = &result.fix {
+            out.push_str(&format!("    \u{2192} {}\n", fix));
+        }
+    }
+    out
+}
+
+/// Text report lines for a permission check, e.g. `  ✓ microphone: granted`
+fn permission_lines(name: &str, result: &CheckResult) -> String {
+    let mut out = String::new();
+    if result.ok {
+        out.push_str(&format!("  \u{2713} {}: granted\n", name));
+    } else {
+        out.push_str(&format!("  \u{2717} {}: not granted\n", name));
+        if let Some(fix) = &result.fix {
+            out.push_str(&format!("    \u{2192} {}\n", fix));
+        }
+    }
+    out
+}
+
+/// Check if Frida agent library is available
+fn check_frida_agent() -> CheckResult {
+    let candidates = frida_agent_candidates();
+    let search_paths = candidates.iter().map(|p| p.display().to_string()).collect();
+
+    match candidates.iter().find(|path| path.exists()) {
+        Some(agent_path) => CheckResult {
+            ok: true,
+            path: Some(agent_path.display().to_string()),
+            fix: None,
+            search_paths,
+            ..Default::default()
+        },
+        None => CheckResult {
+            ok: false,
+            path: None,
+            fix: Some("Set ADA_AGENT_RPATH_SEARCH_PATHS to directory containing libfrida_agent.dylib".to_string()),
+            search_paths,
+            ..Default::default()
+        },
+    }
+}
+
+/// Locations probed for the Frida agent, in search order
+fn frida_agent_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // Check ADA_AGENT_RPATH_SEARCH_PATHS environment variable first
+    if let Ok(search_paths) = std::env::var("ADA_AGENT_RPATH_SEARCH_PATHS") {
+        for path in search_paths.split(':') {
+            candidates.push(PathBuf::from(path).join("libfrida_agent.dylib"));
+        }
+    }
+
+    // Check known paths relative to the ada binary
+    if let Ok(exe_path) = std::env::current_exe() {
+        if let Some(bin_dir) = exe_path.parent() {
+            // Check sibling lib directory
+            if let Some(lib_path) = bin_dir.parent().map(|p| p.join("lib")) {
+                candidates.push(lib_path.join("libfrida_agent.dylib"));
+            }
+
+            // Check same directory as binary
+            candidates.push(bin_dir.join("libfrida_agent.dylib"));
+        }
+    }
+
+    candidates
+}
+
+/// Environment variable overriding the free space `ada doctor` requires, in GB
+pub const MIN_FREE_SPACE_ENV: &str = "ADA_DOCTOR_MIN_FREE_GB";
+
+/// Free space required by default; a screen recording can take gigabytes
+pub const DEFAULT_MIN_FREE_BYTES: u64 = 2 << 30;
+
+const GIB: f64 = (1u64 << 30) as f64;
+
+/// Check that the sessions volume has room, or captures fail halfway through
+///
+/// Requires [`DEFAULT_MIN_FREE_BYTES`] unless [`MIN_FREE_SPACE_ENV`] is set.
+pub fn check_disk_space() -> CheckResult {
+    let Some(dir) = ada_sessions::sessions_dir() else {
+        return CheckResult {
+            ok: false,
+            fix: Some(format!("Set HOME or {}", SESSIONS_DIR_ENV)),
+            ..Default::default()
+        };
+    };
+    let min_free = parse_min_free(std::env::var(MIN_FREE_SPACE_ENV).ok().as_deref());
+
+    let mut result = disk_space_check(
+        &dir,
+        free_bytes(&dir),
+        *min_free.as_ref().unwrap_or(&DEFAULT_MIN_FREE_BYTES),
+    );
+    if let Err(error) = min_free {
+        result.warning = Some(error);
+    }
+    result
+}
+
+/// Minimum free bytes from a [`MIN_FREE_SPACE_ENV`] value in gigabytes
+fn parse_min_free(value: Option<&str>) -> Result<u64, String> {
+    let Some(value) = value.filter(|value| !value.trim().is_empty()) else {
+        return Ok(DEFAULT_MIN_FREE_BYTES);
+    };
+    match value.trim().parse::<f64>() {
+        Ok(gb) if gb.is_finite() && gb >= 0.0 => Ok((gb * GIB) as u64),
+        _ => Err(format!(
+            "Ignoring {}={:?}: expected a number of GB; using {}",
+            MIN_FREE_SPACE_ENV,
+            value,
+            gigabytes(DEFAULT_MIN_FREE_BYTES)
+        )),
+    }
+}
+
+fn disk_space_check(dir: &Path, free: std::io::Result<u64>, min_free: u64) -> CheckResult {
+    let free = match free {
+        Ok(free) => free,
+        Err(error) => {
+            return CheckResult {
+                ok: false,
+                path: Some(format!("cannot read free space of {}", dir.display())),
+                fix: Some(format!(
+                    "Check that {} is readable ({})",
+                    dir.display(),
+                    error
+                )),
+                ..Default::default()
+            }
+        }
+    };
+
+    let ok = free >= min_free;
+    let mut status = format!("{} free at {}", gigabytes(free), dir.display());
+    if !ok {
+        status.push_str(&format!(", below the {} minimum", gigabytes(min_free)));
+    }
+    CheckResult {
+        ok,
+        path: Some(status),
+        fix: (!ok).then(|| {
+            "Delete old sessions, e.g. `ada session prune --days 30`, or free up disk space"
+                .to_string()
+        }),
+        ..Default::default()
+    }
+}
+
+fn gigabytes(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / GIB)
+}
+
+/// Bytes available to unprivileged users on the volume holding `path`
+///
+/// `path` need not exist yet; its nearest existing ancestor is measured.
+#[allow(clippy::useless_conversion)] // statvfs field widths differ between macOS and Linux
+fn free_bytes(path: &Path) -> std::io::Result<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(Path::new("/"));
+    let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(u64::from(stat.f_bavail) * u64::from(stat.f_frsize))
+}
+
+/// Check that the terminal may record the screen, or screen.mp4 comes out black
+///
+/// Uses `CGPreflightScreenCaptureAccess`, which never shows the TCC prompt.
+/// Always passes on platforms other than macOS.
+fn check_screen_recording() -> CheckResult {
+    screen_recording_check(tcc::screen_recording_granted())
+}
+
+fn screen_recording_check(granted: bool) -> CheckResult {
+    CheckResult {
+        ok: granted,
+        fix: (!granted).then(|| {
+            "Allow your terminal in System Settings > Privacy & Security > Screen Recording, \
+             then restart it"
+                .to_string()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Check that the terminal may use the microphone, or the voice track is silent
+///
+/// Reads the AVFoundation authorization status without prompting. A status
+/// not yet determined passes, since capture asks on first use. Always passes
+/// on platforms other than macOS.
+fn check_microphone() -> CheckResult {
+    microphone_check(tcc::microphone_status())
+}
+
+fn microphone_check(status: Option<tcc::AuthorizationStatus>) -> CheckResult {
+    use tcc::AuthorizationStatus::{Denied, Restricted};
+
+    let denied = matches!(status, Some(Denied | Restricted));
+    CheckResult {
+        ok: !denied,
+        fix: denied.then(|| {
+            "Allow your terminal in System Settings > Privacy & Security > Microphone, \
+             then restart it"
+                .to_string()
+        }),
+        ..Default::default()
+    }
+}
+
+/// TCC (privacy permission) queries
+mod tcc {
+    /// `AVAuthorizationStatus` values
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(super) enum AuthorizationStatus {
+        NotDetermined,
+        Restricted,
+        Denied,
+        Authorized,
+    }
+
+    #[cfg(target_os = "macos")]
+    mod sys {
+        use std::ffi::{c_char, c_void};
+
+        #[link(name = "CoreGraphics", kind = "framework")]
+        extern "C" {
+            pub fn CGPreflightScreenCaptureAccess() -> bool;
+        }
+
+        #[link(name = "AVFoundation", kind = "framework")]
+        extern "C" {
+            pub static AVMediaTypeAudio: *const c_void;
+        }
+
+        #[link(name = "objc")]
+        extern "C" {
+            pub fn objc_getClass(name: *const c_char) -> *mut c_void;
+            pub fn sel_registerName(name: *const c_char) -> *mut c_void;
+            pub fn objc_msgSend();
+        }
+    }
+
+    // LCOV_EXCL_START - Queries the real TCC database
+    #[cfg(target_os = "macos")]
+    pub(super) fn screen_recording_granted() -> bool {
+        unsafe { sys::CGPreflightScreenCaptureAccess() }
+    }
+
+    /// `[AVCaptureDevice authorizationStatusForMediaType:AVMediaTypeAudio]`
+    #[cfg(target_os = "macos")]
+    pub(super) fn microphone_status() -> Option<AuthorizationStatus> {
+        let raw = unsafe {
+            let class = sys::objc_getClass(c"AVCaptureDevice".as_ptr());
+            if class.is_null() {
+                return None;
+            }
+            let selector = sys::sel_registerName(c"authorizationStatusForMediaType:".as_ptr());
+            // objc_msgSend must be called through the method's exact signature
+            let send: unsafe extern "C" fn(
+                *mut std::ffi::c_void,
+                *mut std::ffi::c_void,
+                *const std::ffi::c_void,
+            ) -> isize = std::mem::transmute(sys::objc_msgSend as unsafe extern "C" fn());
+            send(class, selector, sys::AVMediaTypeAudio)
+        };
+        match raw {
+            0 => Some(AuthorizationStatus::NotDetermined),
+            1 => Some(AuthorizationStatus::Restricted),
+            2 => Some(AuthorizationStatus::Denied),
+            3 => Some(AuthorizationStatus::Authorized),
+            _ => None,
+        }
+    }
+    // LCOV_EXCL_STOP
+
+    #[cfg(not(target_os = "macos"))]
+    pub(super) fn screen_recording_granted() -> bool {
+        true
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub(super) fn microphone_status() -> Option<AuthorizationStatus> {
+        None
+    }
+}
+
+/// Turn a resolver result into a check
+fn tool_check(tool: Tool, resolved: anyhow::Result<PathBuf>) -> CheckResult {
+    let search_paths = binary_resolver::search_paths(tool)
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
+
+    match resolved {
+        Ok(path) => {
+            let version = tool_version(tool, &path);
+            CheckResult {
+                ok: true,
+                warning: version
+                    .as_deref()
+                    .and_then(|version| version_warning(tool, version)),
+                version,
+                path: Some(path.display().to_string()),
+                fix: None,
+                search_paths,
+                fix_command: None,
+            }
+        }
+        Err(_) => CheckResult {
+            ok: false,
+            path: None,
+            fix: Some(format!("Run: {}", MEDIA_TOOLS_SCRIPT)),
+            version: None,
+            search_paths,
+            warning: None,
+            fix_command: media_tools_script().map(|script| vec![script.display().to_string()]),
+        },
+    }
+}
+
+/// Installs the bundled media tools; relative to the root of an ADA checkout
+const MEDIA_TOOLS_SCRIPT: &str = "./utils/init_media_tools.sh";
+
+/// [`MEDIA_TOOLS_SCRIPT`] in the ADA checkout the running `ada` was built in
+///
+/// `None` for installed copies, which ship without the script, so `--fix`
+/// never runs a script found relative to the working directory.
+fn media_tools_script() -> Option<PathBuf> {
+    media_tools_script_for(&std::env::current_exe().ok()?)
+}
+
+/// [`MEDIA_TOOLS_SCRIPT`] under the nearest ancestor of `exe` that is an
+/// ADA checkout
+fn media_tools_script_for(exe: &Path) -> Option<PathBuf> {
+    exe.ancestors()
+        .skip(1)
+        .filter(|dir| dir.join("ada-cli").join("Cargo.toml").is_file())
+        .map(|root| root.join(MEDIA_TOOLS_SCRIPT.trim_start_matches("./")))
+        .find(|script| script.is_file())
+}
+
+/// Oldest version of each tool ADA is known to work with
+///
+/// ffmpeg 4.4 is the oldest LTS release whose native AAC encoder capture
+/// relies on; whisper.cpp 1.5 introduced the `whisper-cli` options used by
+/// transcription.
+fn minimum_version(tool: Tool) -> &'static str {
+    match tool {
+        Tool::Ffmpeg | Tool::Ffprobe => "4.4",
+        Tool::WhisperCpp => "1.5.0",
+    }
+}
+
+/// Warning for a tool reporting `version` below [`minimum_version`]
+///
+/// Versions without a leading number (e.g. git snapshot builds) are assumed
+/// recent enough.
+fn version_warning(tool: Tool, version: &str) -> Option<String> {
+    let minimum = minimum_version(tool);
+    let (found, wanted) = (version_numbers(version)?, version_numbers(minimum)?);
+    (found < wanted).then(|| {
+        format!(
+            "{} {} is older than the supported minimum {}; some features may not work",
+            tool.display_name(),
+            version,
+            minimum
+        )
+    })
+}
+
+/// Numeric components of a version such as `6.1.1`, `n7.0-tessus` or `v1.7.4`
+fn version_numbers(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let numbers: Vec<u64> = version
+        .split('.')
+        .map_while(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        })
+        .collect();
+    (!numbers.is_empty()).then_some(numbers)
+}
+
+/// Version reported by the tool itself
+// LCOV_EXCL_START - Runs the real tool binary
+fn tool_version(tool: Tool, path: &Path) -> Option<String> {
+    match tool {
+        Tool::Ffmpeg | Tool::Ffprobe => {
+            let output = Command::new(path).arg("-version").output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            parse_version_line(&String::from_utf8_lossy(&output.stdout))
+        }
+        Tool::WhisperCpp => {
+            // whisper-cli has no version flag; some builds name it in --help,
+            // which may go to stderr
+            let output = Command::new(path).arg("--help").output().ok()?;
+            let text = format!(
+                "{}\n{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            parse_help_version(&text)
+        }
+    }
+}
+// LCOV_EXCL_STOP
+
+/// Version named anywhere in help output, e.g. `1.7.4` from
+/// `whisper.cpp version: 1.7.4` or `version v1.5.0`
+fn parse_help_version(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        words.find(|word| word.trim_end_matches(':').eq_ignore_ascii_case("version"))?;
+        let version = words.next()?.trim_end_matches(',');
+        let version = version.strip_prefix('v').unwrap_or(version);
+        version
+            .starts_with(|c: char| c.is_ascii_digit())
+            .then(|| version.to_string())
+    })
+}
+
+/// Version from the first line of `<tool> -version` output, e.g. `6.1.1`
+/// from `ffmpeg version 6.1.1 Copyright (c) 2000-2023 the FFmpeg developers`
+fn parse_version_line(output: &str) -> Option<String> {
+    let mut words = output.lines().next()?.split_whitespace();
+    words.find(|word| *word == "version")?;
+    words.next().map(str::to_string)
+}
+
+/// Check if ffmpeg is installed (bundled or system)
+pub fn check_ffmpeg() -> CheckResult {
+    ToolCheck::new(Tool::Ffmpeg).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Execute a closure with a temporarily modified environment variable.
+    /// Restores the original value (or removes the variable) after the closure completes.
+    fn with_env<F, R>(key: &str, value: Option<&str>, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let _guard = crate::test_utils::ENV_MUTEX.lock().unwrap();
+        let original = std::env::var(key).ok();
+
+        match value {
+            Some(v) => std::env::set_var(key, v),
+            None => std::env::remove_var(key),
+        }
+
+        let result = f();
+
+        match original {
+            Some(v) => std::env::set_var(key, v),
+            None => std::env::remove_var(key),
+        }
+
+        result
+    }
+
+    /// Execute a closure with multiple environment variables temporarily modified.
+    fn with_envs<F, R>(vars: &[(&str, Option<&str>)], f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let _guard = crate::test_utils::ENV_MUTEX.lock().unwrap();
+        let mut originals = Vec::new();
+
+        for (key, value) in vars {
+            originals.push((*key, std::env::var(key).ok()));
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+
+        let result = f();
+
+        for (key, original) in originals {
+            match original {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+
+        result
+    }
+
+    // =========================================================================
+    // Existing serialization tests
+    // =========================================================================
+
+    #[test]
+    fn check_result_json_serialization() {
+        let result = CheckResult {
+            ok: true,
+            path: Some("/usr/bin/test".to_string()),
+            fix: None,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"ok\":true"));
+        assert!(json.contains("\"path\":\"/usr/bin/test\""));
+        // fix should be skipped when None
+        assert!(!json.contains("\"fix\""));
+    }
+
+    #[test]
+    fn check_result_with_fix() {
+        let result = CheckResult {
+            ok: false,
+            path: None,
+            fix: Some("brew install test".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"ok\":false"));
+        assert!(json.contains("\"fix\":\"brew install test\""));
+        // path should be skipped when None
+        assert!(!json.contains("\"path\""));
+    }
+
+    fn granted() -> CheckResult {
+        CheckResult {
+            ok: true,
+            ..Default::default()
+        }
+    }
+
+    fn named(name: &str, section: Section, result: CheckResult) -> NamedResult {
+        NamedResult {
+            name: name.to_string(),
+            section,
+            result,
+        }
+    }
+
+    /// Custom check returning a fixed result
+    struct FixedCheck(&'static str, bool);
+
+    impl HealthCheck for FixedCheck {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn run(&self) -> CheckResult {
+            CheckResult {
+                ok: self.1,
+                fix: (!self.1).then(|| format!("Configure {}", self.0)),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn screen_recording_check__granted__then_ok_without_fix() {
+        let check = screen_recording_check(true);
+
+        assert!(check.ok);
+        assert!(check.fix.is_none());
+    }
+
+    #[test]
+    fn screen_recording_check__not_granted__then_points_to_privacy_settings() {
+        let check = screen_recording_check(false);
+
+        assert!(!check.ok);
+        let fix = check.fix.unwrap();
+        assert!(fix.contains("Privacy & Security > Screen Recording"));
+    }
+
+    #[test]
+    fn microphone_check__statuses__then_only_denials_fail() {
+        use tcc::AuthorizationStatus::*;
+
+        for status in [None, Some(NotDetermined), Some(Authorized)] {
+            assert!(microphone_check(status).ok, "{status:?}");
+        }
+        for status in [Denied, Restricted] {
+            let check = microphone_check(Some(status));
+            assert!(!check.ok, "{status:?}");
+            let fix = check.fix.unwrap();
+            assert!(fix.contains("Privacy & Security > Microphone"));
+        }
+    }
+
+    #[test]
+    fn doctor_report__permissions__then_json_has_permission_keys() {
+        let report = DoctorReport {
+            status: "issues_found".to_string(),
+            checks: vec![
+                named("frida agent", Section::Core, granted()),
+                named(
+                    "screen recording",
+                    Section::Permissions,
+                    screen_recording_check(false),
+                ),
+                named("microphone", Section::Permissions, granted()),
+            ],
+            issues_count: 1,
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["checks"]["screen_recording"]["ok"], false);
+        assert_eq!(json["checks"]["microphone"]["ok"], true);
+    }
+
+    #[test]
+    fn doctor_report_serialization() {
+        let report = DoctorReport {
+            status: "ok".to_string(),
+            checks: vec![
+                named(
+                    "frida agent",
+                    Section::Core,
+                    CheckResult {
+                        ok: true,
+                        path: Some("/path/to/lib".to_string()),
+                        fix: None,
+                        ..Default::default()
+                    },
+                ),
+                named("screen recording", Section::Permissions, granted()),
+                named("microphone", Section::Permissions, granted()),
+                named(
+                    "whisper",
+                    Section::Analysis,
+                    CheckResult {
+                        ok: true,
+                        path: Some("/opt/homebrew/bin/whisper".to_string()),
+                        fix: None,
+                        ..Default::default()
+                    },
+                ),
+                named(
+                    "ffmpeg",
+                    Section::Analysis,
+                    CheckResult {
+                        ok: false,
+                        path: None,
+                        fix: Some("Run: ./utils/init_media_tools.sh".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            issues_count: 1,
+        };
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        assert!(json.contains("\"status\": \"ok\""));
+        assert!(json.contains("\"issues_count\": 1"));
+    }
+
+    #[test]
+    fn builtin_checks__names__then_core_permissions_then_every_tool() {
+        let checks = builtin_checks();
+        let names: Vec<&str> = checks.iter().map(|check| check.name()).collect();
+        let sections: Vec<Section> = checks.iter().map(|check| check.section()).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "frida agent",
+                "screen recording",
+                "microphone",
+                "whisper",
+                "ffmpeg",
+                "ffprobe",
+                "disk space"
+            ]
+        );
+        assert_eq!(
+            sections,
+            vec![
+                Section::Core,
+                Section::Permissions,
+                Section::Permissions,
+                Section::Analysis,
+                Section::Analysis,
+                Section::Analysis,
+                Section::Analysis
+            ]
+        );
+    }
+
+    #[test]
+    fn doctor_report__custom_check_registered__then_counted_and_keyed() {
+        let checks: Vec<Box<dyn HealthCheck>> = vec![
+            Box::new(FixedCheck("signing-identity", false)),
+            Box::new(FixedCheck("dsym cache", true)),
+        ];
+
+        let report = DoctorReport::collect(&checks);
+
+        assert_eq!(report.status, "issues_found");
+        assert_eq!(report.issues_count, 1);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["checks"]["signing_identity"]["ok"], false);
+        assert_eq!(
+            json["checks"]["signing_identity"]["fix"],
+            "Configure signing-identity"
+        );
+        assert_eq!(json["checks"]["dsym_cache"]["ok"], true);
+    }
+
+    /// Tool-like check that passes once its fix command has run
+    struct FixableCheck {
+        name: &'static str,
+        command: &'static str,
+        installed: std::rc::Rc<std::cell::Cell<bool>>,
+    }
+
+    impl HealthCheck for FixableCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn run(&self) -> CheckResult {
+            let ok = self.installed.get();
+            CheckResult {
+                ok,
+                fix: (!ok).then(|| format!("Run: {}", self.command)),
+                fix_command: (!ok).then(|| vec![self.command.to_string()]),
+                ..Default::default()
+            }
+        }
+    }
+
+    fn fixable(name: &'static str, installed: &std::rc::Rc<std::cell::Cell<bool>>) -> FixableCheck {
+        FixableCheck {
+            name,
+            command: "./install.sh",
+            installed: installed.clone(),
+        }
+    }
+
+    #[test]
+    fn apply_fixes__confirmed_shared_command__then_runs_once_and_rechecks() {
+        let installed = std::rc::Rc::new(std::cell::Cell::new(false));
+        let checks: Vec<Box<dyn HealthCheck>> = vec![
+            Box::new(fixable("whisper", &installed)),
+            Box::new(FixedCheck("dsym cache", false)),
+            Box::new(fixable("ffmpeg", &installed)),
+        ];
+        let mut report = DoctorReport::collect(&checks);
+        let mut prompts = 0;
+        let mut runs = Vec::new();
+
+        let outcomes = apply_fixes(
+            &checks,
+            &mut report,
+            &mut |_| {
+                prompts += 1;
+                true
+            },
+            &mut |command| {
+                runs.push(command.to_vec());
+                installed.set(true);
+                Ok(())
+            },
+        );
+
+        assert_eq!(prompts, 1);
+        assert_eq!(runs, vec![vec!["./install.sh".to_string()]]);
+        assert_eq!(
+            outcomes,
+            vec![
+                ("whisper".to_string(), FixOutcome::Fixed),
+                ("dsym cache".to_string(), FixOutcome::Manual),
+                ("ffmpeg".to_string(), FixOutcome::Fixed),
+            ]
+        );
+        assert_eq!(report.issues_count, 1);
+        assert_eq!(report.status, "issues_found");
+    }
+
+    #[test]
+    fn apply_fixes__declined__then_nothing_runs() {
+        let installed = std::rc::Rc::new(std::cell::Cell::new(false));
+        let checks: Vec<Box<dyn HealthCheck>> = vec![
+            Box::new(fixable("whisper", &installed)),
+            Box::new(fixable("ffmpeg", &installed)),
+        ];
+        let mut report = DoctorReport::collect(&checks);
+        let mut prompts = 0;
+
+        let outcomes = apply_fixes(
+            &checks,
+            &mut report,
+            &mut |_| {
+                prompts += 1;
+                false
+            },
+            &mut |_| panic!("declined fix must not run"),
+        );
+
+        assert_eq!(prompts, 1);
+        assert!(outcomes
+            .iter()
+            .all(|(_, outcome)| *outcome == FixOutcome::Declined));
+        assert_eq!(report.issues_count, 2);
+    }
+
+    #[test]
+    fn apply_fixes__command_fails_or_check_still_fails__then_reported() {
+        let installed = std::rc::Rc::new(std::cell::Cell::new(false));
+        let checks: Vec<Box<dyn HealthCheck>> = vec![Box::new(fixable("whisper", &installed))];
+
+        let mut report = DoctorReport::collect(&checks);
+        let outcomes = apply_fixes(&checks, &mut report, &mut |_| true, &mut |_| {
+            bail!("./install.sh exited with exit status: 1")
+        });
+        assert_eq!(
+            outcomes[0].1,
+            FixOutcome::CommandFailed("./install.sh exited with exit status: 1".to_string())
+        );
+
+        let mut report = DoctorReport::collect(&checks);
+        let outcomes = apply_fixes(&checks, &mut report, &mut |_| true, &mut |_| Ok(()));
+        assert_eq!(outcomes[0].1, FixOutcome::StillFailing);
+        assert_eq!(report.issues_count, 1);
+    }
+
+    #[test]
+    fn fix_summary__mixed_outcomes__then_fixed_and_manual_listed() {
+        let mut report = DoctorReport {
+            status: String::new(),
+            checks: vec![
+                named("whisper", Section::Analysis, granted()),
+                named(
+                    "dsym cache",
+                    Section::Custom,
+                    FixedCheck("dsym cache", false).run(),
+                ),
+            ],
+            issues_count: 0,
+        };
+        report.recount();
+        let outcomes = vec![
+            ("whisper".to_string(), FixOutcome::Fixed),
+            ("dsym cache".to_string(), FixOutcome::Manual),
+        ];
+
+        let text = fix_summary(&report, &outcomes);
+
+        assert_eq!(
+            text,
+            "Fixes:\n\
+             \x20 \u{2713} whisper: fixed\n\
+             \x20 \u{2717} dsym cache: needs manual attention\n\
+             \x20   \u{2192} Configure dsym cache\n\
+             \n\
+             Fixed 1 of 2; 1 issue remaining\n"
+        );
     }
-}
 
-fn run_checks(format: &str) -> anyhow::Result<()> {
-    let frida_agent = check_frida_agent();
-    let whisper = check_whisper();
-    let ffmpeg = check_ffmpeg();
+    #[test]
+    fn tool_check__not_found__then_fix_command_is_media_tools_script() {
+        let result = tool_check(Tool::Ffmpeg, Err(anyhow::anyhow!("not found")));
 
-    let issues_count = [&frida_agent, &whisper, &ffmpeg]
-        .iter()
-        .filter(|c| !c.ok)
-        .count();
+        assert_eq!(
+            result.fix_command,
+            media_tools_script().map(|script| vec![script.display().to_string()])
+        );
+        let json = serde_json::to_value(&result).unwrap();
+        assert!(json.get("fix_command").is_none());
+    }
 
-    let status = if issues_count == 0 {
-        "ok".to_string()
-    } else {
-        "issues_found".to_string()
-    };
+    #[test]
+    fn media_tools_script_for__exe_in_checkout__then_checkout_script() {
+        let checkout = TempDir::new().unwrap();
+        let root = checkout.path();
+        std::fs::create_dir_all(root.join("ada-cli")).unwrap();
+        std::fs::write(root.join("ada-cli/Cargo.toml"), "").unwrap();
+        std::fs::create_dir_all(root.join("utils")).unwrap();
+        std::fs::write(root.join("utils/init_media_tools.sh"), "").unwrap();
 
-    if format == "json" {
-        let report = DoctorReport {
-            status,
-            checks: CheckResults {
-                frida_agent,
-                whisper,
-                ffmpeg,
-            },
-            issues_count,
-        };
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        print_text_report(&frida_agent, &whisper, &ffmpeg, issues_count);
+        let script = media_tools_script_for(&root.join("target/debug/ada"));
+
+        assert_eq!(script, Some(root.join("utils/init_media_tools.sh")));
     }
 
-    if issues_count > 0 {
-        std::process::exit(1);
+    #[test]
+    fn media_tools_script_for__installed_or_not_a_checkout__then_none() {
+        let install = TempDir::new().unwrap();
+        // A lone script without the checkout marker isn't trusted
+        std::fs::create_dir_all(install.path().join("utils")).unwrap();
+        std::fs::write(install.path().join("utils/init_media_tools.sh"), "").unwrap();
+
+        assert_eq!(
+            media_tools_script_for(&install.path().join("bin/ada")),
+            None
+        );
     }
 
-    Ok(())
-}
+    #[test]
+    fn tool_check_run__seeded_resolution__then_used_once_then_resolved_again() {
+        let check = ToolCheck::resolved(
+            Tool::Ffmpeg,
+            Some(Ok(PathBuf::from("/seeded/ffmpeg-that-does-not-exist"))),
+        );
 
-fn print_text_report(
-    frida_agent: &CheckResult,
-    whisper: &CheckResult,
-    ffmpeg: &CheckResult,
-    issues_count: usize,
-) {
-    println!("ADA Doctor");
-    println!("==========\n");
+        let first = check.run();
+        let second = with_envs(&[("PATH", Some("")), ("ADA_FFMPEG_PATH", None)], || {
+            check.run()
+        });
+
+        assert_eq!(
+            first.path.as_deref(),
+            Some("/seeded/ffmpeg-that-does-not-exist")
+        );
+        assert!(!second.ok);
+    }
 
-    println!("Core:");
-    print_check("frida agent", frida_agent);
-    println!();
+    #[test]
+    fn disk_space_check__above_minimum__then_ok_with_free_space() {
+        let check = disk_space_check(Path::new("/home/u/.ada/sessions"), Ok(45 << 30), 2 << 30);
 
-    println!("Analysis:");
-    print_check("whisper", whisper);
-    print_check("ffmpeg", ffmpeg);
-    println!();
+        assert!(check.ok);
+        assert_eq!(
+            check.path.as_deref(),
+            Some("45.0 GB free at /home/u/.ada/sessions")
+        );
+        assert!(check.fix.is_none());
+    }
 
-    if issues_count == 0 {
-        println!("Status: All checks passed");
-    } else {
-        println!(
-            "Status: {} issue{} found",
-            issues_count,
-            if issues_count == 1 { "" } else { "s" }
+    #[test]
+    fn disk_space_check__below_minimum__then_fails_suggesting_prune() {
+        let check = disk_space_check(Path::new("/home/u/.ada/sessions"), Ok(1 << 29), 2 << 30);
+
+        assert!(!check.ok);
+        assert_eq!(
+            check_lines("disk space", &check).lines().next(),
+            Some("  \u{2717} disk space: 0.5 GB free at /home/u/.ada/sessions, below the 2.0 GB minimum")
         );
+        assert!(check.fix.unwrap().contains("ada session prune"));
+        assert!(check.fix_command.is_none());
     }
-}
 
-fn print_check(name: &str, result: &CheckResult) {
-    if result.ok {
-        if let Some(path) = &result.path {
-            println!("  \u{2713} {}: {}", name, path);
-        } else {
-            println!("  \u{2713} {}: valid", name);
-        }
-    } else {
-        println!("  \u{2717} {}: not found", name);
-        if let Some(fix) = &result.fix {
-            println!("    \u{2192} {}", fix);
-        }
+    #[test]
+    fn disk_space_check__stat_error__then_fails() {
+        let error = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+
+        let check = disk_space_check(Path::new("/locked"), Err(error), 2 << 30);
+
+        assert!(!check.ok);
+        assert!(check.fix.unwrap().contains("/locked"));
     }
-}
 
-/// Check if Frida agent library is available
-fn check_frida_agent() -> CheckResult {
-    // Check ADA_AGENT_RPATH_SEARCH_PATHS environment variable first
-    if let Ok(search_paths) = std::env::var("ADA_AGENT_RPATH_SEARCH_PATHS") {
-        for path in search_paths.split(':') {
-            let agent_path = PathBuf::from(path).join("libfrida_agent.dylib");
-            if agent_path.exists() {
-                return CheckResult {
-                    ok: true,
-                    path: Some(agent_path.display().to_string()),
-                    fix: None,
-                };
-            }
+    #[test]
+    fn parse_min_free__values__then_gigabytes_or_error() {
+        assert_eq!(parse_min_free(None), Ok(DEFAULT_MIN_FREE_BYTES));
+        assert_eq!(parse_min_free(Some(" ")), Ok(DEFAULT_MIN_FREE_BYTES));
+        assert_eq!(parse_min_free(Some("0.5")), Ok(1 << 29));
+        assert_eq!(parse_min_free(Some("10")), Ok(10 << 30));
+        for invalid in ["2GB", "-1", "inf"] {
+            let error = parse_min_free(Some(invalid)).unwrap_err();
+            assert!(error.contains(MIN_FREE_SPACE_ENV), "{error}");
         }
     }
 
-    // Check known paths relative to the ada binary
-    if let Ok(exe_path) = std::env::current_exe() {
-        if let Some(bin_dir) = exe_path.parent() {
-            // Check sibling lib directory
-            let lib_dir = bin_dir.parent().map(|p| p.join("lib"));
-            if let Some(lib_path) = lib_dir {
-                let agent_path = lib_path.join("libfrida_agent.dylib");
-                if agent_path.exists() {
-                    return CheckResult {
-                        ok: true,
-                        path: Some(agent_path.display().to_string()),
-                        fix: None,
-                    };
-                }
-            }
+    #[test]
+    fn free_bytes__missing_dir__then_measures_existing_ancestor() {
+        let temp_dir = TempDir::new().unwrap();
 
-            // Check same directory as binary
-            let agent_path = bin_dir.join("libfrida_agent.dylib");
-            if agent_path.exists() {
-                return CheckResult {
-                    ok: true,
-                    path: Some(agent_path.display().to_string()),
-                    fix: None,
-                };
-            }
-        }
+        let free = free_bytes(&temp_dir.path().join("not/yet/created"));
+
+        assert!(free.is_ok(), "{free:?}");
     }
 
-    CheckResult {
-        ok: false,
-        path: None,
-        fix: Some("Set ADA_AGENT_RPATH_SEARCH_PATHS to directory containing libfrida_agent.dylib".to_string()),
+    #[test]
+    fn check_disk_space__sessions_dir_override__then_reports_that_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions = temp_dir.path().join("sessions");
+
+        let check = with_envs(
+            &[
+                (SESSIONS_DIR_ENV, Some(sessions.to_str().unwrap())),
+                (MIN_FREE_SPACE_ENV, Some("0")),
+            ],
+            check_disk_space,
+        );
+
+        assert!(check.ok);
+        assert!(check.path.unwrap().ends_with(sessions.to_str().unwrap()));
+        assert!(check.warning.is_none());
     }
-}
 
-/// Check if whisper is installed (bundled or system)
-fn check_whisper() -> CheckResult {
-    match ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::WhisperCpp) {
-        Ok(path) => CheckResult {
-            ok: true,
-            path: Some(path.display().to_string()),
-            fix: None,
-        },
-        Err(_) => CheckResult {
-            ok: false,
-            path: None,
-            fix: Some("Run: ./utils/init_media_tools.sh".to_string()),
-        },
+    #[test]
+    fn check_disk_space__invalid_threshold__then_warns_and_uses_default() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let check = with_envs(
+            &[
+                (SESSIONS_DIR_ENV, Some(temp_dir.path().to_str().unwrap())),
+                (MIN_FREE_SPACE_ENV, Some("lots")),
+            ],
+            check_disk_space,
+        );
+
+        assert!(check.warning.unwrap().contains("lots"));
     }
-}
 
-/// Check if ffmpeg is installed (bundled or system)
-fn check_ffmpeg() -> CheckResult {
-    match ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::Ffmpeg) {
-        Ok(path) => CheckResult {
-            ok: true,
-            path: Some(path.display().to_string()),
-            fix: None,
-        },
-        Err(_) => CheckResult {
-            ok: false,
-            path: None,
-            fix: Some("Run: ./utils/init_media_tools.sh".to_string()),
-        },
+    #[test]
+    fn doctor_report__json_checks__then_keyed_in_run_order() {
+        let report = DoctorReport {
+            status: "ok".to_string(),
+            checks: vec![
+                named("whisper", Section::Analysis, granted()),
+                named("frida agent", Section::Core, granted()),
+            ],
+            issues_count: 0,
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+
+        let whisper = json.find("\"whisper\"").unwrap();
+        let frida = json.find("\"frida_agent\"").unwrap();
+        assert!(whisper < frida, "{json}");
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+    #[test]
+    fn text_report__custom_section__then_listed_after_builtin_sections() {
+        let report = DoctorReport {
+            status: "issues_found".to_string(),
+            checks: vec![
+                named(
+                    "dsym cache",
+                    Section::Custom,
+                    FixedCheck("dsym cache", false).run(),
+                ),
+                named("frida agent", Section::Core, granted()),
+                named("microphone", Section::Permissions, granted()),
+            ],
+            issues_count: 1,
+        };
 
-    /// Execute a closure with a temporarily modified environment variable.
-    /// Restores the original value (or removes the variable) after the closure completes.
-    fn with_env<F, R>(key: &str, value: Option<&str>, f: F) -> R
-    where
-        F: FnOnce() -> R,
-    {
-        let _guard = ada_cli::test_utils::ENV_MUTEX.lock().unwrap();
-        let original = std::env::var(key).ok();
+        let text = text_report(&report);
 
-        match value {
-            Some(v) => std::env::set_var(key, v),
-            None => std::env::remove_var(key),
-        }
+        assert_eq!(
+            text,
+            "ADA Doctor\n==========\n\n\
+             Core:\n  \u{2713} frida agent: valid\n\n\
+             Permissions:\n  \u{2713} microphone: granted\n\n\
+             Custom:\n  \u{2717} dsym cache: not found\n    \u{2192} Configure dsym cache\n\n\
+             Status: 1 issue found\n"
+        );
+    }
 
-        let result = f();
+    #[test]
+    fn doctor_report__versions_and_search_paths__then_json_has_new_keys() {
+        let report = DoctorReport {
+            status: "issues_found".to_string(),
+            checks: vec![
+                named(
+                    "frida agent",
+                    Section::Core,
+                    CheckResult {
+                        ok: false,
+                        fix: Some("Set ADA_AGENT_RPATH_SEARCH_PATHS".to_string()),
+                        search_paths: vec!["/opt/ada/lib/libfrida_agent.dylib".to_string()],
+                        ..Default::default()
+                    },
+                ),
+                named(
+                    "ffmpeg",
+                    Section::Analysis,
+                    CheckResult {
+                        ok: true,
+                        path: Some("/opt/ada/bin/ffmpeg".to_string()),
+                        version: Some("6.1.1".to_string()),
+                        search_paths: vec!["/opt/ada/bin/ffmpeg".to_string()],
+                        ..Default::default()
+                    },
+                ),
+            ],
+            issues_count: 1,
+        };
 
-        match original {
-            Some(v) => std::env::set_var(key, v),
-            None => std::env::remove_var(key),
-        }
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string_pretty(&report).unwrap()).unwrap();
 
-        result
+        assert_eq!(json["checks"]["ffmpeg"]["version"], "6.1.1");
+        assert_eq!(
+            json["checks"]["ffmpeg"]["search_paths"],
+            serde_json::json!(["/opt/ada/bin/ffmpeg"])
+        );
+        assert_eq!(json["checks"]["ffmpeg"]["path"], "/opt/ada/bin/ffmpeg");
+        assert!(json["checks"]["frida_agent"].get("version").is_none());
+        assert_eq!(
+            json["checks"]["frida_agent"]["search_paths"][0],
+            "/opt/ada/lib/libfrida_agent.dylib"
+        );
     }
 
-    /// Execute a closure with multiple environment variables temporarily modified.
-    fn with_envs<F, R>(vars: &[(&str, Option<&str>)], f: F) -> R
-    where
-        F: FnOnce() -> R,
-    {
-        let _guard = ada_cli::test_utils::ENV_MUTEX.lock().unwrap();
-        let mut originals = Vec::new();
+    #[test]
+    fn parse_version_line__ffmpeg_banner__then_version_token() {
+        let output = "ffmpeg version 6.1.1 Copyright (c) 2000-2023 the FFmpeg developers\n\
+                      built with Apple clang version 15.0.0\n";
 
-        for (key, value) in vars {
-            originals.push((*key, std::env::var(key).ok()));
-            match value {
-                Some(v) => std::env::set_var(key, v),
-                None => std::env::remove_var(key),
-            }
-        }
+        assert_eq!(parse_version_line(output).as_deref(), Some("6.1.1"));
+        assert_eq!(
+            parse_version_line("ffprobe version n7.0-tessus  https://evermeet.cx").as_deref(),
+            Some("n7.0-tessus")
+        );
+        assert_eq!(parse_version_line("usage: whisper-cli [options]"), None);
+        assert_eq!(parse_version_line(""), None);
+    }
 
-        let result = f();
+    #[test]
+    fn parse_help_version__whisper_help__then_version_or_none() {
+        let help = "usage: whisper-cli [options] file0 file1 ...\n\
+                    \n\
+                    whisper.cpp version: 1.7.4\n";
 
-        for (key, original) in originals {
-            match original {
-                Some(v) => std::env::set_var(key, v),
-                None => std::env::remove_var(key),
-            }
-        }
+        assert_eq!(parse_help_version(help).as_deref(), Some("1.7.4"));
+        assert_eq!(
+            parse_help_version("Version v1.5.0, built today").as_deref(),
+            Some("1.5.0")
+        );
+        assert_eq!(
+            parse_help_version("  --print-version   show version info"),
+            None
+        );
+        assert_eq!(parse_help_version("usage: whisper-cli [options]"), None);
+    }
 
-        result
+    #[test]
+    fn version_warning__below_minimum__then_warns() {
+        let warning = version_warning(Tool::Ffmpeg, "3.4.8").unwrap();
+
+        assert!(warning.contains("3.4.8"));
+        assert!(warning.contains("4.4"));
+        assert!(version_warning(Tool::WhisperCpp, "1.4.3").is_some());
     }
 
-    // =========================================================================
-    // Existing serialization tests
-    // =========================================================================
+    #[test]
+    fn version_warning__current_or_unparseable__then_none() {
+        for version in [
+            "6.1.1",
+            "4.4",
+            "n7.0-tessus",
+            "N-112345-gabcdef",
+            "git-2024",
+        ] {
+            assert_eq!(version_warning(Tool::Ffmpeg, version), None, "{version}");
+        }
+        assert_eq!(version_warning(Tool::WhisperCpp, "1.7.4"), None);
+    }
 
     #[test]
-    fn check_result_json_serialization() {
-        let result = CheckResult {
+    fn check_lines__version_and_warning__then_shown_after_path() {
+        let check = CheckResult {
             ok: true,
-            path: Some("/usr/bin/test".to_string()),
-            fix: None,
+            path: Some("/opt/ada/bin/ffmpeg".to_string()),
+            version: Some("3.4.8".to_string()),
+            warning: version_warning(Tool::Ffmpeg, "3.4.8"),
+            ..Default::default()
         };
-        let json = serde_json::to_string(&result).unwrap();
-        assert!(json.contains("\"ok\":true"));
-        assert!(json.contains("\"path\":\"/usr/bin/test\""));
-        // fix should be skipped when None
-        assert!(!json.contains("\"fix\""));
+
+        let text = check_lines("ffmpeg", &check);
+
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("  \u{2713} ffmpeg: /opt/ada/bin/ffmpeg (3.4.8)")
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("    ! ffmpeg 3.4.8 is older"));
+        assert_eq!(
+            check_lines("whisper", &granted()),
+            "  \u{2713} whisper: valid\n"
+        );
     }
 
     #[test]
-    fn check_result_with_fix() {
-        let result = CheckResult {
-            ok: false,
-            path: None,
-            fix: Some("brew install test".to_string()),
-        };
-        let json = serde_json::to_string(&result).unwrap();
-        assert!(json.contains("\"ok\":false"));
-        assert!(json.contains("\"fix\":\"brew install test\""));
-        // path should be skipped when None
-        assert!(!json.contains("\"path\""));
+    fn tool_check__env_override__then_search_paths_start_with_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let ffprobe = temp_dir.path().join("ffprobe");
+        std::fs::write(&ffprobe, b"not a binary").unwrap();
+
+        let result = with_env("ADA_FFPROBE_PATH", Some(ffprobe.to_str().unwrap()), || {
+            tool_check(Tool::Ffprobe, binary_resolver::resolve(Tool::Ffprobe))
+        });
+
+        assert!(result.ok);
+        assert_eq!(result.search_paths[0], ffprobe.display().to_string());
+        assert!(result.version.is_none(), "not an executable");
     }
 
     #[test]
-    fn doctor_report_serialization() {
-        let report = DoctorReport {
-            status: "ok".to_string(),
-            checks: CheckResults {
-                frida_agent: CheckResult {
-                    ok: true,
-                    path: Some("/path/to/lib".to_string()),
-                    fix: None,
-                },
-                whisper: CheckResult {
-                    ok: true,
-                    path: Some("/opt/homebrew/bin/whisper".to_string()),
-                    fix: None,
-                },
-                ffmpeg: CheckResult {
-                    ok: false,
-                    path: None,
-                    fix: Some("Run: ./utils/init_media_tools.sh".to_string()),
-                },
-            },
-            issues_count: 1,
-        };
-        let json = serde_json::to_string_pretty(&report).unwrap();
-        assert!(json.contains("\"status\": \"ok\""));
-        assert!(json.contains("\"issues_count\": 1"));
+    fn check_frida_agent__env_paths__then_each_listed_as_searched() {
+        let result = with_env(
+            "ADA_AGENT_RPATH_SEARCH_PATHS",
+            Some("/nonexistent/a:/nonexistent/b"),
+            check_frida_agent,
+        );
+
+        assert_eq!(
+            result.search_paths[..2],
+            [
+                "/nonexistent/a/libfrida_agent.dylib",
+                "/nonexistent/b/libfrida_agent.dylib"
+            ]
+        );
     }
 
     // =========================================================================
@@ -459,11 +1873,9 @@ mod tests {
             std::fs::write(&exe_path, "mock executable").unwrap();
         }
 
-        let result = with_env(
-            "ADA_WHISPER_PATH",
-            Some(exe_path.to_str().unwrap()),
-            check_whisper,
-        );
+        let result = with_env("ADA_WHISPER_PATH", Some(exe_path.to_str().unwrap()), || {
+            ToolCheck::new(Tool::WhisperCpp).run()
+        });
 
         assert!(result.ok, "Whisper should be found via env override");
         assert!(result.path.is_some(), "Path should be set when found");
@@ -476,13 +1888,9 @@ mod tests {
     #[test]
     fn check_whisper__not_in_path__then_not_found() {
         // Use an empty PATH to ensure whisper won't be found
-        let result = with_envs(
-            &[
-                ("PATH", Some("")),
-                ("ADA_WHISPER_PATH", None),
-            ],
-            check_whisper,
-        );
+        let result = with_envs(&[("PATH", Some("")), ("ADA_WHISPER_PATH", None)], || {
+            ToolCheck::new(Tool::WhisperCpp).run()
+        });
 
         assert!(!result.ok, "Whisper should not be found with empty PATH");
         assert!(result.path.is_none(), "Path should be None when not found");
@@ -559,21 +1967,25 @@ mod tests {
                 ok: true,
                 path: Some("/path".to_string()),
                 fix: None,
+                ..Default::default()
             },
             CheckResult {
                 ok: true,
                 path: None,
                 fix: None,
+                ..Default::default()
             },
             CheckResult {
                 ok: true,
                 path: Some("/path2".to_string()),
                 fix: None,
+                ..Default::default()
             },
             CheckResult {
                 ok: true,
                 path: None,
                 fix: None,
+                ..Default::default()
             },
         ];
 
@@ -588,21 +2000,25 @@ mod tests {
                 ok: true,
                 path: Some("/path".to_string()),
                 fix: None,
+                ..Default::default()
             },
             CheckResult {
                 ok: false,
                 path: None,
                 fix: Some("fix1".to_string()),
+                ..Default::default()
             },
             CheckResult {
                 ok: true,
                 path: None,
                 fix: None,
+                ..Default::default()
             },
             CheckResult {
                 ok: false,
                 path: None,
                 fix: Some("fix2".to_string()),
+                ..Default::default()
             },
         ];
 
@@ -617,21 +2033,25 @@ mod tests {
                 ok: false,
                 path: None,
                 fix: Some("fix1".to_string()),
+                ..Default::default()
             },
             CheckResult {
                 ok: false,
                 path: None,
                 fix: Some("fix2".to_string()),
+                ..Default::default()
             },
             CheckResult {
                 ok: false,
                 path: None,
                 fix: Some("fix3".to_string()),
+                ..Default::default()
             },
             CheckResult {
                 ok: false,
                 path: None,
                 fix: Some("fix4".to_string()),
+                ..Default::default()
             },
         ];
 
@@ -661,23 +2081,40 @@ mod tests {
     fn doctor_report__json_format__then_valid_json() {
         let report = DoctorReport {
             status: "issues_found".to_string(),
-            checks: CheckResults {
-                frida_agent: CheckResult {
-                    ok: false,
-                    path: None,
-                    fix: Some("Set ADA_AGENT_RPATH_SEARCH_PATHS".to_string()),
-                },
-                whisper: CheckResult {
-                    ok: false,
-                    path: None,
-                    fix: Some("Run: ./utils/init_media_tools.sh".to_string()),
-                },
-                ffmpeg: CheckResult {
-                    ok: true,
-                    path: Some("/opt/homebrew/bin/ffmpeg".to_string()),
-                    fix: None,
-                },
-            },
+            checks: vec![
+                named(
+                    "frida agent",
+                    Section::Core,
+                    CheckResult {
+                        ok: false,
+                        path: None,
+                        fix: Some("Set ADA_AGENT_RPATH_SEARCH_PATHS".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                named("screen recording", Section::Permissions, granted()),
+                named("microphone", Section::Permissions, granted()),
+                named(
+                    "whisper",
+                    Section::Analysis,
+                    CheckResult {
+                        ok: false,
+                        path: None,
+                        fix: Some("Run: ./utils/init_media_tools.sh".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                named(
+                    "ffmpeg",
+                    Section::Analysis,
+                    CheckResult {
+                        ok: true,
+                        path: Some("/opt/homebrew/bin/ffmpeg".to_string()),
+                        fix: None,
+                        ..Default::default()
+                    },
+                ),
+            ],
             issues_count: 2,
         };
 
@@ -720,6 +2157,7 @@ mod tests {
             ok: true,
             path: Some("/path".to_string()),
             fix: None,
+            ..Default::default()
         };
         validate_check_result(&result);
     }
@@ -730,6 +2168,7 @@ mod tests {
             ok: false,
             path: None,
             fix: Some("some fix".to_string()),
+            ..Default::default()
         };
         validate_check_result(&result);
     }
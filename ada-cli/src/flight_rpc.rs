@@ -0,0 +1,465 @@
+//! Flight recorder JSON-RPC for a running capture
+//!
+//! `ada capture start --rpc-address ADDR` shares the capture's tracer
+//! controller with a query_engine JSON-RPC server that serves
+//! `trace.flight.arm`, `trace.flight.fire` and `trace.flight.disarm`, so
+//! another tool can mark a moment without touching the capturing terminal,
+//! plus `trace.stats` for the live tracer counters.
+
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use anyhow::Context;
+use query_engine::handlers::{
+    FlightRecorderControl, FlightRecorderHandler, FlightState, TraceStatsHandler,
+    TraceStatsProvider,
+};
+use query_engine::server::JsonRpcServer;
+use serde_json::Value;
+use tokio::sync::oneshot;
+use tracer_backend::ffi::{FlightRecorderState, TracerStats};
+use tracer_backend::{TracerController, TracerError};
+
+use crate::capture::TraceStats;
+
+/// Tracer controller shared between the capture loop and the RPC server
+pub type SharedController = Arc<Mutex<TracerController>>;
+
+/// Lock a shared controller, carrying on past a panic in another holder
+fn lock<C>(controller: &Mutex<C>) -> MutexGuard<'_, C> {
+    controller.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Attached session on a [`SharedController`], stopped and detached on drop
+///
+/// The shared counterpart of [`tracer_backend::TracerSession`]: the capture
+/// loop locks it per call instead of holding the controller for the whole
+/// capture, leaving the RPC server room to drive the trigger in between.
+pub struct SharedSession {
+    controller: SharedController,
+    finished: bool,
+}
+
+impl SharedSession {
+    pub fn new(controller: SharedController) -> Self {
+        Self {
+            controller,
+            finished: false,
+        }
+    }
+
+    /// Lock the controller for a single call
+    pub fn lock(&self) -> MutexGuard<'_, TracerController> {
+        lock(&self.controller)
+    }
+
+    /// Stop the session and detach, reporting the first failure
+    pub fn finish(mut self) -> Result<(), TracerError> {
+        self.finished = true;
+        let mut controller = self.lock();
+        let stopped = controller.stop_session();
+        let detached = controller.detach();
+        stopped.and(detached)
+    }
+}
+
+impl Drop for SharedSession {
+    fn drop(&mut self) {
+        if !self.finished {
+            let mut controller = self.lock();
+            let _ = controller.stop_session();
+            let _ = controller.detach();
+        }
+    }
+}
+
+/// Trigger operations the flight recorder RPC maps onto
+pub trait FlightTrigger {
+    fn arm_trigger(&mut self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<(), TracerError>;
+    fn fire_trigger(&mut self) -> Result<(), TracerError>;
+    fn disarm_trigger(&mut self) -> Result<(), TracerError>;
+    fn flight_state(&self) -> FlightRecorderState;
+}
+
+impl FlightTrigger for TracerController {
+    fn arm_trigger(&mut self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<(), TracerError> {
+        TracerController::arm_trigger(self, pre_roll_ms, post_roll_ms)
+    }
+
+    fn fire_trigger(&mut self) -> Result<(), TracerError> {
+        TracerController::fire_trigger(self)
+    }
+
+    fn disarm_trigger(&mut self) -> Result<(), TracerError> {
+        TracerController::disarm_trigger(self)
+    }
+
+    fn flight_state(&self) -> FlightRecorderState {
+        self.get_flight_state()
+    }
+}
+
+/// [`FlightRecorderControl`] backed by a controller shared with the capture loop
+pub struct ControllerFlightRecorder<C> {
+    controller: Arc<Mutex<C>>,
+}
+
+impl<C> ControllerFlightRecorder<C> {
+    pub fn new(controller: Arc<Mutex<C>>) -> Self {
+        Self { controller }
+    }
+
+    /// Run `action` under the lock and report the state it left behind
+    fn apply(
+        &self,
+        action: impl FnOnce(&mut C) -> Result<(), TracerError>,
+    ) -> Result<FlightState, String>
+    where
+        C: FlightTrigger,
+    {
+        let mut controller = lock(&self.controller);
+        action(&mut controller).map_err(|err| err.to_string())?;
+        Ok(flight_state(controller.flight_state()))
+    }
+}
+
+impl<C: FlightTrigger + Send> FlightRecorderControl for ControllerFlightRecorder<C> {
+    fn arm(&self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<FlightState, String> {
+        self.apply(|controller| controller.arm_trigger(pre_roll_ms, post_roll_ms))
+    }
+
+    fn fire(&self) -> Result<FlightState, String> {
+        self.apply(C::fire_trigger)
+    }
+
+    fn disarm(&self) -> Result<FlightState, String> {
+        self.apply(C::disarm_trigger)
+    }
+}
+
+/// Map the backend's recorder state onto the RPC representation
+fn flight_state(state: FlightRecorderState) -> FlightState {
+    match state {
+        FlightRecorderState::Idle => FlightState::Idle,
+        FlightRecorderState::Armed => FlightState::Armed,
+        FlightRecorderState::PreRoll => FlightState::PreRoll,
+        FlightRecorderState::Recording => FlightState::Recording,
+        FlightRecorderState::PostRoll => FlightState::PostRoll,
+    }
+}
+
+/// Live stats the `trace.stats` RPC reports
+pub trait StatsSource {
+    /// Current stats, or `None` when no process is attached
+    fn tracer_stats(&self) -> Option<TracerStats>;
+}
+
+impl StatsSource for TracerController {
+    fn tracer_stats(&self) -> Option<TracerStats> {
+        self.is_attached().then(|| self.get_stats())
+    }
+}
+
+/// [`TraceStatsProvider`] backed by a controller shared with the capture loop
+///
+/// Serializes through the manifest's [`TraceStats`], so `trace.stats` and the
+/// bundle's `trace_stats` have the same shape.
+pub struct ControllerStats<C> {
+    controller: Arc<Mutex<C>>,
+}
+
+impl<C> ControllerStats<C> {
+    pub fn new(controller: Arc<Mutex<C>>) -> Self {
+        Self { controller }
+    }
+}
+
+impl<C: StatsSource + Send> TraceStatsProvider for ControllerStats<C> {
+    fn stats(&self) -> Option<Value> {
+        let stats = lock(&self.controller).tracer_stats()?;
+        serde_json::to_value(TraceStats::from(stats)).ok()
+    }
+}
+
+/// Register the `trace.flight.*` and `trace.stats` handlers for `controller`
+fn register_handlers<C>(controller: Arc<Mutex<C>>, server: &JsonRpcServer)
+where
+    C: FlightTrigger + StatsSource + Send + 'static,
+{
+    FlightRecorderHandler::register(
+        Arc::new(ControllerFlightRecorder::new(controller.clone())),
+        server,
+    );
+    TraceStatsHandler::new(Arc::new(ControllerStats::new(controller))).register(server);
+}
+
+/// JSON-RPC server for the flight recorder, running on its own thread
+///
+/// Shut down by [`FlightRpcServer::stop`] or on drop, so an early return
+/// from the capture never leaves it holding the controller.
+pub struct FlightRpcServer {
+    address: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FlightRpcServer {
+    /// Bind `address` and serve `trace.flight.*` and `trace.stats` on `controller`
+    ///
+    /// Binding happens before returning, so a taken port fails the capture
+    /// up front; port 0 picks a free one, reported by [`Self::address`].
+    pub fn start<C>(address: SocketAddr, controller: Arc<Mutex<C>>) -> anyhow::Result<Self>
+    where
+        C: FlightTrigger + StatsSource + Send + 'static,
+    {
+        let listener = TcpListener::bind(address)
+            .with_context(|| format!("Failed to bind flight recorder RPC to {}", address))?;
+        let address = listener.local_addr()?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("Failed to start flight recorder RPC runtime")?;
+
+        let server = JsonRpcServer::new();
+        register_handlers(controller, &server);
+
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        let thread = thread::spawn(move || {
+            let served = runtime.block_on(server.serve_on_listener(listener, async {
+                let _ = shutdown_rx.await;
+            }));
+            if let Err(err) = served {
+                tracing::warn!("Flight recorder RPC server stopped: {}", err);
+            }
+        });
+
+        Ok(Self {
+            address,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Stop serving and wait for the server thread, releasing the controller
+    pub fn stop(mut self) {
+        self.shutdown_and_join();
+    }
+
+    fn shutdown_and_join(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for FlightRpcServer {
+    fn drop(&mut self) {
+        self.shutdown_and_join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use serde_json::json;
+
+    /// Records each call and tracks the state a real recorder would reach
+    struct MockTrigger {
+        calls: Vec<String>,
+        state: FlightRecorderState,
+        stats: Option<TracerStats>,
+    }
+
+    impl MockTrigger {
+        fn shared() -> Arc<Mutex<Self>> {
+            Arc::new(Mutex::new(Self {
+                calls: Vec::new(),
+                state: FlightRecorderState::Idle,
+                stats: None,
+            }))
+        }
+    }
+
+    impl StatsSource for MockTrigger {
+        fn tracer_stats(&self) -> Option<TracerStats> {
+            self.stats
+        }
+    }
+
+    impl FlightTrigger for MockTrigger {
+        fn arm_trigger(&mut self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<(), TracerError> {
+            self.calls
+                .push(format!("arm_trigger({pre_roll_ms}, {post_roll_ms})"));
+            self.state = FlightRecorderState::Armed;
+            Ok(())
+        }
+
+        fn fire_trigger(&mut self) -> Result<(), TracerError> {
+            self.calls.push("fire_trigger".to_string());
+            if self.state != FlightRecorderState::Armed {
+                return Err(TracerError::Failed("fire trigger"));
+            }
+            self.state = FlightRecorderState::Recording;
+            Ok(())
+        }
+
+        fn disarm_trigger(&mut self) -> Result<(), TracerError> {
+            self.calls.push("disarm_trigger".to_string());
+            self.state = FlightRecorderState::Idle;
+            Ok(())
+        }
+
+        fn flight_state(&self) -> FlightRecorderState {
+            self.state
+        }
+    }
+
+    #[test]
+    fn controller_flight_recorder__each_action__then_calls_matching_trigger_and_returns_state() {
+        let controller = MockTrigger::shared();
+        let recorder = ControllerFlightRecorder::new(controller.clone());
+
+        assert_eq!(recorder.arm(500, 250), Ok(FlightState::Armed));
+        assert_eq!(recorder.fire(), Ok(FlightState::Recording));
+        assert_eq!(recorder.disarm(), Ok(FlightState::Idle));
+
+        assert_eq!(
+            controller.lock().unwrap().calls,
+            vec!["arm_trigger(500, 250)", "fire_trigger", "disarm_trigger"]
+        );
+    }
+
+    #[test]
+    fn controller_flight_recorder__trigger_fails__then_error_message_returned() {
+        let controller = MockTrigger::shared();
+        let recorder = ControllerFlightRecorder::new(controller);
+
+        let err = recorder.fire().unwrap_err();
+
+        assert_eq!(err, "Failed to fire trigger");
+    }
+
+    #[test]
+    fn flight_state__every_backend_state__then_same_rpc_state() {
+        let pairs = [
+            (FlightRecorderState::Idle, FlightState::Idle),
+            (FlightRecorderState::Armed, FlightState::Armed),
+            (FlightRecorderState::PreRoll, FlightState::PreRoll),
+            (FlightRecorderState::Recording, FlightState::Recording),
+            (FlightRecorderState::PostRoll, FlightState::PostRoll),
+        ];
+
+        for (backend, rpc) in pairs {
+            assert_eq!(flight_state(backend), rpc);
+        }
+    }
+
+    #[test]
+    fn flight_rpc_server__registered_methods__then_drive_shared_controller() {
+        let controller = MockTrigger::shared();
+        let server = JsonRpcServer::new();
+        register_handlers(controller.clone(), &server);
+        let registry = server.handler_registry();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let armed = runtime
+            .block_on(registry.call("trace.flight.arm", Some(json!({ "preRollMs": 100 }))))
+            .expect("arm");
+        let fired = runtime
+            .block_on(registry.call("trace.flight.fire", None))
+            .expect("fire");
+
+        assert_eq!(armed, json!("armed"));
+        assert_eq!(fired, json!("recording"));
+        assert_eq!(
+            controller.lock().unwrap().calls,
+            vec!["arm_trigger(100, 0)", "fire_trigger"]
+        );
+    }
+
+    #[test]
+    fn flight_rpc_server__trace_stats__then_manifest_shaped_stats() {
+        let controller = MockTrigger::shared();
+        let stats = TracerStats {
+            events_captured: 1200,
+            events_dropped: 3,
+            bytes_written: 4096,
+            active_threads: 4,
+            hooks_installed: 42,
+            fallback_events: 7,
+            preroll_evicted: 5,
+        };
+        controller.lock().unwrap().stats = Some(stats);
+        let server = JsonRpcServer::new();
+        register_handlers(controller, &server);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let result = runtime
+            .block_on(server.handler_registry().call("trace.stats", None))
+            .expect("stats");
+
+        assert_eq!(
+            result,
+            json!({
+                "events_captured": 1200,
+                "events_dropped": 3,
+                "bytes_written": 4096,
+                "hooks_installed": 42,
+                "fallback_events": 7,
+                "preroll_evicted": 5,
+            })
+        );
+        assert_eq!(
+            result,
+            serde_json::to_value(TraceStats::from(stats)).unwrap()
+        );
+    }
+
+    #[test]
+    fn flight_rpc_server__trace_stats_not_attached__then_error() {
+        let server = JsonRpcServer::new();
+        register_handlers(MockTrigger::shared(), &server);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let err = runtime
+            .block_on(server.handler_registry().call("trace.stats", None))
+            .expect_err("not attached");
+
+        assert_eq!(err.data, Some(json!("no tracer controller attached")));
+    }
+
+    #[test]
+    fn flight_rpc_server__port_taken__then_start_fails() {
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+
+        let result = FlightRpcServer::start(taken.local_addr().unwrap(), MockTrigger::shared());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn flight_rpc_server__port_zero__then_reports_bound_address_and_stops() {
+        let server =
+            FlightRpcServer::start("127.0.0.1:0".parse().unwrap(), MockTrigger::shared()).unwrap();
+
+        assert_ne!(server.address().port(), 0);
+        server.stop();
+    }
+}
@@ -0,0 +1,103 @@
+//! Info.plist key lookup for app bundles.
+//!
+//! Parses XML and binary plists in-process with the `plist` crate and only
+//! shells out to PlistBuddy when the file can't be parsed.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// Read a top-level string value such as `CFBundleIdentifier`.
+pub fn read_string_key(plist_path: &Path, key: &str) -> Result<String> {
+    match plist::Value::from_file(plist_path) {
+        Ok(value) => {
+            string_key(&value, key).with_context(|| format!("{} in {}", key, plist_path.display()))
+        }
+        Err(_) => read_with_plist_buddy(plist_path, key),
+    }
+}
+
+/// Look up `key` in a parsed plist whose root is a dictionary.
+fn string_key(value: &plist::Value, key: &str) -> Result<String> {
+    let Some(dict) = value.as_dictionary() else {
+        bail!("plist root is not a dictionary");
+    };
+    match dict.get(key) {
+        Some(plist::Value::String(s)) => Ok(s.clone()),
+        Some(_) => bail!("key is not a string"),
+        None => bail!("key not found"),
+    }
+}
+
+// LCOV_EXCL_START - PlistBuddy only exists on macOS
+
+/// Read a key using PlistBuddy.
+fn read_with_plist_buddy(plist_path: &Path, key: &str) -> Result<String> {
+    let output = Command::new("/usr/libexec/PlistBuddy")
+        .arg("-c")
+        .arg(format!("Print :{}", key))
+        .arg(plist_path)
+        .output()
+        .context("Failed to execute PlistBuddy")?;
+
+    if !output.status.success() {
+        bail!(
+            "PlistBuddy failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// LCOV_EXCL_STOP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/info_plist")
+            .join(name)
+    }
+
+    #[test]
+    fn read_string_key__xml_plist__then_value() {
+        let path = fixture("Info.xml.plist");
+
+        assert_eq!(
+            read_string_key(&path, "CFBundleIdentifier").unwrap(),
+            "com.example.app"
+        );
+        assert_eq!(
+            read_string_key(&path, "CFBundleExecutable").unwrap(),
+            "Example"
+        );
+    }
+
+    #[test]
+    fn read_string_key__binary_plist__then_value() {
+        let path = fixture("Info.binary.plist");
+
+        assert_eq!(
+            read_string_key(&path, "CFBundleIdentifier").unwrap(),
+            "com.example.app"
+        );
+        assert_eq!(
+            read_string_key(&path, "CFBundleExecutable").unwrap(),
+            "Example"
+        );
+    }
+
+    #[test]
+    fn read_string_key__missing_key__then_error_names_key() {
+        let err = read_string_key(&fixture("Info.xml.plist"), "NSPrincipalClass").unwrap_err();
+
+        let message = format!("{:#}", err);
+        assert!(message.contains("NSPrincipalClass"), "{}", message);
+        assert!(message.contains("key not found"), "{}", message);
+    }
+}
@@ -4,6 +4,10 @@
 
 pub mod audio;
 pub mod binary_resolver;
+pub mod bundle_hash;
+pub mod doctor;
+pub mod info_plist;
+pub mod media_probe;
 pub mod model_manager;
 
 #[doc(hidden)]
@@ -9,8 +9,8 @@
 //! - `ada query` - Query trace data
 
 mod capture;
-mod doctor;
 mod ffi;
+mod flight_rpc;
 mod query;
 mod session_state;
 mod symbols;
@@ -18,6 +18,7 @@ mod trace;
 
 use std::path::PathBuf;
 
+use ada_cli::doctor;
 use clap::{Parser, Subcommand};
 use tracing_subscriber::{fmt, EnvFilter};
 
@@ -29,10 +30,14 @@ use tracing_subscriber::{fmt, EnvFilter};
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
 struct Cli {
-    /// Enable verbose output
-    #[arg(short, long, global = true)]
+    /// Enable verbose output (debug logging)
+    #[arg(short, long, global = true, conflicts_with = "quiet")]
     verbose: bool,
 
+    /// Only log warnings and errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -166,6 +171,24 @@ pub enum QueryCommands {
         format: String,
     },
 
+    /// Summarize bundle contents (media, trace session, transcript cache)
+    Info {
+        /// Output format (text or json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Check that the files referenced by the bundle manifest are present
+    Validate {
+        /// Also recompute the content hash and compare it with the manifest's
+        #[arg(long)]
+        check_hash: bool,
+
+        /// Output format (text or json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
     /// Transcribe voice recording from session
     #[command(subcommand)]
     Transcribe(TranscribeCommands),
@@ -191,7 +214,15 @@ pub enum QueryCommands {
 pub enum TranscribeCommands {
     /// Get transcript metadata without loading full content
     Info {
-        /// Output format (text or json)
+        /// Loudness-normalize (EBU R128) the recording before transcribing
+        #[arg(long)]
+        normalize: bool,
+
+        /// Include per-word timestamps in each segment
+        #[arg(long)]
+        word_timestamps: bool,
+
+        /// Output format (text, json, or line for JSON Lines)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
@@ -202,7 +233,7 @@ pub enum TranscribeCommands {
         #[arg(short, long, default_value = "0")]
         offset: usize,
 
-        /// Maximum number of segments to return
+        /// Maximum number of segments to return (at most 1000)
         #[arg(short, long, default_value = "20")]
         limit: usize,
 
@@ -214,27 +245,53 @@ pub enum TranscribeCommands {
         #[arg(long)]
         until: Option<f64>,
 
-        /// Output format (text or json)
+        /// Loudness-normalize (EBU R128) the recording before transcribing
+        #[arg(long)]
+        normalize: bool,
+
+        /// Include per-word timestamps in each segment
+        #[arg(long)]
+        word_timestamps: bool,
+
+        /// Merge adjacent short segments separated by brief pauses
+        #[arg(long)]
+        coalesce: bool,
+
+        /// Drop segments whose average token log-probability is below this
+        /// value (e.g. -1.0); helps filter text hallucinated over silence
+        #[arg(long, allow_hyphen_values = true)]
+        min_logprob: Option<f64>,
+
+        /// Output format (text, json, or line for JSON Lines)
         #[arg(short = 'f', long, default_value = "text")]
         format: String,
     },
 }
 // LCOV_EXCL_STOP
 
+/// Log filter directive for the `--quiet`/`--verbose` flags, used when
+/// `RUST_LOG` is not set
+fn log_directive(quiet: bool, verbose: bool) -> &'static str {
+    if verbose {
+        "debug"
+    } else if quiet {
+        "warn"
+    } else {
+        "info"
+    }
+}
+
 fn main() -> anyhow::Result<()> {
-    // Initialize logging
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let cli = Cli::parse();
+
+    // Initialize logging; an explicit RUST_LOG wins over the flags
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(log_directive(cli.quiet, cli.verbose)));
     fmt::Subscriber::builder()
         .with_env_filter(filter)
         .with_target(false)
         .init();
 
-    let cli = Cli::parse();
-
-    if cli.verbose {
-        tracing::info!("Verbose mode enabled");
-    }
-
     // LCOV_EXCL_START - CLI entry point, tested via integration
     match cli.command {
         Commands::Trace(cmd) => trace::run(cmd),
@@ -246,3 +303,21 @@ fn main() -> anyhow::Result<()> {
     }
     // LCOV_EXCL_STOP
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_directive__flags__then_matching_level() {
+        assert_eq!(log_directive(false, false), "info");
+        assert_eq!(log_directive(true, false), "warn");
+        assert_eq!(log_directive(false, true), "debug");
+    }
+
+    #[test]
+    fn cli__quiet_and_verbose__then_conflict() {
+        assert!(Cli::try_parse_from(["ada", "-q", "doctor", "check"]).is_ok());
+        assert!(Cli::try_parse_from(["ada", "-q", "-v", "doctor", "check"]).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Media file properties read with ffprobe.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+/// Duration of a media file (container duration) in seconds.
+pub fn duration_sec(path: &Path) -> Result<f64> {
+    let ffprobe = crate::binary_resolver::resolve(crate::binary_resolver::Tool::Ffprobe)
+        .map_err(|_| anyhow!("ffprobe not available. Run: ./utils/init_media_tools.sh"))?;
+
+    let output = Command::new(&ffprobe)
+        .args(["-v", "error", "-show_entries", "format=duration"])
+        .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(path)
+        .output()
+        .with_context(|| "Failed to run ffprobe")?;
+
+    if !output.status.success() {
+        bail!(
+            "ffprobe failed on {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_duration(&String::from_utf8_lossy(&output.stdout))
+        .with_context(|| format!("No duration reported for {}", path.display()))
+}
+
+/// Parse the value printed by
+/// `ffprobe -show_entries format=duration -of default=noprint_wrappers=1:nokey=1`.
+///
+/// ffprobe prints `N/A` when the container has no duration.
+fn parse_duration(output: &str) -> Result<f64> {
+    let value = output.trim();
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds >= 0.0 => Ok(seconds),
+        _ => bail!("unexpected ffprobe duration: {:?}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration__seconds__then_value() {
+        assert_eq!(parse_duration("12.345000\n").unwrap(), 12.345);
+    }
+
+    #[test]
+    fn parse_duration__not_available__then_error() {
+        assert!(parse_duration("N/A\n").is_err());
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("-1.0").is_err());
+    }
+}
@@ -6,10 +6,12 @@
 mod bundle;
 mod capabilities;
 mod events;
+mod info;
 mod output;
 mod screenshot;
 mod session;
 mod transcribe;
+mod validate;
 
 use std::path::Path;
 
@@ -41,6 +43,21 @@ pub fn run(bundle_path: &Path, cmd: QueryCommands) -> Result<()> {
         QueryCommands::Transcribe(transcribe_cmd) => {
             return execute_transcribe_query(&bundle, transcribe_cmd);
         }
+        QueryCommands::Info { format } => {
+            let fmt = parse_format(format)?;
+            let info = info::get_info(&bundle);
+            println!("{}", info::format_info(&info, fmt));
+            return Ok(());
+        }
+        QueryCommands::Validate { check_hash, format } => {
+            let fmt = parse_format(format)?;
+            let report = validate::validate(&bundle, *check_hash)?;
+            println!("{}", validate::format_report(&report, fmt));
+            if !report.ok {
+                anyhow::bail!("Bundle validation failed");
+            }
+            return Ok(());
+        }
         QueryCommands::Screenshot { time, output, format } => {
             let fmt = parse_format(format)?;
             let result = screenshot::extract_screenshot(&bundle, *time, output.as_deref())?;
@@ -60,9 +77,17 @@ pub fn run(bundle_path: &Path, cmd: QueryCommands) -> Result<()> {
 /// Execute a transcribe query
 fn execute_transcribe_query(bundle: &Bundle, cmd: &TranscribeCommands) -> Result<()> {
     match cmd {
-        TranscribeCommands::Info { format } => {
+        TranscribeCommands::Info {
+            normalize,
+            word_timestamps,
+            format,
+        } => {
             let fmt = parse_format(format)?;
-            let info = transcribe::get_info(bundle)?;
+            let options = transcribe::TranscribeOptions {
+                normalize: *normalize,
+                word_timestamps: *word_timestamps,
+            };
+            let info = transcribe::get_info(bundle, &options)?;
             println!("{}", transcribe::format_info(&info, fmt));
         }
         TranscribeCommands::Segments {
@@ -70,10 +95,26 @@ fn execute_transcribe_query(bundle: &Bundle, cmd: &TranscribeCommands) -> Result
             limit,
             since,
             until,
+            normalize,
+            word_timestamps,
+            coalesce,
+            min_logprob,
             format,
         } => {
             let fmt = parse_format(format)?;
-            let result = transcribe::get_segments(bundle, *offset, *limit, *since, *until)?;
+            let options = transcribe::TranscribeOptions {
+                normalize: *normalize,
+                word_timestamps: *word_timestamps,
+            };
+            let query = transcribe::SegmentsQuery {
+                offset: *offset,
+                limit: *limit,
+                since: *since,
+                until: *until,
+                coalesce: *coalesce,
+                min_logprob: *min_logprob,
+            };
+            let result = transcribe::get_segments(bundle, &query, &options)?;
             println!("{}", transcribe::format_segments(&result, fmt));
         }
     }
@@ -137,6 +178,14 @@ fn execute_trace_query(session: &session::Session, cmd: QueryCommands) -> Result
             // Already handled above before opening bundle
             unreachable!("Capabilities handled before session open")
         }
+        QueryCommands::Info { .. } => {
+            // Already handled above before opening session
+            unreachable!("Info handled before session open")
+        }
+        QueryCommands::Validate { .. } => {
+            // Already handled above before opening session
+            unreachable!("Validate handled before session open")
+        }
         QueryCommands::Transcribe(_) => {
             // Already handled above before opening session
             unreachable!("Transcribe handled before session open")
@@ -14,7 +14,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::session_state;
 
@@ -29,6 +29,10 @@ pub struct BundleManifest {
     /// Relative path to trace session directory (specific session within trace_root)
     #[serde(default)]
     pub trace_session: Option<String>,
+    /// Relative paths to every trace session directory, oldest first
+    /// (only written when a capture produced more than one)
+    #[serde(default)]
+    pub trace_sessions: Vec<String>,
     /// Relative path to screen recording (optional)
     #[serde(default)]
     pub screen_path: Option<String>,
@@ -38,6 +42,18 @@ pub struct BundleManifest {
     /// Relative path to lossless voice recording (optional)
     #[serde(default)]
     pub voice_lossless_path: Option<String>,
+    /// Screen recording length in seconds, as probed at capture time (optional)
+    #[serde(default)]
+    pub screen_duration_sec: Option<f64>,
+    /// Voice recording length in seconds, as probed at capture time (optional)
+    #[serde(default)]
+    pub voice_duration_sec: Option<f64>,
+    /// Free-text capture note (optional)
+    #[serde(default)]
+    pub note: Option<String>,
+    /// SHA-256 of the bundle contents at finalization (see `ada_cli::bundle_hash`)
+    #[serde(default)]
+    pub bundle_hash: Option<String>,
 }
 
 /// Resolve user input to a bundle directory path
@@ -99,6 +115,53 @@ pub fn resolve_bundle_path(input: &Path) -> Result<PathBuf> {
     )
 }
 
+/// Count and total size of a group of files
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FileGroup {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+impl FileGroup {
+    fn add(&mut self, bytes: u64) {
+        self.count += 1;
+        self.bytes += bytes;
+    }
+}
+
+/// Output files found in a trace session directory
+///
+/// Lets callers see which data a session actually holds (e.g. events but no
+/// detail lanes) before opening it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceSummary {
+    /// Per-thread event streams (`thread_*/index.atf`)
+    pub index_files: FileGroup,
+    /// Per-thread detail streams (`thread_*/detail.atf`)
+    pub detail_files: FileGroup,
+    /// Size of the ATF `manifest.json`, which carries the symbol map
+    pub symbol_map_bytes: Option<u64>,
+    /// Size of `tracer_stats.json`, if the tracer wrote one into the session
+    pub tracer_stats_bytes: Option<u64>,
+    /// Anything else under the session directory
+    pub other_files: FileGroup,
+    /// Every file above
+    pub total: FileGroup,
+}
+
+impl TraceSummary {
+    fn add(&mut self, path: &Path, bytes: u64) {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some("index.atf") => self.index_files.add(bytes),
+            Some("detail.atf") => self.detail_files.add(bytes),
+            Some("manifest.json") => self.symbol_map_bytes = Some(bytes),
+            Some("tracer_stats.json") => self.tracer_stats_bytes = Some(bytes),
+            _ => self.other_files.add(bytes),
+        }
+        self.total.add(bytes);
+    }
+}
+
 /// An opened ADA bundle with validated manifest
 #[derive(Debug)]
 pub struct Bundle {
@@ -151,6 +214,51 @@ impl Bundle {
         }
     }
 
+    /// Get every trace session directory in the bundle, oldest first
+    ///
+    /// Uses the manifest's `trace_sessions` list when present; otherwise walks
+    /// the trace root for `session_*` directories, taking each one's PID
+    /// subdirectories (or the session directory itself if it has none).
+    /// Falls back to [`Bundle::trace_path`] when nothing is found.
+    pub fn trace_sessions(&self) -> Vec<PathBuf> {
+        if !self.manifest.trace_sessions.is_empty() {
+            return self
+                .manifest
+                .trace_sessions
+                .iter()
+                .map(|p| self.path.join(p))
+                .collect();
+        }
+
+        let trace_root = self.manifest.trace_root.as_deref().unwrap_or("trace");
+        let sessions = find_trace_sessions(&self.path.join(trace_root));
+        if sessions.is_empty() {
+            vec![self.trace_path()]
+        } else {
+            sessions
+        }
+    }
+
+    /// Summarize the output files in the trace session directory
+    ///
+    /// Returns `None` when the directory is missing or holds no files.
+    pub fn trace_manifest(&self) -> Result<Option<TraceSummary>> {
+        let mut summary = TraceSummary::default();
+        collect_trace_files(&self.trace_path(), &mut summary)?;
+        Ok((summary.total.count > 0).then_some(summary))
+    }
+
+    /// Recompute the content hash and compare it with the manifest's
+    ///
+    /// Fails if the manifest records no hash (bundles from older captures).
+    pub fn verify_hash(&self) -> Result<bool> {
+        let Some(recorded) = &self.manifest.bundle_hash else {
+            bail!("Bundle manifest has no bundle_hash to verify");
+        };
+        let actual = ada_cli::bundle_hash::compute(&self.path)?;
+        Ok(actual.eq_ignore_ascii_case(recorded))
+    }
+
     /// Get screen recording path if available
     #[allow(dead_code)]
     pub fn screen_path(&self) -> Option<PathBuf> {
@@ -179,6 +287,66 @@ impl Bundle {
     }
 }
 
+/// Sorted subdirectories of `dir` accepted by `keep`
+fn sorted_subdirs(dir: &Path, keep: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(&keep)
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Trace session directories under `trace_root`: `session_*/<pid dir>`,
+/// or `session_*` itself when it holds no PID directories
+fn find_trace_sessions(trace_root: &Path) -> Vec<PathBuf> {
+    sorted_subdirs(trace_root, |name| name.starts_with("session_"))
+        .into_iter()
+        .flat_map(|session| {
+            let pid_dirs = sorted_subdirs(&session, |_| true);
+            if pid_dirs.is_empty() {
+                vec![session]
+            } else {
+                pid_dirs
+            }
+        })
+        .collect()
+}
+
+/// Add every file under `dir` to `summary`; a missing `dir` adds nothing
+fn collect_trace_files(dir: &Path, summary: &mut TraceSummary) -> Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read trace directory {:?}", dir))
+        }
+    };
+
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read trace directory {:?}", dir))?;
+        let path = entry.path();
+        let metadata = entry
+            .metadata()
+            .with_context(|| format!("Failed to stat {:?}", path))?;
+        if metadata.is_dir() {
+            collect_trace_files(&path, summary)?;
+        } else {
+            summary.add(&path, metadata.len());
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,6 +432,189 @@ mod tests {
         assert_eq!(bundle.trace_path(), temp_dir.path().join("trace"));
     }
 
+    /// Bundle with two flight-recorder sessions: one PID dir each
+    fn create_multi_session_bundle(manifest: &str) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        for dir in [
+            "trace/session_2026_01_24_10_00_00/pid_100",
+            "trace/session_2026_01_24_10_05_00/pid_100",
+        ] {
+            fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        }
+        fs::write(temp_dir.path().join("manifest.json"), manifest).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_bundle__trace_sessions__two_session_dirs__then_all_listed_oldest_first() {
+        let temp_dir = create_multi_session_bundle(
+            r#"{
+                "version": 1,
+                "trace_root": "trace",
+                "trace_session": "trace/session_2026_01_24_10_05_00/pid_100"
+            }"#,
+        );
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            bundle.trace_sessions(),
+            vec![
+                temp_dir
+                    .path()
+                    .join("trace/session_2026_01_24_10_00_00/pid_100"),
+                temp_dir
+                    .path()
+                    .join("trace/session_2026_01_24_10_05_00/pid_100"),
+            ]
+        );
+        // Primary session is unchanged
+        assert_eq!(
+            bundle.trace_path(),
+            temp_dir
+                .path()
+                .join("trace/session_2026_01_24_10_05_00/pid_100")
+        );
+    }
+
+    #[test]
+    fn test_bundle__trace_sessions__manifest_list__then_used_as_is() {
+        let temp_dir = create_multi_session_bundle(
+            r#"{
+                "version": 1,
+                "trace_sessions": ["trace/session_2026_01_24_10_05_00/pid_100"]
+            }"#,
+        );
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            bundle.trace_sessions(),
+            vec![temp_dir
+                .path()
+                .join("trace/session_2026_01_24_10_05_00/pid_100")]
+        );
+    }
+
+    #[test]
+    fn test_bundle__trace_sessions__session_without_pid_dirs__then_session_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("trace/session_1")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("trace/not_a_session")).unwrap();
+        fs::write(temp_dir.path().join("manifest.json"), r#"{ "version": 1 }"#).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            bundle.trace_sessions(),
+            vec![temp_dir.path().join("trace/session_1")]
+        );
+    }
+
+    #[test]
+    fn test_bundle__trace_sessions__empty_trace_root__then_trace_path() {
+        let temp_dir = create_valid_bundle();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert_eq!(bundle.trace_sessions(), vec![bundle.trace_path()]);
+    }
+
+    #[test]
+    fn test_bundle__trace_manifest__fixture_files__then_counts_and_sizes() {
+        let temp_dir = create_valid_bundle();
+        let trace_dir = temp_dir.path().join("trace");
+        for (file, len) in [
+            ("manifest.json", 64),
+            ("tracer_stats.json", 16),
+            ("thread_0/index.atf", 1024),
+            ("thread_0/detail.atf", 256),
+            ("thread_1/index.atf", 512),
+            ("notes.txt", 8),
+        ] {
+            let path = trace_dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![0u8; len]).unwrap();
+        }
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let summary = bundle.trace_manifest().unwrap().unwrap();
+
+        assert_eq!(
+            summary,
+            TraceSummary {
+                index_files: FileGroup {
+                    count: 2,
+                    bytes: 1536
+                },
+                detail_files: FileGroup {
+                    count: 1,
+                    bytes: 256
+                },
+                symbol_map_bytes: Some(64),
+                tracer_stats_bytes: Some(16),
+                other_files: FileGroup { count: 1, bytes: 8 },
+                total: FileGroup {
+                    count: 6,
+                    bytes: 1880
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_bundle__trace_manifest__empty_trace_dir__then_none() {
+        let temp_dir = create_valid_bundle();
+        fs::create_dir_all(temp_dir.path().join("trace/thread_0")).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert_eq!(bundle.trace_manifest().unwrap(), None);
+    }
+
+    #[test]
+    fn test_bundle__trace_manifest__missing_trace_dir__then_none() {
+        let temp_dir = create_valid_bundle();
+        fs::remove_dir(temp_dir.path().join("trace")).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert_eq!(bundle.trace_manifest().unwrap(), None);
+    }
+
+    /// Bundle whose manifest records the hash of its current contents
+    fn create_hashed_bundle() -> TempDir {
+        let temp_dir = create_valid_bundle();
+        fs::write(temp_dir.path().join("trace/index.atf"), b"events").unwrap();
+        let hash = ada_cli::bundle_hash::compute(temp_dir.path()).unwrap();
+        let manifest = format!(
+            r#"{{ "version": 1, "trace_session": "trace", "bundle_hash": "{}" }}"#,
+            hash
+        );
+        fs::write(temp_dir.path().join("manifest.json"), manifest).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_bundle__verify_hash__unmodified__then_true() {
+        let temp_dir = create_hashed_bundle();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert!(bundle.verify_hash().unwrap());
+    }
+
+    #[test]
+    fn test_bundle__verify_hash__file_modified__then_false() {
+        let temp_dir = create_hashed_bundle();
+        fs::write(temp_dir.path().join("trace/index.atf"), b"tampered").unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        assert!(!bundle.verify_hash().unwrap());
+    }
+
+    #[test]
+    fn test_bundle__verify_hash__no_recorded_hash__then_error() {
+        let temp_dir = create_valid_bundle();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let err = bundle.verify_hash().unwrap_err();
+        assert!(err.to_string().contains("no bundle_hash"));
+    }
+
     #[test]
     fn test_bundle__screen_path__returns_joined_path() {
         let temp_dir = create_valid_bundle();
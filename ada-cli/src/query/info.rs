@@ -0,0 +1,341 @@
+//! Bundle overview for `ada query <bundle> info`
+//!
+//! Summarizes what a capture bundle contains: manifest version, media
+//! artifacts and their durations, the trace sessions with their event counts,
+//! and whether a transcript has been cached.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::bundle::{Bundle, TraceSummary};
+use super::output::OutputFormat;
+use super::transcribe::{self, TranscribeOptions};
+use crate::capture::TraceStats;
+
+/// Bundle overview
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleInfo {
+    /// Bundle directory
+    pub path: PathBuf,
+    /// Manifest version
+    pub manifest_version: u32,
+    /// Capture note, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub screen: MediaInfo,
+    pub voice: MediaInfo,
+    pub trace: TraceInfo,
+    pub transcript: TranscriptStatus,
+}
+
+/// A media artifact (screen or voice recording)
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaInfo {
+    /// Whether the file referenced by the manifest exists
+    pub present: bool,
+    /// Path relative to the bundle, as recorded in the manifest
+    pub path: Option<String>,
+    /// Duration in seconds (requires ffprobe)
+    pub duration_sec: Option<f64>,
+}
+
+/// Trace session summary
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceInfo {
+    /// Trace session directory
+    pub path: PathBuf,
+    /// Whether the trace session directory exists
+    pub present: bool,
+    /// Counts from tracer_stats.json (absent for older bundles)
+    pub stats: Option<TraceStats>,
+    /// Output files in the session directory (absent when it holds none)
+    pub files: Option<TraceSummary>,
+    /// Every trace session in the bundle, oldest first; includes `path`
+    pub sessions: Vec<PathBuf>,
+}
+
+/// Transcript cache status
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptStatus {
+    pub cached: bool,
+}
+
+/// Collect the overview for an opened bundle
+pub fn get_info(bundle: &Bundle) -> BundleInfo {
+    let trace_path = bundle.trace_path();
+
+    BundleInfo {
+        path: bundle.path.clone(),
+        manifest_version: bundle.manifest.version,
+        note: bundle.manifest.note.clone(),
+        screen: media_info(
+            bundle.manifest.screen_path.as_deref(),
+            bundle.screen_path(),
+            bundle.manifest.screen_duration_sec,
+        ),
+        voice: voice_info(bundle),
+        trace: TraceInfo {
+            present: trace_path.is_dir(),
+            path: trace_path,
+            stats: read_tracer_stats(&bundle.path),
+            files: bundle.trace_manifest().ok().flatten(),
+            sessions: bundle.trace_sessions(),
+        },
+        transcript: TranscriptStatus {
+            cached: transcribe::is_cached(bundle, &TranscribeOptions::default()),
+        },
+    }
+}
+
+/// Media artifact info, probing the duration only when the manifest
+/// doesn't record it
+fn media_info(
+    relative: Option<&str>,
+    absolute: Option<PathBuf>,
+    recorded_duration_sec: Option<f64>,
+) -> MediaInfo {
+    let present = absolute.as_ref().map(|p| p.exists()).unwrap_or(false);
+    let duration_sec = absolute.filter(|_| present).and_then(|p| {
+        recorded_duration_sec.or_else(|| ada_cli::media_probe::duration_sec(&p).ok())
+    });
+
+    MediaInfo {
+        present,
+        path: relative.map(str::to_string),
+        duration_sec,
+    }
+}
+
+/// Voice recording, falling back to the lossless WAV when no compressed
+/// copy exists
+fn voice_info(bundle: &Bundle) -> MediaInfo {
+    let recorded = bundle.manifest.voice_duration_sec;
+    let compressed = media_info(
+        bundle.manifest.voice_path.as_deref(),
+        bundle.voice_path(),
+        recorded,
+    );
+    if compressed.present || bundle.manifest.voice_lossless_path.is_none() {
+        return compressed;
+    }
+    media_info(
+        bundle.manifest.voice_lossless_path.as_deref(),
+        bundle.voice_lossless_path(),
+        recorded,
+    )
+}
+
+/// Read tracer_stats.json from the bundle root
+fn read_tracer_stats(bundle_path: &Path) -> Option<TraceStats> {
+    let content = fs::read_to_string(bundle_path.join("tracer_stats.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Format bundle overview
+pub fn format_info(info: &BundleInfo, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Text | OutputFormat::Line => format_info_text(info),
+        OutputFormat::Json => format_info_json(info),
+    }
+}
+
+fn format_info_text(info: &BundleInfo) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("Bundle:         {}\n", info.path.display()));
+    output.push_str(&format!("Manifest:       v{}\n", info.manifest_version));
+    if let Some(note) = &info.note {
+        output.push_str(&format!("Note:           {}\n", note));
+    }
+    output.push_str(&format!("Screen:         {}\n", format_media(&info.screen)));
+    output.push_str(&format!("Voice:          {}\n", format_media(&info.voice)));
+    output.push_str(&format!(
+        "Trace Session:  {}{}\n",
+        info.trace.path.display(),
+        if info.trace.present { "" } else { " (missing)" }
+    ));
+    if info.trace.sessions.len() > 1 {
+        output.push_str(&format!(
+            "All Sessions:   {} (queries read the one above)\n",
+            info.trace.sessions.len()
+        ));
+        for session in &info.trace.sessions {
+            output.push_str(&format!("                {}\n", session.display()));
+        }
+    }
+    match &info.trace.stats {
+        Some(stats) => output.push_str(&format!(
+            "Events:         {} captured, {} dropped ({} hooks)\n",
+            stats.events_captured, stats.events_dropped, stats.hooks_installed
+        )),
+        None => output.push_str("Events:         unknown (no tracer_stats.json)\n"),
+    }
+    match &info.trace.files {
+        Some(files) => output.push_str(&format!(
+            "Trace Files:    {} event, {} detail ({} bytes total)\n",
+            files.index_files.count, files.detail_files.count, files.total.bytes
+        )),
+        None => output.push_str("Trace Files:    none\n"),
+    }
+    output.push_str(&format!(
+        "Transcript:     {}\n",
+        if info.transcript.cached {
+            "cached"
+        } else {
+            "not cached"
+        }
+    ));
+    output
+}
+
+fn format_media(media: &MediaInfo) -> String {
+    match (&media.path, media.present, media.duration_sec) {
+        (Some(path), true, Some(duration)) => format!("{} ({:.1} s)", path, duration),
+        (Some(path), true, None) => path.clone(),
+        (Some(path), false, _) => format!("{} (missing)", path),
+        (None, _, _) => "none".to_string(),
+    }
+}
+
+fn format_info_json(info: &BundleInfo) -> String {
+    serde_json::to_string_pretty(info).unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_bundle_with_stats() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("trace/session_1/pid_1")).unwrap();
+        fs::write(temp_dir.path().join("voice.wav"), b"RIFF").unwrap();
+
+        let manifest = r#"{
+            "version": 1,
+            "trace_root": "trace",
+            "trace_session": "trace/session_1/pid_1",
+            "screen_path": "screen.mp4",
+            "voice_path": null,
+            "voice_lossless_path": "voice.wav",
+            "note": "login flow"
+        }"#;
+        fs::write(temp_dir.path().join("manifest.json"), manifest).unwrap();
+
+        let stats = r#"{
+            "events_captured": 1200,
+            "events_dropped": 3,
+            "bytes_written": 4096,
+            "hooks_installed": 42,
+            "fallback_events": 0
+        }"#;
+        fs::write(temp_dir.path().join("tracer_stats.json"), stats).unwrap();
+
+        temp_dir
+    }
+
+    #[test]
+    fn get_info__bundle_with_stats__then_json_has_expected_shape() {
+        let temp_dir = create_bundle_with_stats();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let info = get_info(&bundle);
+        let json: serde_json::Value =
+            serde_json::from_str(&format_info(&info, OutputFormat::Json)).unwrap();
+
+        assert_eq!(json["manifest_version"], 1);
+        assert_eq!(json["note"], "login flow");
+
+        // screen.mp4 is referenced but was never written
+        assert_eq!(json["screen"]["present"], false);
+        assert_eq!(json["screen"]["path"], "screen.mp4");
+        assert!(json["screen"]["duration_sec"].is_null());
+
+        // No compressed voice, so the lossless WAV is reported
+        assert_eq!(json["voice"]["present"], true);
+        assert_eq!(json["voice"]["path"], "voice.wav");
+
+        assert_eq!(json["trace"]["present"], true);
+        assert!(json["trace"]["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("trace/session_1/pid_1"));
+        assert_eq!(json["trace"]["stats"]["events_captured"], 1200);
+        assert_eq!(json["trace"]["stats"]["events_dropped"], 3);
+        assert_eq!(json["trace"]["stats"]["hooks_installed"], 42);
+        // Session directory exists but nothing was written to it
+        assert!(json["trace"]["files"].is_null());
+
+        assert_eq!(json["transcript"]["cached"], false);
+    }
+
+    #[test]
+    fn get_info__no_stats_file__then_stats_null_and_text_says_unknown() {
+        let temp_dir = create_bundle_with_stats();
+        fs::remove_file(temp_dir.path().join("tracer_stats.json")).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let info = get_info(&bundle);
+        assert!(info.trace.stats.is_none());
+
+        let text = format_info(&info, OutputFormat::Text);
+        assert!(text.contains("Manifest:       v1"));
+        assert!(text.contains("Note:           login flow"));
+        assert!(text.contains("Screen:         screen.mp4 (missing)"));
+        assert!(text.contains("Voice:          voice.wav"));
+        assert!(text.contains("unknown (no tracer_stats.json)"));
+        assert!(text.contains("Trace Files:    none"));
+        assert!(text.contains("Transcript:     not cached"));
+    }
+
+    #[test]
+    fn get_info__two_trace_sessions__then_all_listed() {
+        let temp_dir = create_bundle_with_stats();
+        fs::create_dir_all(temp_dir.path().join("trace/session_2/pid_1")).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let info = get_info(&bundle);
+
+        assert_eq!(
+            info.trace.sessions,
+            vec![
+                temp_dir.path().join("trace/session_1/pid_1"),
+                temp_dir.path().join("trace/session_2/pid_1"),
+            ]
+        );
+        let text = format_info(&info, OutputFormat::Text);
+        assert!(text.contains("All Sessions:   2"), "{text}");
+        assert!(text.contains("trace/session_2/pid_1\n"), "{text}");
+    }
+
+    #[test]
+    fn get_info__one_trace_session__then_no_session_list_in_text() {
+        let temp_dir = create_bundle_with_stats();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let info = get_info(&bundle);
+
+        assert_eq!(info.trace.sessions.len(), 1);
+        assert!(!format_info(&info, OutputFormat::Text).contains("All Sessions"));
+    }
+
+    #[test]
+    fn get_info__duration_in_manifest__then_reported_without_probing() {
+        let temp_dir = create_bundle_with_stats();
+        let manifest = r#"{
+            "version": 1,
+            "trace_root": "trace",
+            "voice_lossless_path": "voice.wav",
+            "voice_duration_sec": 4.25
+        }"#;
+        fs::write(temp_dir.path().join("manifest.json"), manifest).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let info = get_info(&bundle);
+
+        assert_eq!(info.voice.duration_sec, Some(4.25));
+        let text = format_info(&info, OutputFormat::Text);
+        assert!(text.contains("Voice:          voice.wav (4.2 s)"));
+    }
+}
@@ -2,9 +2,13 @@
 //!
 //! Wraps Whisper for transcription with caching in session directory.
 
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Instant, SystemTime};
 
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
@@ -13,7 +17,7 @@ use super::bundle::Bundle;
 use super::output::OutputFormat;
 
 /// A transcript segment with timing information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Segment {
     /// Segment index (0-based)
     pub index: usize,
@@ -23,6 +27,33 @@ pub struct Segment {
     pub end_sec: f64,
     /// Transcribed text
     pub text: String,
+    /// Word-level timing (only with `--word-timestamps`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+    /// Probability that the segment contains no speech, if whisper reported it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_speech_prob: Option<f64>,
+    /// Mean token log-probability; low values suggest hallucinated text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_logprob: Option<f64>,
+}
+
+impl Segment {
+    /// Length of the segment in seconds
+    pub fn duration_sec(&self) -> f64 {
+        self.end_sec - self.start_sec
+    }
+}
+
+/// A single word within a segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    /// Start time in seconds
+    pub start_sec: f64,
+    /// End time in seconds
+    pub end_sec: f64,
+    /// Word text, including attached punctuation
+    pub text: String,
 }
 
 /// Cached transcript data
@@ -36,11 +67,31 @@ pub struct Transcript {
     pub voice_path: String,
 }
 
+impl Transcript {
+    /// Number of whitespace-separated words across all segments
+    pub fn word_count(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|s| s.text.split_whitespace().count())
+            .sum()
+    }
+
+    /// Speaking rate over the whole recording (0.0 when the duration is zero)
+    pub fn words_per_minute(&self) -> f64 {
+        if self.total_duration_sec <= 0.0 {
+            return 0.0;
+        }
+        self.word_count() as f64 / (self.total_duration_sec / 60.0)
+    }
+}
+
 /// Transcript metadata (info command output)
 #[derive(Debug, Clone, Serialize)]
 pub struct TranscriptInfo {
     pub segment_count: usize,
     pub total_duration_sec: f64,
+    pub word_count: usize,
+    pub words_per_minute: f64,
     pub time_start_sec: f64,
     pub time_end_sec: f64,
     pub voice_path: String,
@@ -69,9 +120,46 @@ pub struct TimeRange {
     pub end_sec: f64,
 }
 
+/// Options controlling how a transcript is generated
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TranscribeOptions {
+    /// Apply EBU R128 loudness normalization before running whisper
+    pub normalize: bool,
+    /// Ask whisper for token timings and attach per-word timestamps
+    pub word_timestamps: bool,
+}
+
+/// Largest silence (seconds) bridged by `--coalesce`
+pub const COALESCE_MAX_GAP_SEC: f64 = 0.5;
+
+/// Longest merged segment text (characters) produced by `--coalesce`
+pub const COALESCE_MAX_LEN_CHARS: usize = 200;
+
+/// Largest page the segments command returns; bigger `--limit`s are clamped
+pub const SEGMENTS_MAX_LIMIT: usize = 1000;
+
+/// Transcript cache file for the given options
+///
+/// Normalized and word-timed transcripts are cached separately so toggling
+/// `--normalize` or `--word-timestamps` never serves a transcript produced
+/// from a different pipeline.
+fn cache_path(bundle: &Bundle, options: &TranscribeOptions) -> PathBuf {
+    let mut name = String::from("transcript");
+    if options.normalize {
+        name.push_str("_normalized");
+    }
+    if options.word_timestamps {
+        name.push_str("_words");
+    }
+    bundle.path.join(format!("{}.json", name))
+}
+
 /// Get or create transcript for a bundle
 // LCOV_EXCL_START - Requires real bundle with voice recording
-pub fn get_or_create_transcript(bundle: &Bundle) -> Result<Transcript> {
+pub fn get_or_create_transcript(
+    bundle: &Bundle,
+    options: &TranscribeOptions,
+) -> Result<Transcript> {
     // Prefer lossless WAV (whisper-cli requires WAV input) over compressed m4a
     let voice_path = bundle
         .voice_lossless_path()
@@ -86,7 +174,7 @@ pub fn get_or_create_transcript(bundle: &Bundle) -> Result<Transcript> {
         );
     }
 
-    let cache_path = bundle.path.join("transcript.json");
+    let cache_path = cache_path(bundle, options);
 
     // Check if cached and valid
     if cache_path.exists() {
@@ -95,16 +183,12 @@ pub fn get_or_create_transcript(bundle: &Bundle) -> Result<Transcript> {
 
         if cache_modified > voice_modified {
             // Cache is valid
-            let content = fs::read_to_string(&cache_path)
-                .with_context(|| "Failed to read cached transcript")?;
-            let transcript: Transcript = serde_json::from_str(&content)
-                .with_context(|| "Failed to parse cached transcript")?;
-            return Ok(transcript);
+            return load_cached_transcript(&cache_path, cache_modified);
         }
     }
 
     // Run Whisper to generate transcript
-    let transcript = run_whisper(&voice_path, bundle)?;
+    let transcript = run_whisper(&voice_path, bundle, options)?;
 
     // Cache the result
     let content = serde_json::to_string_pretty(&transcript)?;
@@ -114,10 +198,45 @@ pub fn get_or_create_transcript(bundle: &Bundle) -> Result<Transcript> {
 }
 // LCOV_EXCL_STOP
 
+/// Transcripts already parsed by this process, keyed by cache file and
+/// tagged with the cache file's modification time when it was read
+static PARSED_TRANSCRIPTS: OnceLock<Mutex<HashMap<PathBuf, (SystemTime, Transcript)>>> =
+    OnceLock::new();
+
+#[cfg(test)]
+thread_local! {
+    /// Number of transcript cache files read from disk on this thread
+    static TRANSCRIPT_DISK_READS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Read a transcript cache file, reusing an earlier parse within this
+/// process unless the file has been modified since
+fn load_cached_transcript(cache_path: &Path, modified: SystemTime) -> Result<Transcript> {
+    let parsed = PARSED_TRANSCRIPTS.get_or_init(Default::default);
+    if let Some((parsed_modified, transcript)) = parsed.lock().unwrap().get(cache_path) {
+        if *parsed_modified == modified {
+            return Ok(transcript.clone());
+        }
+    }
+
+    #[cfg(test)]
+    TRANSCRIPT_DISK_READS.with(|reads| reads.set(reads.get() + 1));
+    let content =
+        fs::read_to_string(cache_path).with_context(|| "Failed to read cached transcript")?;
+    let transcript: Transcript =
+        serde_json::from_str(&content).with_context(|| "Failed to parse cached transcript")?;
+
+    parsed
+        .lock()
+        .unwrap()
+        .insert(cache_path.to_path_buf(), (modified, transcript.clone()));
+    Ok(transcript)
+}
+
 /// Check if transcript is cached
 // LCOV_EXCL_START - Requires real filesystem
-pub fn is_cached(bundle: &Bundle) -> bool {
-    let cache_path = bundle.path.join("transcript.json");
+pub fn is_cached(bundle: &Bundle, options: &TranscribeOptions) -> bool {
+    let cache_path = cache_path(bundle, options);
     if !cache_path.exists() {
         return false;
     }
@@ -142,7 +261,11 @@ pub fn is_cached(bundle: &Bundle) -> bool {
 
 /// Run whisper.cpp on a voice file
 // LCOV_EXCL_START - Requires whisper executable
-fn run_whisper(voice_path: &Path, bundle: &Bundle) -> Result<Transcript> {
+fn run_whisper(
+    voice_path: &Path,
+    bundle: &Bundle,
+    options: &TranscribeOptions,
+) -> Result<Transcript> {
     let whisper_path = ada_cli::binary_resolver::resolve(ada_cli::binary_resolver::Tool::WhisperCpp)
         .map_err(|_| anyhow::anyhow!("Whisper not available. Run: ./utils/init_media_tools.sh"))?;
 
@@ -153,7 +276,13 @@ fn run_whisper(voice_path: &Path, bundle: &Bundle) -> Result<Transcript> {
     let temp_dir = tempfile::tempdir()?;
 
     // Resample to 16 kHz if needed (whisper-cli requires 16 kHz WAV)
-    let actual_voice_path = ada_cli::audio::ensure_16khz(voice_path, temp_dir.path())?;
+    let mut actual_voice_path = ada_cli::audio::ensure_16khz(voice_path, temp_dir.path())?;
+
+    // Optionally boost quiet recordings (output stays 16 kHz mono)
+    if options.normalize {
+        actual_voice_path =
+            ada_cli::audio::normalize_loudness(&actual_voice_path, temp_dir.path())?;
+    }
 
     let voice_stem = voice_path
         .file_stem()
@@ -161,21 +290,37 @@ fn run_whisper(voice_path: &Path, bundle: &Bundle) -> Result<Transcript> {
         .unwrap_or("voice");
     let output_prefix = temp_dir.path().join(voice_stem);
 
-    // Run whisper.cpp
-    let output = Command::new(&whisper_path)
+    // Run whisper.cpp; full JSON (-ojf) adds per-token timings and
+    // probabilities used for word timestamps and confidence scores
+    let mut child = Command::new(&whisper_path)
         .arg("-f")
         .arg(&actual_voice_path)
         .arg("-m")
         .arg(&model_path)
-        .arg("-oj")        // JSON output
+        .arg("-ojf")
+        .arg("-pp") // progress lines on stderr
         .arg("-of")
         .arg(&output_prefix) // writes <prefix>.json
-        .output()
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
         .with_context(|| "Failed to run whisper-cli")?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("whisper-cli failed: {}", stderr);
+    let started = Instant::now();
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_tail = stream_whisper_stderr(BufReader::new(stderr), |percent| {
+        tracing::info!(
+            "Transcribing: {}% ({:.0}s elapsed)",
+            percent,
+            started.elapsed().as_secs_f64()
+        );
+    });
+    let status = child
+        .wait()
+        .with_context(|| "Failed to wait for whisper-cli")?;
+
+    if !status.success() {
+        bail!("whisper-cli failed: {}", stderr_tail.join("\n"));
     }
 
     // Read the JSON output
@@ -188,18 +333,7 @@ fn run_whisper(voice_path: &Path, bundle: &Bundle) -> Result<Transcript> {
     let cpp_output: WhisperCppOutput = serde_json::from_str(&content)
         .with_context(|| "Failed to parse whisper-cli output")?;
 
-    // Convert whisper.cpp format to our internal format
-    let segments: Vec<Segment> = cpp_output
-        .transcription
-        .into_iter()
-        .enumerate()
-        .map(|(i, seg)| Segment {
-            index: i,
-            start_sec: seg.offsets.from as f64 / 1000.0,
-            end_sec: seg.offsets.to as f64 / 1000.0,
-            text: seg.text.trim().to_string(),
-        })
-        .collect();
+    let segments = segments_from_whisper(cpp_output, options.word_timestamps);
 
     let total_duration = segments.last().map(|s| s.end_sec).unwrap_or(0.0);
 
@@ -217,6 +351,42 @@ fn run_whisper(voice_path: &Path, bundle: &Bundle) -> Result<Transcript> {
 }
 // LCOV_EXCL_STOP
 
+/// Stderr lines kept for the error message when whisper-cli fails
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Read whisper-cli stderr as it is produced
+///
+/// Calls `on_progress` each time the reported percentage advances and
+/// returns the last [`STDERR_TAIL_LINES`] lines for error reporting.
+fn stream_whisper_stderr<R: BufRead>(reader: R, mut on_progress: impl FnMut(u8)) -> Vec<String> {
+    let mut tail: VecDeque<String> = VecDeque::with_capacity(STDERR_TAIL_LINES);
+    let mut last_percent = None;
+
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        if let Some(percent) = parse_progress(&line) {
+            if last_percent.is_none_or(|last| percent > last) {
+                last_percent = Some(percent);
+                on_progress(percent);
+            }
+        }
+        if tail.len() == STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+
+    tail.into()
+}
+
+/// Parse a whisper-cli progress line, e.g.
+/// `whisper_print_progress_callback: progress =  45%`
+fn parse_progress(line: &str) -> Option<u8> {
+    let (_, rest) = line.split_once("progress =")?;
+    let percent = rest.trim().strip_suffix('%')?.trim().parse::<u8>().ok()?;
+    Some(percent.min(100))
+}
+
 /// whisper.cpp JSON output format
 #[derive(Debug, Deserialize)]
 struct WhisperCppOutput {
@@ -227,6 +397,21 @@ struct WhisperCppOutput {
 struct WhisperCppSegment {
     offsets: WhisperCppOffsets,
     text: String,
+    /// Only present in full JSON output (`-ojf`)
+    #[serde(default)]
+    tokens: Vec<WhisperCppToken>,
+    /// Only reported by newer whisper.cpp builds
+    #[serde(default)]
+    no_speech_prob: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperCppToken {
+    text: String,
+    offsets: WhisperCppOffsets,
+    /// Token probability
+    #[serde(default)]
+    p: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -235,11 +420,153 @@ struct WhisperCppOffsets {
     to: u64,   // milliseconds
 }
 
+/// Convert whisper.cpp output to our internal segments
+fn segments_from_whisper(output: WhisperCppOutput, word_timestamps: bool) -> Vec<Segment> {
+    output
+        .transcription
+        .into_iter()
+        .enumerate()
+        .map(|(i, seg)| Segment {
+            index: i,
+            start_sec: seg.offsets.from as f64 / 1000.0,
+            end_sec: seg.offsets.to as f64 / 1000.0,
+            text: seg.text.trim().to_string(),
+            words: if word_timestamps {
+                words_from_tokens(&seg.tokens)
+            } else {
+                Vec::new()
+            },
+            no_speech_prob: seg.no_speech_prob,
+            avg_logprob: avg_logprob(&seg.tokens),
+        })
+        .collect()
+}
+
+/// Mean log-probability of the text tokens, skipping special tokens
+fn avg_logprob(tokens: &[WhisperCppToken]) -> Option<f64> {
+    let logprobs: Vec<f64> = tokens
+        .iter()
+        .filter(|t| !t.text.starts_with("[_"))
+        .filter_map(|t| t.p)
+        .map(|p| p.max(f64::MIN_POSITIVE).ln())
+        .collect();
+    if logprobs.is_empty() {
+        None
+    } else {
+        Some(logprobs.iter().sum::<f64>() / logprobs.len() as f64)
+    }
+}
+
+/// Drop segments whose average log-probability is below `min_logprob`
+///
+/// Segments without a score (e.g. from transcripts cached before scores
+/// were recorded) are kept.
+pub fn filter_by_logprob(segments: Vec<Segment>, min_logprob: f64) -> Vec<Segment> {
+    segments
+        .into_iter()
+        .filter(|s| s.avg_logprob.is_none_or(|logprob| logprob >= min_logprob))
+        .collect()
+}
+
+/// Merge whisper tokens into words
+///
+/// A token starting with a space begins a new word; other tokens (sub-word
+/// pieces, punctuation) extend the current one. Special tokens such as
+/// `[_BEG_]` and `[_TT_75]` are skipped.
+fn words_from_tokens(tokens: &[WhisperCppToken]) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+
+    for token in tokens {
+        if token.text.starts_with("[_") || token.text.trim().is_empty() {
+            continue;
+        }
+        let start_sec = token.offsets.from as f64 / 1000.0;
+        let end_sec = token.offsets.to as f64 / 1000.0;
+
+        match words.last_mut() {
+            Some(word) if !token.text.starts_with(' ') => {
+                word.text.push_str(&token.text);
+                word.end_sec = end_sec;
+            }
+            _ => words.push(Word {
+                start_sec,
+                end_sec,
+                text: token.text.trim_start().to_string(),
+            }),
+        }
+    }
+
+    words
+}
+
+/// Merge consecutive short segments into longer ones
+///
+/// A segment is folded into the previous one when the silence between them
+/// is below `max_gap_sec` and the joined text is no longer than
+/// `max_len_chars`. Indices are renumbered and each merged segment spans
+/// from the first start to the last end. A merged segment's `avg_logprob`
+/// and `no_speech_prob` are the means of its parts weighted by duration, so
+/// it scores like the text it holds.
+pub fn coalesce_segments(
+    input: &[Segment],
+    max_gap_sec: f64,
+    max_len_chars: usize,
+) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+
+    for segment in input {
+        if let Some(last) = segments.last_mut() {
+            let gap = segment.start_sec - last.end_sec;
+            let joined_len = last.text.chars().count() + 1 + segment.text.chars().count();
+            if gap < max_gap_sec && joined_len <= max_len_chars {
+                let (last_weight, weight) = (last.duration_sec(), segment.duration_sec());
+                last.avg_logprob =
+                    merge_score(last.avg_logprob, last_weight, segment.avg_logprob, weight);
+                last.no_speech_prob = merge_score(
+                    last.no_speech_prob,
+                    last_weight,
+                    segment.no_speech_prob,
+                    weight,
+                );
+                last.text.push(' ');
+                last.text.push_str(&segment.text);
+                last.end_sec = last.end_sec.max(segment.end_sec);
+                last.words.extend(segment.words.iter().cloned());
+                continue;
+            }
+        }
+        segments.push(segment.clone());
+    }
+
+    for (i, segment) in segments.iter_mut().enumerate() {
+        segment.index = i;
+    }
+
+    segments
+}
+
+/// Duration-weighted mean of two segment scores; a missing score defers to
+/// the other, and zero-length parts count equally
+fn merge_score(a: Option<f64>, a_weight: f64, b: Option<f64>, b_weight: f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let (a_weight, b_weight) = (a_weight.max(0.0), b_weight.max(0.0));
+            let total = a_weight + b_weight;
+            Some(if total > 0.0 {
+                (a * a_weight + b * b_weight) / total
+            } else {
+                (a + b) / 2.0
+            })
+        }
+        (score, None) | (None, score) => score,
+    }
+}
+
 /// Get transcript info
 // LCOV_EXCL_START - Requires real bundle
-pub fn get_info(bundle: &Bundle) -> Result<TranscriptInfo> {
-    let cached = is_cached(bundle);
-    let transcript = get_or_create_transcript(bundle)?;
+pub fn get_info(bundle: &Bundle, options: &TranscribeOptions) -> Result<TranscriptInfo> {
+    let cached = is_cached(bundle, options);
+    let transcript = get_or_create_transcript(bundle, options)?;
 
     let time_start = transcript.segments.first().map(|s| s.start_sec).unwrap_or(0.0);
     let time_end = transcript.segments.last().map(|s| s.end_sec).unwrap_or(0.0);
@@ -247,6 +574,8 @@ pub fn get_info(bundle: &Bundle) -> Result<TranscriptInfo> {
     Ok(TranscriptInfo {
         segment_count: transcript.segments.len(),
         total_duration_sec: transcript.total_duration_sec,
+        word_count: transcript.word_count(),
+        words_per_minute: transcript.words_per_minute(),
         time_start_sec: time_start,
         time_end_sec: time_end,
         voice_path: transcript.voice_path,
@@ -255,20 +584,78 @@ pub fn get_info(bundle: &Bundle) -> Result<TranscriptInfo> {
 }
 // LCOV_EXCL_STOP
 
+/// Pagination, filtering and post-processing for the segments command
+#[derive(Debug, Clone, Default)]
+pub struct SegmentsQuery {
+    /// Number of segments to skip
+    pub offset: usize,
+    /// Maximum number of segments to return
+    pub limit: usize,
+    /// Keep segments ending at or after this time (seconds)
+    pub since: Option<f64>,
+    /// Keep segments starting at or before this time (seconds)
+    pub until: Option<f64>,
+    /// Merge adjacent short segments that survive `min_logprob`
+    pub coalesce: bool,
+    /// Drop segments with a lower average log-probability
+    pub min_logprob: Option<f64>,
+}
+
 /// Get paginated segments
 // LCOV_EXCL_START - Requires real bundle
 pub fn get_segments(
     bundle: &Bundle,
-    offset: usize,
-    limit: usize,
-    since: Option<f64>,
-    until: Option<f64>,
+    query: &SegmentsQuery,
+    options: &TranscribeOptions,
 ) -> Result<SegmentsResult> {
-    let transcript = get_or_create_transcript(bundle)?;
+    // Fail before spending time in whisper
+    check_time_range(query.since, query.until)?;
+
+    let transcript = get_or_create_transcript(bundle, options)?;
+    select_segments(transcript.segments, query)
+}
+// LCOV_EXCL_STOP
+
+/// Reject a `--since`/`--until` window that ends before it starts
+fn check_time_range(since: Option<f64>, until: Option<f64>) -> Result<()> {
+    if let (Some(since_sec), Some(until_sec)) = (since, until) {
+        if since_sec > until_sec {
+            bail!("--since ({}s) is after --until ({}s)", since_sec, until_sec);
+        }
+    }
+    Ok(())
+}
+
+/// Filter, coalesce and paginate transcript segments for the segments command
+///
+/// `--min-logprob` drops segments before `--coalesce` merges the rest, so a
+/// low-confidence segment never hides inside a merged one. `limit` is clamped
+/// to [`SEGMENTS_MAX_LIMIT`]; an `offset` past the end yields an empty page
+/// with `has_more` false.
+fn select_segments(segments: Vec<Segment>, query: &SegmentsQuery) -> Result<SegmentsResult> {
+    let SegmentsQuery {
+        offset,
+        limit,
+        since,
+        until,
+        min_logprob,
+        coalesce,
+    } = *query;
+    check_time_range(since, until)?;
+    let limit = limit.min(SEGMENTS_MAX_LIMIT);
+
+    let segments = match min_logprob {
+        Some(min_logprob) => filter_by_logprob(segments, min_logprob),
+        None => segments,
+    };
+    let segments = if coalesce {
+        coalesce_segments(&segments, COALESCE_MAX_GAP_SEC, COALESCE_MAX_LEN_CHARS)
+    } else {
+        segments
+    };
 
-    // Apply time filters first
-    let filtered: Vec<Segment> = transcript
-        .segments
+    // Apply time filters
+    let filtered: Vec<Segment> = segments
         .into_iter()
         .filter(|s| {
             if let Some(since_sec) = since {
@@ -311,20 +698,20 @@ pub fn get_segments(
             offset,
             limit,
             total,
-            has_more: offset + segments.len() < total,
+            has_more: offset.saturating_add(segments.len()) < total,
         },
         time_range,
         segments,
     })
 }
-// LCOV_EXCL_STOP
 
 /// Format transcript info
 // LCOV_EXCL_START - Integration tested via CLI
 pub fn format_info(info: &TranscriptInfo, format: OutputFormat) -> String {
     match format {
-        OutputFormat::Text | OutputFormat::Line => format_info_text(info),
+        OutputFormat::Text => format_info_text(info),
         OutputFormat::Json => format_info_json(info),
+        OutputFormat::Line => format_info_line(info),
     }
 }
 
@@ -332,6 +719,8 @@ fn format_info_text(info: &TranscriptInfo) -> String {
     let mut output = String::new();
     output.push_str(&format!("Segment Count:  {}\n", info.segment_count));
     output.push_str(&format!("Duration:       {:.1} s\n", info.total_duration_sec));
+    output.push_str(&format!("Word Count:     {}\n", info.word_count));
+    output.push_str(&format!("Words/Minute:   {:.1}\n", info.words_per_minute));
     output.push_str(&format!("Time Start:     {:.1} s\n", info.time_start_sec));
     output.push_str(&format!("Time End:       {:.1} s\n", info.time_end_sec));
     output.push_str(&format!("Voice Path:     {}\n", info.voice_path));
@@ -343,11 +732,19 @@ fn format_info_json(info: &TranscriptInfo) -> String {
     serde_json::to_string_pretty(info).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// Info as a single compact JSON line
+fn format_info_line(info: &TranscriptInfo) -> String {
+    let mut line = serde_json::to_string(info).unwrap_or_else(|_| "{}".to_string());
+    line.push('\n');
+    line
+}
+
 /// Format segments result
 pub fn format_segments(result: &SegmentsResult, format: OutputFormat) -> String {
     match format {
-        OutputFormat::Text | OutputFormat::Line => format_segments_text(result),
+        OutputFormat::Text => format_segments_text(result),
         OutputFormat::Json => format_segments_json(result),
+        OutputFormat::Line => format_segments_line(result),
     }
 }
 
@@ -384,6 +781,17 @@ fn format_segments_text(result: &SegmentsResult) -> String {
 fn format_segments_json(result: &SegmentsResult) -> String {
     serde_json::to_string_pretty(result).unwrap_or_else(|_| "{}".to_string())
 }
+
+/// Segments as JSON Lines: one compact object per segment, no pagination
+/// header, so the output can be piped straight into `jq`
+fn format_segments_line(result: &SegmentsResult) -> String {
+    let mut output = String::new();
+    for seg in &result.segments {
+        output.push_str(&serde_json::to_string(seg).unwrap_or_else(|_| "{}".to_string()));
+        output.push('\n');
+    }
+    output
+}
 // LCOV_EXCL_STOP
 
 #[cfg(test)]
@@ -397,11 +805,45 @@ mod tests {
             start_sec: 0.0,
             end_sec: 2.5,
             text: "Hello world".to_string(),
+            words: Vec::new(),
+            no_speech_prob: None,
+            avg_logprob: None,
         };
         let json = serde_json::to_string(&seg).unwrap();
         assert!(json.contains("Hello world"));
     }
 
+    #[test]
+    fn test_segment__duration_sec__then_end_minus_start() {
+        assert!((segment(0, 1.25, 4.0, "hi").duration_sec() - 2.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transcript__word_count_and_wpm__then_counts_whitespace_words() {
+        let transcript = Transcript {
+            segments: vec![
+                segment(0, 0.0, 5.0, " Hello, this is a test."),
+                segment(1, 5.0, 30.0, "  Second   segment\nhere. "),
+            ],
+            total_duration_sec: 30.0,
+            voice_path: "voice.wav".to_string(),
+        };
+
+        assert_eq!(transcript.word_count(), 8);
+        assert!((transcript.words_per_minute() - 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transcript__zero_duration__then_zero_wpm() {
+        let transcript = Transcript {
+            segments: vec![segment(0, 0.0, 0.0, "one two")],
+            total_duration_sec: 0.0,
+            voice_path: "voice.wav".to_string(),
+        };
+
+        assert_eq!(transcript.words_per_minute(), 0.0);
+    }
+
     #[test]
     fn test_pagination__has_more__when_more_items() {
         let pagination = Pagination {
@@ -418,6 +860,8 @@ mod tests {
         let info = TranscriptInfo {
             segment_count: 10,
             total_duration_sec: 60.0,
+            word_count: 150,
+            words_per_minute: 150.0,
             time_start_sec: 0.0,
             time_end_sec: 60.0,
             voice_path: "voice.wav".to_string(),
@@ -426,6 +870,7 @@ mod tests {
         let output = format_info(&info, OutputFormat::Text);
         assert!(output.contains("Segment Count:  10"));
         assert!(output.contains("Duration:       60.0 s"));
+        assert!(output.contains("Words/Minute:   150.0"));
         assert!(output.contains("Cached:         yes"));
     }
 
@@ -463,6 +908,8 @@ mod tests {
                         to: 8880,
                     },
                     text: " Hello, test.".to_string(),
+                    tokens: Vec::new(),
+                    no_speech_prob: None,
                 },
                 WhisperCppSegment {
                     offsets: WhisperCppOffsets {
@@ -470,6 +917,8 @@ mod tests {
                         to: 15000,
                     },
                     text: " Second.".to_string(),
+                    tokens: Vec::new(),
+                    no_speech_prob: None,
                 },
             ],
         };
@@ -483,6 +932,9 @@ mod tests {
                 start_sec: seg.offsets.from as f64 / 1000.0,
                 end_sec: seg.offsets.to as f64 / 1000.0,
                 text: seg.text.trim().to_string(),
+                words: Vec::new(),
+                no_speech_prob: None,
+                avg_logprob: None,
             })
             .collect();
 
@@ -541,6 +993,9 @@ mod tests {
                 start_sec: seg.offsets.from as f64 / 1000.0,
                 end_sec: seg.offsets.to as f64 / 1000.0,
                 text: seg.text.trim().to_string(),
+                words: Vec::new(),
+                no_speech_prob: None,
+                avg_logprob: None,
             })
             .collect();
         let total_duration = segments.last().map(|s| s.end_sec).unwrap_or(0.0);
@@ -589,12 +1044,18 @@ mod tests {
                     start_sec: 0.0,
                     end_sec: 2.5,
                     text: "First".to_string(),
+                    words: Vec::new(),
+                    no_speech_prob: None,
+                    avg_logprob: None,
                 },
                 Segment {
                     index: 1,
                     start_sec: 2.5,
                     end_sec: 5.0,
                     text: "Second".to_string(),
+                    words: Vec::new(),
+                    no_speech_prob: None,
+                    avg_logprob: None,
                 },
             ],
         };
@@ -603,4 +1064,416 @@ mod tests {
         assert!(output.contains("First"));
         assert!(output.contains("3 more segments"));
     }
+
+    #[test]
+    fn test_format_segments_line__then_one_json_object_per_segment() {
+        let result = SegmentsResult {
+            pagination: Pagination {
+                offset: 0,
+                limit: 2,
+                total: 5,
+                has_more: true,
+            },
+            time_range: TimeRange {
+                start_sec: 0.0,
+                end_sec: 5.0,
+            },
+            segments: vec![
+                segment(0, 0.0, 2.5, "First"),
+                segment(1, 2.5, 5.0, "Second, with \"quotes\"\nand a newline"),
+            ],
+        };
+
+        let output = format_segments(&result, OutputFormat::Line);
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, expected) in lines.iter().zip(&result.segments) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["index"], expected.index);
+            assert_eq!(value["text"], expected.text.as_str());
+        }
+        assert!(output.ends_with('\n'));
+        assert!(!output.contains("Segments 0-2"));
+    }
+
+    #[test]
+    fn test_format_segments_line__no_segments__then_empty() {
+        let result = SegmentsResult {
+            pagination: Pagination {
+                offset: 0,
+                limit: 10,
+                total: 0,
+                has_more: false,
+            },
+            time_range: TimeRange {
+                start_sec: 0.0,
+                end_sec: 0.0,
+            },
+            segments: Vec::new(),
+        };
+
+        assert_eq!(format_segments(&result, OutputFormat::Line), "");
+    }
+
+    #[test]
+    fn test_format_info_line__then_single_compact_json_line() {
+        let info = TranscriptInfo {
+            segment_count: 10,
+            total_duration_sec: 60.0,
+            word_count: 150,
+            words_per_minute: 150.0,
+            time_start_sec: 0.0,
+            time_end_sec: 60.0,
+            voice_path: "voice.wav".to_string(),
+            cached: true,
+        };
+
+        let output = format_info(&info, OutputFormat::Line);
+
+        assert_eq!(output.lines().count(), 1);
+        let value: serde_json::Value = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(value["segment_count"], 10);
+        assert_eq!(value["voice_path"], "voice.wav");
+    }
+
+    #[test]
+    fn test_segments_from_whisper__word_offsets_fixture__then_words_attached() {
+        let fixture_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/transcribe/whisper_words.json");
+        let content = std::fs::read_to_string(&fixture_path).unwrap();
+        let cpp_output: WhisperCppOutput = serde_json::from_str(&content).unwrap();
+
+        let segments = segments_from_whisper(cpp_output, true);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Hello world.");
+        assert_eq!(
+            segments[0].words,
+            vec![
+                Word {
+                    start_sec: 0.0,
+                    end_sec: 0.6,
+                    text: "Hello".to_string(),
+                },
+                Word {
+                    start_sec: 0.6,
+                    end_sec: 1.5,
+                    text: "world.".to_string(),
+                },
+            ]
+        );
+        // Sub-word tokens merge into one word
+        let texts: Vec<&str> = segments[1].words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, vec!["Testing", "again"]);
+        assert!((segments[1].words[0].start_sec - 1.5).abs() < 0.001);
+        assert!((segments[1].words[0].end_sec - 2.2).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_segments_from_whisper__word_timestamps_off__then_words_omitted_from_json() {
+        let fixture_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/transcribe/whisper_words.json");
+        let content = std::fs::read_to_string(&fixture_path).unwrap();
+        let cpp_output: WhisperCppOutput = serde_json::from_str(&content).unwrap();
+
+        let segments = segments_from_whisper(cpp_output, false);
+
+        assert!(segments.iter().all(|s| s.words.is_empty()));
+        let json = serde_json::to_value(&segments[0]).unwrap();
+        assert!(json.get("words").is_none());
+    }
+
+    #[test]
+    fn test_segment__legacy_cache_without_words__then_parses() {
+        let json = r#"{"index": 0, "start_sec": 0.0, "end_sec": 1.0, "text": "Hi"}"#;
+
+        let segment: Segment = serde_json::from_str(json).unwrap();
+
+        assert!(segment.words.is_empty());
+    }
+
+    fn segment(index: usize, start_sec: f64, end_sec: f64, text: &str) -> Segment {
+        Segment {
+            index,
+            start_sec,
+            end_sec,
+            text: text.to_string(),
+            words: Vec::new(),
+            no_speech_prob: None,
+            avg_logprob: None,
+        }
+    }
+
+    fn transcript(segments: Vec<Segment>) -> Transcript {
+        Transcript {
+            segments,
+            total_duration_sec: 10.0,
+            voice_path: "voice.m4a".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_coalesce_segments__small_gap__then_merged() {
+        let input = transcript(vec![
+            segment(0, 0.0, 1.0, "Hello"),
+            segment(1, 1.1, 1.8, "there"),
+            segment(2, 1.9, 2.5, "friend."),
+        ]);
+
+        let result = coalesce_segments(&input.segments, 0.5, 200);
+
+        assert_eq!(
+            result,
+            vec![segment(0, 0.0, 2.5, "Hello there friend.")]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_segments__large_gap__then_kept_apart() {
+        let input = transcript(vec![
+            segment(0, 0.0, 1.0, "Hello"),
+            segment(1, 1.2, 2.0, "there."),
+            segment(2, 5.0, 6.0, "Next"),
+            segment(3, 6.1, 7.0, "thought."),
+        ]);
+
+        let result = coalesce_segments(&input.segments, 0.5, 200);
+
+        assert_eq!(
+            result,
+            vec![
+                segment(0, 0.0, 2.0, "Hello there."),
+                segment(1, 5.0, 7.0, "Next thought."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_segments__length_cap__then_new_segment_started() {
+        let input = transcript(vec![
+            segment(0, 0.0, 1.0, "aaaa"),
+            segment(1, 1.0, 2.0, "bbbb"),
+            segment(2, 2.0, 3.0, "cccc"),
+        ]);
+
+        // "aaaa bbbb" is 9 chars; adding " cccc" would exceed 10
+        let result = coalesce_segments(&input.segments, 0.5, 10);
+
+        assert_eq!(
+            result,
+            vec![
+                segment(0, 0.0, 2.0, "aaaa bbbb"),
+                segment(1, 2.0, 3.0, "cccc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_whisper_stderr__progress_lines__then_percentages_reported() {
+        let stderr = "\
+whisper_init_from_file_with_params_no_state: loading model from 'ggml-tiny.bin'
+whisper_print_progress_callback: progress =   5%
+whisper_print_progress_callback: progress =  50%
+whisper_print_progress_callback: progress =  50%
+some unrelated line
+whisper_print_progress_callback: progress = 100%
+";
+        let mut reported = Vec::new();
+
+        let tail = stream_whisper_stderr(stderr.as_bytes(), |percent| reported.push(percent));
+
+        assert_eq!(reported, vec![5, 50, 100]);
+        assert_eq!(tail.len(), 6);
+        assert_eq!(
+            tail.last().unwrap(),
+            "whisper_print_progress_callback: progress = 100%"
+        );
+    }
+
+    #[test]
+    fn test_stream_whisper_stderr__long_output__then_tail_kept() {
+        let stderr: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+
+        let tail = stream_whisper_stderr(stderr.as_bytes(), |_| {});
+
+        assert_eq!(tail.len(), STDERR_TAIL_LINES);
+        assert_eq!(tail.first().unwrap(), "line 30");
+        assert_eq!(tail.last().unwrap(), "line 49");
+    }
+
+    #[test]
+    fn test_parse_progress__non_progress_line__then_none() {
+        assert_eq!(parse_progress("progress =  7%"), Some(7));
+        assert_eq!(parse_progress("whisper_full: processing"), None);
+        assert_eq!(parse_progress("progress = abc%"), None);
+    }
+
+    #[test]
+    fn test_segments_from_whisper__token_probabilities__then_scores_parsed() {
+        let json = r#"{
+            "transcription": [
+                {
+                    "offsets": {"from": 0, "to": 1000},
+                    "text": " Hi there",
+                    "no_speech_prob": 0.12,
+                    "tokens": [
+                        {"text": "[_BEG_]", "offsets": {"from": 0, "to": 0}, "p": 0.01},
+                        {"text": " Hi", "offsets": {"from": 0, "to": 400}, "p": 1.0},
+                        {"text": " there", "offsets": {"from": 400, "to": 1000}, "p": 0.5}
+                    ]
+                },
+                {
+                    "offsets": {"from": 1000, "to": 2000},
+                    "text": " No scores"
+                }
+            ]
+        }"#;
+        let cpp_output: WhisperCppOutput = serde_json::from_str(json).unwrap();
+
+        let segments = segments_from_whisper(cpp_output, false);
+
+        assert_eq!(segments[0].no_speech_prob, Some(0.12));
+        // Special tokens are ignored: (ln 1.0 + ln 0.5) / 2
+        let expected = (0.5f64).ln() / 2.0;
+        assert!((segments[0].avg_logprob.unwrap() - expected).abs() < 1e-9);
+        assert_eq!(segments[1].no_speech_prob, None);
+        assert_eq!(segments[1].avg_logprob, None);
+        let json = serde_json::to_value(&segments[1]).unwrap();
+        assert!(json.get("avg_logprob").is_none());
+    }
+
+    #[test]
+    fn test_get_info__called_twice__then_transcript_read_from_disk_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = serde_json::json!({
+            "version": 1,
+            "trace_session": "trace",
+            "voice_lossless_path": "voice.wav",
+        });
+        fs::write(temp_dir.path().join("manifest.json"), manifest.to_string()).unwrap();
+        let voice = fs::File::create(temp_dir.path().join("voice.wav")).unwrap();
+        voice
+            .set_modified(SystemTime::now() - std::time::Duration::from_secs(60))
+            .unwrap();
+        let cached = transcript(vec![segment(0, 0.0, 2.0, "one two")]);
+        fs::write(
+            temp_dir.path().join("transcript.json"),
+            serde_json::to_string(&cached).unwrap(),
+        )
+        .unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+        let options = TranscribeOptions::default();
+        let reads_before = TRANSCRIPT_DISK_READS.with(|reads| reads.get());
+
+        let first = get_info(&bundle, &options).unwrap();
+        let second = get_info(&bundle, &options).unwrap();
+
+        let reads = TRANSCRIPT_DISK_READS.with(|reads| reads.get()) - reads_before;
+        assert_eq!(reads, 1);
+        assert_eq!(first.word_count, 2);
+        assert_eq!(second.word_count, 2);
+        assert!(second.cached);
+    }
+
+    fn numbered_segments(count: usize) -> Vec<Segment> {
+        (0..count)
+            .map(|i| segment(i, i as f64, i as f64 + 1.0, "text"))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_segments__limit_above_max__then_clamped() {
+        let query = SegmentsQuery {
+            limit: usize::MAX,
+            ..Default::default()
+        };
+
+        let result = select_segments(numbered_segments(SEGMENTS_MAX_LIMIT + 5), &query).unwrap();
+
+        assert_eq!(result.segments.len(), SEGMENTS_MAX_LIMIT);
+        assert_eq!(result.pagination.limit, SEGMENTS_MAX_LIMIT);
+        assert!(result.pagination.has_more);
+    }
+
+    #[test]
+    fn test_select_segments__offset_past_end__then_empty_without_more() {
+        let query = SegmentsQuery {
+            offset: 50,
+            limit: 20,
+            ..Default::default()
+        };
+
+        let result = select_segments(numbered_segments(10), &query).unwrap();
+
+        assert!(result.segments.is_empty());
+        assert_eq!(result.pagination.total, 10);
+        assert!(!result.pagination.has_more);
+    }
+
+    #[test]
+    fn test_select_segments__since_after_until__then_error() {
+        let query = SegmentsQuery {
+            limit: 20,
+            since: Some(8.0),
+            until: Some(2.0),
+            ..Default::default()
+        };
+
+        let err = select_segments(numbered_segments(10), &query).unwrap_err();
+
+        assert!(err.to_string().contains("--since"), "{}", err);
+    }
+
+    #[test]
+    fn test_filter_by_logprob__low_confidence__then_dropped() {
+        let mut confident = segment(0, 0.0, 1.0, "Real speech");
+        confident.avg_logprob = Some(-0.3);
+        let mut hallucinated = segment(1, 1.0, 2.0, "Thanks for watching!");
+        hallucinated.avg_logprob = Some(-1.8);
+        let unscored = segment(2, 2.0, 3.0, "From an old cache");
+
+        let kept = filter_by_logprob(vec![confident, hallucinated, unscored], -1.0);
+
+        let texts: Vec<&str> = kept.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Real speech", "From an old cache"]);
+    }
+
+    #[test]
+    fn test_select_segments__coalesce_with_min_logprob__then_filtered_before_merge() {
+        let mut first = segment(0, 0.0, 1.0, "Real speech");
+        first.avg_logprob = Some(-0.3);
+        let mut hallucinated = segment(1, 1.0, 1.2, "Thanks for watching!");
+        hallucinated.avg_logprob = Some(-1.8);
+        let mut second = segment(2, 1.3, 2.3, "continues here");
+        second.avg_logprob = Some(-0.5);
+        let query = SegmentsQuery {
+            limit: 20,
+            coalesce: true,
+            min_logprob: Some(-1.0),
+            ..Default::default()
+        };
+
+        let result = select_segments(vec![first, hallucinated, second], &query).unwrap();
+
+        assert_eq!(result.segments.len(), 1);
+        let merged = &result.segments[0];
+        assert_eq!(merged.text, "Real speech continues here");
+        assert!((merged.avg_logprob.unwrap() - -0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_coalesce_segments__scored_parts__then_duration_weighted_scores() {
+        let mut short = segment(0, 0.0, 1.0, "Hi");
+        short.avg_logprob = Some(-0.2);
+        short.no_speech_prob = Some(0.1);
+        let mut long = segment(1, 1.0, 4.0, "there");
+        long.avg_logprob = Some(-1.8);
+
+        let result = coalesce_segments(&[short, long], 0.5, 200);
+
+        let merged = &result[0];
+        assert!((merged.avg_logprob.unwrap() - -1.4).abs() < 1e-9);
+        assert_eq!(merged.no_speech_prob, Some(0.1));
+    }
 }
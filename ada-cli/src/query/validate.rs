@@ -0,0 +1,232 @@
+//! Bundle integrity check for `ada query <bundle> validate`
+//!
+//! Confirms that the trace sessions and media files the manifest references
+//! exist and, with `--check-hash`, that the bundle contents still match the
+//! hash recorded at capture time.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::bundle::Bundle;
+use super::output::OutputFormat;
+
+/// Outcome of validating a bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    /// Whether every check passed
+    pub ok: bool,
+    pub checks: Vec<ValidationCheck>,
+}
+
+/// A single validation check
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl ValidationCheck {
+    fn new(name: &'static str, ok: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Check the files referenced by the manifest, and the content hash if asked
+pub fn validate(bundle: &Bundle, check_hash: bool) -> Result<ValidationReport> {
+    let trace_path = bundle.trace_path();
+    let mut checks = vec![ValidationCheck::new(
+        "trace",
+        trace_path.is_dir(),
+        trace_path.display().to_string(),
+    )];
+    // Sessions besides the primary one, e.g. from repeated flight recorder fires
+    for session in bundle.trace_sessions() {
+        if session != trace_path {
+            checks.push(ValidationCheck::new(
+                "trace_session",
+                session.is_dir(),
+                session.display().to_string(),
+            ));
+        }
+    }
+
+    let media = [
+        ("screen", bundle.screen_path()),
+        ("voice", bundle.voice_path()),
+        ("voice_lossless", bundle.voice_lossless_path()),
+    ];
+    for (name, path) in media {
+        if let Some(path) = path {
+            checks.push(ValidationCheck::new(
+                name,
+                path.is_file(),
+                path.display().to_string(),
+            ));
+        }
+    }
+
+    if check_hash {
+        checks.push(match bundle.manifest.bundle_hash {
+            None => ValidationCheck::new("hash", false, "manifest records no bundle_hash"),
+            Some(_) if bundle.verify_hash()? => ValidationCheck::new("hash", true, "matches"),
+            Some(_) => ValidationCheck::new(
+                "hash",
+                false,
+                "contents changed since capture (hash mismatch)",
+            ),
+        });
+    }
+
+    Ok(ValidationReport {
+        ok: checks.iter().all(|check| check.ok),
+        checks,
+    })
+}
+
+/// Format a validation report
+pub fn format_report(report: &ValidationReport, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Text | OutputFormat::Line => format_report_text(report),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(report).unwrap_or_else(|_| "{}".to_string())
+        }
+    }
+}
+
+fn format_report_text(report: &ValidationReport) -> String {
+    let mut output = String::new();
+    for check in &report.checks {
+        let mark = if check.ok { "\u{2713}" } else { "\u{2717}" };
+        output.push_str(&format!("{} {}: {}\n", mark, check.name, check.detail));
+    }
+    output.push_str(if report.ok {
+        "Bundle is valid\n"
+    } else {
+        "Bundle is invalid\n"
+    });
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_bundle(with_hash: bool) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("trace")).unwrap();
+        fs::write(temp_dir.path().join("trace/index.atf"), b"events").unwrap();
+        fs::write(temp_dir.path().join("voice.wav"), b"RIFF").unwrap();
+
+        let hash = ada_cli::bundle_hash::compute(temp_dir.path()).unwrap();
+        let manifest = serde_json::json!({
+            "version": 1,
+            "trace_session": "trace",
+            "voice_lossless_path": "voice.wav",
+            "bundle_hash": with_hash.then_some(hash),
+        });
+        fs::write(temp_dir.path().join("manifest.json"), manifest.to_string()).unwrap();
+        temp_dir
+    }
+
+    fn check<'a>(report: &'a ValidationReport, name: &str) -> &'a ValidationCheck {
+        report.checks.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[test]
+    fn validate__intact_bundle_with_hash__then_ok() {
+        let temp_dir = create_bundle(true);
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let report = validate(&bundle, true).unwrap();
+
+        assert!(report.ok, "{:?}", report);
+        assert!(check(&report, "hash").ok);
+        assert!(check(&report, "voice_lossless").ok);
+    }
+
+    #[test]
+    fn validate__file_modified__then_hash_check_fails() {
+        let temp_dir = create_bundle(true);
+        fs::write(temp_dir.path().join("voice.wav"), b"RIFX").unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let report = validate(&bundle, true).unwrap();
+
+        assert!(!report.ok);
+        assert!(!check(&report, "hash").ok);
+        assert!(format_report(&report, OutputFormat::Text).contains("hash mismatch"));
+    }
+
+    #[test]
+    fn validate__missing_media__then_fails_without_hash_check() {
+        let temp_dir = create_bundle(false);
+        fs::remove_file(temp_dir.path().join("voice.wav")).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let report = validate(&bundle, false).unwrap();
+
+        assert!(!report.ok);
+        assert!(!check(&report, "voice_lossless").ok);
+        assert!(report.checks.iter().all(|c| c.name != "hash"));
+    }
+
+    #[test]
+    fn validate__listed_trace_session_missing__then_fails() {
+        let temp_dir = create_bundle(false);
+        fs::create_dir_all(temp_dir.path().join("trace/session_1")).unwrap();
+        let manifest = serde_json::json!({
+            "version": 1,
+            "trace_session": "trace",
+            "trace_sessions": ["trace/session_1", "trace/session_2"],
+        });
+        fs::write(temp_dir.path().join("manifest.json"), manifest.to_string()).unwrap();
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let report = validate(&bundle, false).unwrap();
+
+        assert!(!report.ok);
+        let sessions: Vec<&ValidationCheck> = report
+            .checks
+            .iter()
+            .filter(|c| c.name == "trace_session")
+            .collect();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions[0].ok);
+        assert!(!sessions[1].ok);
+        assert!(sessions[1].detail.ends_with("trace/session_2"));
+    }
+
+    #[test]
+    fn validate__single_trace_session__then_no_extra_session_checks() {
+        let temp_dir = create_bundle(false);
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let report = validate(&bundle, false).unwrap();
+
+        assert!(report.checks.iter().all(|c| c.name != "trace_session"));
+    }
+
+    #[test]
+    fn validate__no_recorded_hash__then_hash_check_fails() {
+        let temp_dir = create_bundle(false);
+        let bundle = Bundle::open(temp_dir.path()).unwrap();
+
+        let report = validate(&bundle, true).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_report(&report, OutputFormat::Json)).unwrap();
+        assert_eq!(json["ok"], false);
+        assert!(json["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|c| c["name"] == "hash" && c["detail"] == "manifest records no bundle_hash"));
+    }
+}
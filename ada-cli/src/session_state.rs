@@ -11,14 +11,34 @@
 //! The session directory IS the bundle - no nested `.adabundle` needed.
 
 use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
 use clap::Subcommand;
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::Command;
-
-/// Sessions directory path relative to home: ~/.ada/sessions/
-pub const SESSIONS_DIR: &str = ".ada/sessions";
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+pub use ada_sessions::SESSIONS_DIR_ENV;
+
+/// Errors locating or removing sessions in the registry
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    /// Neither `ADA_SESSIONS_DIR` nor `HOME` is set
+    #[error("HOME environment variable not set (set HOME or {SESSIONS_DIR_ENV})")]
+    HomeNotSet,
+    /// No session is registered under the ID
+    #[error("Session {0} not found")]
+    NotFound(String),
+    /// The session is still capturing, so it can't be deleted or renamed
+    #[error("Session {0} is still running")]
+    Running(String),
+    /// Another session already uses the ID
+    #[error("Session {0} already exists")]
+    AlreadyExists(String),
+    /// The ID isn't `session_` followed by letters, digits, `_` or `-`
+    #[error("Invalid session ID {0:?}: expected session_ followed by letters, digits, '_' or '-'")]
+    InvalidId(String),
+}
 
 /// Session status enum
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -51,6 +71,99 @@ pub struct SessionState {
     pub pid: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub capture_pid: Option<u32>,
+    /// Free-text annotation supplied via `capture start --note` or
+    /// `session note`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Exit code of the traced process if it exited during the capture
+    /// (128 + signal number if it was killed); `None` while it is running
+    /// or after the capture detached from it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// User-assigned labels, managed via `session tag`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl SessionState {
+    /// Total size in bytes of the files under the session directory
+    ///
+    /// Symlinks are not followed, so linked data is neither double-counted
+    /// nor able to create cycles.
+    pub fn disk_usage(&self) -> Result<u64> {
+        dir_size(&self.session_path)
+    }
+
+    /// Remove the `remove` tags, then add the `add` tags not already present
+    ///
+    /// Tags are trimmed; empty tags are rejected.
+    pub fn apply_tags(&mut self, add: &[String], remove: &[String]) -> Result<()> {
+        if let Some(empty) = add.iter().chain(remove).find(|tag| tag.trim().is_empty()) {
+            bail!("Invalid tag {:?}: tags must not be empty", empty);
+        }
+
+        self.tags
+            .retain(|tag| !remove.iter().any(|r| r.trim() == tag));
+        for tag in add {
+            let tag = tag.trim();
+            if !self.tags.iter().any(|existing| existing == tag) {
+                self.tags.push(tag.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Target PID to re-attach to for `ada capture resume`
+    ///
+    /// Only sessions whose capture died (Failed, or Running with a dead
+    /// capture process) while the target kept running can be resumed.
+    pub fn resume_target(&self, is_alive: impl Fn(u32) -> bool) -> Result<u32> {
+        match self.status {
+            SessionStatus::Complete => {
+                bail!("Session {} is already complete", self.session_id)
+            }
+            SessionStatus::Running => {
+                if let Some(capture_pid) = self.capture_pid.filter(|pid| is_alive(*pid)) {
+                    bail!(
+                        "Session {} is still being captured (PID {})",
+                        self.session_id,
+                        capture_pid
+                    );
+                }
+            }
+            SessionStatus::Failed => {}
+        }
+
+        let Some(pid) = self.pid else {
+            bail!("Session {} has no target process ID", self.session_id);
+        };
+        if !is_alive(pid) {
+            bail!(
+                "Target process {} of session {} is no longer running",
+                pid,
+                self.session_id
+            );
+        }
+        Ok(pid)
+    }
+}
+
+/// Total size in bytes of the files under `path`, without following symlinks
+pub fn dir_size(path: &Path) -> Result<u64> {
+    let metadata =
+        fs::symlink_metadata(path).with_context(|| format!("Failed to stat {:?}", path))?;
+    if metadata.is_file() {
+        return Ok(metadata.len());
+    }
+    if !metadata.is_dir() {
+        return Ok(0);
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += dir_size(&entry?.path())?;
+    }
+    Ok(total)
 }
 
 /// Session subcommands for CLI
@@ -66,6 +179,26 @@ pub enum SessionCommands {
         #[arg(long)]
         app: Option<String>,
 
+        /// Only sessions with this tag (repeat to match any of several)
+        #[arg(long)]
+        tag: Vec<String>,
+
+        /// Only sessions started at or after this time (RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only sessions started at or before this time (RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Sort order: started (newest first), app (A-Z) or size (largest first)
+        #[arg(long, default_value = "started", value_parser = ["started", "app", "size"])]
+        sort: String,
+
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+
         /// Output format (text or json)
         #[arg(short, long, default_value = "text")]
         format: String,
@@ -76,10 +209,87 @@ pub enum SessionCommands {
         /// Show latest running session only
         #[arg(long)]
         running: bool,
+
+        /// Only sessions whose app name contains this (case-insensitive)
+        #[arg(long)]
+        app: Option<String>,
+    },
+
+    /// Add or remove tags on a session
+    Tag {
+        /// Session ID to tag
+        session_id: String,
+
+        /// Tag to add (repeatable)
+        #[arg(long)]
+        add: Vec<String>,
+
+        /// Tag to remove (repeatable)
+        #[arg(long)]
+        remove: Vec<String>,
+    },
+
+    /// Set a session's note (an empty note clears it)
+    Note {
+        /// Session ID to annotate
+        session_id: String,
+
+        /// Note text
+        text: String,
+    },
+
+    /// Export a session to a portable .tar.zst archive
+    Export {
+        /// Session ID to export
+        session_id: String,
+
+        /// Archive file to write (e.g. session.tar.zst)
+        output: PathBuf,
+    },
+
+    /// Import a session archive written by `session export`
+    Import {
+        /// Archive file to read
+        archive: PathBuf,
+    },
+
+    /// Give a session a new ID, e.g. session_login_bug
+    Rename {
+        /// Current session ID
+        old_id: String,
+
+        /// New session ID (session_ followed by letters, digits, '_' or '-')
+        new_id: String,
+    },
+
+    /// Delete a session and its bundle directory
+    Delete {
+        /// Session ID to delete
+        session_id: String,
+
+        /// Delete the session even if it is still running
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Delete old finished sessions, or the oldest ones to fit a size cap
+    Prune {
+        /// Delete complete and failed sessions started more than this many days ago
+        #[arg(long)]
+        days: Option<u64>,
+
+        /// Then delete the oldest finished sessions until all sessions fit in
+        /// this size (e.g. 500MB, 5GB)
+        #[arg(long)]
+        max_size: Option<String>,
     },
 
     /// Clean up orphaned sessions
-    Cleanup,
+    Cleanup {
+        /// Output format (text or json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
 }
 
 // LCOV_EXCL_START - CLI command handlers output to stdout, tested via integration
@@ -90,14 +300,54 @@ pub fn run(cmd: SessionCommands) -> Result<()> {
         SessionCommands::List {
             running,
             app,
+            tag,
+            since,
+            until,
+            sort,
+            reverse,
             format,
-        } => cmd_list(running, app.as_deref(), &format),
-        SessionCommands::Latest { running } => cmd_latest(running),
-        SessionCommands::Cleanup => cmd_cleanup(),
+        } => cmd_list(
+            &ListFilter {
+                running,
+                app: app.as_deref(),
+                tags: &tag,
+                since: since.as_deref(),
+                until: until.as_deref(),
+                sort: &sort,
+                reverse,
+            },
+            &format,
+        ),
+        SessionCommands::Latest { running, app } => cmd_latest(running, app.as_deref()),
+        SessionCommands::Tag {
+            session_id,
+            add,
+            remove,
+        } => cmd_tag(&session_id, &add, &remove),
+        SessionCommands::Note { session_id, text } => cmd_note(&session_id, &text),
+        SessionCommands::Export { session_id, output } => cmd_export(&session_id, &output),
+        SessionCommands::Import { archive } => cmd_import(&archive),
+        SessionCommands::Rename { old_id, new_id } => cmd_rename(&old_id, &new_id),
+        SessionCommands::Delete { session_id, force } => cmd_delete(&session_id, force),
+        SessionCommands::Prune { days, max_size } => cmd_prune(days, max_size.as_deref()),
+        SessionCommands::Cleanup { format } => cmd_cleanup(&format),
     }
 }
 
-fn cmd_list(running_only: bool, app_filter: Option<&str>, format: &str) -> Result<()> {
+/// Filters and ordering of `ada session list`
+struct ListFilter<'a> {
+    running: bool,
+    app: Option<&'a str>,
+    tags: &'a [String],
+    since: Option<&'a str>,
+    until: Option<&'a str>,
+    sort: &'a str,
+    reverse: bool,
+}
+
+fn cmd_list(filter: &ListFilter, format: &str) -> Result<()> {
+    let running_only = filter.running;
+    let app_filter = filter.app;
     let sessions = if running_only {
         list_running()?
     } else if let Some(app) = app_filter {
@@ -116,40 +366,18 @@ fn cmd_list(running_only: bool, app_filter: Option<&str>, format: &str) -> Resul
     } else {
         sessions
     };
+    let sessions = filter_by_tags(sessions, filter.tags);
+    let mut sessions = filter_by_start_time(sessions, filter.since, filter.until)?;
+    sort_sessions(&mut sessions, filter.sort, filter.reverse)?;
 
-    match format {
-        "json" => {
-            let json = serde_json::to_string_pretty(&sessions)?;
-            println!("{}", json);
-        }
-        _ => {
-            if sessions.is_empty() {
-                println!("No sessions found.");
-            } else {
-                println!(
-                    "{:<40} {:<20} {:<10} {:<24}",
-                    "SESSION ID", "APP", "STATUS", "STARTED"
-                );
-                println!("{}", "-".repeat(94));
-                for session in &sessions {
-                    println!(
-                        "{:<40} {:<20} {:<10} {:<24}",
-                        session.session_id,
-                        truncate(&session.app_info.name, 20),
-                        format!("{:?}", session.status).to_lowercase(),
-                        &session.start_time[..std::cmp::min(19, session.start_time.len())]
-                    );
-                }
-                println!("\nTotal: {} session(s)", sessions.len());
-            }
-        }
-    }
-
+    print!("{}", list_output(&sessions, format)?);
     Ok(())
 }
 
-fn cmd_latest(running_only: bool) -> Result<()> {
-    let session = if running_only {
+fn cmd_latest(running_only: bool, app_filter: Option<&str>) -> Result<()> {
+    let session = if let Some(app) = app_filter {
+        latest_by_app(app, running_only)?
+    } else if running_only {
         latest_running()?
     } else {
         latest()?
@@ -160,7 +388,9 @@ fn cmd_latest(running_only: bool) -> Result<()> {
             println!("{}", s.session_path.display());
         }
         None => {
-            if running_only {
+            if let Some(app) = app_filter {
+                bail!("No matching session for app '{}'", app);
+            } else if running_only {
                 bail!("No running sessions found");
             } else {
                 bail!("No sessions found");
@@ -171,18 +401,79 @@ fn cmd_latest(running_only: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_cleanup() -> Result<()> {
-    let orphaned = cleanup_orphaned()?;
+fn cmd_tag(session_id: &str, add: &[String], remove: &[String]) -> Result<()> {
+    let session = set_tags(session_id, add, remove)?;
+    if session.tags.is_empty() {
+        println!("Session {} has no tags", session_id);
+    } else {
+        println!("Tags for {}: {}", session_id, session.tags.join(", "));
+    }
+    Ok(())
+}
+
+fn cmd_note(session_id: &str, text: &str) -> Result<()> {
+    let session = set_note(session_id, text)?;
+    match session.note {
+        Some(_) => println!("Updated note for {}", session_id),
+        None => println!("Cleared note for {}", session_id),
+    }
+    Ok(())
+}
 
-    if orphaned.is_empty() {
-        println!("No orphaned sessions found.");
+fn cmd_export(session_id: &str, output: &Path) -> Result<()> {
+    export(session_id, output)?;
+    println!("Exported session {} to {}", session_id, output.display());
+    Ok(())
+}
+
+fn cmd_import(archive: &Path) -> Result<()> {
+    let session = import(archive)?;
+    println!(
+        "Imported session {} into {}",
+        session.session_id,
+        session.session_path.display()
+    );
+    Ok(())
+}
+
+fn cmd_rename(old_id: &str, new_id: &str) -> Result<()> {
+    rename(old_id, new_id)?;
+    println!("Renamed session {} to {}", old_id, new_id);
+    Ok(())
+}
+
+fn cmd_delete(session_id: &str, force: bool) -> Result<()> {
+    let freed = match delete(session_id, force) {
+        Err(err) if matches!(err.downcast_ref(), Some(SessionError::Running(_))) => {
+            bail!("{} (use --force to delete it anyway)", err)
+        }
+        result => result?,
+    };
+    println!("Deleted session {} (freed {} bytes)", session_id, freed);
+    Ok(())
+}
+
+fn cmd_prune(days: Option<u64>, max_size: Option<&str>) -> Result<()> {
+    if days.is_none() && max_size.is_none() {
+        bail!("Nothing to prune by: pass --days and/or --max-size");
+    }
+    let older_than = days.map(|days| Duration::from_secs(days.saturating_mul(24 * 60 * 60)));
+    let max_total_bytes = max_size.map(parse_size).transpose()?;
+
+    let pruned = prune(older_than, max_total_bytes)?;
+    if pruned.is_empty() {
+        println!("No sessions to prune.");
     } else {
-        println!("Marked {} session(s) as failed:", orphaned.len());
-        for session in &orphaned {
+        println!("Pruned {} session(s):", pruned.len());
+        for session in &pruned {
             println!("  - {} ({})", session.session_id, session.app_info.name);
         }
     }
+    Ok(())
+}
 
+fn cmd_cleanup(format: &str) -> Result<()> {
+    print!("{}", cleanup_output(format)?);
     Ok(())
 }
 
@@ -196,10 +487,95 @@ fn truncate(s: &str, max_len: usize) -> String {
 
 // LCOV_EXCL_STOP
 
-/// Get sessions directory: ~/.ada/sessions/
-pub fn sessions_dir() -> Result<PathBuf> {
-    let home = std::env::var("HOME").context("HOME environment variable not set")?;
-    Ok(PathBuf::from(home).join(SESSIONS_DIR))
+/// A listed session with its on-disk size (`None` if it couldn't be measured)
+#[derive(Serialize)]
+struct ListedSession<'a> {
+    #[serde(flatten)]
+    session: &'a SessionState,
+    size_bytes: Option<u64>,
+}
+
+/// Render `sessions` for `session list` in `format`, measuring each once
+fn list_output(sessions: &[SessionState], format: &str) -> Result<String> {
+    let listed: Vec<_> = sessions
+        .iter()
+        .map(|session| ListedSession {
+            session,
+            size_bytes: session.disk_usage().ok(),
+        })
+        .collect();
+
+    let mut out = String::new();
+    match format {
+        "json" => {
+            out.push_str(&serde_json::to_string_pretty(&listed)?);
+            out.push('\n');
+        }
+        _ => {
+            if listed.is_empty() {
+                out.push_str("No sessions found.\n");
+            } else {
+                out.push_str(&format!(
+                    "{:<40} {:<20} {:<10} {:<24} {:>10}\n",
+                    "SESSION ID", "APP", "STATUS", "STARTED", "SIZE"
+                ));
+                out.push_str(&format!("{}\n", "-".repeat(108)));
+                for ListedSession {
+                    session,
+                    size_bytes,
+                } in &listed
+                {
+                    out.push_str(&format!(
+                        "{:<40} {:<20} {:<10} {:<24} {:>10}\n",
+                        session.session_id,
+                        truncate(&session.app_info.name, 20),
+                        format!("{:?}", session.status).to_lowercase(),
+                        &session.start_time[..std::cmp::min(19, session.start_time.len())],
+                        size_bytes.map_or_else(|| "-".to_string(), format_size)
+                    ));
+                }
+                out.push_str(&format!("\nTotal: {} session(s)\n", listed.len()));
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Run orphan cleanup and render the affected sessions in `format`
+fn cleanup_output(format: &str) -> Result<String> {
+    let orphaned = cleanup_orphaned()?;
+
+    let mut out = String::new();
+    match format {
+        "json" => {
+            out.push_str(&serde_json::to_string_pretty(&orphaned)?);
+            out.push('\n');
+        }
+        _ => {
+            if orphaned.is_empty() {
+                out.push_str("No orphaned sessions found.\n");
+            } else {
+                out.push_str(&format!(
+                    "Marked {} session(s) as failed:\n",
+                    orphaned.len()
+                ));
+                for session in &orphaned {
+                    out.push_str(&format!(
+                        "  - {} ({})\n",
+                        session.session_id, session.app_info.name
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Get sessions directory: `$ADA_SESSIONS_DIR` if set, else ~/.ada/sessions/
+pub fn sessions_dir() -> Result<PathBuf, SessionError> {
+    ada_sessions::sessions_dir().ok_or(SessionError::HomeNotSet)
 }
 
 /// Generate unique session ID: session_YYYY_MM_DD_hh_mm_ss_{short_hash}
@@ -282,6 +658,407 @@ pub fn get(session_id: &str) -> Result<Option<SessionState>> {
     Ok(Some(session))
 }
 
+/// Add and remove tags on a registered session, returning the updated state
+pub fn set_tags(session_id: &str, add: &[String], remove: &[String]) -> Result<SessionState> {
+    let mut session =
+        get(session_id)?.ok_or_else(|| SessionError::NotFound(session_id.to_string()))?;
+    session.apply_tags(add, remove)?;
+    update(session_id, &session)?;
+    Ok(session)
+}
+
+/// Replace a registered session's note (blank text clears it), returning the
+/// updated state
+pub fn set_note(session_id: &str, text: &str) -> Result<SessionState> {
+    let mut session =
+        get(session_id)?.ok_or_else(|| SessionError::NotFound(session_id.to_string()))?;
+    let text = text.trim();
+    session.note = (!text.is_empty()).then(|| text.to_string());
+    update(session_id, &session)?;
+    Ok(session)
+}
+
+/// Whether `session_id` is a single plain path component
+fn is_plain_session_id(session_id: &str) -> bool {
+    Path::new(session_id).file_name() == Some(session_id.as_ref())
+}
+
+/// Check that `session_id` is `session_` followed by letters, digits, `_`
+/// or `-`, so it is also a safe directory name
+pub fn validate_session_id(session_id: &str) -> Result<(), SessionError> {
+    let valid = session_id.strip_prefix("session_").is_some_and(|rest| {
+        !rest.is_empty()
+            && rest
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(SessionError::InvalidId(session_id.to_string()))
+    }
+}
+
+/// Give a finished session a new ID
+///
+/// Moves the registry directory with a single `fs::rename` and rewrites the
+/// ID in `session.json` and the bundle's `manifest.json`. A bundle stored
+/// outside the registry stays where it is. Fails if `new_id` is invalid or
+/// taken, or if the session is running.
+pub fn rename(old_id: &str, new_id: &str) -> Result<()> {
+    validate_session_id(new_id)?;
+    let session = if is_plain_session_id(old_id) {
+        get(old_id)?
+    } else {
+        None
+    };
+    let Some(mut session) = session else {
+        return Err(SessionError::NotFound(old_id.to_string()).into());
+    };
+    if session.status == SessionStatus::Running {
+        return Err(SessionError::Running(old_id.to_string()).into());
+    }
+
+    let old_dir = session_dir(old_id)?;
+    let new_dir = session_dir(new_id)?;
+    if fs::symlink_metadata(&new_dir).is_ok() {
+        return Err(SessionError::AlreadyExists(new_id.to_string()).into());
+    }
+    fs::rename(&old_dir, &new_dir)
+        .with_context(|| format!("Failed to move {:?} to {:?}", old_dir, new_dir))?;
+
+    session.session_id = new_id.to_string();
+    if session.session_path == old_dir {
+        session.session_path = new_dir.clone();
+    }
+    if let Err(err) = update(new_id, &session) {
+        // Put the directory back so the old ID keeps working
+        let _ = fs::rename(&new_dir, &old_dir);
+        return Err(err);
+    }
+
+    let manifest_path = session.session_path.join("manifest.json");
+    if let Ok(json) = fs::read_to_string(&manifest_path) {
+        let mut manifest: serde_json::Value = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse {:?}", manifest_path))?;
+        if manifest["session_name"] == old_id {
+            manifest["session_name"] = new_id.into();
+            fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Manifest fields holding bundle paths, rewritten by [`import`]
+const MANIFEST_PATH_FIELDS: &[&str] = &[
+    "trace_root",
+    "trace_session",
+    "trace_sessions",
+    "screen_path",
+    "voice_path",
+    "voice_lossless_path",
+];
+
+/// Write a session's bundle to a zstd-compressed tar archive at `output`
+///
+/// Entries are stored under `<session_id>/`, relative to the bundle, with
+/// symlinks archived as links rather than followed. The registry's
+/// `session.json` is included even when the bundle lives elsewhere.
+pub fn export(session_id: &str, output: &Path) -> Result<()> {
+    let session = if is_plain_session_id(session_id) {
+        get(session_id)?
+    } else {
+        None
+    };
+    let session = session.ok_or_else(|| SessionError::NotFound(session_id.to_string()))?;
+
+    let file =
+        File::create(output).with_context(|| format!("Failed to create archive {:?}", output))?;
+    let mut builder = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+    builder.follow_symlinks(false);
+    builder
+        .append_dir_all(session_id, &session.session_path)
+        .with_context(|| format!("Failed to archive {:?}", session.session_path))?;
+    if !session.session_path.join("session.json").exists() {
+        builder.append_path_with_name(
+            session_dir(session_id)?.join("session.json"),
+            Path::new(session_id).join("session.json"),
+        )?;
+    }
+    builder.into_inner()?.finish()?;
+
+    Ok(())
+}
+
+/// Unpack an archive written by [`export`] into the sessions directory
+///
+/// Rejects archives with absolute or `..` entry paths, or with entries
+/// outside a single `<session_id>/` directory; links and special files are
+/// skipped. `session_path` is pointed at the new location and absolute
+/// `manifest.json` paths into the old bundle are made relative. Fails if the
+/// session already exists.
+pub fn import(archive: &Path) -> Result<SessionState> {
+    let root = sessions_dir()?;
+    fs::create_dir_all(&root).with_context(|| format!("Failed to create {:?}", root))?;
+    let staging = tempfile::Builder::new()
+        .prefix(".import-")
+        .tempdir_in(&root)
+        .with_context(|| format!("Failed to create staging directory in {:?}", root))?;
+
+    let file = File::open(archive).with_context(|| format!("Failed to open {:?}", archive))?;
+    let mut tar = tar::Archive::new(zstd::Decoder::new(file)?);
+    let mut session_id: Option<String> = None;
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let top = archive_entry_root(&path)
+            .with_context(|| format!("Refusing unsafe archive entry {:?}", path))?;
+        match &session_id {
+            Some(id) if *id != top => {
+                bail!("Archive holds more than one session ({} and {})", id, top)
+            }
+            Some(_) => {}
+            None => session_id = Some(top),
+        }
+
+        let kind = entry.header().entry_type();
+        let depth = path
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .count();
+        if depth == 1 && !kind.is_dir() {
+            bail!("Archive entry {:?} is not inside a session directory", path);
+        }
+        if !kind.is_file() && !kind.is_dir() {
+            tracing::warn!("Skipping non-regular archive entry {:?}", path);
+            continue;
+        }
+        entry
+            .unpack_in(staging.path())
+            .with_context(|| format!("Failed to unpack {:?}", path))?;
+    }
+
+    let Some(session_id) = session_id else {
+        bail!("Archive {:?} is empty", archive);
+    };
+    let unpacked = staging.path().join(&session_id);
+    let session_file = unpacked.join("session.json");
+    let json = fs::read_to_string(&session_file)
+        .with_context(|| format!("Archive {:?} has no {}/session.json", archive, session_id))?;
+    let mut session: SessionState = serde_json::from_str(&json)
+        .with_context(|| format!("Failed to parse session file in {:?}", archive))?;
+    if session.session_id != session_id {
+        bail!(
+            "Archive directory {} doesn't match its session ID {}",
+            session_id,
+            session.session_id
+        );
+    }
+
+    let target = root.join(&session_id);
+    if target.exists() {
+        return Err(SessionError::AlreadyExists(session_id).into());
+    }
+
+    let manifest_path = unpacked.join("manifest.json");
+    if manifest_path.exists() {
+        let json = fs::read_to_string(&manifest_path)?;
+        let mut manifest: serde_json::Value = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse manifest in {:?}", archive))?;
+        relativize_manifest_paths(&mut manifest, &session.session_path);
+        fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    }
+
+    fs::rename(&unpacked, &target)
+        .with_context(|| format!("Failed to move imported session to {:?}", target))?;
+    session.session_path = target;
+    register(&session)?;
+
+    Ok(session)
+}
+
+/// The top-level directory of an archive entry, or `None` if the path is
+/// absolute or climbs with `..`
+fn archive_entry_root(path: &Path) -> Option<String> {
+    let mut top = None;
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => {
+                top.get_or_insert(name.to_str()?.to_string());
+            }
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    top
+}
+
+/// Make absolute manifest paths under `old_root` relative to the bundle
+fn relativize_manifest_paths(manifest: &mut serde_json::Value, old_root: &Path) {
+    let relativize = |value: &mut serde_json::Value| {
+        if let Some(relative) = value
+            .as_str()
+            .and_then(|path| Path::new(path).strip_prefix(old_root).ok())
+        {
+            *value = serde_json::Value::String(relative.to_string_lossy().into_owned());
+        }
+    };
+    for field in MANIFEST_PATH_FIELDS {
+        match manifest.get_mut(*field) {
+            Some(serde_json::Value::Array(values)) => values.iter_mut().for_each(relativize),
+            Some(value) => relativize(value),
+            None => {}
+        }
+    }
+}
+
+/// Delete a session's bundle directory and registry entry
+///
+/// Fails with [`SessionError::Running`] for a running session unless `force`
+/// is set. Returns the number of bytes freed.
+pub fn delete(session_id: &str, force: bool) -> Result<u64> {
+    // A single path component only, so deletion stays inside the registry
+    let session = if is_plain_session_id(session_id) {
+        get(session_id)?
+    } else {
+        None
+    };
+    let Some(session) = session else {
+        return Err(SessionError::NotFound(session_id.to_string()).into());
+    };
+    if session.status == SessionStatus::Running && !force {
+        return Err(SessionError::Running(session_id.to_string()).into());
+    }
+
+    let registry_dir = session_dir(session_id)?;
+    let mut freed = 0;
+    for path in [&session.session_path, &registry_dir] {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            // Bundle already gone, or the same directory as the registry entry
+            continue;
+        };
+        freed += dir_size(path)?;
+        if metadata.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        }
+        .with_context(|| format!("Failed to delete {:?}", path))?;
+    }
+
+    Ok(freed)
+}
+
+/// Parse a size such as `1024`, `500MB` or `1.5GB` into bytes
+///
+/// Units are case-insensitive powers of 1024 (K, M, G, T), optionally
+/// followed by `B` or `iB`; a bare number is bytes.
+pub fn parse_size(value: &str) -> Result<u64> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid size '{}': expected a number with an optional unit like 500MB or 5GB",
+            value
+        )
+    };
+    let trimmed = value.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return Err(invalid()),
+    };
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Format a byte count for display, in the 1024-based units [`parse_size`]
+/// accepts (e.g. `512 B`, `1.5 MB`)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Delete finished sessions started before `older_than` ago, then the
+/// oldest finished sessions until all sessions fit in `max_total_bytes`
+///
+/// Running sessions are never deleted, though their size counts toward the
+/// total. Sessions with an unparseable `start_time` are exempt from the age
+/// cutoff. Returns the pruned sessions in the order they were deleted.
+pub fn prune(
+    older_than: Option<Duration>,
+    max_total_bytes: Option<u64>,
+) -> Result<Vec<SessionState>> {
+    prune_at(Utc::now(), older_than, max_total_bytes)
+}
+
+fn prune_at(
+    now: DateTime<Utc>,
+    older_than: Option<Duration>,
+    max_total_bytes: Option<u64>,
+) -> Result<Vec<SessionState>> {
+    let cutoff = older_than.map(|age| {
+        chrono::Duration::from_std(age)
+            .ok()
+            .and_then(|age| now.checked_sub_signed(age))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+    });
+
+    let mut sessions = list()?;
+    sessions.reverse(); // Oldest first
+
+    let mut pruned = Vec::new();
+    let mut kept = Vec::new();
+    for session in sessions {
+        let expired = session.status != SessionStatus::Running
+            && cutoff.is_some_and(|cutoff| {
+                start_instant(&session).is_some_and(|started| started < cutoff)
+            });
+        if expired {
+            delete(&session.session_id, false)?;
+            pruned.push(session);
+        } else {
+            kept.push(session);
+        }
+    }
+
+    if let Some(max_total_bytes) = max_total_bytes {
+        // A bundle that has already vanished takes no space
+        let sizes: Vec<u64> = kept.iter().map(|s| s.disk_usage().unwrap_or(0)).collect();
+        let mut total: u64 = sizes.iter().sum();
+        for (session, size) in kept.into_iter().zip(sizes) {
+            if total <= max_total_bytes {
+                break;
+            }
+            if session.status == SessionStatus::Running {
+                continue;
+            }
+            delete(&session.session_id, false)?;
+            total = total.saturating_sub(size);
+            pruned.push(session);
+        }
+    }
+
+    Ok(pruned)
+}
+
 /// List all sessions (sorted by start_time, newest first)
 pub fn list() -> Result<Vec<SessionState>> {
     let dir = sessions_dir()?;
@@ -323,8 +1100,7 @@ pub fn list() -> Result<Vec<SessionState>> {
         }
     }
 
-    // Sort by start_time, newest first
-    sessions.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+    sort_newest_first(&mut sessions);
 
     Ok(sessions)
 }
@@ -348,18 +1124,120 @@ pub fn find_by_app(app_name: &str) -> Result<Vec<SessionState>> {
         .collect())
 }
 
-/// Get the most recent session (any status)
-pub fn latest() -> Result<Option<SessionState>> {
-    let sessions = list()?;
-    Ok(sessions.into_iter().next())
+/// Keep sessions carrying any of `tags`; no tags keeps every session
+pub fn filter_by_tags(sessions: Vec<SessionState>, tags: &[String]) -> Vec<SessionState> {
+    if tags.is_empty() {
+        return sessions;
+    }
+    sessions
+        .into_iter()
+        .filter(|s| s.tags.iter().any(|tag| tags.contains(tag)))
+        .collect()
 }
 
-/// Get the most recent running session
-pub fn latest_running() -> Result<Option<SessionState>> {
+/// Keep sessions whose `start_time` falls within `[since, until]`
+///
+/// Both bounds are inclusive RFC 3339 timestamps; either may be omitted.
+/// Sessions with an unparseable `start_time` are dropped when a bound is given.
+pub fn filter_by_start_time(
+    sessions: Vec<SessionState>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<Vec<SessionState>> {
+    let parse_bound = |flag: &str, value: &str| {
+        DateTime::parse_from_rfc3339(value).with_context(|| {
+            format!(
+                "Invalid --{} '{}': expected an RFC 3339 timestamp like 2024-01-24T10:30:00Z",
+                flag, value
+            )
+        })
+    };
+    let since = since.map(|v| parse_bound("since", v)).transpose()?;
+    let until = until.map(|v| parse_bound("until", v)).transpose()?;
+
+    if since.is_none() && until.is_none() {
+        return Ok(sessions);
+    }
+
+    Ok(sessions
+        .into_iter()
+        .filter(|s| match DateTime::parse_from_rfc3339(&s.start_time) {
+            Ok(started) => {
+                since.is_none_or(|since| started >= since)
+                    && until.is_none_or(|until| started <= until)
+            }
+            Err(_) => false,
+        })
+        .collect())
+}
+
+/// `start_time` as an instant, or `None` if it isn't RFC 3339
+fn start_instant(session: &SessionState) -> Option<DateTime<Utc>> {
+    ada_sessions::start_instant(&session.start_time)
+}
+
+/// Order two sessions by `start_time`; see [`ada_sessions::compare_start_times`]
+fn compare_start_times(a: &SessionState, b: &SessionState) -> std::cmp::Ordering {
+    ada_sessions::compare_start_times(&a.start_time, &b.start_time)
+}
+
+/// Sort sessions by `start_time`, newest first; equal instants keep their
+/// existing relative order
+fn sort_newest_first(sessions: &mut [SessionState]) {
+    for session in sessions.iter().filter(|s| start_instant(s).is_none()) {
+        tracing::warn!(
+            "Session {} has unparseable start_time '{}'; sorting it as text",
+            session.session_id,
+            session.start_time
+        );
+    }
+    sessions.sort_by(|a, b| compare_start_times(b, a));
+}
+
+/// Sort sessions by `key`: `started` (newest first), `app` (A-Z, case-insensitive)
+/// or `size` (largest first); `reverse` flips the order
+///
+/// Ties keep their existing relative order. Sessions whose directory can't be
+/// measured sort as empty.
+pub fn sort_sessions(sessions: &mut [SessionState], key: &str, reverse: bool) -> Result<()> {
+    match key {
+        "started" => sort_newest_first(sessions),
+        "app" => sessions.sort_by_cached_key(|s| s.app_info.name.to_lowercase()),
+        "size" => {
+            sessions.sort_by_cached_key(|s| std::cmp::Reverse(s.disk_usage().unwrap_or_default()))
+        }
+        other => bail!(
+            "Unknown sort key '{}' (expected started, app or size)",
+            other
+        ),
+    }
+    if reverse {
+        sessions.reverse();
+    }
+    Ok(())
+}
+
+/// Get the most recent session (any status)
+pub fn latest() -> Result<Option<SessionState>> {
+    let sessions = list()?;
+    Ok(sessions.into_iter().next())
+}
+
+/// Get the most recent running session
+pub fn latest_running() -> Result<Option<SessionState>> {
     let sessions = list_running()?;
     Ok(sessions.into_iter().next())
 }
 
+/// Get the most recent session whose app name matches `app_name`
+/// (case-insensitive substring), optionally only among running sessions
+pub fn latest_by_app(app_name: &str, running_only: bool) -> Result<Option<SessionState>> {
+    let sessions = find_by_app(app_name)?;
+    Ok(sessions
+        .into_iter()
+        .find(|s| !running_only || s.status == SessionStatus::Running))
+}
+
 /// Clean up orphaned sessions (mark as Failed if process dead)
 pub fn cleanup_orphaned() -> Result<Vec<SessionState>> {
     let sessions = list_running()?;
@@ -383,10 +1261,25 @@ pub fn cleanup_orphaned() -> Result<Vec<SessionState>> {
     Ok(orphaned)
 }
 
-/// Check if a process is alive
-fn is_process_alive(pid: u32) -> bool {
-    // Use kill with signal 0 to check if process exists
-    unsafe { libc::kill(pid as i32, 0) == 0 }
+/// Check if a process exists, whoever owns it
+///
+/// Uses `kill(pid, 0)`, which performs the permission check without sending
+/// a signal. `EPERM` means the process exists but we may not signal it (e.g.
+/// it runs under another effective uid), so it counts as alive; only `ESRCH`
+/// means it is gone.
+pub fn is_process_alive(pid: u32) -> bool {
+    // 0 and values that wrap to negative would address process groups
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    if pid == 0 {
+        return false;
+    }
+
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
 }
 
 /// Extract app name and bundle_id from binary path
@@ -406,7 +1299,7 @@ pub fn extract_app_info(binary_path: &str) -> AppInfo {
     AppInfo { name, bundle_id }
 }
 
-// LCOV_EXCL_START - macOS-specific PlistBuddy integration
+// LCOV_EXCL_START - macOS-specific app bundle lookup
 
 /// Extract CFBundleIdentifier from .app bundle's Info.plist
 fn extract_bundle_id(binary_path: &Path) -> Option<String> {
@@ -433,23 +1326,11 @@ fn extract_bundle_id(binary_path: &Path) -> Option<String> {
     None
 }
 
-/// Read CFBundleIdentifier from Info.plist using PlistBuddy
+/// Read CFBundleIdentifier from Info.plist
 fn read_bundle_id(plist_path: &Path) -> Option<String> {
-    let output = Command::new("/usr/libexec/PlistBuddy")
-        .arg("-c")
-        .arg("Print :CFBundleIdentifier")
-        .arg(plist_path)
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let bundle_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !bundle_id.is_empty() {
-            return Some(bundle_id);
-        }
-    }
-
-    None
+    ada_cli::info_plist::read_string_key(plist_path, "CFBundleIdentifier")
+        .ok()
+        .filter(|bundle_id| !bundle_id.is_empty())
 }
 
 // LCOV_EXCL_STOP
@@ -471,17 +1352,87 @@ mod tests {
         let _guard = HOME_MUTEX.lock().unwrap();
         let temp_dir = TempDir::new().unwrap();
         let original_home = env::var("HOME").ok();
+        let original_override = env::var_os(SESSIONS_DIR_ENV);
 
         env::set_var("HOME", temp_dir.path());
+        env::remove_var(SESSIONS_DIR_ENV);
         let result = f(temp_dir.path());
 
         if let Some(home) = original_home {
             env::set_var("HOME", home);
         }
+        if let Some(dir) = original_override {
+            env::set_var(SESSIONS_DIR_ENV, dir);
+        }
 
         result
     }
 
+    fn resumable_session(status: SessionStatus) -> SessionState {
+        SessionState {
+            session_id: "session_crashed".to_string(),
+            session_path: PathBuf::from("/tmp/session_crashed"),
+            start_time: "2024-01-24T10:30:00Z".to_string(),
+            end_time: None,
+            app_info: AppInfo {
+                name: "MyApp".to_string(),
+                bundle_id: None,
+            },
+            status,
+            pid: Some(100),
+            capture_pid: Some(200),
+            note: None,
+            exit_code: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resume_target__failed_session_target_alive__then_pid() {
+        let session = resumable_session(SessionStatus::Failed);
+
+        assert_eq!(session.resume_target(|pid| pid == 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_resume_target__target_dead__then_error() {
+        let session = resumable_session(SessionStatus::Failed);
+
+        let err = session.resume_target(|_| false).unwrap_err();
+
+        assert!(err.to_string().contains("no longer running"));
+    }
+
+    #[test]
+    fn test_resume_target__complete_session__then_error() {
+        let session = resumable_session(SessionStatus::Complete);
+
+        let err = session.resume_target(|_| true).unwrap_err();
+
+        assert!(err.to_string().contains("already complete"));
+    }
+
+    #[test]
+    fn test_resume_target__running_with_live_capture__then_error() {
+        let session = resumable_session(SessionStatus::Running);
+
+        let err = session.resume_target(|_| true).unwrap_err();
+        assert!(err.to_string().contains("still being captured"));
+
+        // Capture process gone but target alive: the crash case
+        assert_eq!(session.resume_target(|pid| pid == 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_resume_target__no_target_pid__then_error() {
+        let mut session = resumable_session(SessionStatus::Failed);
+        session.pid = None;
+
+        let err = session.resume_target(|_| true).unwrap_err();
+
+        assert!(err.to_string().contains("no target process ID"));
+    }
+
     #[test]
     fn test_session_state__serialize__then_matches_schema() {
         let session = SessionState {
@@ -496,6 +1447,9 @@ mod tests {
             status: SessionStatus::Running,
             pid: Some(12345),
             capture_pid: Some(67890),
+            note: None,
+            exit_code: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string_pretty(&session).unwrap();
@@ -504,6 +1458,142 @@ mod tests {
         assert!(json.contains("\"bundle_id\": \"com.example.myapp\""));
     }
 
+    #[test]
+    fn test_session_state__note_roundtrip__then_preserved() {
+        let session = SessionState {
+            session_id: "session_note".to_string(),
+            session_path: PathBuf::from("/tmp/note"),
+            start_time: "2024-01-24T10:30:00Z".to_string(),
+            end_time: None,
+            app_info: AppInfo {
+                name: "MyApp".to_string(),
+                bundle_id: None,
+            },
+            status: SessionStatus::Complete,
+            pid: None,
+            capture_pid: None,
+            note: Some("reproducing login hang".to_string()),
+            exit_code: None,
+            tags: Vec::new(),
+        };
+
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(json.contains("\"note\":\"reproducing login hang\""));
+
+        let loaded: SessionState = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.note.as_deref(), Some("reproducing login hang"));
+    }
+
+    #[test]
+    fn test_session_state__exit_code__then_roundtrips_and_omitted_when_unknown() {
+        let mut session = session_started_at("session_exit", "2024-01-24T10:30:00Z");
+        session.exit_code = Some(3);
+
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(json.contains("\"exit_code\":3"));
+        let loaded: SessionState = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.exit_code, Some(3));
+
+        session.exit_code = None;
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(!json.contains("exit_code"));
+        let loaded: SessionState = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.exit_code, None);
+    }
+
+    #[test]
+    fn test_session_state__no_note__then_field_omitted_and_legacy_parses() {
+        let legacy = r#"{
+            "session_id": "session_legacy",
+            "session_path": "/tmp/legacy",
+            "start_time": "2024-01-24T10:30:00Z",
+            "app_info": {"name": "Legacy"},
+            "status": "complete"
+        }"#;
+
+        let session: SessionState = serde_json::from_str(legacy).unwrap();
+        assert!(session.note.is_none());
+        assert!(session.tags.is_empty());
+
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(!json.contains("note"));
+        assert!(!json.contains("tags"));
+    }
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_apply_tags__add_and_remove__then_deduplicated_in_order() {
+        let mut session = resumable_session(SessionStatus::Complete);
+        session.tags = tags(&["login", "slow"]);
+
+        session
+            .apply_tags(&tags(&["crash", " login ", "crash"]), &tags(&["slow"]))
+            .unwrap();
+
+        assert_eq!(session.tags, tags(&["login", "crash"]));
+    }
+
+    #[test]
+    fn test_apply_tags__empty_tag__then_error_and_unchanged() {
+        let mut session = resumable_session(SessionStatus::Complete);
+
+        assert!(session.apply_tags(&tags(&["ok", "  "]), &[]).is_err());
+        assert!(session.tags.is_empty());
+    }
+
+    #[test]
+    fn test_set_tags_and_note__registered_session__then_persisted() {
+        with_temp_home(|_| {
+            register_sized("session_tagged", "TagApp", "2024-01-24T10:00:00Z", 10);
+
+            set_tags("session_tagged", &tags(&["repro", "beta"]), &[]).unwrap();
+            set_note("session_tagged", "  hangs on login  ").unwrap();
+
+            let loaded = get("session_tagged").unwrap().unwrap();
+            assert_eq!(loaded.tags, tags(&["repro", "beta"]));
+            assert_eq!(loaded.note.as_deref(), Some("hangs on login"));
+
+            set_note("session_tagged", "").unwrap();
+            assert!(get("session_tagged").unwrap().unwrap().note.is_none());
+        });
+    }
+
+    #[test]
+    fn test_set_tags__unknown_session__then_not_found() {
+        with_temp_home(|_| {
+            let err = set_tags("session_missing", &tags(&["x"]), &[]).unwrap_err();
+
+            assert!(matches!(
+                err.downcast_ref::<SessionError>(),
+                Some(SessionError::NotFound(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_filter_by_tags__any_match__then_kept() {
+        let mut a = resumable_session(SessionStatus::Complete);
+        a.session_id = "a".to_string();
+        a.tags = tags(&["login"]);
+        let mut b = resumable_session(SessionStatus::Complete);
+        b.session_id = "b".to_string();
+        b.tags = tags(&["crash", "beta"]);
+        let c = resumable_session(SessionStatus::Complete);
+        let sessions = vec![a, b, c];
+
+        let ids = |sessions: Vec<SessionState>| -> Vec<String> {
+            sessions.into_iter().map(|s| s.session_id).collect()
+        };
+        assert_eq!(
+            ids(filter_by_tags(sessions.clone(), &tags(&["beta", "login"]))),
+            vec!["a", "b"]
+        );
+        assert_eq!(filter_by_tags(sessions, &[]).len(), 3);
+    }
+
     #[test]
     fn test_generate_session_id__then_correct_format() {
         let id = generate_session_id("MyApp");
@@ -558,6 +1648,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -583,6 +1676,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -608,6 +1704,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: Some(123),
                 capture_pid: Some(456),
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -642,6 +1741,9 @@ mod tests {
                 status: SessionStatus::Complete,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             let session2 = SessionState {
@@ -656,6 +1758,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session1).unwrap();
@@ -691,6 +1796,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             let complete = SessionState {
@@ -705,6 +1813,9 @@ mod tests {
                 status: SessionStatus::Complete,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&running).unwrap();
@@ -731,6 +1842,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -760,6 +1874,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -784,6 +1901,9 @@ mod tests {
                 status: SessionStatus::Complete,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             let new = SessionState {
@@ -798,6 +1918,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&old).unwrap();
@@ -808,6 +1931,99 @@ mod tests {
         });
     }
 
+    /// Register a session for `app` started at `start_time`
+    fn register_app_session(id: &str, app: &str, start_time: &str, status: SessionStatus) {
+        let mut session = session_started_at(id, start_time);
+        session.app_info.name = app.to_string();
+        session.status = status;
+        register(&session).unwrap();
+    }
+
+    #[test]
+    fn test_latest_by_app__matching_sessions__then_newest_match() {
+        with_temp_home(|_| {
+            register_app_session(
+                "session_a",
+                "MyApp",
+                "2024-01-24T10:00:00Z",
+                SessionStatus::Running,
+            );
+            register_app_session(
+                "session_b",
+                "MyApp",
+                "2024-01-24T11:00:00Z",
+                SessionStatus::Complete,
+            );
+            register_app_session(
+                "session_c",
+                "OtherApp",
+                "2024-01-24T12:00:00Z",
+                SessionStatus::Running,
+            );
+
+            let any = latest_by_app("myapp", false).unwrap().unwrap();
+            let running = latest_by_app("MYAPP", true).unwrap().unwrap();
+
+            assert_eq!(any.session_id, "session_b");
+            assert_eq!(running.session_id, "session_a");
+        });
+    }
+
+    #[test]
+    fn test_latest_by_app__no_match__then_none() {
+        with_temp_home(|_| {
+            register_app_session(
+                "session_a",
+                "MyApp",
+                "2024-01-24T10:00:00Z",
+                SessionStatus::Complete,
+            );
+
+            assert!(latest_by_app("Safari", false).unwrap().is_none());
+            // Name matches but nothing is running
+            assert!(latest_by_app("MyApp", true).unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_register__external_session_path__then_listed_and_resolvable() {
+        with_temp_home(|home| {
+            let external = TempDir::new().unwrap();
+            let bundle_dir = external.path().join("session_external");
+            let session = SessionState {
+                session_id: "session_external".to_string(),
+                session_path: bundle_dir.clone(),
+                start_time: "2024-01-24T12:00:00Z".to_string(),
+                end_time: None,
+                app_info: AppInfo {
+                    name: "CiApp".to_string(),
+                    bundle_id: None,
+                },
+                status: SessionStatus::Running,
+                pid: None,
+                capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
+            };
+
+            register(&session).unwrap();
+
+            // Registration lives under HOME, bundle path points elsewhere
+            assert!(home
+                .join(".ada/sessions/session_external/session.json")
+                .exists());
+            assert!(!bundle_dir.exists());
+
+            let loaded = get("session_external").unwrap().unwrap();
+            assert_eq!(loaded.session_path, bundle_dir);
+
+            let latest = latest().unwrap().unwrap();
+            assert_eq!(latest.session_id, "session_external");
+            assert_eq!(latest.session_path, bundle_dir);
+        });
+    }
+
     #[test]
     fn test_latest__empty__then_none() {
         with_temp_home(|_| {
@@ -831,6 +2047,9 @@ mod tests {
                 status: SessionStatus::Complete,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&complete).unwrap();
@@ -855,6 +2074,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: None,
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -886,6 +2108,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: Some(99999999), // Very unlikely to exist
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -917,6 +2142,9 @@ mod tests {
                 status: SessionStatus::Running,
                 pid: None,
                 capture_pid: Some(std::process::id()),
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
             };
 
             register(&session).unwrap();
@@ -930,6 +2158,620 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_cleanup_output__json_format__then_lists_failed_session() {
+        with_temp_home(|_| {
+            let session = SessionState {
+                session_id: "session_orphan_json".to_string(),
+                session_path: PathBuf::from("/tmp/orphan_json.adabundle"),
+                start_time: "2024-01-24T10:00:00Z".to_string(),
+                end_time: None,
+                app_info: AppInfo {
+                    name: "OrphanApp".to_string(),
+                    bundle_id: None,
+                },
+                status: SessionStatus::Running,
+                pid: None,
+                capture_pid: Some(99999999), // Very unlikely to exist
+                note: None,
+                exit_code: None,
+                tags: Vec::new(),
+            };
+
+            register(&session).unwrap();
+
+            let output = cleanup_output("json").unwrap();
+            let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+            let sessions = json.as_array().unwrap();
+            assert_eq!(sessions.len(), 1);
+            assert_eq!(sessions[0]["session_id"], "session_orphan_json");
+            assert_eq!(sessions[0]["status"], "failed");
+        });
+    }
+
+    #[test]
+    fn test_cleanup_output__nothing_orphaned__then_empty_json_array() {
+        with_temp_home(|_| {
+            let output = cleanup_output("json").unwrap();
+            assert_eq!(output.trim(), "[]");
+        });
+    }
+
+    fn session_started_at(session_id: &str, start_time: &str) -> SessionState {
+        SessionState {
+            session_id: session_id.to_string(),
+            session_path: PathBuf::from(format!("/tmp/{}.adabundle", session_id)),
+            start_time: start_time.to_string(),
+            end_time: None,
+            app_info: AppInfo {
+                name: "DateApp".to_string(),
+                bundle_id: None,
+            },
+            status: SessionStatus::Complete,
+            pid: None,
+            capture_pid: None,
+            note: None,
+            exit_code: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn ids(sessions: &[SessionState]) -> Vec<&str> {
+        sessions.iter().map(|s| s.session_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_newest_first__mixed_offsets__then_ordered_by_instant() {
+        // 10:30+02:00 is 08:30Z, earlier than 09:00Z despite sorting later as text
+        let mut sessions = vec![
+            session_started_at("session_offset", "2024-01-24T10:30:00+02:00"),
+            session_started_at("session_z", "2024-01-24T09:00:00Z"),
+            session_started_at("session_utc_offset", "2024-01-24T09:00:00+00:00"),
+        ];
+
+        sort_newest_first(&mut sessions);
+
+        assert_eq!(
+            ids(&sessions),
+            vec!["session_z", "session_utc_offset", "session_offset"]
+        );
+    }
+
+    #[test]
+    fn test_sort_newest_first__unparseable_start_time__then_string_fallback() {
+        let mut sessions = vec![
+            session_started_at("session_a", "2024-01-24T09:00:00Z"),
+            session_started_at("session_bad", "not a timestamp"),
+        ];
+
+        sort_newest_first(&mut sessions);
+
+        assert_eq!(ids(&sessions), vec!["session_bad", "session_a"]);
+    }
+
+    #[test]
+    fn test_filter_by_start_time__inclusive_bounds__then_edges_kept() {
+        let sessions = vec![
+            session_started_at("before", "2024-01-23T23:59:59Z"),
+            session_started_at("at_since", "2024-01-24T00:00:00Z"),
+            session_started_at("midday", "2024-01-24T12:00:00+02:00"),
+            session_started_at("at_until", "2024-01-24T23:59:59Z"),
+            session_started_at("after", "2024-01-25T00:00:00Z"),
+        ];
+
+        let filtered = filter_by_start_time(
+            sessions,
+            Some("2024-01-24T00:00:00Z"),
+            Some("2024-01-24T23:59:59Z"),
+        )
+        .unwrap();
+
+        assert_eq!(ids(&filtered), vec!["at_since", "midday", "at_until"]);
+    }
+
+    #[test]
+    fn test_filter_by_start_time__single_bound__then_open_ended() {
+        let sessions = vec![
+            session_started_at("old", "2024-01-01T00:00:00Z"),
+            session_started_at("new", "2024-02-01T00:00:00Z"),
+        ];
+
+        let since =
+            filter_by_start_time(sessions.clone(), Some("2024-01-15T00:00:00Z"), None).unwrap();
+        let until = filter_by_start_time(sessions, None, Some("2024-01-15T00:00:00Z")).unwrap();
+
+        assert_eq!(ids(&since), vec!["new"]);
+        assert_eq!(ids(&until), vec!["old"]);
+    }
+
+    #[test]
+    fn test_filter_by_start_time__no_bounds__then_unchanged() {
+        let sessions = vec![session_started_at("unparsed", "not a date")];
+
+        let filtered = filter_by_start_time(sessions, None, None).unwrap();
+
+        assert_eq!(ids(&filtered), vec!["unparsed"]);
+    }
+
+    #[test]
+    fn test_filter_by_start_time__malformed_bound__then_error() {
+        let sessions = vec![session_started_at("s", "2024-01-24T00:00:00Z")];
+
+        let err = filter_by_start_time(sessions, Some("2024-01-24"), None).unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("--since"), "{}", message);
+        assert!(message.contains("2024-01-24"), "{}", message);
+    }
+
+    /// Register a session under the temp home with `payload_bytes` of trace data
+    fn register_sized(session_id: &str, app: &str, start_time: &str, payload_bytes: usize) {
+        let mut session = session_started_at(session_id, start_time);
+        session.app_info.name = app.to_string();
+        session.session_path = session_dir(session_id).unwrap();
+        register(&session).unwrap();
+        fs::create_dir_all(session.session_path.join("trace")).unwrap();
+        fs::write(
+            session.session_path.join("trace/events.bin"),
+            vec![0u8; payload_bytes],
+        )
+        .unwrap();
+    }
+
+    fn sorted_ids(key: &str, reverse: bool) -> Vec<String> {
+        let mut sessions = list().unwrap();
+        sort_sessions(&mut sessions, key, reverse).unwrap();
+        sessions.into_iter().map(|s| s.session_id).collect()
+    }
+
+    fn with_sized_sessions(f: impl FnOnce()) {
+        with_temp_home(|_| {
+            register_sized("session_b", "beta", "2024-01-24T11:00:00Z", 10_000);
+            register_sized("session_a", "Alpha", "2024-01-24T12:00:00Z", 100);
+            register_sized("session_c", "gamma", "2024-01-24T10:00:00Z", 50_000);
+            f();
+        });
+    }
+
+    #[test]
+    fn test_sort_sessions__started__then_newest_first() {
+        with_sized_sessions(|| {
+            assert_eq!(
+                sorted_ids("started", false),
+                vec!["session_a", "session_b", "session_c"]
+            );
+            assert_eq!(
+                sorted_ids("started", true),
+                vec!["session_c", "session_b", "session_a"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_sort_sessions__app__then_case_insensitive_alphabetical() {
+        with_sized_sessions(|| {
+            assert_eq!(
+                sorted_ids("app", false),
+                vec!["session_a", "session_b", "session_c"]
+            );
+            assert_eq!(
+                sorted_ids("app", true),
+                vec!["session_c", "session_b", "session_a"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_sort_sessions__size__then_largest_first() {
+        with_sized_sessions(|| {
+            assert_eq!(
+                sorted_ids("size", false),
+                vec!["session_c", "session_b", "session_a"]
+            );
+            assert_eq!(
+                sorted_ids("size", true),
+                vec!["session_a", "session_b", "session_c"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_sort_sessions__unknown_key__then_error() {
+        let mut sessions = vec![session_started_at("s", "2024-01-24T00:00:00Z")];
+        assert!(sort_sessions(&mut sessions, "duration", false).is_err());
+    }
+
+    #[test]
+    fn test_disk_usage__symlink__then_not_followed() {
+        with_temp_home(|home| {
+            register_sized("session_link", "LinkApp", "2024-01-24T10:00:00Z", 1000);
+            let session = get("session_link").unwrap().unwrap();
+            let before = session.disk_usage().unwrap();
+
+            let outside = home.join("outside.bin");
+            fs::write(&outside, vec![0u8; 5000]).unwrap();
+            std::os::unix::fs::symlink(&outside, session.session_path.join("linked.bin")).unwrap();
+            std::os::unix::fs::symlink(&session.session_path, session.session_path.join("loop"))
+                .unwrap();
+
+            assert!(before >= 1000);
+            assert_eq!(session.disk_usage().unwrap(), before);
+        });
+    }
+
+    #[test]
+    fn test_delete__complete_session__then_removed_and_bytes_reported() {
+        with_temp_home(|_| {
+            register_sized("session_done", "DoneApp", "2024-01-24T10:00:00Z", 4096);
+            let dir = session_dir("session_done").unwrap();
+
+            let freed = delete("session_done", false).unwrap();
+
+            assert!(freed >= 4096);
+            assert!(!dir.exists());
+            assert!(get("session_done").unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_delete__external_bundle__then_both_directories_removed() {
+        with_temp_home(|_| {
+            let external = TempDir::new().unwrap();
+            let mut session = session_started_at("session_ext", "2024-01-24T10:00:00Z");
+            session.session_path = external.path().join("bundle");
+            register(&session).unwrap();
+            fs::create_dir_all(&session.session_path).unwrap();
+            fs::write(session.session_path.join("screen.mp4"), vec![0u8; 2048]).unwrap();
+
+            let freed = delete("session_ext", false).unwrap();
+
+            assert!(freed >= 2048);
+            assert!(!session.session_path.exists());
+            assert!(!session_dir("session_ext").unwrap().exists());
+        });
+    }
+
+    #[test]
+    fn test_delete__running_session__then_refused_unless_forced() {
+        with_temp_home(|_| {
+            let mut session = session_started_at("session_live", "2024-01-24T10:00:00Z");
+            session.session_path = session_dir("session_live").unwrap();
+            session.status = SessionStatus::Running;
+            register(&session).unwrap();
+
+            let err = delete("session_live", false).unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<SessionError>(),
+                Some(SessionError::Running(id)) if id == "session_live"
+            ));
+            assert!(session.session_path.exists());
+
+            delete("session_live", true).unwrap();
+            assert!(!session.session_path.exists());
+        });
+    }
+
+    #[test]
+    fn test_delete__unknown_or_path_like_id__then_not_found() {
+        with_temp_home(|home| {
+            register_sized("session_keep", "KeepApp", "2024-01-24T10:00:00Z", 10);
+
+            for id in ["session_missing", "..", "session_keep/trace", ""] {
+                let err = delete(id, true).unwrap_err();
+                assert!(
+                    matches!(
+                        err.downcast_ref::<SessionError>(),
+                        Some(SessionError::NotFound(_))
+                    ),
+                    "{id}: {err}"
+                );
+            }
+            assert!(home.join(".ada/sessions/session_keep").exists());
+        });
+    }
+
+    fn prune_now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-01-25T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn remaining_ids() -> Vec<String> {
+        list().unwrap().into_iter().map(|s| s.session_id).collect()
+    }
+
+    #[test]
+    fn test_prune__older_than__then_only_old_finished_sessions_deleted() {
+        with_temp_home(|_| {
+            register_sized("session_old", "Old", "2024-01-10T10:00:00Z", 10);
+            register_sized("session_new", "New", "2024-01-20T10:00:00Z", 10);
+            let mut live = session_started_at("session_live", "2024-01-01T10:00:00Z");
+            live.status = SessionStatus::Running;
+            register(&live).unwrap();
+
+            let week = Duration::from_secs(7 * 24 * 60 * 60);
+            let pruned = prune_at(prune_now(), Some(week), None).unwrap();
+
+            let pruned: Vec<_> = pruned.into_iter().map(|s| s.session_id).collect();
+            assert_eq!(pruned, vec!["session_old"]);
+            assert_eq!(remaining_ids(), vec!["session_new", "session_live"]);
+        });
+    }
+
+    #[test]
+    fn test_prune__size_cap__then_oldest_deleted_until_under_cap() {
+        with_sized_sessions(|| {
+            let pruned = prune_at(prune_now(), None, Some(20_000)).unwrap();
+
+            let pruned: Vec<_> = pruned.into_iter().map(|s| s.session_id).collect();
+            assert_eq!(pruned, vec!["session_c"]);
+            assert_eq!(remaining_ids(), vec!["session_a", "session_b"]);
+        });
+    }
+
+    #[test]
+    fn test_prune__size_cap_below_running_session__then_running_kept() {
+        with_temp_home(|_| {
+            register_sized("session_big", "Big", "2024-01-10T10:00:00Z", 10_000);
+            register_sized("session_small", "Small", "2024-01-20T10:00:00Z", 100);
+            let mut live = session_started_at("session_live", "2024-01-01T10:00:00Z");
+            live.session_path = session_dir("session_live").unwrap();
+            live.status = SessionStatus::Running;
+            register(&live).unwrap();
+
+            let pruned = prune_at(prune_now(), None, Some(0)).unwrap();
+
+            assert_eq!(pruned.len(), 2);
+            assert_eq!(remaining_ids(), vec!["session_live"]);
+        });
+    }
+
+    #[test]
+    fn test_prune__no_limits__then_nothing_deleted() {
+        with_sized_sessions(|| {
+            assert!(prune_at(prune_now(), None, None).unwrap().is_empty());
+            assert_eq!(remaining_ids().len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_list_output__json__then_size_bytes_included() {
+        with_temp_home(|_| {
+            register_sized("session_sized", "SizedApp", "2024-01-24T10:00:00Z", 4096);
+            let sessions = list().unwrap();
+            let expected = sessions[0].disk_usage().unwrap();
+
+            let json: serde_json::Value =
+                serde_json::from_str(&list_output(&sessions, "json").unwrap()).unwrap();
+
+            assert_eq!(json[0]["session_id"], "session_sized");
+            assert_eq!(json[0]["size_bytes"], expected);
+            assert!(expected >= 4096);
+        });
+    }
+
+    #[test]
+    fn test_list_output__text__then_size_column() {
+        with_temp_home(|_| {
+            register_sized("session_sized", "SizedApp", "2024-01-24T10:00:00Z", 2 << 20);
+            let mut missing = session_started_at("session_gone", "2024-01-24T09:00:00Z");
+            missing.session_path = PathBuf::from("/nonexistent/session_gone");
+            register(&missing).unwrap();
+
+            let text = list_output(&list().unwrap(), "text").unwrap();
+
+            let lines: Vec<_> = text.lines().collect();
+            assert!(lines[0].ends_with("SIZE"));
+            assert!(lines[2].starts_with("session_sized") && lines[2].ends_with("2.0 MB"));
+            assert!(lines[3].starts_with("session_gone") && lines[3].ends_with('-'));
+        });
+    }
+
+    #[test]
+    fn test_format_size__magnitudes__then_largest_whole_unit() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 << 30), "5.0 GB");
+        assert_eq!(format_size(3 << 50), "3072.0 TB");
+    }
+
+    #[test]
+    fn test_parse_size__units__then_binary_bytes() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("500MB").unwrap(), 500 << 20);
+        assert_eq!(parse_size("5gb").unwrap(), 5 << 30);
+        assert_eq!(parse_size("1.5 GiB").unwrap(), 3 << 29);
+        assert_eq!(parse_size("2K").unwrap(), 2048);
+    }
+
+    #[test]
+    fn test_parse_size__malformed__then_error() {
+        for value in ["", "GB", "5XB", "-1GB", "1.2.3MB"] {
+            assert!(parse_size(value).is_err(), "{value}");
+        }
+    }
+
+    fn register_with_manifest(session_id: &str) -> PathBuf {
+        register_sized(session_id, "PortApp", "2024-01-24T10:00:00Z", 1000);
+        let bundle = session_dir(session_id).unwrap();
+        let manifest = serde_json::json!({
+            "trace_root": bundle.join("trace"),
+            "trace_sessions": [bundle.join("trace/s1"), "trace/s2"],
+            "screen_path": "screen.mp4",
+            "voice_path": "/elsewhere/voice.m4a",
+        });
+        fs::write(bundle.join("manifest.json"), manifest.to_string()).unwrap();
+        bundle
+    }
+
+    #[test]
+    fn test_export_import__new_sessions_dir__then_relocated_with_relative_manifest() {
+        with_temp_home(|home| {
+            register_with_manifest("session_port");
+            let archive = home.join("session_port.tar.zst");
+            export("session_port", &archive).unwrap();
+
+            let new_root = home.join("elsewhere/sessions");
+            env::set_var(SESSIONS_DIR_ENV, &new_root);
+            let imported = import(&archive);
+            env::remove_var(SESSIONS_DIR_ENV);
+            let imported = imported.unwrap();
+
+            let bundle = new_root.join("session_port");
+            assert_eq!(imported.session_path, bundle);
+            assert_eq!(
+                fs::metadata(bundle.join("trace/events.bin")).unwrap().len(),
+                1000
+            );
+            let session_json = fs::read_to_string(bundle.join("session.json")).unwrap();
+            let stored: SessionState = serde_json::from_str(&session_json).unwrap();
+            assert_eq!(stored.session_path, bundle);
+
+            let manifest: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(bundle.join("manifest.json")).unwrap())
+                    .unwrap();
+            assert_eq!(manifest["trace_root"], "trace");
+            assert_eq!(
+                manifest["trace_sessions"],
+                serde_json::json!(["trace/s1", "trace/s2"])
+            );
+            assert_eq!(manifest["screen_path"], "screen.mp4");
+            assert_eq!(manifest["voice_path"], "/elsewhere/voice.m4a");
+        });
+    }
+
+    #[test]
+    fn test_import__session_exists__then_error_and_original_kept() {
+        with_temp_home(|home| {
+            let bundle = register_with_manifest("session_dup");
+            let archive = home.join("session_dup.tar.zst");
+            export("session_dup", &archive).unwrap();
+
+            let err = import(&archive).unwrap_err();
+
+            assert!(err.to_string().contains("already exists"), "{err}");
+            assert!(bundle.join("trace/events.bin").exists());
+            assert_eq!(list().unwrap().len(), 1);
+        });
+    }
+
+    fn write_raw_archive(path: &Path, entries: &[&str]) {
+        let file = File::create(path).unwrap();
+        let mut builder = tar::Builder::new(zstd::Encoder::new(file, 0).unwrap());
+        for name in entries {
+            // Set the raw name: Header::set_path refuses `..` and absolute paths
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(1);
+            header.set_mode(0o644);
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_cksum();
+            builder.append(&header, &b"x"[..]).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_import__hostile_entries__then_rejected_without_writing() {
+        with_temp_home(|home| {
+            let archive = home.join("hostile.tar.zst");
+            for entries in [
+                &["session_x/../../escaped"][..],
+                &["/tmp/ada_import_escaped"][..],
+                &["session_x/session.json", "session_y/other"][..],
+                &["top_level_file"][..],
+            ] {
+                write_raw_archive(&archive, entries);
+
+                assert!(import(&archive).is_err(), "{entries:?}");
+            }
+
+            assert!(!home.join("escaped").exists());
+            assert!(!Path::new("/tmp/ada_import_escaped").exists());
+            assert!(list().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_rename__finished_session__then_moved_and_ids_rewritten() {
+        with_temp_home(|_| {
+            let old_bundle = register_with_manifest("session_2024_01_24_10_00_00_abc");
+            let mut manifest: serde_json::Value = serde_json::from_str(
+                &fs::read_to_string(old_bundle.join("manifest.json")).unwrap(),
+            )
+            .unwrap();
+            manifest["session_name"] = "session_2024_01_24_10_00_00_abc".into();
+            fs::write(old_bundle.join("manifest.json"), manifest.to_string()).unwrap();
+
+            rename("session_2024_01_24_10_00_00_abc", "session_login_bug").unwrap();
+
+            assert!(!old_bundle.exists());
+            assert!(get("session_2024_01_24_10_00_00_abc").unwrap().is_none());
+            let renamed = get("session_login_bug").unwrap().unwrap();
+            let new_bundle = session_dir("session_login_bug").unwrap();
+            assert_eq!(renamed.session_id, "session_login_bug");
+            assert_eq!(renamed.session_path, new_bundle);
+            assert!(new_bundle.join("trace/events.bin").exists());
+            let manifest: serde_json::Value = serde_json::from_str(
+                &fs::read_to_string(new_bundle.join("manifest.json")).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(manifest["session_name"], "session_login_bug");
+        });
+    }
+
+    #[test]
+    fn test_rename__target_exists_or_running__then_error_and_unchanged() {
+        with_temp_home(|_| {
+            register_sized("session_a", "A", "2024-01-24T10:00:00Z", 10);
+            register_sized("session_b", "B", "2024-01-24T11:00:00Z", 10);
+            let mut live = session_started_at("session_live", "2024-01-24T12:00:00Z");
+            live.session_path = session_dir("session_live").unwrap();
+            live.status = SessionStatus::Running;
+            register(&live).unwrap();
+
+            let taken = rename("session_a", "session_b").unwrap_err();
+            let running = rename("session_live", "session_c").unwrap_err();
+
+            assert!(matches!(
+                taken.downcast_ref::<SessionError>(),
+                Some(SessionError::AlreadyExists(id)) if id == "session_b"
+            ));
+            assert!(matches!(
+                running.downcast_ref::<SessionError>(),
+                Some(SessionError::Running(_))
+            ));
+            assert_eq!(
+                remaining_ids(),
+                vec!["session_live", "session_b", "session_a"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_validate_session_id__rules__then_prefix_and_safe_chars_only() {
+        for valid in ["session_login_bug", "session_2024-01-24", "session_x"] {
+            assert!(validate_session_id(valid).is_ok(), "{valid}");
+        }
+        for invalid in [
+            "login_bug",
+            "session_",
+            "session_a/b",
+            "session_..",
+            "session_a b",
+            "Session_x",
+        ] {
+            assert!(
+                matches!(
+                    validate_session_id(invalid),
+                    Err(SessionError::InvalidId(_))
+                ),
+                "{invalid}"
+            );
+        }
+    }
+
     #[test]
     fn test_extract_app_info__simple_binary__then_name_only() {
         let info = extract_app_info("/usr/bin/ls");
@@ -945,6 +2787,29 @@ mod tests {
         // bundle_id might be None if PlistBuddy fails, so we just check name extraction works
     }
 
+    #[test]
+    fn test_is_process_alive__current_pid__then_true() {
+        assert!(is_process_alive(std::process::id()));
+    }
+
+    #[test]
+    fn test_is_process_alive__init_owned_by_root__then_true() {
+        // Unprivileged callers get EPERM for pid 1, which still means alive
+        assert!(is_process_alive(1));
+    }
+
+    #[test]
+    fn test_is_process_alive__very_high_pid__then_false() {
+        assert!(!is_process_alive(i32::MAX as u32));
+    }
+
+    #[test]
+    fn test_is_process_alive__pid_outside_i32__then_false() {
+        // Would be a negative pid (a process group) if cast directly
+        assert!(!is_process_alive(u32::MAX));
+        assert!(!is_process_alive(0));
+    }
+
     #[test]
     fn test_status__serialize__then_lowercase() {
         let json = serde_json::to_string(&SessionStatus::Running).unwrap();
@@ -964,4 +2829,43 @@ mod tests {
             assert!(dir.ends_with(".ada/sessions"));
         });
     }
+
+    #[test]
+    fn test_sessions_dir__override_set__then_sessions_live_there() {
+        with_temp_home(|home| {
+            let override_dir = home.join("sandbox/sessions");
+            env::set_var(SESSIONS_DIR_ENV, &override_dir);
+
+            let mut session = session_started_at("session_sandboxed", "2024-01-24T10:30:00Z");
+            session.session_path = override_dir.join("session_sandboxed");
+            register(&session).unwrap();
+            let dir = sessions_dir().unwrap();
+            let listed = list().unwrap();
+            env::remove_var(SESSIONS_DIR_ENV);
+
+            assert_eq!(dir, override_dir);
+            assert!(override_dir.join("session_sandboxed/session.json").exists());
+            assert!(!home.join(ada_sessions::SESSIONS_DIR).exists());
+            assert_eq!(listed.len(), 1);
+            assert_eq!(listed[0].session_id, "session_sandboxed");
+        });
+    }
+
+    #[test]
+    fn test_sessions_dir__no_home_no_override__then_home_not_set() {
+        with_temp_home(|_| {
+            env::remove_var("HOME");
+
+            let dir = sessions_dir();
+            let listed = list();
+
+            assert!(matches!(dir, Err(SessionError::HomeNotSet)));
+            let err = listed.unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<SessionError>(),
+                Some(SessionError::HomeNotSet)
+            ));
+            assert!(err.to_string().contains(SESSIONS_DIR_ENV));
+        });
+    }
 }
@@ -0,0 +1,34 @@
+//! Integration test for reading media durations with ffprobe.
+//!
+//! Requires the `ffprobe` binary (bundled or via env override); skipped
+//! automatically when it is not available.
+
+use std::path::Path;
+
+#[test]
+fn duration_sec__250ms_wav_fixture__then_quarter_second() {
+    if !ada_cli::binary_resolver::is_available(ada_cli::binary_resolver::Tool::Ffprobe) {
+        eprintln!("SKIPPED: ffprobe not available");
+        return;
+    }
+
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/media/silence_250ms.wav");
+    assert!(fixture.exists(), "media fixture must exist");
+
+    let duration = ada_cli::media_probe::duration_sec(&fixture).expect("duration");
+
+    assert!((duration - 0.25).abs() < 0.01, "duration was {}", duration);
+}
+
+#[test]
+fn duration_sec__missing_file__then_error() {
+    if !ada_cli::binary_resolver::is_available(ada_cli::binary_resolver::Tool::Ffprobe) {
+        eprintln!("SKIPPED: ffprobe not available");
+        return;
+    }
+
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    assert!(ada_cli::media_probe::duration_sec(&temp_dir.path().join("missing.mp4")).is_err());
+}
@@ -138,3 +138,23 @@ fn transcribe__48khz_fixture__then_whisper_accepts_resampled_audio() {
         "transcription should have at least one segment"
     );
 }
+
+#[test]
+fn normalize_loudness__16khz_input__then_writes_whisper_ready_file_in_temp_dir() {
+    if !ffprobe_available() || !ffmpeg_available() {
+        eprintln!("SKIPPED: ffprobe/ffmpeg not available");
+        return;
+    }
+
+    let fixture_16k = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/transcribe/test_voice.wav");
+    assert!(fixture_16k.exists(), "16 kHz fixture must exist");
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let result = ada_cli::audio::normalize_loudness(&fixture_16k, temp_dir.path())
+        .expect("normalize_loudness should succeed");
+
+    assert_eq!(result, temp_dir.path().join("voice_loudnorm.wav"));
+    assert!(result.exists(), "normalized file should exist");
+    assert_eq!(probe_sample_rate(&result), 16_000);
+}
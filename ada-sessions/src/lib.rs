@@ -0,0 +1,97 @@
+//! ADA capture session registry basics
+//!
+//! Shared by `ada session` (ada-cli) and the `trace.sessions` RPC (query_engine)
+//! so both find the same registry and list sessions in the same order.
+
+use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+
+/// Registry location relative to `$HOME`
+pub const SESSIONS_DIR: &str = ".ada/sessions";
+
+/// Environment variable overriding the registry location
+pub const SESSIONS_DIR_ENV: &str = "ADA_SESSIONS_DIR";
+
+/// Registry directory: `$ADA_SESSIONS_DIR` if set, else `~/.ada/sessions`;
+/// `None` when neither variable is set
+pub fn sessions_dir() -> Option<PathBuf> {
+    sessions_dir_from(std::env::var_os(SESSIONS_DIR_ENV), std::env::var_os("HOME"))
+}
+
+fn sessions_dir_from(override_dir: Option<OsString>, home: Option<OsString>) -> Option<PathBuf> {
+    if let Some(dir) = override_dir.filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    home.filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(SESSIONS_DIR))
+}
+
+/// A session `start_time` as an instant, or `None` if it isn't RFC 3339
+pub fn start_instant(start_time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(start_time)
+        .ok()
+        .map(|started| started.with_timezone(&Utc))
+}
+
+/// Order two session `start_time`s
+///
+/// Compares instants when both parse, so `Z` and `+00:00` (or any other
+/// offset) order correctly; otherwise falls back to comparing the strings.
+pub fn compare_start_times(a: &str, b: &str) -> Ordering {
+    match (start_instant(a), start_instant(b)) {
+        (Some(a_instant), Some(b_instant)) => a_instant.cmp(&b_instant),
+        _ => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    #[test]
+    fn sessions_dir_from__override_set__then_override_wins() {
+        let dir = sessions_dir_from(Some("/data/sessions".into()), Some("/home/u".into()));
+
+        assert_eq!(dir, Some(PathBuf::from("/data/sessions")));
+    }
+
+    #[test]
+    fn sessions_dir_from__override_empty__then_under_home() {
+        let dir = sessions_dir_from(Some("".into()), Some("/home/u".into()));
+
+        assert_eq!(dir, Some(PathBuf::from("/home/u/.ada/sessions")));
+    }
+
+    #[test]
+    fn sessions_dir_from__nothing_set__then_none() {
+        assert_eq!(sessions_dir_from(None, None), None);
+        assert_eq!(sessions_dir_from(None, Some("".into())), None);
+    }
+
+    #[test]
+    fn compare_start_times__different_offsets__then_by_instant() {
+        // 09:30 UTC is earlier than 10:00Z even though it sorts later as text
+        let earlier = "2026-01-01T11:30:00+02:00";
+        let later = "2026-01-01T10:00:00Z";
+
+        assert_eq!(compare_start_times(earlier, later), Ordering::Less);
+        assert_eq!(
+            compare_start_times("2026-01-01T10:00:00Z", "2026-01-01T10:00:00+00:00"),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn compare_start_times__unparseable__then_as_text() {
+        assert_eq!(compare_start_times("b", "a"), Ordering::Greater);
+        assert_eq!(
+            compare_start_times("not a time", "2026-01-01T10:00:00Z"),
+            Ordering::Greater
+        );
+    }
+}
@@ -2,17 +2,20 @@ use std::{
     future::Future,
     net::SocketAddr,
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
+use ada_sessions::SESSIONS_DIR_ENV;
 use anyhow::{Context, Result};
 use clap::Parser;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::EnvFilter;
 
 use crate::{
     // TODO: Re-enable handlers after updating to ATF V2 API
     // handlers::{EventsGetHandler, SpansListHandler, TraceInfoHandler},
+    handlers::{SessionRegistryDir, TraceSessionsHandler},
     server::{JsonRpcServer, ServerError},
 };
 
@@ -40,6 +43,10 @@ pub struct Args {
     /// Cache time-to-live in seconds
     #[arg(long, default_value_t = 300)]
     pub cache_ttl: u64,
+
+    /// Serve `rpc.connections`, reporting active connections per client IP
+    #[arg(long)]
+    pub expose_connections: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +55,7 @@ pub struct AppConfig {
     pub trace_root: PathBuf,
     pub cache_size: usize,
     pub cache_ttl: Duration,
+    pub expose_connections: bool,
 }
 
 impl From<Args> for AppConfig {
@@ -57,6 +65,7 @@ impl From<Args> for AppConfig {
             trace_root: value.trace_root,
             cache_size: value.cache_size,
             cache_ttl: Duration::from_secs(value.cache_ttl),
+            expose_connections: value.expose_connections,
         }
     }
 }
@@ -88,6 +97,18 @@ pub async fn run(config: AppConfig) -> Result<()> {
     // let spans_handler = SpansListHandler::new(config.trace_root.clone());
     // spans_handler.register(&server);
 
+    match SessionRegistryDir::from_env() {
+        Some(registry) => TraceSessionsHandler::new(Arc::new(registry)).register(&server),
+        None => warn!(
+            "neither {} nor HOME is set; trace.sessions is unavailable",
+            SESSIONS_DIR_ENV
+        ),
+    }
+
+    if config.expose_connections {
+        server.register_connections_handler();
+    }
+
     info!(
         address = %config.address,
         trace_root = %config.trace_root.display(),
@@ -319,11 +340,17 @@ mod tests {
             trace_root: trace_root.path().to_path_buf(),
             cache_size: 8,
             cache_ttl: Duration::from_secs(1),
+            expose_connections: false,
         };
 
         let result = run(config).await;
 
-        assert!(result.is_err(), "expected binding error");
+        let err = result.expect_err("expected binding error");
+        assert!(
+            err.to_string()
+                .contains(&format!("port {} already in use", addr.port())),
+            "unexpected error: {err}"
+        );
     }
 
     #[cfg(unix)]
@@ -336,6 +363,7 @@ mod tests {
             trace_root: trace_root.path().to_path_buf(),
             cache_size: 8,
             cache_ttl: Duration::from_secs(1),
+            expose_connections: false,
         };
 
         let server_task = tokio::spawn(run(config));
@@ -428,6 +456,7 @@ mod tests {
             trace_root: PathBuf::from("/custom/trace/path"),
             cache_size: 512,
             cache_ttl: 900,
+            expose_connections: true,
         };
 
         let config = AppConfig::from(args);
@@ -436,6 +465,7 @@ mod tests {
         assert_eq!(config.trace_root, PathBuf::from("/custom/trace/path"));
         assert_eq!(config.cache_size, 512);
         assert_eq!(config.cache_ttl, Duration::from_secs(900));
+        assert!(config.expose_connections);
     }
 
     /// Direct unit test for init_tracing function coverage
@@ -473,6 +503,7 @@ mod tests {
             trace_root: file_path,
             cache_size: 10,
             cache_ttl: Duration::from_secs(30),
+            expose_connections: false,
         };
 
         let result = run(config).await;
@@ -492,6 +523,7 @@ mod tests {
             trace_root: trace_path,
             cache_size: 25,
             cache_ttl: Duration::from_secs(60),
+            expose_connections: false,
         };
 
         // Run for a very short time to exercise initialization but not full serving
@@ -0,0 +1,248 @@
+//! Typed JSON-RPC client for talking to a running query engine
+
+use std::{
+    net::SocketAddr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use hyper::{
+    body, client::HttpConnector, header::CONTENT_TYPE, http::StatusCode, Body, Method, Request,
+};
+use serde_json::Value;
+
+use crate::server::types::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+
+#[derive(Debug)]
+pub struct Client {
+    http: hyper::Client<HttpConnector>,
+    next_id: AtomicU64,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self {
+            http: hyper::Client::new(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Call `method` and wait for its result
+    ///
+    /// Server-side errors are returned as-is; transport and protocol
+    /// failures are reported as internal errors.
+    pub async fn call(
+        &self,
+        addr: SocketAddr,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<Value, JsonRpcError> {
+        let id = Value::from(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (status, bytes) = self
+            .post(addr, request(method, params, Some(id.clone())))
+            .await?;
+
+        let response: JsonRpcResponse = serde_json::from_slice(&bytes).map_err(|err| {
+            JsonRpcError::internal(format!("invalid response (HTTP {status}): {err}"))
+        })?;
+        if let Some(error) = response.error {
+            return Err(error);
+        }
+        if response.id.as_ref() != Some(&id) {
+            return Err(JsonRpcError::internal(format!(
+                "response id {:?} does not match request id {id}",
+                response.id
+            )));
+        }
+        Ok(response.result.unwrap_or(Value::Null))
+    }
+
+    /// Send `method` as a notification (no id); the server does not reply
+    /// with a result
+    pub async fn notify(
+        &self,
+        addr: SocketAddr,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<(), JsonRpcError> {
+        let (status, bytes) = self.post(addr, request(method, params, None)).await?;
+        if status == StatusCode::NO_CONTENT {
+            return Ok(());
+        }
+
+        // Rejected before dispatch (e.g. rate limited) still carries an error body
+        match serde_json::from_slice::<JsonRpcResponse>(&bytes) {
+            Ok(JsonRpcResponse {
+                error: Some(error), ..
+            }) => Err(error),
+            _ => Err(JsonRpcError::internal(format!(
+                "unexpected HTTP {status} for notification"
+            ))),
+        }
+    }
+
+    async fn post(
+        &self,
+        addr: SocketAddr,
+        request: JsonRpcRequest,
+    ) -> Result<(StatusCode, body::Bytes), JsonRpcError> {
+        let payload =
+            serde_json::to_vec(&request).map_err(|err| JsonRpcError::internal(err.to_string()))?;
+        let http_request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("http://{addr}/rpc"))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(payload))
+            .map_err(|err| JsonRpcError::internal(err.to_string()))?;
+
+        let response =
+            self.http.request(http_request).await.map_err(|err| {
+                JsonRpcError::internal(format!("request to {addr} failed: {err}"))
+            })?;
+        let status = response.status();
+        let bytes = body::to_bytes(response.into_body())
+            .await
+            .map_err(|err| JsonRpcError::internal(format!("failed to read response: {err}")))?;
+        Ok((status, bytes))
+    }
+}
+
+fn request(method: &str, params: Option<Value>, id: Option<Value>) -> JsonRpcRequest {
+    JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        method: method.to_string(),
+        params,
+        id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::server::JsonRpcServer;
+    use serde_json::json;
+    use std::{
+        net::TcpListener,
+        sync::{atomic::AtomicUsize, Arc},
+        time::Duration,
+    };
+    use tokio::{sync::oneshot, task::JoinHandle};
+
+    struct TestServer {
+        addr: SocketAddr,
+        shutdown: Option<oneshot::Sender<()>>,
+        task: JoinHandle<()>,
+    }
+
+    impl TestServer {
+        fn start(server: JsonRpcServer) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let (shutdown, rx) = oneshot::channel::<()>();
+            let task = tokio::spawn(async move {
+                let _ = server
+                    .serve_on_listener(listener, async {
+                        let _ = rx.await;
+                    })
+                    .await;
+            });
+            Self {
+                addr,
+                shutdown: Some(shutdown),
+                task,
+            }
+        }
+
+        async fn stop(mut self) {
+            if let Some(shutdown) = self.shutdown.take() {
+                let _ = shutdown.send(());
+            }
+            let _ = self.task.await;
+        }
+    }
+
+    #[tokio::test]
+    async fn client_call__registered_method__then_returns_result() {
+        let server = JsonRpcServer::new();
+        server.register_sync("math.double", |params| {
+            let n = params.and_then(|p| p["n"].as_i64()).unwrap_or_default();
+            Ok(json!({ "value": n * 2 }))
+        });
+        let test_server = TestServer::start(server);
+        let client = Client::new();
+
+        let first = client
+            .call(test_server.addr, "math.double", Some(json!({ "n": 21 })))
+            .await
+            .expect("call");
+        let second = client
+            .call(test_server.addr, "math.double", Some(json!({ "n": 2 })))
+            .await
+            .expect("call");
+
+        assert_eq!(first, json!({ "value": 42 }));
+        assert_eq!(second, json!({ "value": 4 }));
+        test_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn client_call__unknown_method__then_returns_server_error() {
+        let test_server = TestServer::start(JsonRpcServer::new());
+
+        let err = Client::new()
+            .call(test_server.addr, "trace.missing", None)
+            .await
+            .expect_err("method not found");
+
+        assert_eq!(err.code, -32601);
+        assert_eq!(err.data, Some(json!("trace.missing")));
+        test_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn client_notify__registered_method__then_handler_runs() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let server = JsonRpcServer::new();
+        server.register_sync("trace.ping", {
+            let calls = calls.clone();
+            move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(Value::Null)
+            }
+        });
+        let test_server = TestServer::start(server);
+
+        Client::new()
+            .notify(test_server.addr, "trace.ping", Some(json!([1])))
+            .await
+            .expect("notify");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        test_server.stop().await;
+    }
+
+    #[tokio::test]
+    async fn client_call__server_not_running__then_internal_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        drop(listener);
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(5),
+            Client::new().call(addr, "trace.info", None),
+        )
+        .await
+        .expect("connection refused promptly")
+        .expect_err("nothing listening");
+
+        assert_eq!(err.code, -32603);
+        assert!(err.data.unwrap().as_str().unwrap().contains("failed"));
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::server::{
+    handler::{JsonRpcHandler, JsonRpcResult},
+    types::JsonRpcError,
+    JsonRpcServer,
+};
+
+/// Flight recorder state, mirroring the tracer backend's `FlightRecorderState`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlightState {
+    Idle,
+    Armed,
+    PreRoll,
+    Recording,
+    PostRoll,
+}
+
+/// Flight recorder of the active capture, implemented by whatever owns the
+/// tracer controller (typically behind an `Arc<Mutex<_>>` shared with the
+/// capture loop)
+///
+/// Each method performs the action and returns the state it left the
+/// recorder in; errors are reported to the caller as-is.
+pub trait FlightRecorderControl: Send + Sync {
+    fn arm(&self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<FlightState, String>;
+    fn fire(&self) -> Result<FlightState, String>;
+    fn disarm(&self) -> Result<FlightState, String>;
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArmParams {
+    #[serde(default, rename = "preRollMs", alias = "pre_roll_ms")]
+    pre_roll_ms: u32,
+    #[serde(default, rename = "postRollMs", alias = "post_roll_ms")]
+    post_roll_ms: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FlightAction {
+    Arm,
+    Fire,
+    Disarm,
+}
+
+/// Serves `trace.flight.arm`, `trace.flight.fire` and `trace.flight.disarm`
+#[derive(Clone)]
+pub struct FlightRecorderHandler {
+    control: Arc<dyn FlightRecorderControl>,
+    action: FlightAction,
+}
+
+impl FlightRecorderHandler {
+    pub fn register(control: Arc<dyn FlightRecorderControl>, server: &JsonRpcServer) {
+        let registry = server.handler_registry();
+        for (method, action) in [
+            ("trace.flight.arm", FlightAction::Arm),
+            ("trace.flight.fire", FlightAction::Fire),
+            ("trace.flight.disarm", FlightAction::Disarm),
+        ] {
+            let handler = Self {
+                control: control.clone(),
+                action,
+            };
+            registry.register_handler(method, handler);
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcHandler for FlightRecorderHandler {
+    async fn call(&self, params: Option<Value>) -> JsonRpcResult {
+        let state = match self.action {
+            FlightAction::Arm => {
+                let params: ArmParams = match params {
+                    Some(Value::Null) | None => ArmParams::default(),
+                    Some(value) => serde_json::from_value(value).map_err(|err| {
+                        JsonRpcError::invalid_params(format!(
+                            "invalid trace.flight.arm parameters: {err}"
+                        ))
+                    })?,
+                };
+                self.control.arm(params.pre_roll_ms, params.post_roll_ms)
+            }
+            FlightAction::Fire => self.control.fire(),
+            FlightAction::Disarm => self.control.disarm(),
+        }
+        .map_err(JsonRpcError::internal)?;
+        serde_json::to_value(state).map_err(|err| JsonRpcError::internal(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    /// Records each call and walks the states a real recorder would
+    #[derive(Default)]
+    struct MockControl {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl FlightRecorderControl for MockControl {
+        fn arm(&self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<FlightState, String> {
+            self.calls
+                .lock()
+                .push(format!("arm({pre_roll_ms}, {post_roll_ms})"));
+            Ok(FlightState::Armed)
+        }
+
+        fn fire(&self) -> Result<FlightState, String> {
+            self.calls.lock().push("fire".to_string());
+            Ok(FlightState::PostRoll)
+        }
+
+        fn disarm(&self) -> Result<FlightState, String> {
+            self.calls.lock().push("disarm".to_string());
+            Err("trigger is not armed".to_string())
+        }
+    }
+
+    fn server_with(control: Arc<MockControl>) -> JsonRpcServer {
+        let server = JsonRpcServer::new();
+        FlightRecorderHandler::register(control, &server);
+        server
+    }
+
+    #[tokio::test]
+    async fn trace_flight__arm_then_fire__then_calls_controller_and_returns_state() {
+        let control = Arc::new(MockControl::default());
+        let server = server_with(control.clone());
+        let registry = server.handler_registry();
+
+        let armed = registry
+            .call(
+                "trace.flight.arm",
+                Some(json!({ "preRollMs": 500, "post_roll_ms": 250 })),
+            )
+            .await
+            .expect("arm");
+        let fired = registry
+            .call("trace.flight.fire", None)
+            .await
+            .expect("fire");
+
+        assert_eq!(armed, json!("armed"));
+        assert_eq!(fired, json!("postroll"));
+        assert_eq!(*control.calls.lock(), vec!["arm(500, 250)", "fire"]);
+    }
+
+    #[tokio::test]
+    async fn trace_flight__disarm_fails__then_internal_error_with_message() {
+        let control = Arc::new(MockControl::default());
+        let server = server_with(control.clone());
+
+        let err = server
+            .handler_registry()
+            .call("trace.flight.disarm", None)
+            .await
+            .expect_err("not armed");
+
+        assert_eq!(err.code, -32603);
+        assert_eq!(err.data, Some(json!("trigger is not armed")));
+        assert_eq!(*control.calls.lock(), vec!["disarm"]);
+    }
+
+    #[tokio::test]
+    async fn trace_flight__arm_without_params__then_zero_rolls() {
+        let control = Arc::new(MockControl::default());
+        let server = server_with(control.clone());
+
+        server
+            .handler_registry()
+            .call("trace.flight.arm", None)
+            .await
+            .expect("arm");
+
+        assert_eq!(*control.calls.lock(), vec!["arm(0, 0)"]);
+    }
+
+    #[tokio::test]
+    async fn trace_flight__arm_unknown_param__then_invalid_params() {
+        let server = server_with(Arc::new(MockControl::default()));
+
+        let err = server
+            .handler_registry()
+            .call("trace.flight.arm", Some(json!({ "preRoll": 500 })))
+            .await
+            .expect_err("unknown field");
+
+        assert_eq!(err.code, -32602);
+    }
+}
@@ -1,7 +1,14 @@
-pub mod events;
-pub mod spans;
-pub mod trace_info;
+// TODO: Update handlers to use ATF V2 API
+// pub mod events;
+// pub mod spans;
+// pub mod trace_info;
+pub mod flight;
+pub mod sessions;
+pub mod trace_stats;
 
-pub use events::EventsGetHandler;
-pub use spans::SpansListHandler;
-pub use trace_info::TraceInfoHandler;
+// pub use events::EventsGetHandler;
+// pub use spans::SpansListHandler;
+// pub use trace_info::TraceInfoHandler;
+pub use flight::{FlightRecorderControl, FlightRecorderHandler, FlightState};
+pub use sessions::{SessionRegistryDir, SessionsProvider, TraceSessionsHandler};
+pub use trace_stats::{TraceStatsHandler, TraceStatsProvider};
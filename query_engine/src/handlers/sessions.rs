@@ -0,0 +1,264 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ada_sessions::{compare_start_times, SESSIONS_DIR};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::server::{
+    handler::{JsonRpcHandler, JsonRpcResult},
+    types::JsonRpcError,
+    JsonRpcServer,
+};
+
+/// Source of capture sessions, as `ada session list --format json` reports them
+pub trait SessionsProvider: Send + Sync {
+    /// All sessions, newest first
+    fn sessions(&self) -> Result<Vec<Value>, String>;
+}
+
+/// Reads the on-disk session registry (`<dir>/<session_id>/session.json`)
+///
+/// Records are passed through untouched so the RPC output tracks the CLI's
+/// `SessionState` format without query_engine depending on ada-cli.
+#[derive(Debug, Clone)]
+pub struct SessionRegistryDir {
+    dir: PathBuf,
+}
+
+impl SessionRegistryDir {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Registry under `home`, i.e. `~/.ada/sessions`
+    pub fn in_home(home: &Path) -> Self {
+        Self::new(home.join(SESSIONS_DIR))
+    }
+
+    /// Registry named by `ADA_SESSIONS_DIR`, else the one under `$HOME`;
+    /// `None` when neither is set
+    pub fn from_env() -> Option<Self> {
+        ada_sessions::sessions_dir().map(Self::new)
+    }
+}
+
+impl SessionsProvider for SessionRegistryDir {
+    fn sessions(&self) -> Result<Vec<Value>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(format!("failed to read {}: {err}", self.dir.display())),
+        };
+
+        let mut sessions = Vec::new();
+        for entry in entries.flatten() {
+            let session_file = entry.path().join("session.json");
+            let Ok(json) = fs::read_to_string(&session_file) else {
+                continue;
+            };
+            match serde_json::from_str::<Value>(&json) {
+                Ok(session) if session.is_object() => sessions.push(session),
+                _ => tracing::warn!("Skipping corrupted session file {:?}", session_file),
+            }
+        }
+
+        // Newest first, ordered as `ada session list` orders them
+        sessions.sort_by(|a, b| compare_start_times(start_time(b), start_time(a)));
+        Ok(sessions)
+    }
+}
+
+fn start_time(session: &Value) -> &str {
+    session["start_time"].as_str().unwrap_or_default()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SessionsParams {
+    #[serde(default, rename = "runningOnly", alias = "running_only")]
+    running_only: bool,
+    /// Case-insensitive substring of the app name
+    #[serde(default)]
+    app: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct TraceSessionsHandler {
+    provider: Arc<dyn SessionsProvider>,
+}
+
+impl TraceSessionsHandler {
+    pub fn new(provider: Arc<dyn SessionsProvider>) -> Self {
+        Self { provider }
+    }
+
+    pub fn register(self, server: &JsonRpcServer) {
+        server
+            .handler_registry()
+            .register_handler("trace.sessions", self);
+    }
+}
+
+#[async_trait]
+impl JsonRpcHandler for TraceSessionsHandler {
+    async fn call(&self, params: Option<Value>) -> JsonRpcResult {
+        let params: SessionsParams = match params {
+            Some(Value::Null) | None => SessionsParams::default(),
+            Some(value) => serde_json::from_value(value).map_err(|err| {
+                JsonRpcError::invalid_params(format!("invalid trace.sessions parameters: {err}"))
+            })?,
+        };
+        let app = params.app.map(|app| app.to_lowercase());
+
+        let sessions: Vec<Value> = self
+            .provider
+            .sessions()
+            .map_err(JsonRpcError::internal)?
+            .into_iter()
+            .filter(|s| !params.running_only || s["status"] == "running")
+            .filter(|s| {
+                app.as_ref().is_none_or(|app| {
+                    s["app_info"]["name"]
+                        .as_str()
+                        .is_some_and(|name| name.to_lowercase().contains(app))
+                })
+            })
+            .collect();
+        Ok(Value::Array(sessions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// Write a session record started on January `day`
+    fn write_session(home: &Path, id: &str, app: &str, status: &str, day: u32) {
+        let dir = home.join(SESSIONS_DIR).join(id);
+        fs::create_dir_all(&dir).unwrap();
+        let session = json!({
+            "session_id": id,
+            "session_path": dir,
+            "start_time": format!("2026-01-{day:02}T10:00:00Z"),
+            "app_info": { "name": app, "bundle_id": format!("com.example.{app}") },
+            "status": status,
+            "pid": 42,
+        });
+        fs::write(dir.join("session.json"), session.to_string()).unwrap();
+    }
+
+    fn server_for(home: &Path) -> JsonRpcServer {
+        let server = JsonRpcServer::new();
+        TraceSessionsHandler::new(Arc::new(SessionRegistryDir::in_home(home))).register(&server);
+        server
+    }
+
+    async fn session_ids(server: &JsonRpcServer, params: Option<Value>) -> Vec<String> {
+        let result = server
+            .handler_registry()
+            .call("trace.sessions", params)
+            .await
+            .expect("sessions");
+        result
+            .as_array()
+            .expect("array")
+            .iter()
+            .map(|s| s["session_id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn trace_sessions__registry_under_home__then_all_sessions_newest_first() {
+        let home = TempDir::new().unwrap();
+        write_session(home.path(), "s1", "Calculator", "complete", 1);
+        write_session(home.path(), "s2", "Safari", "running", 2);
+        fs::create_dir_all(home.path().join(SESSIONS_DIR).join("no_state")).unwrap();
+        let server = server_for(home.path());
+
+        let result = server
+            .handler_registry()
+            .call("trace.sessions", None)
+            .await
+            .expect("sessions");
+
+        assert_eq!(result.as_array().unwrap().len(), 2);
+        assert_eq!(result[0]["session_id"], "s2");
+        assert_eq!(result[0]["status"], "running");
+        assert_eq!(
+            result[0]["app_info"],
+            json!({ "name": "Safari", "bundle_id": "com.example.Safari" })
+        );
+        assert_eq!(result[1]["session_id"], "s1");
+    }
+
+    #[tokio::test]
+    async fn trace_sessions__running_only_and_app_filters__then_matching_sessions() {
+        let home = TempDir::new().unwrap();
+        write_session(home.path(), "s1", "Calculator", "complete", 1);
+        write_session(home.path(), "s2", "Safari", "running", 2);
+        write_session(home.path(), "s3", "Calculator", "running", 3);
+        let server = server_for(home.path());
+
+        let running = session_ids(&server, Some(json!({ "runningOnly": true }))).await;
+        let calculator = session_ids(&server, Some(json!({ "app": "calc" }))).await;
+        let both = session_ids(
+            &server,
+            Some(json!({ "running_only": true, "app": "CALC" })),
+        )
+        .await;
+
+        assert_eq!(running, vec!["s3", "s2"]);
+        assert_eq!(calculator, vec!["s3", "s1"]);
+        assert_eq!(both, vec!["s3"]);
+    }
+
+    #[tokio::test]
+    async fn trace_sessions__mixed_utc_offsets__then_ordered_by_instant() {
+        let home = TempDir::new().unwrap();
+        for (id, start_time) in [
+            // 09:30 UTC: oldest, though it sorts last as text
+            ("s_offset", "2026-01-01T11:30:00+02:00"),
+            ("s_utc", "2026-01-01T10:00:00Z"),
+        ] {
+            let dir = home.path().join(SESSIONS_DIR).join(id);
+            fs::create_dir_all(&dir).unwrap();
+            let session = json!({ "session_id": id, "start_time": start_time });
+            fs::write(dir.join("session.json"), session.to_string()).unwrap();
+        }
+
+        let ids = session_ids(&server_for(home.path()), None).await;
+
+        assert_eq!(ids, vec!["s_utc", "s_offset"]);
+    }
+
+    #[tokio::test]
+    async fn trace_sessions__no_registry__then_empty_list() {
+        let home = TempDir::new().unwrap();
+
+        let ids = session_ids(&server_for(home.path()), None).await;
+
+        assert!(ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn trace_sessions__unknown_param__then_invalid_params() {
+        let home = TempDir::new().unwrap();
+
+        let err = server_for(home.path())
+            .handler_registry()
+            .call("trace.sessions", Some(json!({ "status": "running" })))
+            .await
+            .expect_err("unknown field");
+
+        assert_eq!(err.code, -32602);
+    }
+}
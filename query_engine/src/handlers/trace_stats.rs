@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::server::{
+    handler::{JsonRpcHandler, JsonRpcResult},
+    types::JsonRpcError,
+    JsonRpcServer,
+};
+
+/// Source of live stats, implemented by whatever owns the tracer controller
+///
+/// Stats arrive already serialized by the owner, so `trace.stats` reports
+/// the same shape it records elsewhere (the bundle manifest's
+/// `trace_stats`) instead of a copy of the struct kept in sync by hand.
+pub trait TraceStatsProvider: Send + Sync {
+    /// Current stats, or `None` when no controller is attached
+    fn stats(&self) -> Option<Value>;
+}
+
+#[derive(Clone)]
+pub struct TraceStatsHandler {
+    provider: Arc<dyn TraceStatsProvider>,
+}
+
+impl TraceStatsHandler {
+    pub fn new(provider: Arc<dyn TraceStatsProvider>) -> Self {
+        Self { provider }
+    }
+
+    pub fn register(self, server: &JsonRpcServer) {
+        server
+            .handler_registry()
+            .register_handler("trace.stats", self);
+    }
+}
+
+#[async_trait]
+impl JsonRpcHandler for TraceStatsHandler {
+    async fn call(&self, _params: Option<Value>) -> JsonRpcResult {
+        self.provider
+            .stats()
+            .ok_or_else(|| JsonRpcError::internal("no tracer controller attached"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use serde_json::json;
+
+    struct FakeProvider(Option<Value>);
+
+    impl TraceStatsProvider for FakeProvider {
+        fn stats(&self) -> Option<Value> {
+            self.0.clone()
+        }
+    }
+
+    fn server_with(stats: Option<Value>) -> JsonRpcServer {
+        let server = JsonRpcServer::new();
+        TraceStatsHandler::new(Arc::new(FakeProvider(stats))).register(&server);
+        server
+    }
+
+    #[tokio::test]
+    async fn trace_stats__provider_attached__then_returns_provider_stats() {
+        let stats = json!({
+            "events_captured": 1200,
+            "events_dropped": 3,
+            "bytes_written": 4096,
+            "hooks_installed": 42,
+            "fallback_events": 7,
+            "preroll_evicted": 5,
+        });
+        let server = server_with(Some(stats.clone()));
+
+        let result = server
+            .handler_registry()
+            .call("trace.stats", None)
+            .await
+            .expect("stats");
+
+        assert_eq!(result, stats);
+    }
+
+    #[tokio::test]
+    async fn trace_stats__no_controller__then_internal_error_with_message() {
+        let server = server_with(None);
+
+        let err = server
+            .handler_registry()
+            .call("trace.stats", Some(json!({})))
+            .await
+            .expect_err("no controller");
+
+        assert_eq!(err.code, -32603);
+        assert_eq!(err.data, Some(json!("no tracer controller attached")));
+    }
+}
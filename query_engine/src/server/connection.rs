@@ -102,6 +102,27 @@ impl ConnectionManager {
     pub fn active_for_ip(&self, ip: IpAddr) -> usize {
         self.inner.per_ip.get(&ip).map(|entry| *entry).unwrap_or(0)
     }
+
+    /// Connections currently held across all IPs
+    pub fn total(&self) -> usize {
+        self.active_total()
+    }
+
+    /// Active connection count per IP, busiest first
+    ///
+    /// Counts are copied out one shard at a time, so `acquire` and `release`
+    /// are only ever blocked on the shard being read. Connections opened or
+    /// closed meanwhile may or may not be reflected.
+    pub fn per_ip_snapshot(&self) -> Vec<(IpAddr, usize)> {
+        let mut snapshot: Vec<(IpAddr, usize)> = self
+            .inner
+            .per_ip
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect();
+        snapshot.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        snapshot
+    }
 }
 
 pub struct ConnectionGuard {
@@ -251,4 +272,30 @@ mod tests {
         assert_eq!(manager.active_total(), 1);
         assert_eq!(manager.active_for_ip(ip), 1);
     }
+
+    #[test]
+    fn json_rpc_connection__guards_for_two_ips__then_snapshot_reflects_both() {
+        let manager = ConnectionManager::new(ConnectionManagerConfig::default());
+        let ip1 = localhost();
+        let ip2 = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 2));
+
+        let _a = manager.acquire(ip1).expect("ip1 first");
+        let _b = manager.acquire(ip2).expect("ip2 first");
+        let c = manager.acquire(ip2).expect("ip2 second");
+
+        assert_eq!(manager.per_ip_snapshot(), vec![(ip2, 2), (ip1, 1)]);
+        assert_eq!(manager.total(), 3);
+
+        drop(c);
+        assert_eq!(manager.per_ip_snapshot(), vec![(ip1, 1), (ip2, 1)]);
+        assert_eq!(manager.total(), 2);
+    }
+
+    #[test]
+    fn json_rpc_connection__no_connections__then_empty_snapshot() {
+        let manager = ConnectionManager::new(ConnectionManagerConfig::default());
+
+        assert!(manager.per_ip_snapshot().is_empty());
+        assert_eq!(manager.total(), 0);
+    }
 }
@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, net::SocketAddr};
 
 use serde_json::Value;
 use thiserror::Error;
@@ -11,6 +11,23 @@ pub enum ServerError {
     Hyper(#[from] hyper::Error),
     #[error("failed to bind address: {0}")]
     Io(#[from] io::Error),
+    #[error(
+        "port {port} already in use ({addr}); pass a different --address or stop the other process",
+        port = .0.port(),
+        addr = .0
+    )]
+    AddressInUse(SocketAddr),
+}
+
+impl ServerError {
+    /// Classify a failure to bind `addr`
+    pub fn bind(addr: SocketAddr, err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::AddrInUse {
+            ServerError::AddressInUse(addr)
+        } else {
+            ServerError::Io(err)
+        }
+    }
 }
 
 #[derive(Debug, Error, Clone)]
@@ -98,6 +115,25 @@ mod tests {
         assert_eq!(inner.message, "Method not found");
     }
 
+    #[test]
+    fn server_error_bind__addr_in_use__then_address_in_use_with_port() {
+        let addr: SocketAddr = "127.0.0.1:9090".parse().unwrap();
+
+        let err = ServerError::bind(addr, io::Error::from(io::ErrorKind::AddrInUse));
+
+        assert!(matches!(err, ServerError::AddressInUse(a) if a == addr));
+        assert!(err.to_string().starts_with("port 9090 already in use"));
+    }
+
+    #[test]
+    fn server_error_bind__other_io_error__then_io() {
+        let addr: SocketAddr = "127.0.0.1:9090".parse().unwrap();
+
+        let err = ServerError::bind(addr, io::Error::from(io::ErrorKind::PermissionDenied));
+
+        assert!(matches!(err, ServerError::Io(_)));
+    }
+
     #[test]
     fn json_rpc_errors__server_error_display__then_contains_context() {
         let err = JsonRpcServerError::InvalidRequest("bad".into());
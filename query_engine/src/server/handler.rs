@@ -1,4 +1,4 @@
-use std::{future::Future, sync::Arc};
+use std::{future::Future, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use dashmap::DashMap;
@@ -34,6 +34,9 @@ where
 #[derive(Clone, Default)]
 pub struct HandlerRegistry {
     handlers: Arc<DashMap<String, Arc<dyn JsonRpcHandler>>>,
+    /// Per-method overrides of `default_timeout`
+    timeouts: Arc<DashMap<String, Duration>>,
+    default_timeout: Option<Duration>,
 }
 
 impl HandlerRegistry {
@@ -41,6 +44,27 @@ impl HandlerRegistry {
         Self::default()
     }
 
+    /// Bound every call to `timeout` unless the method sets its own
+    pub fn with_default_timeout(timeout: Option<Duration>) -> Self {
+        Self {
+            default_timeout: timeout,
+            ..Self::default()
+        }
+    }
+
+    /// Override the timeout for one method
+    pub fn set_timeout(&self, method: impl Into<String>, timeout: Duration) {
+        self.timeouts.insert(method.into(), timeout);
+    }
+
+    fn timeout_for(&self, method: &str) -> Option<Duration> {
+        self.timeouts
+            .get(method)
+            .map(|timeout| *timeout)
+            .or(self.default_timeout)
+    }
+
+    /// Register `handler` for `method`, replacing any existing handler
     pub fn register_handler<H>(&self, method: impl Into<String>, handler: H)
     where
         H: JsonRpcHandler + 'static,
@@ -72,15 +96,32 @@ impl HandlerRegistry {
     }
 
     pub async fn call(&self, method: &str, params: Option<Value>) -> JsonRpcResult {
-        match self.handlers.get(method) {
-            Some(handler) => handler.call(params).await,
-            None => Err(JsonRpcError::method_not_found(method)),
+        // Clone out of the map so the shard lock isn't held across the await
+        let handler = match self.handlers.get(method) {
+            Some(handler) => Arc::clone(&handler),
+            None => return Err(JsonRpcError::method_not_found(method)),
+        };
+
+        match self.timeout_for(method) {
+            Some(timeout) => tokio::time::timeout(timeout, handler.call(params))
+                .await
+                .unwrap_or_else(|_| Err(JsonRpcError::handler_timed_out(method))),
+            None => handler.call(params).await,
         }
     }
 
     pub fn contains(&self, method: &str) -> bool {
         self.handlers.contains_key(method)
     }
+
+    /// Remove the handler (and any timeout override) for `method`
+    ///
+    /// Returns whether a handler was registered. Calls already in flight
+    /// finish with the removed handler.
+    pub fn unregister(&self, method: &str) -> bool {
+        self.timeouts.remove(method);
+        self.handlers.remove(method).is_some()
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +202,68 @@ mod tests {
         registry.register_sync("trace.echo", |_| Ok(json!(null)));
         assert!(registry.contains("trace.echo"));
     }
+
+    #[tokio::test]
+    async fn json_rpc_handler__slow_handler__then_timed_out_error() {
+        let registry = HandlerRegistry::with_default_timeout(Some(Duration::from_millis(20)));
+        registry.register_async("trace.slow", |_params| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(json!(null))
+        });
+
+        let err = registry
+            .call("trace.slow", None)
+            .await
+            .expect_err("expected timeout");
+
+        assert_eq!(err.code, -32004);
+        assert_eq!(err.message, "Handler timed out");
+        assert_eq!(err.data, Some(json!("trace.slow")));
+    }
+
+    #[tokio::test]
+    async fn json_rpc_handler__per_method_timeout__then_overrides_default() {
+        let registry = HandlerRegistry::with_default_timeout(Some(Duration::from_millis(10)));
+        registry.register_async("trace.query", |_params| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(json!({"done": true}))
+        });
+        registry.set_timeout("trace.query", Duration::from_secs(5));
+
+        let result = registry
+            .call("trace.query", None)
+            .await
+            .expect("override should allow the slower handler");
+
+        assert_eq!(result, json!({"done": true}));
+    }
+
+    #[tokio::test]
+    async fn json_rpc_handler__unregister__then_method_not_found() {
+        let registry = HandlerRegistry::new();
+        registry.register_sync("trace.echo", |_| Ok(json!(null)));
+
+        assert!(registry.unregister("trace.echo"));
+        assert!(!registry.unregister("trace.echo"));
+        assert!(!registry.contains("trace.echo"));
+        let err = registry
+            .call("trace.echo", None)
+            .await
+            .expect_err("expected method not found error");
+        assert_eq!(err.code, -32601);
+    }
+
+    #[tokio::test]
+    async fn json_rpc_handler__re_register__then_new_handler_wins() {
+        let registry = HandlerRegistry::new();
+        registry.register_sync("trace.version", |_| Ok(json!(1)));
+        registry.register_async("trace.version", |_params| async { Ok(json!(2)) });
+
+        let result = registry
+            .call("trace.version", None)
+            .await
+            .expect("handler should succeed");
+
+        assert_eq!(result, json!(2));
+    }
 }
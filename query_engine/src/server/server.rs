@@ -1,14 +1,25 @@
 use std::{
     convert::Infallible,
     future::Future,
+    io::Write,
     net::{IpAddr, SocketAddr},
     sync::Arc,
+    time::{Duration, Instant},
 };
 
+use flate2::{write::GzEncoder, Compression};
+use serde::{de::DeserializeOwned, Serialize};
+
+use parking_lot::Mutex;
+
 use hyper::server::{conn::AddrIncoming, conn::AddrStream, Builder};
 use hyper::{
     body,
-    header::CONTENT_TYPE,
+    header::{
+        HeaderMap, HeaderValue, ACCEPT_ENCODING, ACCESS_CONTROL_ALLOW_HEADERS,
+        ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_MAX_AGE,
+        CONTENT_ENCODING, CONTENT_TYPE, ORIGIN, VARY,
+    },
     http::StatusCode,
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response,
@@ -27,6 +38,26 @@ pub struct JsonRpcServerConfig {
     pub max_requests_per_second: u32,
     pub max_concurrent_per_ip: usize,
     pub max_total_concurrent: usize,
+    /// Gzip responses for clients that send `Accept-Encoding: gzip`
+    pub enable_compression: bool,
+    /// Responses smaller than this are sent uncompressed
+    pub compress_min_bytes: usize,
+    /// Origins allowed to call `/rpc` from a browser; `*` allows any.
+    /// Empty disables CORS handling.
+    pub allowed_origins: Vec<String>,
+    /// Longest a handler may run before the call fails with "Handler timed
+    /// out"; `None` waits forever. Override per method with
+    /// [`JsonRpcServer::set_method_timeout`].
+    pub handler_timeout: Option<Duration>,
+    /// Set `TCP_NODELAY` on accepted connections so small responses are not
+    /// held back by Nagle's algorithm
+    pub tcp_nodelay: bool,
+    /// TCP keep-alive interval for accepted connections; `None` leaves it off
+    pub tcp_keepalive: Option<Duration>,
+    /// Shut down gracefully once no request has been handled for this long
+    /// and no connection is active; `None` serves until the shutdown future
+    /// completes
+    pub idle_shutdown: Option<Duration>,
 }
 
 impl Default for JsonRpcServerConfig {
@@ -35,6 +66,13 @@ impl Default for JsonRpcServerConfig {
             max_requests_per_second: 2_000,
             max_concurrent_per_ip: 2_000,
             max_total_concurrent: 20_000,
+            enable_compression: true,
+            compress_min_bytes: 1024,
+            allowed_origins: Vec::new(),
+            handler_timeout: None,
+            tcp_nodelay: false,
+            tcp_keepalive: None,
+            idle_shutdown: None,
         }
     }
 }
@@ -49,8 +87,14 @@ struct JsonRpcServerInner {
     handlers: HandlerRegistry,
     connections: ConnectionManager,
     rate_limiter: RateLimiter,
+    /// When the last request finished, or serving started
+    last_activity: Mutex<Instant>,
 }
 
+/// Longest the idle watchdog sleeps before re-checking while a connection
+/// is still active
+const IDLE_RECHECK: Duration = Duration::from_millis(50);
+
 impl JsonRpcServer {
     pub fn new() -> Self {
         Self::with_config(JsonRpcServerConfig::default())
@@ -62,14 +106,17 @@ impl JsonRpcServer {
             max_per_ip: config.max_concurrent_per_ip,
         };
 
-        Self {
+        let server = Self {
             inner: Arc::new(JsonRpcServerInner {
-                handlers: HandlerRegistry::new(),
+                handlers: HandlerRegistry::with_default_timeout(config.handler_timeout),
                 connections: ConnectionManager::new(connection_config),
                 rate_limiter: RateLimiter::new(config.max_requests_per_second),
+                last_activity: Mutex::new(Instant::now()),
                 config,
             }),
-        }
+        };
+        server.register_config_handler();
+        server
     }
 
     pub fn config(&self) -> &JsonRpcServerConfig {
@@ -98,6 +145,72 @@ impl JsonRpcServer {
         self.inner.handlers.register_sync(method, func);
     }
 
+    /// Register a handler taking typed params and returning a serializable
+    /// result
+    ///
+    /// Params that don't deserialize into `P` (including missing params,
+    /// unless `P` accepts `null`) fail with `invalid_params` before `func`
+    /// runs.
+    pub fn register_typed<P, R, F>(&self, method: impl Into<String>, func: F)
+    where
+        P: DeserializeOwned,
+        R: Serialize,
+        F: Fn(P) -> Result<R, JsonRpcError> + Send + Sync + 'static,
+    {
+        let method = method.into();
+        let name = method.clone();
+        self.register_sync(method, move |params| {
+            let params = parse_params(&name, params)?;
+            to_result(func(params)?)
+        });
+    }
+
+    /// Async variant of [`JsonRpcServer::register_typed`]
+    pub fn register_typed_async<P, R, F, Fut>(&self, method: impl Into<String>, func: F)
+    where
+        P: DeserializeOwned,
+        R: Serialize,
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, JsonRpcError>> + Send + 'static,
+    {
+        let method = method.into();
+        let name = method.clone();
+        self.register_async(method, move |params| {
+            let call = parse_params(&name, params).map(&func);
+            async move { to_result(call?.await?) }
+        });
+    }
+
+    /// Register `rpc.connections`, which reports active connections in total
+    /// and per client IP (busiest first)
+    ///
+    /// Not registered by default since it reveals client addresses.
+    pub fn register_connections_handler(&self) {
+        let connections = self.inner.connections.clone();
+        self.register_sync("rpc.connections", move |_| {
+            let per_ip: Vec<serde_json::Value> = connections
+                .per_ip_snapshot()
+                .into_iter()
+                .map(|(ip, count)| serde_json::json!({ "ip": ip.to_string(), "count": count }))
+                .collect();
+            Ok(serde_json::json!({
+                "total": connections.total(),
+                "perIp": per_ip,
+            }))
+        });
+    }
+
+    /// Register `rpc.config`, which reports the server's effective limits
+    fn register_config_handler(&self) {
+        let config = config_json(&self.inner.config);
+        self.register_sync("rpc.config", move |_| Ok(config.clone()));
+    }
+
+    /// Give `method` its own timeout instead of the configured default
+    pub fn set_method_timeout(&self, method: impl Into<String>, timeout: Duration) {
+        self.inner.handlers.set_timeout(method, timeout);
+    }
+
     pub async fn serve(&self, addr: SocketAddr) -> Result<(), ServerError> {
         self.serve_with_shutdown(addr, async { std::future::pending::<()>().await })
             .await
@@ -111,8 +224,9 @@ impl JsonRpcServer {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        let builder = hyper::Server::try_bind(&addr)?;
-        self.serve_from_builder(builder, shutdown).await
+        let listener =
+            std::net::TcpListener::bind(addr).map_err(|err| ServerError::bind(addr, err))?;
+        self.serve_on_listener(listener, shutdown).await
     }
 
     pub async fn serve_on_listener<F>(
@@ -148,43 +262,160 @@ impl JsonRpcServer {
             }
         });
 
+        self.touch();
+        let idle_shutdown = self.inner.config.idle_shutdown;
+        let watchdog = self.clone();
+        let shutdown = async move {
+            match idle_shutdown {
+                Some(window) => tokio::select! {
+                    _ = shutdown => {},
+                    _ = watchdog.wait_until_idle(window) => {
+                        tracing::info!("No requests for {:?}; shutting down", window);
+                    },
+                },
+                None => shutdown.await,
+            }
+        };
+
         builder
+            .tcp_nodelay(self.inner.config.tcp_nodelay)
+            .tcp_keepalive(self.inner.config.tcp_keepalive)
             .serve(make_service)
             .with_graceful_shutdown(shutdown)
             .await?;
         Ok(())
     }
 
+    /// Record activity, restarting the idle window
+    fn touch(&self) {
+        *self.inner.last_activity.lock() = Instant::now();
+    }
+
+    /// Resolve once `window` has passed since the last activity with no
+    /// connection active
+    async fn wait_until_idle(&self, window: Duration) {
+        loop {
+            let idle_for = self.inner.last_activity.lock().elapsed();
+            if idle_for >= window && self.inner.connections.total() == 0 {
+                return;
+            }
+            let remaining = window.saturating_sub(idle_for);
+            tokio::time::sleep(if remaining.is_zero() {
+                IDLE_RECHECK
+            } else {
+                remaining
+            })
+            .await;
+        }
+    }
+
     async fn handle_http_request(
         &self,
         req: Request<Body>,
         remote_addr: SocketAddr,
     ) -> Result<Response<Body>, Infallible> {
+        let cors_origin = self.cors_origin(req.headers());
+
+        if req.method() == Method::OPTIONS
+            && req.uri().path() == "/rpc"
+            && !self.inner.config.allowed_origins.is_empty()
+        {
+            return Ok(preflight_response(cors_origin));
+        }
+
+        let mut response = self.route_http_request(req, remote_addr).await;
+        if let Some(origin) = cors_origin {
+            let headers = response.headers_mut();
+            headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+            headers.append(VARY, HeaderValue::from_static("origin"));
+        }
+        Ok(response)
+    }
+
+    async fn route_http_request(
+        &self,
+        req: Request<Body>,
+        remote_addr: SocketAddr,
+    ) -> Response<Body> {
         if req.method() != Method::POST || req.uri().path() != "/rpc" {
-            return Ok(Response::builder()
+            return Response::builder()
                 .status(StatusCode::NOT_FOUND)
                 .body(Body::empty())
-                .expect("building 404 response"));
+                .expect("building 404 response");
         }
 
         let remote_ip = remote_addr.ip();
 
         if !self.inner.rate_limiter.allow(remote_ip) {
             let response = JsonRpcServerError::RateLimited.to_response(None);
-            return Ok(json_response(response));
+            return json_response(response);
         }
 
         let guard = match self.inner.connections.acquire(remote_ip) {
             Ok(guard) => guard,
             Err(ConnectionError::GlobalLimit) | Err(ConnectionError::PerIpLimit(_)) => {
                 let response = JsonRpcServerError::ConnectionLimit.to_response(None);
-                return Ok(json_response(response));
+                return json_response(response);
             }
         };
 
+        let gzip = self.inner.config.enable_compression && accepts_gzip(req.headers());
         let outcome = self.handle_json_rpc(req, remote_ip).await;
+        self.touch();
         drop(guard);
-        Ok(outcome)
+
+        if gzip {
+            return self.compress_response(outcome).await;
+        }
+        outcome
+    }
+
+    /// `Access-Control-Allow-Origin` value for the request's `Origin`, if
+    /// that origin is allowed
+    fn cors_origin(&self, headers: &HeaderMap) -> Option<HeaderValue> {
+        let allowed = &self.inner.config.allowed_origins;
+        if allowed.is_empty() {
+            return None;
+        }
+        let origin = headers.get(ORIGIN)?;
+        if allowed.iter().any(|a| a == "*") {
+            return Some(HeaderValue::from_static("*"));
+        }
+        let origin_str = origin.to_str().ok()?;
+        allowed
+            .iter()
+            .any(|a| a == origin_str)
+            .then(|| origin.clone())
+    }
+
+    /// Gzip the response body if it is at least `compress_min_bytes`
+    async fn compress_response(&self, response: Response<Body>) -> Response<Body> {
+        let (mut parts, body) = response.into_parts();
+        let bytes = match body::to_bytes(body).await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                let error = JsonRpcError::internal(format!("failed to read response: {err}"));
+                return json_response(JsonRpcResponse::error(None, error));
+            }
+        };
+
+        if bytes.len() < self.inner.config.compress_min_bytes {
+            return Response::from_parts(parts, Body::from(bytes));
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let compressed = match encoder.write_all(&bytes).and_then(|_| encoder.finish()) {
+            Ok(compressed) => compressed,
+            Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+        };
+
+        parts
+            .headers
+            .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        parts
+            .headers
+            .insert(VARY, HeaderValue::from_static("accept-encoding"));
+        Response::from_parts(parts, Body::from(compressed))
     }
 
     async fn handle_json_rpc(&self, req: Request<Body>, remote_ip: IpAddr) -> Response<Body> {
@@ -231,7 +462,8 @@ impl JsonRpcServer {
         };
 
         if let Err(err) = request.validate() {
-            let id = request.id.clone();
+            // An id we can't echo back is answered with a null id
+            let id = request.id.clone().filter(|_| request.has_valid_id());
             return json_response(JsonRpcResponse::error(id, err));
         }
 
@@ -267,6 +499,79 @@ impl JsonRpcServer {
     }
 }
 
+/// Effective configuration as reported by `rpc.config`
+///
+/// Fields are listed one by one rather than serializing the whole config,
+/// so anything secret added later stays out of the response by default.
+fn config_json(config: &JsonRpcServerConfig) -> serde_json::Value {
+    let millis = |duration: Option<Duration>| duration.map(|d| d.as_millis() as u64);
+    serde_json::json!({
+        "rpcPath": "/rpc",
+        "maxRequestsPerSecond": config.max_requests_per_second,
+        "maxConcurrentPerIp": config.max_concurrent_per_ip,
+        "maxTotalConcurrent": config.max_total_concurrent,
+        "enableCompression": config.enable_compression,
+        "compressMinBytes": config.compress_min_bytes,
+        "allowedOrigins": config.allowed_origins,
+        "handlerTimeoutMs": millis(config.handler_timeout),
+        "tcpNodelay": config.tcp_nodelay,
+        "tcpKeepaliveMs": millis(config.tcp_keepalive),
+        "idleShutdownMs": millis(config.idle_shutdown),
+    })
+}
+
+/// Deserialize `params` for `method`, treating missing params as `null`
+fn parse_params<P: DeserializeOwned>(
+    method: &str,
+    params: Option<serde_json::Value>,
+) -> Result<P, JsonRpcError> {
+    serde_json::from_value(params.unwrap_or(serde_json::Value::Null))
+        .map_err(|err| JsonRpcError::invalid_params(format!("invalid {method} parameters: {err}")))
+}
+
+fn to_result<R: Serialize>(result: R) -> Result<serde_json::Value, JsonRpcError> {
+    serde_json::to_value(result).map_err(|err| JsonRpcError::internal(err.to_string()))
+}
+
+/// Answer a CORS preflight; disallowed origins get 403 without CORS headers
+fn preflight_response(origin: Option<HeaderValue>) -> Response<Body> {
+    let Some(origin) = origin else {
+        return Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::empty())
+            .expect("building preflight response");
+    };
+
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .header(ACCESS_CONTROL_ALLOW_ORIGIN, origin)
+        .header(ACCESS_CONTROL_ALLOW_METHODS, "POST, OPTIONS")
+        .header(ACCESS_CONTROL_ALLOW_HEADERS, "content-type")
+        .header(ACCESS_CONTROL_MAX_AGE, "600")
+        .header(VARY, "origin")
+        .body(Body::empty())
+        .expect("building preflight response")
+}
+
+/// Whether `Accept-Encoding` lists gzip with a non-zero quality
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|coding| {
+            let mut params = coding.split(';').map(str::trim);
+            let name = params.next().unwrap_or_default();
+            let rejected = params.any(|p| {
+                p.strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            name.eq_ignore_ascii_case("gzip") && !rejected
+        })
+}
+
 fn json_response(response: JsonRpcResponse) -> Response<Body> {
     let payload = serde_json::to_vec(&response).expect("serializing JSON-RPC response");
     Response::builder()
@@ -303,6 +608,7 @@ mod tests {
             max_requests_per_second: 0,
             max_concurrent_per_ip: 10,
             max_total_concurrent: 10,
+            ..JsonRpcServerConfig::default()
         }
     }
 
@@ -322,12 +628,246 @@ mod tests {
         serde_json::from_slice(&bytes).expect("valid json")
     }
 
+    fn gzip_request(body: &str) -> Request<Body> {
+        let mut request = build_request(Body::from(body.to_string()));
+        request
+            .headers_mut()
+            .insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
+        request
+    }
+
+    fn large_payload_server() -> JsonRpcServer {
+        let server = JsonRpcServer::with_config(test_config());
+        server.register_sync("trace.large", |_| Ok(json!({"blob": "x".repeat(4096)})));
+        server.register_sync("trace.small", |_| Ok(json!({"ok": true})));
+        server
+    }
+
+    fn gunzip(bytes: &[u8]) -> Value {
+        let mut decoder = flate2::read::GzDecoder::new(bytes);
+        let mut json = String::new();
+        std::io::Read::read_to_string(&mut decoder, &mut json).expect("valid gzip");
+        serde_json::from_str(&json).expect("valid json")
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__large_response_with_accept_gzip__then_compressed() {
+        let server = large_payload_server();
+
+        let response = server
+            .handle_http_request(
+                gzip_request(r#"{"jsonrpc":"2.0","method":"trace.large","id":1}"#),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let bytes = to_bytes(response.into_body()).await.expect("body");
+        assert!(bytes.len() < 4096);
+        assert_eq!(
+            gunzip(&bytes)["result"]["blob"].as_str().unwrap().len(),
+            4096
+        );
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__large_response_without_accept_gzip__then_plain() {
+        let server = large_payload_server();
+
+        let response = server
+            .handle_http_request(
+                build_request(Body::from(
+                    r#"{"jsonrpc":"2.0","method":"trace.large","id":1}"#,
+                )),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        let payload = parse_body(response).await;
+        assert_eq!(payload["result"]["blob"].as_str().unwrap().len(), 4096);
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__small_response_or_compression_disabled__then_plain() {
+        let server = large_payload_server();
+        let small = server
+            .handle_http_request(
+                gzip_request(r#"{"jsonrpc":"2.0","method":"trace.small","id":1}"#),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert!(small.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(parse_body(small).await["result"]["ok"], true);
+
+        let disabled = JsonRpcServer::with_config(JsonRpcServerConfig {
+            enable_compression: false,
+            ..test_config()
+        });
+        disabled.register_sync("trace.large", |_| Ok(json!({"blob": "x".repeat(4096)})));
+        let response = disabled
+            .handle_http_request(
+                gzip_request(r#"{"jsonrpc":"2.0","method":"trace.large","id":1}"#),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+    }
+
+    fn cors_server(allowed_origins: &[&str]) -> JsonRpcServer {
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            allowed_origins: allowed_origins.iter().map(|o| o.to_string()).collect(),
+            ..test_config()
+        });
+        server.register_sync("trace.small", |_| Ok(json!({"ok": true})));
+        server
+    }
+
+    fn request_with_origin(method: Method, origin: &'static str) -> Request<Body> {
+        let body = if method == Method::POST {
+            Body::from(r#"{"jsonrpc":"2.0","method":"trace.small","id":1}"#)
+        } else {
+            Body::empty()
+        };
+        let mut request = build_request(body);
+        *request.method_mut() = method;
+        request
+            .headers_mut()
+            .insert(ORIGIN, HeaderValue::from_static(origin));
+        request
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__cors_preflight_allowed_origin__then_allow_headers() {
+        let server = cors_server(&["http://localhost:5173"]);
+
+        let response = server
+            .handle_http_request(
+                request_with_origin(Method::OPTIONS, "http://localhost:5173"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let headers = response.headers();
+        assert_eq!(
+            headers[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:5173"
+        );
+        assert!(headers[ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap()
+            .contains("POST"));
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_HEADERS], "content-type");
+
+        let rejected = server
+            .handle_http_request(
+                request_with_origin(Method::OPTIONS, "http://evil.example"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert_eq!(rejected.status(), StatusCode::FORBIDDEN);
+        assert!(rejected
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__cors_allowed_vs_disallowed_origin__then_echoes_only_allowed() {
+        let server = cors_server(&["http://localhost:5173"]);
+
+        let allowed = server
+            .handle_http_request(
+                request_with_origin(Method::POST, "http://localhost:5173"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert_eq!(
+            allowed.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:5173"
+        );
+        assert_eq!(parse_body(allowed).await["result"]["ok"], true);
+
+        let disallowed = server
+            .handle_http_request(
+                request_with_origin(Method::POST, "http://evil.example"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert!(disallowed
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+        // The request itself is still served; the browser enforces CORS
+        assert_eq!(parse_body(disallowed).await["result"]["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__cors_wildcard_or_disabled__then_any_or_no_headers() {
+        let wildcard = cors_server(&["*"]);
+        let response = wildcard
+            .handle_http_request(
+                request_with_origin(Method::POST, "http://anything.example"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+
+        let disabled = cors_server(&[]);
+        let preflight = disabled
+            .handle_http_request(
+                request_with_origin(Method::OPTIONS, "http://localhost:5173"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert_eq!(preflight.status(), StatusCode::NOT_FOUND);
+        let response = disabled
+            .handle_http_request(
+                request_with_origin(Method::POST, "http://localhost:5173"),
+                remote_addr(),
+            )
+            .await
+            .expect("response");
+        assert!(response
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[test]
+    fn accepts_gzip__header_variants__then_respects_quality() {
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(ACCEPT_ENCODING, HeaderValue::from_static(value));
+            headers
+        };
+
+        assert!(accepts_gzip(&headers("gzip")));
+        assert!(accepts_gzip(&headers("br, GZIP;q=0.5")));
+        assert!(!accepts_gzip(&headers("gzip;q=0")));
+        assert!(!accepts_gzip(&headers("deflate")));
+        assert!(!accepts_gzip(&HeaderMap::new()));
+    }
+
     #[test]
     fn json_rpc_server__config_getter__then_returns_config() {
         let config = JsonRpcServerConfig {
             max_requests_per_second: 42,
             max_concurrent_per_ip: 24,
             max_total_concurrent: 100,
+            ..JsonRpcServerConfig::default()
         };
         let server = JsonRpcServer::with_config(config.clone());
 
@@ -337,6 +877,69 @@ mod tests {
         assert_eq!(retrieved.max_total_concurrent, 100);
     }
 
+    #[derive(serde::Deserialize)]
+    struct AddParams {
+        a: i64,
+        b: i64,
+    }
+
+    #[derive(serde::Serialize)]
+    struct AddResult {
+        sum: i64,
+    }
+
+    fn add(params: AddParams) -> Result<AddResult, JsonRpcError> {
+        Ok(AddResult {
+            sum: params.a + params.b,
+        })
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__register_typed__then_params_deserialized_and_result_serialized() {
+        let server = JsonRpcServer::new();
+        server.register_typed("math.add", add);
+        server.register_typed_async(
+            "math.add_async",
+            |params: AddParams| async move { add(params) },
+        );
+        let registry = server.handler_registry();
+
+        for method in ["math.add", "math.add_async"] {
+            let result = registry
+                .call(method, Some(json!({ "a": 2, "b": 3 })))
+                .await
+                .expect(method);
+            assert_eq!(result, json!({ "sum": 5 }), "{method}");
+        }
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__register_typed_malformed_params__then_invalid_params() {
+        let server = JsonRpcServer::new();
+        server.register_typed("math.add", add);
+        server.register_typed_async(
+            "math.add_async",
+            |params: AddParams| async move { add(params) },
+        );
+        let registry = server.handler_registry();
+
+        for method in ["math.add", "math.add_async"] {
+            for params in [Some(json!({ "a": "two", "b": 3 })), Some(json!([1])), None] {
+                let err = registry
+                    .call(method, params.clone())
+                    .await
+                    .expect_err("malformed params");
+                assert_eq!(err.code, -32602, "{method} {params:?}");
+                let details = err
+                    .data
+                    .as_ref()
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                assert!(details.contains(method), "{details}");
+            }
+        }
+    }
+
     #[test]
     fn json_rpc_server__handler_registry_getter__then_returns_registry() {
         let server = JsonRpcServer::new();
@@ -392,6 +995,45 @@ mod tests {
         assert!(handle.is_finished());
     }
 
+    #[tokio::test]
+    async fn json_rpc_server__tcp_nodelay_and_keepalive__then_serves_request() {
+        use hyper::{Client, StatusCode};
+
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            tcp_nodelay: true,
+            tcp_keepalive: Some(Duration::from_secs(30)),
+            ..test_config()
+        });
+        server.register_sync("test", |_| Ok(json!({"result": "ok"})));
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let _ = server
+                .serve_on_listener(listener, async move {
+                    let _ = rx.await;
+                })
+                .await;
+        });
+
+        let uri = format!("http://{}/rpc", addr)
+            .parse::<hyper::Uri>()
+            .expect("uri");
+        let request = hyper::Request::post(uri)
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(
+                r#"{"jsonrpc":"2.0","method":"test","id":1}"#,
+            ))
+            .expect("request");
+        let response = Client::new().request(request).await.expect("response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(parse_body(response).await["result"]["result"], "ok");
+        let _ = tx.send(());
+        let _ = handle.await;
+    }
+
     #[tokio::test]
     async fn json_rpc_server__serve_with_shutdown_via_addr__then_serves() {
         use hyper::{Client, StatusCode};
@@ -459,6 +1101,7 @@ mod tests {
             max_requests_per_second: 1,
             max_concurrent_per_ip: 10,
             max_total_concurrent: 10,
+            ..JsonRpcServerConfig::default()
         });
         let body = build_request(Body::from(
             r#"{"jsonrpc":"2.0","method":"trace.info","id":1}"#,
@@ -489,6 +1132,7 @@ mod tests {
             max_requests_per_second: 0,
             max_concurrent_per_ip: 1,
             max_total_concurrent: 1,
+            ..JsonRpcServerConfig::default()
         });
         let ip = localhost();
         let guard = server
@@ -516,6 +1160,77 @@ mod tests {
         drop(guard);
     }
 
+    #[tokio::test]
+    async fn json_rpc_server__rpc_connections__then_reports_counts_per_ip() {
+        let server = JsonRpcServer::with_config(test_config());
+        server.register_connections_handler();
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7));
+        let _held = server.inner.connections.acquire(other).expect("acquire");
+
+        let response = server
+            .handle_http_request(
+                build_request(Body::from(
+                    r#"{"jsonrpc":"2.0","method":"rpc.connections","id":1}"#,
+                )),
+                remote_addr(),
+            )
+            .await
+            .expect("http response");
+
+        // The request's own connection counts while it is being handled
+        let payload = parse_body(response).await;
+        assert_eq!(payload["result"]["total"], 2);
+        let per_ip = payload["result"]["perIp"].as_array().expect("perIp");
+        assert_eq!(per_ip.len(), 2);
+        assert!(per_ip.contains(&json!({ "ip": "10.0.0.7", "count": 1 })));
+        assert!(per_ip.contains(&json!({ "ip": "127.0.0.1", "count": 1 })));
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__rpc_config__then_reports_constructed_config_without_secrets() {
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            max_requests_per_second: 42,
+            max_concurrent_per_ip: 24,
+            max_total_concurrent: 100,
+            allowed_origins: vec!["http://localhost:3000".to_string()],
+            handler_timeout: Some(Duration::from_secs(5)),
+            idle_shutdown: None,
+            ..JsonRpcServerConfig::default()
+        });
+
+        let response = server
+            .handle_http_request(
+                build_request(Body::from(
+                    r#"{"jsonrpc":"2.0","method":"rpc.config","id":1}"#,
+                )),
+                remote_addr(),
+            )
+            .await
+            .expect("http response");
+
+        let payload = parse_body(response).await;
+        let config = &payload["result"];
+        assert_eq!(
+            *config,
+            json!({
+                "rpcPath": "/rpc",
+                "maxRequestsPerSecond": 42,
+                "maxConcurrentPerIp": 24,
+                "maxTotalConcurrent": 100,
+                "enableCompression": true,
+                "compressMinBytes": 1024,
+                "allowedOrigins": ["http://localhost:3000"],
+                "handlerTimeoutMs": 5000,
+                "tcpNodelay": false,
+                "tcpKeepaliveMs": null,
+                "idleShutdownMs": null,
+            })
+        );
+        let text = config.to_string().to_lowercase();
+        assert!(!text.contains("auth"));
+        assert!(!text.contains("token"));
+    }
+
     #[tokio::test]
     async fn json_rpc_server__empty_body__then_invalid_request_error() {
         let server = JsonRpcServer::with_config(test_config());
@@ -591,6 +1306,29 @@ mod tests {
         assert_eq!(payload["error"]["data"], "method must not be empty");
     }
 
+    #[tokio::test]
+    async fn json_rpc_server__object_id__then_invalid_request_with_null_id() {
+        let server = JsonRpcServer::with_config(test_config());
+        server.register_sync("trace.echo", |_| Ok(json!("ok")));
+        let response = server
+            .handle_http_request(
+                build_request(Body::from(
+                    r#"{"jsonrpc":"2.0","method":"trace.echo","id":{}}"#,
+                )),
+                remote_addr(),
+            )
+            .await
+            .expect("http response");
+
+        let payload = parse_body(response).await;
+        assert_eq!(payload["error"]["code"], -32600);
+        assert_eq!(
+            payload["error"]["data"],
+            "id must be a string, number, or null"
+        );
+        assert_eq!(payload["id"], Value::Null);
+    }
+
     #[tokio::test]
     async fn json_rpc_server__notification_request__then_returns_no_content() {
         let server = JsonRpcServer::with_config(test_config());
@@ -658,6 +1396,74 @@ mod tests {
         assert_eq!(payload["id"], 9);
     }
 
+    #[tokio::test]
+    async fn json_rpc_server__handler_exceeds_timeout__then_timeout_error_and_slot_released() {
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            max_concurrent_per_ip: 1,
+            max_total_concurrent: 1,
+            handler_timeout: Some(Duration::from_millis(20)),
+            ..test_config()
+        });
+        server.register_async("trace.hang", |_params| async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(json!(null))
+        });
+        server.register_sync("trace.echo", |_| Ok(json!("ok")));
+
+        let response = server
+            .handle_http_request(
+                build_request(Body::from(
+                    r#"{"jsonrpc":"2.0","method":"trace.hang","id":3}"#,
+                )),
+                remote_addr(),
+            )
+            .await
+            .expect("http response");
+
+        let payload = parse_body(response).await;
+        assert_eq!(payload["error"]["code"], -32004);
+        assert_eq!(payload["error"]["message"], "Handler timed out");
+        assert_eq!(payload["id"], 3);
+
+        // The only connection slot is free again
+        let response = server
+            .handle_http_request(
+                build_request(Body::from(
+                    r#"{"jsonrpc":"2.0","method":"trace.echo","id":4}"#,
+                )),
+                remote_addr(),
+            )
+            .await
+            .expect("http response");
+        assert_eq!(parse_body(response).await["result"], "ok");
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__slow_notification__then_bounded_by_timeout() {
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            handler_timeout: Some(Duration::from_secs(30)),
+            ..test_config()
+        });
+        server.register_async("trace.hang", |_params| async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(json!(null))
+        });
+        server.set_method_timeout("trace.hang", Duration::from_millis(20));
+
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            server.handle_http_request(
+                build_request(Body::from(r#"{"jsonrpc":"2.0","method":"trace.hang"}"#)),
+                remote_addr(),
+            ),
+        )
+        .await
+        .expect("notification should not hang")
+        .expect("http response");
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
     #[tokio::test]
     async fn json_rpc_server__unknown_method__then_returns_method_not_found() {
         let server = JsonRpcServer::with_config(test_config());
@@ -718,6 +1524,71 @@ mod tests {
             .expect("server should bind and exit");
     }
 
+    #[tokio::test]
+    async fn json_rpc_server__serve_with_shutdown_port_taken__then_address_in_use() {
+        let server = JsonRpcServer::with_config(test_config());
+        let listener = TcpListener::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+            .expect("listener should bind");
+        let addr = listener.local_addr().expect("local addr");
+
+        let err = server
+            .serve_with_shutdown(addr, async {})
+            .await
+            .expect_err("port is taken");
+
+        assert!(
+            matches!(err, ServerError::AddressInUse(a) if a == addr),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__idle_shutdown_elapsed__then_stops_on_its_own() {
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            idle_shutdown: Some(Duration::from_millis(100)),
+            ..test_config()
+        });
+        let listener = TcpListener::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))
+            .expect("listener should bind");
+        let started = std::time::Instant::now();
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            server.serve_on_listener(listener, std::future::pending::<()>()),
+        )
+        .await
+        .expect("server should stop after the idle window")
+        .expect("serve should exit cleanly");
+
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn json_rpc_server__active_connection__then_idle_watchdog_waits() {
+        let server = JsonRpcServer::with_config(JsonRpcServerConfig {
+            idle_shutdown: Some(Duration::from_millis(20)),
+            ..test_config()
+        });
+        let guard = server
+            .inner
+            .connections
+            .acquire(localhost())
+            .expect("acquire");
+        let watchdog = tokio::spawn({
+            let server = server.clone();
+            async move { server.wait_until_idle(Duration::from_millis(20)).await }
+        });
+
+        sleep(Duration::from_millis(100)).await;
+        assert!(!watchdog.is_finished());
+
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(5), watchdog)
+            .await
+            .expect("watchdog should fire once the connection closes")
+            .expect("watchdog task");
+    }
+
     #[tokio::test]
     async fn json_rpc_server__serve_on_listener__then_returns_ok() {
         let server = JsonRpcServer::with_config(test_config());
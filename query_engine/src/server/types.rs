@@ -21,8 +21,18 @@ impl JsonRpcRequest {
         if self.method.trim().is_empty() {
             return Err(JsonRpcError::invalid_request("method must not be empty"));
         }
+        if !self.has_valid_id() {
+            return Err(JsonRpcError::invalid_request(
+                "id must be a string, number, or null",
+            ));
+        }
         Ok(())
     }
+
+    /// Whether `id` is absent, null, a string or a number
+    pub fn has_valid_id(&self) -> bool {
+        !matches!(self.id, Some(Value::Object(_)) | Some(Value::Array(_)))
+    }
 }
 
 /// JSON-RPC 2.0 response as defined by the specification.
@@ -127,6 +137,14 @@ impl JsonRpcError {
     pub fn too_many_connections() -> Self {
         Self::new(-32002, "Too many concurrent connections", None)
     }
+
+    pub fn handler_timed_out(method: &str) -> Self {
+        Self::new(
+            -32004,
+            "Handler timed out",
+            Some(Value::String(method.to_string())),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -184,6 +202,43 @@ mod tests {
         );
     }
 
+    fn request_with_id(id: Option<Value>) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "trace.info".to_string(),
+            params: None,
+            id,
+        }
+    }
+
+    #[test]
+    fn json_rpc_types__validate_with_object_or_array_id__then_error() {
+        for id in [json!({}), json!({"n": 1}), json!([1])] {
+            let err = request_with_id(Some(id.clone()))
+                .validate()
+                .expect_err("expected validation error");
+
+            assert_eq!(err.code, -32600, "id {id}");
+            assert_eq!(
+                err.data,
+                Some(Value::String("id must be a string, number, or null".into()))
+            );
+        }
+    }
+
+    #[test]
+    fn json_rpc_types__validate_with_scalar_or_missing_id__then_ok() {
+        for id in [
+            Some(json!("abc")),
+            Some(json!(7)),
+            Some(json!(1.5)),
+            Some(Value::Null),
+            None,
+        ] {
+            assert!(request_with_id(id.clone()).validate().is_ok(), "id {id:?}");
+        }
+    }
+
     #[test]
     fn json_rpc_types__response_success_constructor__then_sets_result() {
         let response = JsonRpcResponse::success(Some(json!(1)), json!({"ok": true}));
@@ -258,6 +313,11 @@ mod tests {
             "Too many concurrent connections"
         );
         assert!(too_many_connections.data.is_none());
+
+        let timed_out = JsonRpcError::handler_timed_out("trace.slow");
+        assert_eq!(timed_out.code, -32004);
+        assert_eq!(timed_out.message, "Handler timed out");
+        assert_eq!(timed_out.data, Some(Value::String("trace.slow".into())));
     }
 
     #[test]
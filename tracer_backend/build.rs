@@ -1119,6 +1119,7 @@ fn generate_bindings(out_dir: &Path) {
                 .allowlist_type("IndexEvent")
                 .allowlist_type("DetailEvent")
                 .allowlist_type("TracerStats")
+                .allowlist_type("ThreadStats")
                 .allowlist_function("frida_controller_.*")
                 .allowlist_function("shared_memory_.*")
                 .allowlist_function("ring_buffer_.*")
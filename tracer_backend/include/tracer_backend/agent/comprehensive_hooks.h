@@ -6,6 +6,7 @@
 #define ADA_COMPREHENSIVE_HOOKS_H
 
 #include <cstdint>
+#include <regex>
 #include <string>
 #include <vector>
 #include <utility>
@@ -25,17 +26,35 @@ struct HookPlanEntry {
     uint64_t function_id;
 };
 
+// User-supplied include/exclude patterns, matched against both the symbol
+// name and the module's file name.
+// - include: when non-empty, a symbol must match at least one pattern
+// - exclude: a symbol matching any pattern is skipped
+struct HookFilter {
+    std::vector<std::regex> include;
+    std::vector<std::regex> exclude;
+
+    // Compile and add a pattern; returns false (and adds nothing) if invalid.
+    bool add_include(const std::string& pattern);
+    bool add_exclude(const std::string& pattern);
+
+    bool empty() const { return include.empty() && exclude.empty(); }
+    bool allows(const std::string& module_path, const std::string& symbol) const;
+};
+
 // Plan hooks for a single module, given its exported symbol names.
 // - module_path: the path or logical name of the DSO
 // - exports: list of exported symbol names
 // - excludes: optional exclude list (can be nullptr)
 // - registry: used to assign stable function ids
+// - filter: optional user hook filter (can be nullptr)
 // Returns: vector of HookPlanEntry for symbols that are not excluded.
 std::vector<HookPlanEntry> plan_module_hooks(
     const std::string& module_path,
     const std::vector<std::string>& exports,
     AdaExcludeList* excludes,
-    HookRegistry& registry);
+    HookRegistry& registry,
+    const HookFilter* filter = nullptr);
 
 // Plan hooks for main binary and a set of DSOs.
 // - main_exports: export names from the main binary
@@ -20,10 +20,48 @@ int frida_controller_spawn_suspended(FridaController* controller,
                                      const char* path, 
                                      char* const argv[],
                                      uint32_t* out_pid);
+// Like frida_controller_spawn_suspended, with extra "KEY=VALUE" entries
+// (NULL-terminated; may be NULL) added to the child's environment. They
+// replace inherited defaults of the same name, except ADA_SHM_* variables.
+int frida_controller_spawn_suspended_env(FridaController* controller,
+                                         const char* path,
+                                         char* const argv[],
+                                         char* const envp[],
+                                         uint32_t* out_pid);
+// Redirect the stdout/stderr of subsequently spawned processes to the given
+// file descriptors (-1 keeps inheriting the tracer's). The descriptors are
+// duplicated, so the caller may close its copies after this returns.
+int frida_controller_set_stdio_redirect(FridaController* controller,
+                                        int stdout_fd,
+                                        int stderr_fd);
 int frida_controller_attach(FridaController* controller, uint32_t pid);
 int frida_controller_detach(FridaController* controller);
 int frida_controller_resume(FridaController* controller);
 int frida_controller_pause(FridaController* controller);
+// Wait up to timeout_ms for the spawned/attached process to exit. Returns 1
+// once it has exited, with *out_exit_code set to its exit status (128 +
+// signal for a killed process, -1 if the status couldn't be collected because
+// the process isn't our child, as with a Frida-spawned process on macOS), 0
+// on timeout, and a negative FRIDA_CONTROLLER_ERROR status on error.
+int frida_controller_wait(FridaController* controller,
+                          uint32_t timeout_ms,
+                          int* out_exit_code);
+
+/**
+ * @brief Restrict which functions install_hooks() will hook
+ *
+ * @param controller the FridaController instance
+ * @param include NULL-terminated regex patterns (may be NULL); when non-empty,
+ *        only symbols whose name or module name matches one are hooked
+ * @param exclude NULL-terminated regex patterns (may be NULL); symbols whose
+ *        name or module name matches one are never hooked
+ * @return int 0 on success, -1 on failure (including after install_hooks)
+ *
+ * Patterns use ECMAScript regex syntax and are searched (not anchored).
+ */
+int frida_controller_set_hook_filter(FridaController* controller,
+                                     const char* const include[],
+                                     const char* const exclude[]);
 
 /**
  * @brief Install hooks into the target process
@@ -55,12 +93,23 @@ int frida_controller_arm_trigger(FridaController* controller,
                                  uint32_t post_roll_ms);
 int frida_controller_fire_trigger(FridaController* controller);
 int frida_controller_disarm_trigger(FridaController* controller);
+// Cap each detail ring's pre-roll while armed; the drain thread evicts the
+// oldest events beyond either limit. 0 leaves that limit unbounded.
+int frida_controller_set_flight_buffer_limit(FridaController* controller,
+                                             uint64_t max_bytes,
+                                             uint64_t max_events);
 int frida_controller_set_detail_enabled(FridaController* controller, uint32_t enabled);
 int frida_controller_start_session(FridaController* controller);
 int frida_controller_stop_session(FridaController* controller);
 
 // Statistics
 TracerStats frida_controller_get_stats(FridaController* controller);
+// Fill out[0..capacity) with per-thread statistics for the registered
+// threads. Returns the number of entries written; threads beyond capacity
+// are left out. Returns -1 on error.
+int frida_controller_get_thread_stats(FridaController* controller,
+                                      ThreadStats* out,
+                                      uint32_t capacity);
 
 // State query
 ProcessState frida_controller_get_state(FridaController* controller);
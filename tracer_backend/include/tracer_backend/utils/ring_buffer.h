@@ -72,6 +72,11 @@ size_t ring_buffer_read_batch_raw(RingBufferHeader* header, size_t event_size, v
 size_t ring_buffer_available_read_raw(RingBufferHeader* header);
 size_t ring_buffer_available_write_raw(RingBufferHeader* header);
 
+// Consumer-side: drop the oldest events until at most `keep` remain and
+// return how many were dropped. Like the reads, only the ring's single
+// consumer may call this; the producer must never advance read_pos.
+size_t ring_buffer_evict_oldest_raw(RingBufferHeader* header, size_t keep);
+
 #ifdef __cplusplus
 }
 #endif
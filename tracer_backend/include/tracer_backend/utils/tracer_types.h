@@ -164,9 +164,27 @@ typedef struct {
     uint64_t mode_transitions;      // Number of mode transitions observed (agent/controller)
     uint64_t fallback_events;       // Number of fallbacks to global-only path
 
+    // Flight recorder pre-roll limits per detail ring (0 = unlimited).
+    // While armed the drain thread, as the rings' only consumer, evicts the
+    // oldest detail events to stay within them.
+    uint64_t preroll_max_bytes;
+    uint64_t preroll_max_events;
+    uint64_t preroll_evicted;       // Detail events evicted to honour the limits
+
     ShmDirectory shm_directory;
 } ControlBlock;
 
+// Status codes returned by the int-returning frida_controller_* calls.
+// Any other negative value is an unclassified failure.
+typedef enum {
+    FRIDA_CONTROLLER_OK = 0,
+    FRIDA_CONTROLLER_ERROR = -1,                   // Generic failure
+    FRIDA_CONTROLLER_ERROR_ALREADY_ATTACHED = -2,  // attach() while attached
+    FRIDA_CONTROLLER_ERROR_PERMISSION_DENIED = -3, // OS refused spawn/attach/resume
+    FRIDA_CONTROLLER_ERROR_NO_SUCH_PROCESS = -4,   // Target pid doesn't exist
+    FRIDA_CONTROLLER_ERROR_NOT_SUSPENDED = -5,     // resume() with nothing to resume
+} FridaControllerStatus;
+
 // Statistics
 #ifndef TRACER_STATS_DEFINED
 #define TRACER_STATS_DEFINED
@@ -177,9 +195,21 @@ typedef struct {
     uint32_t active_threads;
     uint32_t hooks_installed;
     uint64_t fallback_events;
+    uint64_t preroll_evicted;
 } TracerStats;
 #endif
 
+// Per-thread statistics (one entry per registered thread)
+#ifndef THREAD_STATS_DEFINED
+#define THREAD_STATS_DEFINED
+typedef struct {
+    uint64_t tid;
+    uint64_t events_captured;
+    uint64_t events_dropped;
+    uint64_t last_event_ns;   // Monotonic time of the thread's last ring swap (0 if none)
+} ThreadStats;
+#endif
+
 // ============================================================================
 // Thread Registry - Opaque types for public API
 // ============================================================================
@@ -253,8 +253,8 @@ uint64_t platform_get_timestamp();
 // Safe stack capture with signal handling
 size_t safe_stack_capture(void* dest, void* stack_ptr, size_t max_size);
 
-// Parse initialization payload
-void parse_init_payload(const char* data, int data_size, 
+// Parse initialization payload; false if a hook filter pattern doesn't compile
+bool parse_init_payload(const char* data, int data_size, 
                        uint32_t* out_host_pid, uint32_t* out_session_id);
 
 } // namespace internal
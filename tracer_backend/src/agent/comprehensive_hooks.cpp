@@ -15,17 +15,55 @@ static inline bool is_excluded(AdaExcludeList* xs, const std::string& name) {
     return ada_exclude_contains(xs, name.c_str());
 }
 
+static bool compile_pattern(const std::string& pattern, std::vector<std::regex>& out) {
+    try {
+        out.emplace_back(pattern, std::regex::ECMAScript | std::regex::optimize);
+        return true;
+    } catch (const std::regex_error&) {
+        return false;
+    }
+}
+
+static bool matches_any(const std::vector<std::regex>& patterns,
+                        const std::string& module_name,
+                        const std::string& symbol) {
+    return std::any_of(patterns.begin(), patterns.end(), [&](const std::regex& re) {
+        return std::regex_search(symbol, re) || std::regex_search(module_name, re);
+    });
+}
+
+bool HookFilter::add_include(const std::string& pattern) {
+    return compile_pattern(pattern, include);
+}
+
+bool HookFilter::add_exclude(const std::string& pattern) {
+    return compile_pattern(pattern, exclude);
+}
+
+bool HookFilter::allows(const std::string& module_path, const std::string& symbol) const {
+    const size_t slash = module_path.find_last_of('/');
+    const std::string module_name =
+        slash == std::string::npos ? module_path : module_path.substr(slash + 1);
+
+    if (!include.empty() && !matches_any(include, module_name, symbol)) {
+        return false;
+    }
+    return !matches_any(exclude, module_name, symbol);
+}
+
 std::vector<HookPlanEntry> plan_module_hooks(
     const std::string& module_path,
     const std::vector<std::string>& exports,
     AdaExcludeList* excludes,
-    HookRegistry& registry) {
+    HookRegistry& registry,
+    const HookFilter* filter) {
 
     std::vector<HookPlanEntry> out;
     out.reserve(exports.size());
     for (const auto& sym : exports) {
         if (sym.empty()) continue;
         if (is_excluded(excludes, sym)) continue;
+        if (filter && !filter->allows(module_path, sym)) continue;
         uint64_t id = registry.register_symbol(module_path, sym);
         out.push_back(HookPlanEntry{sym, id});
     }
@@ -146,6 +146,7 @@ static void agent_log_cat(uint32_t category, const char* format, ...) {
 static uint32_t g_host_pid = UINT32_MAX;
 static uint32_t g_session_id = UINT32_MAX;
 static char g_exclude_csv[256] = {0};
+static ada::agent::HookFilter g_hook_filter;
 
 
 static volatile sig_atomic_t g_segfault_occurred = 0;
@@ -858,7 +859,7 @@ void AgentContext::install_hooks() {
     cap_symbol_names(main_symbol_names, symbol_limit);
 
     // Plan main hooks
-    auto main_plan = ada::agent::plan_module_hooks(effective_path ? effective_path : "<main>", main_symbol_names, xs, hook_registry_);
+    auto main_plan = ada::agent::plan_module_hooks(effective_path ? effective_path : "<main>", main_symbol_names, xs, hook_registry_, &g_hook_filter);
 
     // Build precise address lookup for main module
     // First use addresses from enumeration (works for local/internal symbols)
@@ -999,7 +1000,7 @@ void AgentContext::install_hooks() {
                 }
                 addr.emplace(e.name, a);
             }
-            auto plan = ada::agent::plan_module_hooks(path, names, xs, hook_registry_);
+            auto plan = ada::agent::plan_module_hooks(path, names, xs, hook_registry_, &g_hook_filter);
             [[maybe_unused]] int32_t plan_index = 0;
             for (const auto& pe : plan) {
                 LOG_HOOK_INSTALL("[Agent] (%d/%zu) Will attach DSO hook to %s\n", plan_index, plan.size(), pe.symbol.c_str());
@@ -1280,20 +1281,37 @@ size_t safe_stack_capture(void* dest, void* stack_ptr, size_t max_size) {
     return copied;
 }
 
-void parse_init_payload(const char* data, int data_size,
+// Reverse the controller's percent-encoding of a payload value
+static std::string decode_payload_value(const char* value) {
+    std::string out;
+    for (const char* p = value; *p; ++p) {
+        if (*p == '%' && isxdigit(static_cast<unsigned char>(p[1])) &&
+            isxdigit(static_cast<unsigned char>(p[2]))) {
+            const char hex[3] = {p[1], p[2], '\0'};
+            out.push_back(static_cast<char>(strtoul(hex, nullptr, 16)));
+            p += 2;
+        } else {
+            out.push_back(*p);
+        }
+    }
+    return out;
+}
+
+bool parse_init_payload(const char* data, int data_size,
                        uint32_t* out_host_pid, uint32_t* out_session_id) {
-    if (!data || data_size <= 0) return;
+    if (!data || data_size <= 0) return true;
 
     // Initialize outputs
     *out_host_pid = 0;
     *out_session_id = 0;
+    g_hook_filter = ada::agent::HookFilter{};
     
-    // Copy to a null-terminated buffer
-    char buf[256];
-    size_t copy_len = static_cast<size_t>(data_size) < sizeof(buf) - 1 ? 
-                     static_cast<size_t>(data_size) : sizeof(buf) - 1;
-    memcpy(buf, data, copy_len);
-    buf[copy_len] = '\0';
+    // Copy to a null-terminated buffer (hook filter patterns can make the
+    // payload arbitrarily long)
+    std::vector<char> storage(data, data + data_size);
+    storage.push_back('\0');
+    char* buf = storage.data();
+    size_t copy_len = strlen(buf);
     
     // Normalize separators to spaces
     for (size_t i = 0; i < copy_len; i++) {
@@ -1334,12 +1352,24 @@ void parse_init_payload(const char* data, int data_size,
             if (n >= sizeof(g_exclude_csv)) n = sizeof(g_exclude_csv) - 1;
             memcpy(g_exclude_csv, val, n);
             g_exclude_csv[n] = '\0';
+        } else if (strcmp(key, "hook_include") == 0 || strcmp(key, "hook_exclude") == 0) {
+            const std::string pattern = decode_payload_value(val);
+            const bool added = strcmp(key, "hook_include") == 0
+                                   ? g_hook_filter.add_include(pattern)
+                                   : g_hook_filter.add_exclude(pattern);
+            // Skipping the pattern could leave an empty include list, which
+            // hooks everything
+            if (!added) {
+                LOG_LIFECYCLE("[Agent] Invalid %s pattern: %s\n", key, pattern.c_str());
+                return false;
+            }
         }
     }
 
     // Debug output for parsing results
     LOG_LIFECYCLE("[Agent] Parsed init payload: host_pid=%u, session_id=0x%08x\n",
             *out_host_pid, *out_session_id);
+    return true;
 }
 
 // ============================================================================
@@ -1856,7 +1886,8 @@ uint32_t agent_estimate_hooks(void) {
     }
     size_t symbol_limit = ada::internal::main_symbol_limit();
     ada::internal::cap_symbol_names(main_symbol_names, symbol_limit);
-    auto main_plan = ada::agent::plan_module_hooks("<main>", main_symbol_names, xs, registry);
+    auto main_plan = ada::agent::plan_module_hooks("<main>", main_symbol_names, xs, registry,
+                                                 &ada::internal::g_hook_filter);
     for (const auto& entry : main_plan) {
         GumAddress addr = 0;
         // First: use address from enumeration (works for local symbols)
@@ -1896,7 +1927,8 @@ uint32_t agent_estimate_hooks(void) {
             for (auto& e : exps) {
                 names.push_back(e.name);
             }
-            auto plan = ada::agent::plan_module_hooks(path, names, xs, registry);
+            auto plan = ada::agent::plan_module_hooks(path, names, xs, registry,
+                                                    &ada::internal::g_hook_filter);
             count += static_cast<uint32_t>(plan.size());
         }
         g_object_unref(map);
@@ -1974,7 +2006,10 @@ void agent_init(const gchar* data, gint data_size) {
     
     // Parse initialization data
     uint32_t arg_host = 0, arg_sid = 0;
-    ada::internal::parse_init_payload(data, data_size, &arg_host, &arg_sid);
+    if (!ada::internal::parse_init_payload(data, data_size, &arg_host, &arg_sid)) {
+        LOG_LIFECYCLE("[Agent] Invalid init payload, not installing hooks\n");
+        return;
+    }
     ada::internal::g_host_pid = arg_host;
     ada::internal::g_session_id = arg_sid;
     
@@ -187,15 +187,19 @@ fn main() -> Result<()> {
     if final_stats.fallback_events > 0 {
         println!("Fallback events: {}", final_stats.fallback_events);
     }
+    if final_stats.preroll_evicted > 0 {
+        println!("Pre-roll evicted: {}", final_stats.preroll_evicted);
+    }
 
     // Write tracer_stats.json for benchmark harness consumption
     let stats_json = format!(
-        "{{\n  \"events_captured\": {},\n  \"events_dropped\": {},\n  \"bytes_written\": {},\n  \"hooks_installed\": {},\n  \"fallback_events\": {}\n}}\n",
+        "{{\n  \"events_captured\": {},\n  \"events_dropped\": {},\n  \"bytes_written\": {},\n  \"hooks_installed\": {},\n  \"fallback_events\": {},\n  \"preroll_evicted\": {}\n}}\n",
         final_stats.events_captured,
         final_stats.events_dropped,
         final_stats.bytes_written,
         final_stats.hooks_installed,
-        final_stats.fallback_events
+        final_stats.fallback_events,
+        final_stats.preroll_evicted
     );
     let stats_path = output_dir.join("tracer_stats.json");
     if let Err(e) = std::fs::write(&stats_path, &stats_json) {
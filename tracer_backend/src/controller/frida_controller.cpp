@@ -8,10 +8,14 @@ extern "C" {
 }
 #include "../utils/thread_registry_private.h"
 
+#include <algorithm>
+#include <cerrno>
 #include <cstring>
 #include <cstdio>
 #include <cstdlib>
 #include <unistd.h>
+#include <signal.h>
+#include <sys/wait.h>
 #include <chrono>
 #include <thread>
 #include <vector>
@@ -165,6 +169,39 @@ static gboolean on_script_load_timeout_cb(gpointer user_data) {
     return G_SOURCE_REMOVE;
 }
 
+// Status code for a failed Frida call
+static int status_for_frida_error(const GError* error) {
+    if (error && error->domain == FRIDA_ERROR) {
+        switch (error->code) {
+            case FRIDA_ERROR_PROCESS_NOT_FOUND:
+                return FRIDA_CONTROLLER_ERROR_NO_SUCH_PROCESS;
+            case FRIDA_ERROR_PERMISSION_DENIED:
+                return FRIDA_CONTROLLER_ERROR_PERMISSION_DENIED;
+            default:
+                break;
+        }
+    }
+    return FRIDA_CONTROLLER_ERROR;
+}
+
+// Percent-encode a payload value so it survives the agent's separator
+// splitting (';', ',', whitespace) and embedding in a quoted JS string
+static std::string encode_payload_value(const std::string& value) {
+    static const char* kHex = "0123456789ABCDEF";
+    std::string out;
+    out.reserve(value.size());
+    for (unsigned char c : value) {
+        if (isalnum(c) || strchr("-._~*^$()[]{}+?|/:<>@!#&", c)) {
+            out.push_back(static_cast<char>(c));
+        } else {
+            out.push_back('%');
+            out.push_back(kHex[c >> 4]);
+            out.push_back(kHex[c & 0x0F]);
+        }
+    }
+    return out;
+}
+
 } // namespace
 
 // ============================================================================
@@ -272,6 +309,10 @@ FridaController::~FridaController() {
     // Cleanup Frida objects
     cleanup_frida_objects();
 
+    // Close duplicated stdio redirect descriptors
+    if (stdout_fd_ >= 0) close(stdout_fd_);
+    if (stderr_fd_ >= 0) close(stderr_fd_);
+
     // Cleanup event loop
     if (main_loop_) {
         g_main_loop_quit(main_loop_);
@@ -483,6 +524,9 @@ bool FridaController::initialize_shared_memory() {
     control_block_->detail_lane_enabled = 1;
     control_block_->pre_roll_ms = 1000;
     control_block_->post_roll_ms = 1000;
+    control_block_->preroll_max_bytes = 0;
+    control_block_->preroll_max_events = 0;
+    control_block_->preroll_evicted = 0;
     // Init IPC fields to defaults
     cb_set_registry_ready(control_block_, 0);
     cb_set_registry_version(control_block_, 0);
@@ -584,6 +628,10 @@ void FridaController::cleanup_frida_objects() {
     }
     
     if (device_) {
+        if (output_handler_id_ != 0) {
+            g_signal_handler_disconnect(device_, output_handler_id_);
+            output_handler_id_ = 0;
+        }
         frida_unref(device_);
         device_ = nullptr;
     }
@@ -613,6 +661,11 @@ void FridaController::wait_for_debugger_if_needed() const {
 }
 
 int FridaController::spawn_suspended(const char* path, char* const argv[], uint32_t* out_pid) {
+    return spawn_suspended(path, argv, nullptr, out_pid);
+}
+
+int FridaController::spawn_suspended(const char* path, char* const argv[], char* const envp[],
+                                     uint32_t* out_pid) {
     printf("[Controller] Spawning process: %s\n", path);
     
     if (!path) {
@@ -662,10 +715,13 @@ int FridaController::spawn_suspended(const char* path, char* const argv[], uint3
 
     // Build envp array dynamically
     std::vector<const char*> envp_vec;
-    envp_vec.push_back(g_strdup_printf("PATH=%s", g_getenv("PATH")));
-    envp_vec.push_back(g_strdup_printf("HOME=%s", g_get_home_dir()));
-    envp_vec.push_back(g_strdup_printf("__CF_USER_TEXT_ENCODING=%s",
-                       g_getenv("__CF_USER_TEXT_ENCODING") ?: "0x1F5:0x0:0x0"));
+    char* owned[] = {
+        g_strdup_printf("PATH=%s", g_getenv("PATH")),
+        g_strdup_printf("HOME=%s", g_get_home_dir()),
+        g_strdup_printf("__CF_USER_TEXT_ENCODING=%s",
+                        g_getenv("__CF_USER_TEXT_ENCODING") ?: "0x1F5:0x0:0x0"),
+    };
+    envp_vec.insert(envp_vec.end(), std::begin(owned), std::end(owned));
     envp_vec.push_back(ada_session.c_str());
     envp_vec.push_back(ada_host.c_str());
 
@@ -703,26 +759,58 @@ int FridaController::spawn_suspended(const char* path, char* const argv[], uint3
         envp_vec.push_back(agent_verbose_str.c_str());
     }
 
+    // Caller-supplied variables replace defaults of the same name; the
+    // shared-memory handshake variables can't be overridden
+    if (envp) {
+        for (size_t i = 0; envp[i]; i++) {
+            const char* entry = envp[i];
+            const char* eq = strchr(entry, '=');
+            if (!eq || eq == entry) {
+                continue;
+            }
+            std::string prefix(entry, eq - entry + 1);
+            if (prefix.rfind("ADA_SHM_", 0) == 0) {
+                continue;
+            }
+            envp_vec.erase(std::remove_if(envp_vec.begin(), envp_vec.end(),
+                                          [&](const char* existing) {
+                                              return strncmp(existing, prefix.c_str(),
+                                                             prefix.size()) == 0;
+                                          }),
+                           envp_vec.end());
+            envp_vec.push_back(entry);
+        }
+    }
+
     envp_vec.push_back(nullptr);
 
     frida_spawn_options_set_envp(options, const_cast<gchar**>(envp_vec.data()), envp_vec.size() - 1);
-    frida_spawn_options_set_stdio(options, FRIDA_STDIO_INHERIT);
+    // Pipe the child's stdio through the device "output" signal only when
+    // a redirect was requested; otherwise it shares our descriptors
+    const bool redirect_stdio = stdout_fd_ >= 0 || stderr_fd_ >= 0;
+    frida_spawn_options_set_stdio(options,
+                                  redirect_stdio ? FRIDA_STDIO_PIPE : FRIDA_STDIO_INHERIT);
+    if (redirect_stdio && output_handler_id_ == 0) {
+        output_handler_id_ = g_signal_connect(device_, "output",
+                                              G_CALLBACK(on_output_callback), this);
+    }
 
     // Spawn suspended
     guint pid = frida_device_spawn_sync(device_, path, options, nullptr, &error);
     g_object_unref(options);
 
-    // Free duplicated strings (first 3 entries)
-    for (int i = 0; i < 3; i++) {
-        g_free(const_cast<char*>(envp_vec[i]));
+    // Free duplicated strings
+    for (char* entry : owned) {
+        g_free(entry);
     }
     
     if (error) {
         g_printerr("Failed to spawn: %s\n", error->message);
+        const int status = status_for_frida_error(error);
         g_error_free(error);
         state_ = PROCESS_STATE_FAILED;
         control_block_->process_state = PROCESS_STATE_FAILED;
-        return -1;
+        return status;
     }
     
     pid_ = pid;
@@ -737,7 +825,53 @@ int FridaController::spawn_suspended(const char* path, char* const argv[], uint3
     return 0;
 }
 
+int FridaController::set_stdio_redirect(int stdout_fd, int stderr_fd) {
+    if (state_ != PROCESS_STATE_UNINITIALIZED && state_ != PROCESS_STATE_INITIALIZED) {
+        return -1;
+    }
+
+    int new_stdout = -1;
+    int new_stderr = -1;
+    if (stdout_fd >= 0 && (new_stdout = dup(stdout_fd)) < 0) {
+        return -1;
+    }
+    if (stderr_fd >= 0 && (new_stderr = dup(stderr_fd)) < 0) {
+        if (new_stdout >= 0) close(new_stdout);
+        return -1;
+    }
+
+    if (stdout_fd_ >= 0) close(stdout_fd_);
+    if (stderr_fd_ >= 0) close(stderr_fd_);
+    stdout_fd_ = new_stdout;
+    stderr_fd_ = new_stderr;
+    return 0;
+}
+
+int FridaController::set_hook_filter(const char* const include[],
+                                     const char* const exclude[]) {
+    // The filter travels with the agent init payload, so it can't change
+    // once the agent is loaded
+    if (script_) {
+        return -1;
+    }
+
+    auto collect = [](const char* const patterns[]) {
+        std::vector<std::string> out;
+        for (size_t i = 0; patterns && patterns[i]; ++i) {
+            out.emplace_back(patterns[i]);
+        }
+        return out;
+    };
+    hook_include_ = collect(include);
+    hook_exclude_ = collect(exclude);
+    return 0;
+}
+
 int FridaController::attach(uint32_t pid) {
+    if (state_ == PROCESS_STATE_ATTACHED || state_ == PROCESS_STATE_RUNNING) {
+        return FRIDA_CONTROLLER_ERROR_ALREADY_ATTACHED;
+    }
+
     state_ = PROCESS_STATE_ATTACHING;
     control_block_->process_state = PROCESS_STATE_ATTACHING;
 
@@ -753,18 +887,20 @@ int FridaController::attach(uint32_t pid) {
             break;
         }
 
+        const gint code = error->code;
         bool retry = (error->domain == FRIDA_ERROR &&
-                      (error->code == FRIDA_ERROR_TIMED_OUT ||
-                       error->code == FRIDA_ERROR_PROCESS_NOT_FOUND ||
-                       error->code == FRIDA_ERROR_PROCESS_NOT_RESPONDING));
+                      (code == FRIDA_ERROR_TIMED_OUT ||
+                       code == FRIDA_ERROR_PROCESS_NOT_FOUND ||
+                       code == FRIDA_ERROR_PROCESS_NOT_RESPONDING));
         g_printerr("Failed to attach (attempt %d/%d): %s\n", attempt, max_attempts, error->message);
+        const int status = status_for_frida_error(error);
         g_error_free(error);
         session_ = nullptr;
 
         if (retry && attempt < max_attempts) {
             const int sleep_ms =
-                (error->code == FRIDA_ERROR_PROCESS_NOT_FOUND ||
-                 error->code == FRIDA_ERROR_PROCESS_NOT_RESPONDING)
+                (code == FRIDA_ERROR_PROCESS_NOT_FOUND ||
+                 code == FRIDA_ERROR_PROCESS_NOT_RESPONDING)
                     ? 500
                     : 200;
             std::this_thread::sleep_for(std::chrono::milliseconds(sleep_ms));
@@ -773,7 +909,7 @@ int FridaController::attach(uint32_t pid) {
 
         state_ = PROCESS_STATE_FAILED;
         control_block_->process_state = PROCESS_STATE_FAILED;
-        return -1;
+        return status;
     }
     
     if (!session_) {
@@ -795,6 +931,39 @@ int FridaController::attach(uint32_t pid) {
     return 0;
 }
 
+int FridaController::wait(uint32_t timeout_ms, int* out_exit_code) {
+    if (!out_exit_code) {
+        return FRIDA_CONTROLLER_ERROR;
+    }
+    if (pid_ == 0) {
+        return FRIDA_CONTROLLER_ERROR_NO_SUCH_PROCESS;
+    }
+
+    const auto pid = static_cast<pid_t>(pid_);
+    const auto deadline = std::chrono::steady_clock::now() +
+                          std::chrono::milliseconds(timeout_ms);
+    while (true) {
+        int status = 0;
+        pid_t result = waitpid(pid, &status, WNOHANG);
+        if (result > 0 && (WIFEXITED(status) || WIFSIGNALED(status))) {
+            *out_exit_code = WIFEXITED(status) ? WEXITSTATUS(status)
+                                               : 128 + WTERMSIG(status);
+            return 1;
+        }
+        // Not our child (attached to, or spawned by Frida on macOS): only
+        // liveness is observable
+        if (result < 0 && kill(pid, 0) != 0 && errno == ESRCH) {
+            *out_exit_code = -1;
+            return 1;
+        }
+
+        if (std::chrono::steady_clock::now() >= deadline) {
+            return 0;
+        }
+        std::this_thread::sleep_for(std::chrono::milliseconds(10));
+    }
+}
+
 int FridaController::detach() {
     if (!session_) {
         return -1;
@@ -823,7 +992,7 @@ int FridaController::detach() {
 int FridaController::resume() {
     if (state_ != PROCESS_STATE_SUSPENDED && 
         state_ != PROCESS_STATE_ATTACHED) {
-        return -1;
+        return FRIDA_CONTROLLER_ERROR_NOT_SUSPENDED;
     }
     
     if (spawn_method_ != SpawnMethod::Frida || !device_ || pid_ == 0) {
@@ -854,10 +1023,11 @@ int FridaController::resume() {
     frida_device_resume_sync(device_, pid_, nullptr, &error);
 
     if (error) {
+        const int status = status_for_frida_error(error);
         g_error_free(error);
         state_ = PROCESS_STATE_FAILED;
         control_block_->process_state = PROCESS_STATE_FAILED;
-        return -1;
+        return status;
     }
 
     state_ = PROCESS_STATE_RUNNING;
@@ -968,7 +1138,7 @@ int FridaController::install_hooks() {
 
     // Prepare initialization payload (optionally include exclude CSV)
     const char* exclude_csv = getenv("ADA_EXCLUDE");
-    char init_payload[512];
+    char payload_head[512];
     if (exclude_csv && *exclude_csv) {
         // Trim payload if too long
         char exclude_buf[256];
@@ -976,15 +1146,24 @@ int FridaController::install_hooks() {
         if (n >= sizeof(exclude_buf)) n = sizeof(exclude_buf) - 1;
         memcpy(exclude_buf, exclude_csv, n);
         exclude_buf[n] = '\0';
-        snprintf(init_payload, sizeof(init_payload),
+        snprintf(payload_head, sizeof(payload_head),
                  "host_pid=%u;session_id=%08x;exclude=%s",
                  shared_memory_get_pid(), shared_memory_get_session_id(), exclude_buf);
     } else {
-        snprintf(init_payload, sizeof(init_payload),
+        snprintf(payload_head, sizeof(payload_head),
                  "host_pid=%u;session_id=%08x",
                  shared_memory_get_pid(), shared_memory_get_session_id());
     }
 
+    // Hook filter patterns, one key per pattern
+    std::string init_payload = payload_head;
+    for (const auto& pattern : hook_include_) {
+        init_payload += ";hook_include=" + encode_payload_value(pattern);
+    }
+    for (const auto& pattern : hook_exclude_) {
+        init_payload += ";hook_exclude=" + encode_payload_value(pattern);
+    }
+
     // --------------------------------------------------------------------
     // Phase 1: Estimate symbol count via lightweight QuickJS script
     // --------------------------------------------------------------------
@@ -1073,8 +1252,9 @@ int FridaController::install_hooks() {
     // --------------------------------------------------------------------
     // Phase 2: Create QuickJS loader script and load asynchronously
     // --------------------------------------------------------------------
-    char script_source[4096];
-    snprintf(script_source, sizeof(script_source),
+    // The payload is embedded up to four times (twice outside DEBUG builds)
+    std::vector<char> script_source(4096 + 4 * init_payload.size());
+    snprintf(script_source.data(), script_source.size(),
 #if DEBUG
         "console.log('[Loader] Starting native agent injection');\n"
         "console.log('[Loader] Agent path: %s');\n"
@@ -1131,14 +1311,14 @@ int FridaController::install_hooks() {
 #endif
         "  throw e;\n"
         "}\n",
-        agent_path, init_payload, agent_path, init_payload);
+        agent_path, init_payload.c_str(), agent_path, init_payload.c_str());
 
     GError* error = nullptr;
     FridaScriptOptions* options = frida_script_options_new();
     frida_script_options_set_name(options, "agent-loader");
     frida_script_options_set_runtime(options, FRIDA_SCRIPT_RUNTIME_QJS);
 
-    script_ = frida_session_create_script_sync(session_, script_source,
+    script_ = frida_session_create_script_sync(session_, script_source.data(),
                                                options, nullptr, &error);
     g_object_unref(options);
 
@@ -1315,6 +1495,17 @@ int FridaController::disarm_trigger() {
     return 0;
 }
 
+int FridaController::set_flight_buffer_limit(uint64_t max_bytes, uint64_t max_events) {
+    if (!control_block_) {
+        return -1;
+    }
+
+    __atomic_store_n(&control_block_->preroll_max_bytes, max_bytes, __ATOMIC_RELEASE);
+    __atomic_store_n(&control_block_->preroll_max_events, max_events, __ATOMIC_RELEASE);
+
+    return 0;
+}
+
 int FridaController::set_detail_enabled(uint32_t enabled) {
     if (!control_block_) {
         return -1;
@@ -1359,11 +1550,38 @@ TracerStats FridaController::get_stats() const {
     if (control_block_) {
         result.hooks_installed = __atomic_load_n(&control_block_->actual_hook_count, __ATOMIC_ACQUIRE);
         result.fallback_events = cb_get_fallback_events(const_cast<ControlBlock*>(control_block_));
+        result.preroll_evicted = __atomic_load_n(&control_block_->preroll_evicted, __ATOMIC_ACQUIRE);
     }
 
     return result;
 }
 
+uint32_t FridaController::get_thread_stats(ThreadStats* out, uint32_t capacity) const {
+    if (!registry_ || !out) {
+        return 0;
+    }
+
+    const uint32_t slots = thread_registry_get_capacity(registry_);
+    uint32_t count = 0;
+    for (uint32_t i = 0; i < slots && count < capacity; ++i) {
+        ThreadLaneSet* lanes = thread_registry_get_thread_at(registry_, i);
+        ada_thread_metrics_t* metrics = thread_lanes_get_metrics(lanes);
+        if (!metrics) {
+            continue;
+        }
+
+        ada_thread_metrics_snapshot_t snapshot = {};
+        ada_thread_metrics_snapshot_capture(metrics, ada_metrics_now_ns(), &snapshot);
+        out[count].tid = snapshot.thread_id;
+        out[count].events_captured = snapshot.events_written;
+        out[count].events_dropped = snapshot.events_dropped;
+        out[count].last_event_ns = snapshot.last_swap_timestamp_ns;
+        ++count;
+    }
+
+    return count;
+}
+
 // ============================================================================
 // Callbacks
 // ============================================================================
@@ -1384,6 +1602,46 @@ void FridaController::on_message_callback(FridaScript* script,
     controller->on_message(message, data);
 }
 
+void FridaController::on_output_callback(FridaDevice* device,
+                                         guint pid,
+                                         gint fd,
+                                         GBytes* data,
+                                         gpointer user_data) {
+    (void)device;
+    auto* controller = static_cast<FridaController*>(user_data);
+    controller->on_output(pid, fd, data);
+}
+
+void FridaController::on_output(guint pid, gint fd, GBytes* data) {
+    if (pid != pid_ || !data) {
+        return;
+    }
+
+    // Streams that weren't redirected still go to the tracer's own stdio
+    int target = -1;
+    if (fd == 1) {
+        target = stdout_fd_ >= 0 ? stdout_fd_ : STDOUT_FILENO;
+    } else if (fd == 2) {
+        target = stderr_fd_ >= 0 ? stderr_fd_ : STDERR_FILENO;
+    } else {
+        return;
+    }
+
+    gsize size = 0;
+    const auto* bytes = static_cast<const char*>(g_bytes_get_data(data, &size));
+    while (size > 0) {
+        ssize_t written = write(target, bytes, size);
+        if (written < 0) {
+            if (errno == EINTR) continue;
+            g_debug("[Controller] Failed to write child output (fd=%d): %s\n",
+                    fd, strerror(errno));
+            return;
+        }
+        bytes += written;
+        size -= static_cast<gsize>(written);
+    }
+}
+
 void FridaController::on_detached(FridaSessionDetachReason reason, FridaCrash* crash) {
     (void)crash;
     g_debug("Frida session detached (reason=%d)\n", static_cast<int>(reason));
@@ -1536,6 +1794,32 @@ int frida_controller_spawn_suspended(FridaController* controller,
         ->spawn_suspended(path, argv, out_pid);
 }
 
+int frida_controller_spawn_suspended_env(FridaController* controller,
+                                         const char* path,
+                                         char* const argv[],
+                                         char* const envp[],
+                                         uint32_t* out_pid) {
+    if (!controller) return -1;
+    return reinterpret_cast<ada::internal::FridaController*>(controller)
+        ->spawn_suspended(path, argv, envp, out_pid);
+}
+
+int frida_controller_set_stdio_redirect(FridaController* controller,
+                                        int stdout_fd,
+                                        int stderr_fd) {
+    if (!controller) return -1;
+    return reinterpret_cast<ada::internal::FridaController*>(controller)
+        ->set_stdio_redirect(stdout_fd, stderr_fd);
+}
+
+int frida_controller_set_hook_filter(FridaController* controller,
+                                     const char* const include[],
+                                     const char* const exclude[]) {
+    if (!controller) return -1;
+    return reinterpret_cast<ada::internal::FridaController*>(controller)
+        ->set_hook_filter(include, exclude);
+}
+
 int frida_controller_attach(FridaController* controller, uint32_t pid) {
     if (!controller) return -1;
     return reinterpret_cast<ada::internal::FridaController*>(controller)
@@ -1548,6 +1832,14 @@ int frida_controller_detach(FridaController* controller) {
         ->detach();
 }
 
+int frida_controller_wait(FridaController* controller,
+                          uint32_t timeout_ms,
+                          int* out_exit_code) {
+    if (!controller) return -1;
+    return reinterpret_cast<ada::internal::FridaController*>(controller)
+        ->wait(timeout_ms, out_exit_code);
+}
+
 int frida_controller_resume(FridaController* controller) {
     if (!controller) return -1;
     return reinterpret_cast<ada::internal::FridaController*>(controller)
@@ -1592,6 +1884,14 @@ int frida_controller_disarm_trigger(FridaController* controller) {
         ->disarm_trigger();
 }
 
+int frida_controller_set_flight_buffer_limit(FridaController* controller,
+                                             uint64_t max_bytes,
+                                             uint64_t max_events) {
+    if (!controller) return -1;
+    return reinterpret_cast<ada::internal::FridaController*>(controller)
+        ->set_flight_buffer_limit(max_bytes, max_events);
+}
+
 int frida_controller_set_detail_enabled(FridaController* controller, uint32_t enabled) {
     if (!controller) return -1;
     return reinterpret_cast<ada::internal::FridaController*>(controller)
@@ -1631,4 +1931,12 @@ TracerStats frida_controller_get_stats(FridaController* controller) {
         ->get_stats();
 }
 
+int frida_controller_get_thread_stats(FridaController* controller,
+                                      ThreadStats* out,
+                                      uint32_t capacity) {
+    if (!controller || !out) return -1;
+    return static_cast<int>(reinterpret_cast<ada::internal::FridaController*>(controller)
+        ->get_thread_stats(out, capacity));
+}
+
 } // extern "C"
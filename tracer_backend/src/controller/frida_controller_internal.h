@@ -4,6 +4,7 @@
 #include <memory>
 #include <atomic>
 #include <string>
+#include <vector>
 #include <cstdint>
 #include <thread>
 
@@ -54,19 +55,25 @@ public:
     
     // Process management
     int spawn_suspended(const char* path, char* const argv[], uint32_t* out_pid);
+    int spawn_suspended(const char* path, char* const argv[], char* const envp[],
+                        uint32_t* out_pid);
+    int set_stdio_redirect(int stdout_fd, int stderr_fd);
     int attach(uint32_t pid);
     int detach();
     int resume();
     int pause();
+    int wait(uint32_t timeout_ms, int* out_exit_code);
     
     // Agent injection
     int install_hooks();
+    int set_hook_filter(const char* const include[], const char* const exclude[]);
     int inject_agent(const char* agent_path);
     
     // Flight recorder control
     int arm_trigger(uint32_t pre_roll_ms, uint32_t post_roll_ms);
     int fire_trigger();
     int disarm_trigger();
+    int set_flight_buffer_limit(uint64_t max_bytes, uint64_t max_events);
     int set_detail_enabled(uint32_t enabled);
     int start_session();
     int stop_session();
@@ -75,6 +82,7 @@ public:
     ProcessState get_state() const { return state_; }
     FlightRecorderState get_flight_state() const;
     TracerStats get_stats() const;
+    uint32_t get_thread_stats(ThreadStats* out, uint32_t capacity) const;
     
 private:
     // Spawn methods
@@ -124,9 +132,16 @@ private:
                                     GBytes* data, 
                                     gpointer user_data);
     
+    static void on_output_callback(FridaDevice* device,
+                                   guint pid,
+                                   gint fd,
+                                   GBytes* data,
+                                   gpointer user_data);
+    
     // Instance callbacks
     void on_detached(FridaSessionDetachReason reason, FridaCrash* crash);
     void on_message(const gchar* message, GBytes* data);
+    void on_output(guint pid, gint fd, GBytes* data);
     
     // Data members
     std::string output_dir_;
@@ -142,6 +157,11 @@ private:
     std::atomic<ProcessState> state_{PROCESS_STATE_UNINITIALIZED};
     SpawnMethod spawn_method_{SpawnMethod::None};
     
+    // Child stdio redirection (-1 = inherit)
+    int stdout_fd_{-1};
+    int stderr_fd_{-1};
+    gulong output_handler_id_{0};
+    
     // Shared memory
     shared_memory_ptr shm_control_;
     shared_memory_ptr shm_index_;
@@ -176,6 +196,10 @@ private:
     // Async script loading state (M1_E6_I1)
     GCancellable* script_cancellable_{nullptr};
 
+    // Hook filter patterns (regex) forwarded to the agent init payload
+    std::vector<std::string> hook_include_;
+    std::vector<std::string> hook_exclude_;
+
     // Unfiltered symbol count from Phase 1 estimation (M1_E6_I1)
     std::atomic<uint32_t> unfiltered_symbol_count_{0};
     std::atomic<bool> has_unfiltered_symbol_count_{false};
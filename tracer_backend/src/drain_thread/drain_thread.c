@@ -279,6 +279,39 @@ static AtfThreadWriter* get_or_create_thread_writer(DrainThread* drain, uint32_t
     return writer;
 }
 
+// Most detail events a ring may keep while the flight recorder is armed,
+// or 0 when the pre-roll is unbounded
+static uint64_t preroll_event_limit(const ControlBlock* cb) {
+    if (!cb || __atomic_load_n(&cb->flight_state, __ATOMIC_ACQUIRE) != FLIGHT_RECORDER_ARMED) {
+        return 0;
+    }
+
+    uint64_t limit = __atomic_load_n(&cb->preroll_max_events, __ATOMIC_ACQUIRE);
+    uint64_t max_bytes = __atomic_load_n(&cb->preroll_max_bytes, __ATOMIC_ACQUIRE);
+    if (max_bytes > 0) {
+        uint64_t by_bytes = max_bytes / sizeof(DetailEvent);
+        if (by_bytes == 0) by_bytes = 1;  // Always keep the newest event
+        if (limit == 0 || by_bytes < limit) limit = by_bytes;
+    }
+    return limit;
+}
+
+// Trim a detail ring to the pre-roll limit before reading it. The drain is
+// the ring's only consumer, so the eviction can't race with another reader
+// the way a producer-side drop would.
+static void evict_preroll(DrainThread* drain, RingBufferHeader* ring_hdr) {
+    const uint64_t limit = preroll_event_limit(drain->control_block);
+    if (limit == 0 || !ring_hdr) {
+        return;
+    }
+
+    size_t evicted = ring_buffer_evict_oldest_raw(ring_hdr, (size_t)limit);
+    if (evicted > 0) {
+        __atomic_fetch_add(&drain->control_block->preroll_evicted, (uint64_t)evicted,
+                           __ATOMIC_RELAXED);
+    }
+}
+
 static uint32_t drain_lane(DrainThread* drain,
                            uint32_t slot_index,
                            Lane* lane,
@@ -316,6 +349,7 @@ static uint32_t drain_lane(DrainThread* drain,
 
             if (ring_hdr) {
                 if (is_detail) {
+                    evict_preroll(drain, ring_hdr);
                     // Detail lane - read DetailEvents
                     DetailEvent detail_event;
                     while (ring_buffer_read_raw(ring_hdr, sizeof(DetailEvent), &detail_event)) {
@@ -364,6 +398,7 @@ static uint32_t drain_lane(DrainThread* drain,
 
         if (active_hdr) {
             if (is_detail) {
+                evict_preroll(drain, active_hdr);
                 DetailEvent detail_event;
                 while (ring_buffer_read_raw(active_hdr, sizeof(DetailEvent), &detail_event)) {
                     atf_thread_writer_write_event(
@@ -4,9 +4,12 @@
 //! components built with Frida.
 
 use std::ffi::CString;
-use std::os::raw::{c_char, c_uint};
-use std::path::Path;
+use std::fs::File;
+use std::os::raw::{c_char, c_int, c_uint};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::time::{Duration, Instant};
 
 pub mod ffi {
     //! Foreign Function Interface bindings
@@ -39,6 +42,16 @@ pub mod ffi {
             pub active_threads: u32,
             pub hooks_installed: u32,
             pub fallback_events: u64,
+            pub preroll_evicted: u64,
+        }
+
+        #[repr(C)]
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct ThreadStats {
+            pub tid: u64,
+            pub events_captured: u64,
+            pub events_dropped: u64,
+            pub last_event_ns: u64,
         }
 
         #[repr(C)]
@@ -65,6 +78,126 @@ pub mod ffi {
             PostRoll = 4,
         }
 
+        /// Display and serde support for the state enums
+        ///
+        /// Serialized as lowercase names; deserialization also accepts the
+        /// numeric discriminant, rejecting values with no matching variant
+        /// instead of transmuting them.
+        macro_rules! state_enum_names {
+            ($ty:ident { $($variant:ident => $name:literal, $human:literal;)* }) => {
+                impl $ty {
+                    /// Stable lowercase name, as used in JSON
+                    pub fn as_str(self) -> &'static str {
+                        match self {
+                            $($ty::$variant => $name,)*
+                        }
+                    }
+
+                    /// Variant for a lowercase name
+                    pub fn from_name(name: &str) -> Option<Self> {
+                        match name {
+                            $($name => Some($ty::$variant),)*
+                            _ => None,
+                        }
+                    }
+
+                    /// Variant for a raw discriminant, `None` if out of range
+                    pub fn from_raw(value: i64) -> Option<Self> {
+                        $(if value == $ty::$variant as i64 {
+                            return Some($ty::$variant);
+                        })*
+                        None
+                    }
+                }
+
+                impl std::fmt::Display for $ty {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str(match self {
+                            $($ty::$variant => $human,)*
+                        })
+                    }
+                }
+
+                impl serde::Serialize for $ty {
+                    fn serialize<S: serde::Serializer>(
+                        &self,
+                        serializer: S,
+                    ) -> Result<S::Ok, S::Error> {
+                        serializer.serialize_str(self.as_str())
+                    }
+                }
+
+                impl<'de> serde::Deserialize<'de> for $ty {
+                    fn deserialize<D: serde::Deserializer<'de>>(
+                        deserializer: D,
+                    ) -> Result<Self, D::Error> {
+                        struct Visitor;
+
+                        impl serde::de::Visitor<'_> for Visitor {
+                            type Value = $ty;
+
+                            fn expecting(
+                                &self,
+                                f: &mut std::fmt::Formatter<'_>,
+                            ) -> std::fmt::Result {
+                                f.write_str(concat!(
+                                    "a ",
+                                    stringify!($ty),
+                                    " name or discriminant"
+                                ))
+                            }
+
+                            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<$ty, E> {
+                                $ty::from_name(v).ok_or_else(|| {
+                                    E::unknown_variant(v, &[$($name),*])
+                                })
+                            }
+
+                            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<$ty, E> {
+                                $ty::from_raw(v).ok_or_else(|| {
+                                    E::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                                })
+                            }
+
+                            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<$ty, E> {
+                                i64::try_from(v)
+                                    .ok()
+                                    .and_then($ty::from_raw)
+                                    .ok_or_else(|| {
+                                        E::invalid_value(
+                                            serde::de::Unexpected::Unsigned(v),
+                                            &self,
+                                        )
+                                    })
+                            }
+                        }
+
+                        deserializer.deserialize_any(Visitor)
+                    }
+                }
+            };
+        }
+
+        state_enum_names!(ProcessState {
+            Uninitialized => "uninitialized", "uninitialized";
+            Initialized => "initialized", "initialized";
+            Spawning => "spawning", "spawning";
+            Suspended => "suspended", "suspended";
+            Attaching => "attaching", "attaching";
+            Attached => "attached", "attached";
+            Running => "running", "running";
+            Detaching => "detaching", "detaching";
+            Failed => "failed", "failed";
+        });
+
+        state_enum_names!(FlightRecorderState {
+            Idle => "idle", "idle";
+            Armed => "armed", "armed";
+            PreRoll => "preroll", "pre-roll";
+            Recording => "recording", "recording";
+            PostRoll => "postroll", "post-roll";
+        });
+
         extern "C" {
             pub fn frida_controller_create(output_dir: *const c_char) -> *mut FridaController;
             pub fn frida_controller_destroy(controller: *mut FridaController);
@@ -74,9 +207,31 @@ pub mod ffi {
                 argv: *const *const c_char,
                 out_pid: *mut c_uint,
             ) -> c_int;
+            pub fn frida_controller_spawn_suspended_env(
+                controller: *mut FridaController,
+                path: *const c_char,
+                argv: *const *const c_char,
+                envp: *const *const c_char,
+                out_pid: *mut c_uint,
+            ) -> c_int;
+            pub fn frida_controller_set_stdio_redirect(
+                controller: *mut FridaController,
+                stdout_fd: c_int,
+                stderr_fd: c_int,
+            ) -> c_int;
             pub fn frida_controller_attach(controller: *mut FridaController, pid: c_uint) -> c_int;
             pub fn frida_controller_detach(controller: *mut FridaController) -> c_int;
             pub fn frida_controller_resume(controller: *mut FridaController) -> c_int;
+            pub fn frida_controller_wait(
+                controller: *mut FridaController,
+                timeout_ms: c_uint,
+                out_exit_code: *mut c_int,
+            ) -> c_int;
+            pub fn frida_controller_set_hook_filter(
+                controller: *mut FridaController,
+                include: *const *const c_char,
+                exclude: *const *const c_char,
+            ) -> c_int;
             pub fn frida_controller_install_hooks(controller: *mut FridaController) -> c_int;
             pub fn frida_controller_arm_trigger(
                 controller: *mut FridaController,
@@ -85,6 +240,11 @@ pub mod ffi {
             ) -> c_int;
             pub fn frida_controller_fire_trigger(controller: *mut FridaController) -> c_int;
             pub fn frida_controller_disarm_trigger(controller: *mut FridaController) -> c_int;
+            pub fn frida_controller_set_flight_buffer_limit(
+                controller: *mut FridaController,
+                max_bytes: u64,
+                max_events: u64,
+            ) -> c_int;
             pub fn frida_controller_set_detail_enabled(
                 controller: *mut FridaController,
                 enabled: c_uint,
@@ -92,6 +252,11 @@ pub mod ffi {
             pub fn frida_controller_start_session(controller: *mut FridaController) -> c_int;
             pub fn frida_controller_stop_session(controller: *mut FridaController) -> c_int;
             pub fn frida_controller_get_stats(controller: *mut FridaController) -> TracerStats;
+            pub fn frida_controller_get_thread_stats(
+                controller: *mut FridaController,
+                out: *mut ThreadStats,
+                capacity: c_uint,
+            ) -> c_int;
             pub fn frida_controller_get_state(controller: *mut FridaController) -> ProcessState;
             pub fn frida_controller_get_flight_state(
                 controller: *mut FridaController,
@@ -105,6 +270,395 @@ pub mod ffi {
 
 use ffi::*;
 
+// Implemented by hand so it also covers the bindgen-generated struct
+impl serde::Serialize for TracerStats {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("TracerStats", 7)?;
+        state.serialize_field("events_captured", &self.events_captured)?;
+        state.serialize_field("events_dropped", &self.events_dropped)?;
+        state.serialize_field("bytes_written", &self.bytes_written)?;
+        state.serialize_field("active_threads", &self.active_threads)?;
+        state.serialize_field("hooks_installed", &self.hooks_installed)?;
+        state.serialize_field("fallback_events", &self.fallback_events)?;
+        state.serialize_field("preroll_evicted", &self.preroll_evicted)?;
+        state.end()
+    }
+}
+
+impl TracerStats {
+    /// Average events recorded per traced function call
+    ///
+    /// A call produces an entry and a return event, and on average about one
+    /// more for arguments or detail-lane records.
+    pub const EVENTS_PER_CALL: u64 = 3;
+
+    /// Approximate number of traced function calls behind `events_captured`
+    pub fn estimated_fn_calls(&self) -> u64 {
+        self.events_captured / Self::EVENTS_PER_CALL
+    }
+}
+
+/// Most threads [`TracerController::get_thread_stats`] reports, matching the
+/// native registry's `MAX_THREADS`; any further threads are left out
+pub const MAX_THREAD_STATS: usize = 64;
+
+/// Number of valid entries for a native per-thread stats `count` (negative on
+/// error) written into a buffer of `capacity`
+fn thread_stats_len(count: c_int, capacity: usize) -> usize {
+    usize::try_from(count).map_or(0, |count| count.min(capacity))
+}
+
+/// Errors raised by [`TracerController`] and the native calls it wraps
+#[derive(Debug, thiserror::Error)]
+pub enum TracerError {
+    /// Operation is not valid in the controller's current process state
+    #[error("Cannot {operation} while the process is {actual} (expected {})", join_states(.expected))]
+    InvalidState {
+        operation: &'static str,
+        expected: &'static [ProcessState],
+        actual: ProcessState,
+    },
+    /// `attach` was called while already attached to a process
+    #[error("Process is already attached")]
+    AlreadyAttached,
+    /// The OS refused to let the tracer spawn, attach to, or resume the process
+    #[error("Permission denied")]
+    PermissionDenied,
+    /// The target process doesn't exist
+    #[error("No such process")]
+    NoSuchProcess,
+    /// `resume` was called with no suspended process
+    #[error("Process is not suspended")]
+    NotSuspended,
+    /// A native call failed without a more specific status
+    #[error("Failed to {0}")]
+    Failed(&'static str),
+    /// A native call returned a status code this crate doesn't know
+    #[error("Native tracer call failed with status {0}")]
+    Unknown(i32),
+    /// An argument was rejected before reaching the native side
+    #[error("{0}")]
+    InvalidArgument(String),
+    /// A stdio redirect file couldn't be opened
+    #[error("Failed to open {stream} redirect {}: {source}", path.display())]
+    Redirect {
+        stream: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// No running process has the requested name
+    #[error("No running process named {0:?}")]
+    ProcessNotFound(String),
+    /// More than one running process has the requested name
+    #[error("Multiple processes named {name:?} (PIDs {}); attach by PID instead", join_pids(.pids))]
+    AmbiguousProcessName { name: String, pids: Vec<u32> },
+}
+
+fn join_pids(pids: &[u32]) -> String {
+    pids.iter()
+        .map(|pid| pid.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Running processes as `(pid, name)` pairs
+#[cfg(target_os = "macos")]
+fn running_processes() -> Vec<(u32, String)> {
+    // Ask for the count first, with headroom for processes started meanwhile
+    let count = unsafe { libc::proc_listallpids(ptr::null_mut(), 0) };
+    if count <= 0 {
+        return Vec::new();
+    }
+    let mut pids = vec![0 as libc::pid_t; count as usize + 64];
+    let bytes = (pids.len() * std::mem::size_of::<libc::pid_t>()) as c_int;
+    let count = unsafe { libc::proc_listallpids(pids.as_mut_ptr().cast(), bytes) };
+    pids.truncate(count.max(0) as usize);
+
+    pids.into_iter()
+        .filter(|&pid| pid > 0)
+        .filter_map(|pid| {
+            let mut name = [0u8; 2 * libc::MAXCOMLEN + 1];
+            let len = unsafe { libc::proc_name(pid, name.as_mut_ptr().cast(), name.len() as u32) };
+            (len > 0).then(|| {
+                let name = String::from_utf8_lossy(&name[..len as usize]).into_owned();
+                (pid as u32, name)
+            })
+        })
+        .collect()
+}
+
+/// Running processes as `(pid, name)` pairs
+#[cfg(not(target_os = "macos"))]
+fn running_processes() -> Vec<(u32, String)> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let name = std::fs::read_to_string(entry.path().join("comm")).ok()?;
+            Some((pid, name.trim_end().to_string()))
+        })
+        .collect()
+}
+
+/// The single process in `processes` named exactly `name`, ignoring `own_pid`
+fn resolve_process_name(
+    processes: &[(u32, String)],
+    name: &str,
+    own_pid: u32,
+) -> Result<u32, TracerError> {
+    let mut pids: Vec<u32> = processes
+        .iter()
+        .filter(|(pid, process_name)| *pid != own_pid && process_name == name)
+        .map(|(pid, _)| *pid)
+        .collect();
+    pids.sort_unstable();
+
+    match pids.as_slice() {
+        [] => Err(TracerError::ProcessNotFound(name.to_string())),
+        [pid] => Ok(*pid),
+        _ => Err(TracerError::AmbiguousProcessName {
+            name: name.to_string(),
+            pids,
+        }),
+    }
+}
+
+// Native status codes (`FridaControllerStatus` in tracer_types.h)
+const STATUS_ERROR: c_int = -1;
+const STATUS_ALREADY_ATTACHED: c_int = -2;
+const STATUS_PERMISSION_DENIED: c_int = -3;
+const STATUS_NO_SUCH_PROCESS: c_int = -4;
+const STATUS_NOT_SUSPENDED: c_int = -5;
+
+/// Map a native status code for `operation` (0 is success)
+fn check_status(status: c_int, operation: &'static str) -> Result<(), TracerError> {
+    match status {
+        0 => Ok(()),
+        STATUS_ERROR => Err(TracerError::Failed(operation)),
+        STATUS_ALREADY_ATTACHED => Err(TracerError::AlreadyAttached),
+        STATUS_PERMISSION_DENIED => Err(TracerError::PermissionDenied),
+        STATUS_NO_SUCH_PROCESS => Err(TracerError::NoSuchProcess),
+        STATUS_NOT_SUSPENDED => Err(TracerError::NotSuspended),
+        other => Err(TracerError::Unknown(other)),
+    }
+}
+
+/// C string for an argument, rejecting interior NUL bytes
+fn c_string(value: &str, what: &str) -> Result<CString, TracerError> {
+    CString::new(value).map_err(|_| {
+        TracerError::InvalidArgument(format!("{} {:?} contains a NUL byte", what, value))
+    })
+}
+
+fn join_states(states: &[ProcessState]) -> String {
+    states
+        .iter()
+        .map(|state| state.as_str())
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Run `f` between `start` and `stop`, calling `stop` whenever `start` succeeded
+fn bracket<T, R>(
+    target: &mut T,
+    start: impl FnOnce(&mut T) -> anyhow::Result<()>,
+    stop: impl FnOnce(&mut T) -> anyhow::Result<()>,
+    f: impl FnOnce(&mut T) -> anyhow::Result<R>,
+) -> anyhow::Result<R> {
+    start(target)?;
+    let result = f(target);
+    let stopped = stop(target);
+    let value = result?;
+    stopped?;
+    Ok(value)
+}
+
+/// Whether operations that need an attached process are valid in `state`
+fn is_attached_state(state: ProcessState) -> bool {
+    matches!(state, ProcessState::Attached | ProcessState::Running)
+}
+
+const ATTACHED_STATES: &[ProcessState] = &[ProcessState::Attached, ProcessState::Running];
+
+/// Process states each guarded operation may be called in
+///
+/// Operations not listed here (spawn, attach, stop_session, detach) are left
+/// to the native side, so teardown is always attempted.
+const PRECONDITIONS: &[(&str, &[ProcessState])] = &[
+    ("install_hooks", ATTACHED_STATES),
+    ("arm_trigger", ATTACHED_STATES),
+    ("fire_trigger", ATTACHED_STATES),
+    ("disarm_trigger", ATTACHED_STATES),
+    ("set_detail_enabled", ATTACHED_STATES),
+    ("set_flight_buffer_limit", ATTACHED_STATES),
+    ("start_session", ATTACHED_STATES),
+    ("resume", &[ProcessState::Suspended, ProcessState::Attached]),
+];
+
+/// Check `operation` against [`PRECONDITIONS`] for a process in `actual`
+fn check_transition(operation: &'static str, actual: ProcessState) -> Result<(), TracerError> {
+    let expected = PRECONDITIONS
+        .iter()
+        .find(|(name, _)| *name == operation)
+        .map_or(&[][..], |(_, states)| *states);
+    if expected.is_empty() || expected.contains(&actual) {
+        return Ok(());
+    }
+    Err(TracerError::InvalidState {
+        operation,
+        expected,
+        actual,
+    })
+}
+
+/// Longest single native wait, so the process state is rechecked regularly
+const WAIT_SLICE: Duration = Duration::from_millis(100);
+
+/// How long the next native wait may block before `deadline`, or `None` once
+/// it has passed
+fn next_wait_slice(deadline: Option<Instant>, now: Instant) -> Option<Duration> {
+    match deadline {
+        None => Some(WAIT_SLICE),
+        Some(deadline) if now >= deadline => None,
+        Some(deadline) => Some((deadline - now).min(WAIT_SLICE)),
+    }
+}
+
+/// Exit code from a finished native wait; the native side reports -1 when
+/// the process wasn't our child and its status couldn't be collected
+fn collected_exit_code(exit_code: c_int) -> Option<i32> {
+    (exit_code >= 0).then_some(exit_code)
+}
+
+/// `KEY=VALUE` C strings for a child environment
+///
+/// Rejects empty keys, keys containing `=`, and NUL bytes anywhere, which
+/// would otherwise be split or truncated on the native side.
+fn env_entries(env: &[(String, String)]) -> Result<Vec<CString>, TracerError> {
+    env.iter()
+        .map(|(key, value)| {
+            if key.is_empty() || key.contains('=') {
+                return Err(TracerError::InvalidArgument(format!(
+                    "Invalid environment variable name {:?}",
+                    key
+                )));
+            }
+            CString::new(format!("{key}={value}")).map_err(|_| {
+                TracerError::InvalidArgument(format!(
+                    "Environment variable {:?} contains a NUL byte",
+                    key
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Native regex for a hook filter pattern
+///
+/// Patterns prefixed with `re:` are regexes, searched anywhere in the name.
+/// Anything else is a glob (`*`, `?`) that must match the whole name. The
+/// agent compiles patterns as ECMAScript `std::regex`, so a regex is checked
+/// here for both valid syntax and constructs ECMAScript lacks; a bad pattern
+/// then fails at call time instead of failing the agent's init.
+fn hook_filter_regex(pattern: &str) -> Result<CString, TracerError> {
+    let regex = match pattern.strip_prefix("re:") {
+        Some(regex) => regex.to_string(),
+        None => {
+            let mut regex = String::from("^");
+            for c in pattern.chars() {
+                match c {
+                    '*' => regex.push_str(".*"),
+                    '?' => regex.push('.'),
+                    '\\' | '.' | '+' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => {
+                        regex.push('\\');
+                        regex.push(c);
+                    }
+                    _ => regex.push(c),
+                }
+            }
+            regex.push('$');
+            regex
+        }
+    };
+
+    let invalid = |reason: String| {
+        TracerError::InvalidArgument(format!(
+            "Invalid hook filter pattern {:?}: {}",
+            pattern, reason
+        ))
+    };
+    if regex.is_empty() {
+        return Err(invalid("pattern is empty".to_string()));
+    }
+    regex::Regex::new(&regex).map_err(|e| invalid(e.to_string()))?;
+    if let Some(reason) = ecmascript_unsupported(&regex) {
+        return Err(invalid(reason.to_string()));
+    }
+    c_string(&regex, "Hook filter pattern")
+}
+
+/// Why `regex`, already valid for the `regex` crate, wouldn't compile (or
+/// would mean something else) as an ECMAScript `std::regex`
+fn ecmascript_unsupported(regex: &str) -> Option<&'static str> {
+    let mut chars = regex.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('p' | 'P') => return Some("Unicode classes (\\p, \\P) aren't supported"),
+                Some('A' | 'z') => return Some("\\A and \\z aren't supported; use ^ and $"),
+                Some('a' | 'U' | '<' | '>') => {
+                    return Some("\\a, \\U, \\< and \\> escapes aren't supported")
+                }
+                Some('x' | 'u' | 'b' | 'B') if chars.peek() == Some(&'{') => {
+                    return Some("braced escapes such as \\x{..} aren't supported")
+                }
+                _ => {}
+            },
+            '[' if !in_class => {
+                in_class = true;
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                }
+                // A literal here, but ECMAScript reads `[]` as an empty class
+                if chars.peek() == Some(&']') {
+                    return Some("a leading ] in a character class isn't supported; escape it");
+                }
+            }
+            '[' => {
+                if chars.peek() != Some(&':') {
+                    return Some("nested character classes aren't supported");
+                }
+                // POSIX class such as [:alpha:], which std::regex accepts
+                while let Some(c) = chars.next() {
+                    if c == ':' && chars.peek() == Some(&']') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            ']' if in_class => in_class = false,
+            '&' | '-' | '~' if in_class && chars.peek() == Some(&c) => {
+                return Some("character class set operations (&&, --, ~~) aren't supported");
+            }
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                chars.next();
+                if chars.peek() != Some(&':') {
+                    return Some("inline flags and named groups such as (?i) aren't supported");
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 /// High-level Rust wrapper for the tracer controller
 pub struct TracerController {
     ptr: *mut ffi::FridaController,
@@ -112,14 +666,14 @@ pub struct TracerController {
 
 impl TracerController {
     /// Create a new tracer controller
-    pub fn new<P: AsRef<Path>>(output_dir: P) -> anyhow::Result<Self> {
+    pub fn new<P: AsRef<Path>>(output_dir: P) -> Result<Self, TracerError> {
         let output_dir = output_dir.as_ref();
-        let c_path = CString::new(output_dir.to_str().unwrap())?;
+        let c_path = c_string(output_dir.to_str().unwrap(), "Output directory")?;
 
         let ptr = unsafe { ffi::frida_controller_create(c_path.as_ptr()) };
 
         if ptr.is_null() {
-            anyhow::bail!("Failed to create tracer controller");
+            return Err(TracerError::Failed("create tracer controller"));
         }
 
         Ok(TracerController { ptr })
@@ -130,140 +684,294 @@ impl TracerController {
         &mut self,
         path: P,
         args: &[String],
-    ) -> anyhow::Result<u32> {
-        let path = CString::new(path.as_ref().to_str().unwrap())?;
+    ) -> Result<u32, TracerError> {
+        self.spawn_suspended_with_env(path, args, &[])
+    }
+
+    /// Spawn a process in suspended state with extra environment variables
+    ///
+    /// `env` entries replace the tracer's defaults of the same name (such as
+    /// `PATH`); the shared-memory handshake variables can't be overridden.
+    pub fn spawn_suspended_with_env<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        args: &[String],
+        env: &[(String, String)],
+    ) -> Result<u32, TracerError> {
+        let path = c_string(path.as_ref().to_str().unwrap(), "Executable path")?;
+        let c_env = env_entries(env)?;
 
         // Convert args to C strings
         let c_args: Vec<CString> = args
             .iter()
-            .map(|s| CString::new(s.as_str()))
+            .map(|s| c_string(s, "Argument"))
             .collect::<Result<_, _>>()?;
 
         // Create argv array
         let mut argv: Vec<*const c_char> = c_args.iter().map(|s| s.as_ptr()).collect();
         argv.push(ptr::null());
 
+        let mut envp: Vec<*const c_char> = c_env.iter().map(|s| s.as_ptr()).collect();
+        envp.push(ptr::null());
+
         let mut pid: c_uint = 0;
 
         let result = unsafe {
-            ffi::frida_controller_spawn_suspended(self.ptr, path.as_ptr(), argv.as_ptr(), &mut pid)
+            ffi::frida_controller_spawn_suspended_env(
+                self.ptr,
+                path.as_ptr(),
+                argv.as_ptr(),
+                envp.as_ptr(),
+                &mut pid,
+            )
         };
 
-        if result != 0 {
-            anyhow::bail!("Failed to spawn process");
-        }
+        check_status(result, "spawn process")?;
 
         Ok(pid)
     }
 
+    /// Save the stdout and/or stderr of processes spawned afterwards to files
+    ///
+    /// Must be called before [`spawn_suspended`](Self::spawn_suspended). Each
+    /// file is created (or truncated) here, so an unwritable path fails before
+    /// anything is spawned; `None` keeps inheriting the tracer's stream.
+    pub fn set_stdio_redirect(
+        &mut self,
+        stdout: Option<PathBuf>,
+        stderr: Option<PathBuf>,
+    ) -> Result<(), TracerError> {
+        let open = |path: Option<PathBuf>, stream: &'static str| {
+            path.map(|path| {
+                File::create(&path).map_err(|source| TracerError::Redirect {
+                    stream,
+                    path,
+                    source,
+                })
+            })
+            .transpose()
+        };
+        let stdout = open(stdout, "stdout")?;
+        let stderr = open(stderr, "stderr")?;
+        let fd = |file: &Option<File>| file.as_ref().map_or(-1, |f| f.as_raw_fd());
+
+        // The native side duplicates the descriptors, so the files can close
+        let result =
+            unsafe { ffi::frida_controller_set_stdio_redirect(self.ptr, fd(&stdout), fd(&stderr)) };
+
+        check_status(result, "set stdio redirect")
+    }
+
     /// Attach to a running process
-    pub fn attach(&mut self, pid: u32) -> anyhow::Result<()> {
+    pub fn attach(&mut self, pid: u32) -> Result<(), TracerError> {
         let result = unsafe { ffi::frida_controller_attach(self.ptr, pid) };
 
-        if result != 0 {
-            anyhow::bail!("Failed to attach to process {}", pid);
-        }
+        check_status(result, "attach to process")
+    }
 
-        Ok(())
+    /// Attach to the running process named `name` and return its PID
+    ///
+    /// The name must match exactly (as shown by `ps -c`; Linux truncates
+    /// names to 15 characters). Fails without attaching when no process or
+    /// more than one process matches, listing the candidate PIDs.
+    pub fn attach_by_name(&mut self, name: &str) -> Result<u32, TracerError> {
+        let pid = resolve_process_name(&running_processes(), name, std::process::id())?;
+        self.attach(pid)?;
+        Ok(pid)
+    }
+
+    /// Only hook functions matching `include` (all when empty) and not
+    /// matching `exclude`
+    ///
+    /// Each pattern is checked against both the symbol name and the module's
+    /// file name; see [`hook_filter_regex`] for the glob and `re:` syntax. Must
+    /// be called before [`install_hooks`](Self::install_hooks).
+    pub fn set_hook_filter(
+        &mut self,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<(), TracerError> {
+        let compile = |patterns: &[String]| -> Result<Vec<CString>, TracerError> {
+            patterns.iter().map(|p| hook_filter_regex(p)).collect()
+        };
+        let include = compile(include)?;
+        let exclude = compile(exclude)?;
+
+        let pointers = |patterns: &[CString]| -> Vec<*const c_char> {
+            patterns
+                .iter()
+                .map(|p| p.as_ptr())
+                .chain(std::iter::once(ptr::null()))
+                .collect()
+        };
+        let include_ptrs = pointers(&include);
+        let exclude_ptrs = pointers(&exclude);
+
+        let result = unsafe {
+            ffi::frida_controller_set_hook_filter(
+                self.ptr,
+                include_ptrs.as_ptr(),
+                exclude_ptrs.as_ptr(),
+            )
+        };
+
+        check_status(
+            result,
+            "set hook filter (it must be set before install_hooks)",
+        )
     }
 
     /// Install hooks in the attached process
-    pub fn install_hooks(&mut self) -> anyhow::Result<()> {
-        let result = unsafe { ffi::frida_controller_install_hooks(self.ptr) };
+    pub fn install_hooks(&mut self) -> Result<(), TracerError> {
+        self.ensure_state("install_hooks")?;
 
-        if result != 0 {
-            anyhow::bail!("Failed to install hooks");
-        }
+        let result = unsafe { ffi::frida_controller_install_hooks(self.ptr) };
 
-        Ok(())
+        check_status(result, "install hooks")
     }
 
     /// Arm flight recorder trigger
-    pub fn arm_trigger(&mut self, pre_roll_ms: u32, post_roll_ms: u32) -> anyhow::Result<()> {
-        let result = unsafe { ffi::frida_controller_arm_trigger(self.ptr, pre_roll_ms, post_roll_ms) };
+    pub fn arm_trigger(&mut self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<(), TracerError> {
+        self.ensure_state("arm_trigger")?;
 
-        if result != 0 {
-            anyhow::bail!("Failed to arm flight recorder trigger");
-        }
+        let result = unsafe { ffi::frida_controller_arm_trigger(self.ptr, pre_roll_ms, post_roll_ms) };
 
-        Ok(())
+        check_status(result, "arm flight recorder trigger")
     }
 
     /// Fire flight recorder trigger
-    pub fn fire_trigger(&mut self) -> anyhow::Result<()> {
-        let result = unsafe { ffi::frida_controller_fire_trigger(self.ptr) };
+    pub fn fire_trigger(&mut self) -> Result<(), TracerError> {
+        self.ensure_state("fire_trigger")?;
 
-        if result != 0 {
-            anyhow::bail!("Failed to fire flight recorder trigger");
-        }
+        let result = unsafe { ffi::frida_controller_fire_trigger(self.ptr) };
 
-        Ok(())
+        check_status(result, "fire flight recorder trigger")
     }
 
     /// Disarm flight recorder trigger
-    pub fn disarm_trigger(&mut self) -> anyhow::Result<()> {
+    pub fn disarm_trigger(&mut self) -> Result<(), TracerError> {
+        self.ensure_state("disarm_trigger")?;
+
         let result = unsafe { ffi::frida_controller_disarm_trigger(self.ptr) };
 
-        if result != 0 {
-            anyhow::bail!("Failed to disarm flight recorder trigger");
-        }
+        check_status(result, "disarm flight recorder trigger")
+    }
 
-        Ok(())
+    /// Cap the flight recorder's pre-roll buffer
+    ///
+    /// While armed, the drain thread trims each detail ring to its newest
+    /// `max_bytes` or `max_events` worth of events, whichever is smaller,
+    /// before reading it; 0 leaves that limit unbounded. Evictions are
+    /// counted in [`TracerStats::preroll_evicted`].
+    pub fn set_flight_buffer_limit(
+        &mut self,
+        max_bytes: usize,
+        max_events: usize,
+    ) -> Result<(), TracerError> {
+        self.ensure_state("set_flight_buffer_limit")?;
+
+        let result = unsafe {
+            ffi::frida_controller_set_flight_buffer_limit(
+                self.ptr,
+                max_bytes as u64,
+                max_events as u64,
+            )
+        };
+
+        check_status(result, "set flight buffer limit")
     }
 
     /// Enable or disable the detail lane
-    pub fn set_detail_enabled(&mut self, enabled: bool) -> anyhow::Result<()> {
-        let result = unsafe { ffi::frida_controller_set_detail_enabled(self.ptr, enabled as u32) };
+    pub fn set_detail_enabled(&mut self, enabled: bool) -> Result<(), TracerError> {
+        self.ensure_state("set_detail_enabled")?;
 
-        if result != 0 {
-            anyhow::bail!("Failed to update detail lane state");
-        }
+        let result = unsafe { ffi::frida_controller_set_detail_enabled(self.ptr, enabled as u32) };
 
-        Ok(())
+        check_status(result, "update detail lane state")
     }
 
     /// Start ATF session output without resuming the process
-    pub fn start_session(&mut self) -> anyhow::Result<()> {
-        let result = unsafe { ffi::frida_controller_start_session(self.ptr) };
+    pub fn start_session(&mut self) -> Result<(), TracerError> {
+        self.ensure_state("start_session")?;
 
-        if result != 0 {
-            anyhow::bail!("Failed to start ATF session");
-        }
+        let result = unsafe { ffi::frida_controller_start_session(self.ptr) };
 
-        Ok(())
+        check_status(result, "start ATF session")
     }
 
     /// Stop ATF session output and finalize files
-    pub fn stop_session(&mut self) -> anyhow::Result<()> {
+    pub fn stop_session(&mut self) -> Result<(), TracerError> {
         let result = unsafe { ffi::frida_controller_stop_session(self.ptr) };
 
-        if result != 0 {
-            anyhow::bail!("Failed to stop ATF session");
-        }
+        check_status(result, "stop ATF session")
+    }
 
-        Ok(())
+    /// Run `f` inside an ATF session, stopping the session even if `f` fails
+    ///
+    /// Returns the closure's result; a stop failure is reported only when the
+    /// closure itself succeeded.
+    pub fn with_session<F, R>(&mut self, f: F) -> anyhow::Result<R>
+    where
+        F: FnOnce(&mut Self) -> anyhow::Result<R>,
+    {
+        bracket(
+            self,
+            |controller| Ok(controller.start_session()?),
+            |controller| Ok(controller.stop_session()?),
+            f,
+        )
     }
 
     /// Resume a suspended process
-    pub fn resume(&mut self) -> anyhow::Result<()> {
+    pub fn resume(&mut self) -> Result<(), TracerError> {
+        self.ensure_state("resume")?;
+
         let result = unsafe { ffi::frida_controller_resume(self.ptr) };
 
-        if result != 0 {
-            anyhow::bail!("Failed to resume process");
-        }
+        check_status(result, "resume process")
+    }
 
-        Ok(())
+    /// Block until the spawned or attached process exits
+    ///
+    /// Returns the exit code (128 + signal number for a killed process), or
+    /// `None` if `timeout` elapsed first; a `None` timeout waits indefinitely.
+    /// Fails if the controller enters the failed state.
+    ///
+    /// The status comes from `waitpid`, which only works for our own
+    /// children. A process spawned through Frida on macOS, or one we attached
+    /// to, isn't our child; once it exits this returns `Ok(None)` because
+    /// Frida's detach signal doesn't carry an exit code either.
+    pub fn wait_for_exit(&mut self, timeout: Option<Duration>) -> Result<Option<i32>, TracerError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            if self.get_state() == ProcessState::Failed {
+                return Err(TracerError::Failed(
+                    "wait for exit (the traced process failed)",
+                ));
+            }
+
+            let Some(slice) = next_wait_slice(deadline, Instant::now()) else {
+                return Ok(None);
+            };
+            let mut exit_code: c_int = 0;
+            let result = unsafe {
+                ffi::frida_controller_wait(self.ptr, slice.as_millis() as c_uint, &mut exit_code)
+            };
+
+            match result {
+                0 => continue,
+                1 => return Ok(collected_exit_code(exit_code)),
+                status => check_status(status, "wait for process exit")?,
+            }
+        }
     }
 
     /// Detach from the process
-    pub fn detach(&mut self) -> anyhow::Result<()> {
+    pub fn detach(&mut self) -> Result<(), TracerError> {
         let result = unsafe { ffi::frida_controller_detach(self.ptr) };
 
-        if result != 0 {
-            anyhow::bail!("Failed to detach from process");
-        }
-
-        Ok(())
+        check_status(result, "detach from process")
     }
 
     /// Get current statistics
@@ -271,6 +979,24 @@ impl TracerController {
         unsafe { ffi::frida_controller_get_stats(self.ptr) }
     }
 
+    /// Per-thread event counts for the registered threads
+    ///
+    /// At most [`MAX_THREAD_STATS`] threads are reported. `last_event_ns` is
+    /// the monotonic time of the thread's most recent ring swap, so it lags
+    /// the thread's actual last event by up to one ring.
+    pub fn get_thread_stats(&self) -> Vec<ThreadStats> {
+        let mut stats = vec![ThreadStats::default(); MAX_THREAD_STATS];
+        let count = unsafe {
+            ffi::frida_controller_get_thread_stats(
+                self.ptr,
+                stats.as_mut_ptr(),
+                MAX_THREAD_STATS as c_uint,
+            )
+        };
+        stats.truncate(thread_stats_len(count, MAX_THREAD_STATS));
+        stats
+    }
+
     /// Get current process state
     pub fn get_state(&self) -> ProcessState {
         unsafe { ffi::frida_controller_get_state(self.ptr) }
@@ -280,6 +1006,134 @@ impl TracerController {
     pub fn get_flight_state(&self) -> FlightRecorderState {
         unsafe { ffi::frida_controller_get_flight_state(self.ptr) }
     }
+
+    /// Whether the controller is attached to a process (attached or running)
+    pub fn is_attached(&self) -> bool {
+        is_attached_state(self.get_state())
+    }
+
+    /// Number of hooks installed in the attached process
+    pub fn hook_count(&self) -> u32 {
+        self.get_stats().hooks_installed
+    }
+
+    /// Guard the attached session so it is stopped and detached on drop
+    pub fn session(&mut self) -> TracerSession<'_> {
+        TracerSession::new(self)
+    }
+
+    fn ensure_state(&self, operation: &'static str) -> Result<(), TracerError> {
+        check_transition(operation, self.get_state())
+    }
+}
+
+/// Attached tracing session that is torn down when dropped
+///
+/// Dropping the guard stops ATF output and detaches, so an early `?` return
+/// after attach never leaves the target attached. Use [`TracerSession::finish`]
+/// to observe teardown errors instead of discarding them.
+pub struct TracerSession<'a, C: SessionTeardown = TracerController> {
+    controller: &'a mut C,
+    finished: bool,
+}
+
+/// Teardown a [`TracerSession`] runs when finished or dropped
+pub trait SessionTeardown {
+    fn stop_session(&mut self) -> Result<(), TracerError>;
+    fn detach(&mut self) -> Result<(), TracerError>;
+}
+
+impl SessionTeardown for TracerController {
+    fn stop_session(&mut self) -> Result<(), TracerError> {
+        TracerController::stop_session(self)
+    }
+
+    fn detach(&mut self) -> Result<(), TracerError> {
+        TracerController::detach(self)
+    }
+}
+
+impl<'a, C: SessionTeardown> TracerSession<'a, C> {
+    fn new(controller: &'a mut C) -> Self {
+        Self {
+            controller,
+            finished: false,
+        }
+    }
+
+    /// Stop the session and detach, reporting the first failure
+    pub fn finish(mut self) -> Result<(), TracerError> {
+        self.finished = true;
+        let stopped = self.controller.stop_session();
+        let detached = self.controller.detach();
+        stopped.and(detached)
+    }
+}
+
+impl TracerSession<'_> {
+    /// Install hooks in the attached process
+    pub fn install_hooks(&mut self) -> Result<(), TracerError> {
+        self.controller.install_hooks()
+    }
+
+    /// Arm flight recorder trigger
+    pub fn arm_trigger(&mut self, pre_roll_ms: u32, post_roll_ms: u32) -> Result<(), TracerError> {
+        self.controller.arm_trigger(pre_roll_ms, post_roll_ms)
+    }
+
+    /// Fire flight recorder trigger
+    pub fn fire_trigger(&mut self) -> Result<(), TracerError> {
+        self.controller.fire_trigger()
+    }
+
+    /// Disarm flight recorder trigger
+    pub fn disarm_trigger(&mut self) -> Result<(), TracerError> {
+        self.controller.disarm_trigger()
+    }
+
+    /// Cap the flight recorder's pre-roll buffer
+    pub fn set_flight_buffer_limit(
+        &mut self,
+        max_bytes: usize,
+        max_events: usize,
+    ) -> Result<(), TracerError> {
+        self.controller
+            .set_flight_buffer_limit(max_bytes, max_events)
+    }
+
+    /// Enable or disable the detail lane
+    pub fn set_detail_enabled(&mut self, enabled: bool) -> Result<(), TracerError> {
+        self.controller.set_detail_enabled(enabled)
+    }
+
+    /// Resume a suspended process
+    pub fn resume(&mut self) -> Result<(), TracerError> {
+        self.controller.resume()
+    }
+
+    /// Get current statistics
+    pub fn get_stats(&self) -> TracerStats {
+        self.controller.get_stats()
+    }
+
+    /// Get current flight recorder state
+    pub fn get_flight_state(&self) -> FlightRecorderState {
+        self.controller.get_flight_state()
+    }
+
+    /// Block until the process exits; see [`TracerController::wait_for_exit`]
+    pub fn wait_for_exit(&mut self, timeout: Option<Duration>) -> Result<Option<i32>, TracerError> {
+        self.controller.wait_for_exit(timeout)
+    }
+}
+
+impl<C: SessionTeardown> Drop for TracerSession<'_, C> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.controller.stop_session();
+            let _ = self.controller.detach();
+        }
+    }
 }
 
 impl Drop for TracerController {
@@ -298,6 +1152,8 @@ unsafe impl Sync for TracerController {}
 
 #[cfg(test)]
 mod tests {
+    #![allow(non_snake_case)]
+
     use super::*;
     // Keep only a lightweight sanity test here; all C++ tests run via
     // generated wrappers in tests/tests.rs to avoid duplicate execution.
@@ -305,4 +1161,705 @@ mod tests {
     fn test_controller_creation() {
         let _ = TracerController::new("./test_output");
     }
+
+    #[test]
+    fn is_attached_state__attached_or_running__then_true() {
+        assert!(is_attached_state(ProcessState::Attached));
+        assert!(is_attached_state(ProcessState::Running));
+    }
+
+    #[test]
+    fn is_attached_state__other_states__then_false() {
+        for state in [
+            ProcessState::Uninitialized,
+            ProcessState::Initialized,
+            ProcessState::Spawning,
+            ProcessState::Suspended,
+            ProcessState::Attaching,
+            ProcessState::Detaching,
+            ProcessState::Failed,
+        ] {
+            assert!(!is_attached_state(state), "{:?}", state);
+        }
+    }
+
+    #[test]
+    fn process_state__serde__then_round_trips_as_lowercase_name() {
+        for state in [
+            ProcessState::Uninitialized,
+            ProcessState::Initialized,
+            ProcessState::Spawning,
+            ProcessState::Suspended,
+            ProcessState::Attaching,
+            ProcessState::Attached,
+            ProcessState::Running,
+            ProcessState::Detaching,
+            ProcessState::Failed,
+        ] {
+            let json = serde_json::to_value(state).unwrap();
+            assert_eq!(json, serde_json::json!(state.as_str()));
+            assert_eq!(json, serde_json::json!(state.to_string()));
+            assert_eq!(serde_json::from_value::<ProcessState>(json).unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn flight_recorder_state__serde__then_round_trips_as_lowercase_name() {
+        for state in [
+            FlightRecorderState::Idle,
+            FlightRecorderState::Armed,
+            FlightRecorderState::PreRoll,
+            FlightRecorderState::Recording,
+            FlightRecorderState::PostRoll,
+        ] {
+            let json = serde_json::to_value(state).unwrap();
+            assert_eq!(
+                serde_json::from_value::<FlightRecorderState>(json).unwrap(),
+                state
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&FlightRecorderState::PreRoll).unwrap(),
+            "\"preroll\""
+        );
+        assert_eq!(FlightRecorderState::PostRoll.to_string(), "post-roll");
+    }
+
+    #[test]
+    fn state_enums__numeric_discriminant__then_deserialized() {
+        assert_eq!(
+            serde_json::from_str::<ProcessState>("5").unwrap(),
+            ProcessState::Attached
+        );
+        assert_eq!(
+            serde_json::from_str::<FlightRecorderState>("2").unwrap(),
+            FlightRecorderState::PreRoll
+        );
+    }
+
+    #[test]
+    fn state_enums__unknown_value__then_error() {
+        assert!(serde_json::from_str::<ProcessState>("9").is_err());
+        assert!(serde_json::from_str::<ProcessState>("-1").is_err());
+        assert!(serde_json::from_str::<FlightRecorderState>("5").is_err());
+        assert!(serde_json::from_str::<FlightRecorderState>("18446744073709551615").is_err());
+        assert!(serde_json::from_str::<ProcessState>("\"Attached\"").is_err());
+        assert_eq!(ProcessState::from_raw(42), None);
+    }
+
+    #[test]
+    fn tracer_stats__serialize__then_json_has_all_fields() {
+        let stats = TracerStats {
+            events_captured: 1200,
+            events_dropped: 3,
+            bytes_written: 4096,
+            active_threads: 2,
+            hooks_installed: 42,
+            fallback_events: 1,
+            preroll_evicted: 5,
+        };
+
+        let json = serde_json::to_value(stats).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "events_captured": 1200,
+                "events_dropped": 3,
+                "bytes_written": 4096,
+                "active_threads": 2,
+                "hooks_installed": 42,
+                "fallback_events": 1,
+                "preroll_evicted": 5,
+            })
+        );
+    }
+
+    #[test]
+    fn tracer_stats__estimated_fn_calls__then_three_events_per_call() {
+        let stats = TracerStats {
+            events_captured: 3_001,
+            events_dropped: 0,
+            bytes_written: 0,
+            active_threads: 1,
+            hooks_installed: 1,
+            fallback_events: 0,
+            preroll_evicted: 0,
+        };
+
+        assert_eq!(TracerStats::EVENTS_PER_CALL, 3);
+        assert_eq!(stats.estimated_fn_calls(), 1_000);
+    }
+
+    #[test]
+    fn thread_stats_len__native_counts__then_clamped_to_buffer() {
+        assert_eq!(thread_stats_len(3, MAX_THREAD_STATS), 3);
+        assert_eq!(thread_stats_len(100, MAX_THREAD_STATS), MAX_THREAD_STATS);
+        assert_eq!(thread_stats_len(-1, MAX_THREAD_STATS), 0);
+    }
+
+    #[test]
+    fn set_flight_buffer_limit__before_attach__then_invalid_state() {
+        let mut controller = detached_controller();
+
+        let err = controller
+            .set_flight_buffer_limit(1 << 20, 10_000)
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            TracerError::InvalidState {
+                operation: "set_flight_buffer_limit",
+                actual: ProcessState::Uninitialized,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn get_thread_stats__native_error__then_empty() {
+        let controller = detached_controller();
+
+        assert!(controller.get_thread_stats().is_empty());
+    }
+
+    fn detached_controller() -> TracerController {
+        // Null handle: every FFI call is rejected by the C side
+        TracerController {
+            ptr: ptr::null_mut(),
+        }
+    }
+
+    /// Records teardown calls; each one fails, like a detached controller's
+    #[derive(Default)]
+    struct MockTeardown {
+        calls: Vec<&'static str>,
+    }
+
+    impl SessionTeardown for MockTeardown {
+        fn stop_session(&mut self) -> Result<(), TracerError> {
+            self.calls.push("stop_session");
+            Err(TracerError::Failed("stop ATF session"))
+        }
+
+        fn detach(&mut self) -> Result<(), TracerError> {
+            self.calls.push("detach");
+            Err(TracerError::Failed("detach from process"))
+        }
+    }
+
+    #[test]
+    fn tracer_session__dropped__then_stops_and_detaches() {
+        let mut teardown = MockTeardown::default();
+
+        {
+            let _session = TracerSession::new(&mut teardown);
+        }
+
+        assert_eq!(teardown.calls, vec!["stop_session", "detach"]);
+    }
+
+    #[test]
+    fn tracer_session__early_return__then_detaches() {
+        fn run(teardown: &mut MockTeardown) -> Result<(), TracerError> {
+            let session = TracerSession::new(teardown);
+            Err(TracerError::Failed("install hooks"))?;
+            session.finish()
+        }
+
+        let mut teardown = MockTeardown::default();
+
+        assert!(run(&mut teardown).is_err());
+        assert_eq!(teardown.calls, vec!["stop_session", "detach"]);
+    }
+
+    #[test]
+    fn tracer_session__finished__then_drop_does_not_detach_again() {
+        let mut teardown = MockTeardown::default();
+
+        let result = TracerSession::new(&mut teardown).finish();
+
+        assert!(matches!(
+            result,
+            Err(TracerError::Failed("stop ATF session"))
+        ));
+        assert_eq!(teardown.calls, vec!["stop_session", "detach"]);
+    }
+
+    #[derive(Default)]
+    struct Bracketed {
+        calls: Vec<&'static str>,
+    }
+
+    fn start(target: &mut Bracketed) -> anyhow::Result<()> {
+        target.calls.push("start");
+        Ok(())
+    }
+
+    fn stop(target: &mut Bracketed) -> anyhow::Result<()> {
+        target.calls.push("stop");
+        Ok(())
+    }
+
+    #[test]
+    fn bracket__closure_succeeds__then_stops_and_returns_value() {
+        let mut target = Bracketed::default();
+
+        let value = bracket(&mut target, start, stop, |t| {
+            t.calls.push("body");
+            Ok(7)
+        })
+        .unwrap();
+
+        assert_eq!(value, 7);
+        assert_eq!(target.calls, vec!["start", "body", "stop"]);
+    }
+
+    #[test]
+    fn bracket__closure_fails__then_still_stops_and_returns_closure_error() {
+        let mut target = Bracketed::default();
+
+        let err = bracket(&mut target, start, stop, |_| -> anyhow::Result<()> {
+            anyhow::bail!("body failed")
+        })
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "body failed");
+        assert_eq!(target.calls, vec!["start", "stop"]);
+    }
+
+    #[test]
+    fn bracket__stop_fails_after_success__then_stop_error() {
+        let mut target = Bracketed::default();
+
+        let err = bracket(
+            &mut target,
+            start,
+            |_| anyhow::bail!("stop failed"),
+            |_| Ok(()),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "stop failed");
+    }
+
+    #[test]
+    fn bracket__start_fails__then_closure_and_stop_skipped() {
+        let mut target = Bracketed::default();
+
+        let err = bracket(
+            &mut target,
+            |_| anyhow::bail!("start failed"),
+            stop,
+            |t| -> anyhow::Result<()> {
+                t.calls.push("body");
+                Ok(())
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "start failed");
+        assert!(target.calls.is_empty());
+    }
+
+    #[test]
+    fn with_session__start_fails__then_closure_skipped() {
+        let mut controller = detached_controller();
+        let mut ran = false;
+
+        let err = controller
+            .with_session(|_| {
+                ran = true;
+                Ok(())
+            })
+            .unwrap_err();
+
+        assert!(!ran);
+        assert!(matches!(
+            err.downcast_ref::<TracerError>(),
+            Some(TracerError::InvalidState {
+                operation: "start_session",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn env_entries__valid_pairs__then_key_value_strings() {
+        let env = vec![
+            (
+                "DYLD_INSERT_LIBRARIES".to_string(),
+                "/tmp/libfoo.dylib".to_string(),
+            ),
+            ("APP_CONFIG".to_string(), "mode=debug".to_string()),
+            ("EMPTY".to_string(), String::new()),
+        ];
+
+        let entries = env_entries(&env).unwrap();
+
+        let entries: Vec<&str> = entries.iter().map(|e| e.to_str().unwrap()).collect();
+        assert_eq!(
+            entries,
+            vec![
+                "DYLD_INSERT_LIBRARIES=/tmp/libfoo.dylib",
+                "APP_CONFIG=mode=debug",
+                "EMPTY=",
+            ]
+        );
+    }
+
+    #[test]
+    fn env_entries__invalid_key__then_error() {
+        for key in ["", "A=B", "NUL\0KEY"] {
+            let err = env_entries(&[(key.to_string(), "1".to_string())]).unwrap_err();
+            assert!(
+                err.to_string()
+                    .to_lowercase()
+                    .contains("environment variable"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn env_entries__nul_in_value__then_error() {
+        let err = env_entries(&[("KEY".to_string(), "a\0b".to_string())]).unwrap_err();
+
+        assert!(err.to_string().contains("NUL byte"), "{err}");
+    }
+
+    #[test]
+    fn spawn_suspended_with_env__invalid_env__then_error_before_spawning() {
+        let mut controller = detached_controller();
+
+        let err = controller
+            .spawn_suspended_with_env("/bin/true", &[], &[("A=B".to_string(), "1".to_string())])
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Invalid environment variable name"));
+    }
+
+    #[test]
+    fn set_stdio_redirect__unopenable_path__then_error_before_ffi() {
+        let mut controller = detached_controller();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let missing = temp_dir.path().join("missing/stdout.log");
+
+        let err = controller
+            .set_stdio_redirect(Some(missing), None)
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("Failed to open stdout redirect"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn set_stdio_redirect__openable_paths__then_files_created() {
+        let mut controller = detached_controller();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let stdout = temp_dir.path().join("stdout.log");
+        let stderr = temp_dir.path().join("stderr.log");
+
+        // The null handle rejects the native call, but only after opening
+        let err = controller
+            .set_stdio_redirect(Some(stdout.clone()), Some(stderr.clone()))
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Failed to set stdio redirect"));
+        assert!(stdout.exists());
+        assert!(stderr.exists());
+    }
+
+    #[test]
+    fn next_wait_slice__deadlines__then_bounded_by_slice_and_remaining_time() {
+        let now = Instant::now();
+
+        assert_eq!(next_wait_slice(None, now), Some(WAIT_SLICE));
+        assert_eq!(
+            next_wait_slice(Some(now + Duration::from_secs(5)), now),
+            Some(WAIT_SLICE)
+        );
+        assert_eq!(
+            next_wait_slice(Some(now + Duration::from_millis(30)), now),
+            Some(Duration::from_millis(30))
+        );
+        assert_eq!(next_wait_slice(Some(now), now), None);
+    }
+
+    #[test]
+    fn collected_exit_code__status_unavailable__then_none_not_error() {
+        assert_eq!(collected_exit_code(0), Some(0));
+        assert_eq!(collected_exit_code(137), Some(137));
+        assert_eq!(collected_exit_code(-1), None);
+    }
+
+    #[test]
+    fn wait_for_exit__zero_timeout__then_none() {
+        let mut controller = detached_controller();
+
+        assert_eq!(
+            controller.wait_for_exit(Some(Duration::ZERO)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn wait_for_exit__native_wait_fails__then_error() {
+        let mut controller = detached_controller();
+
+        let err = controller
+            .wait_for_exit(Some(Duration::from_secs(1)))
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Failed to wait for process exit"));
+    }
+
+    #[test]
+    fn hook_filter_regex__glob__then_anchored_with_literals_escaped() {
+        let regex = hook_filter_regex("MyKit*.v?").unwrap();
+
+        assert_eq!(regex.to_str().unwrap(), r"^MyKit.*\.v.$");
+    }
+
+    #[test]
+    fn hook_filter_regex__re_prefix__then_regex_passed_through() {
+        let regex = hook_filter_regex("re:^_swift_(retain|release)").unwrap();
+
+        assert_eq!(regex.to_str().unwrap(), "^_swift_(retain|release)");
+    }
+
+    #[test]
+    fn hook_filter_regex__invalid_regex__then_error() {
+        let err = hook_filter_regex("re:(unclosed").unwrap_err();
+
+        assert!(
+            err.to_string().contains("Invalid hook filter pattern"),
+            "{err}"
+        );
+        assert!(hook_filter_regex("re:").is_err());
+    }
+
+    #[test]
+    fn hook_filter_regex__rust_only_syntax__then_error() {
+        for pattern in [
+            "re:(?i)malloc",
+            r"re:^\p{L}+$",
+            r"re:\pL",
+            "re:(?P<name>alloc)",
+            "re:(?<name>alloc)",
+            r"re:\Amalloc\z",
+            r"re:\x{41}",
+            "re:[a-z&&[^aeiou]]",
+            "re:[[a-z]]",
+            "re:[]a]",
+        ] {
+            let err = hook_filter_regex(pattern).unwrap_err();
+            assert!(err.to_string().contains("supported"), "{pattern}: {err}");
+        }
+    }
+
+    #[test]
+    fn hook_filter_regex__ecmascript_compatible_syntax__then_accepted() {
+        for pattern in [
+            r"re:^_swift_(?:retain|release)\d*$",
+            "re:[[:alpha:]_][[:alnum:]_]*",
+            r"re:[\]a-]",
+            r"re:\bobjc_msgSend\b",
+            "MyKit*",
+        ] {
+            assert!(hook_filter_regex(pattern).is_ok(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn set_hook_filter__invalid_pattern__then_error_before_ffi() {
+        let mut controller = detached_controller();
+
+        let err = controller
+            .set_hook_filter(&["MyKit".to_string()], &["re:[".to_string()])
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("Invalid hook filter pattern"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn set_hook_filter__native_rejects__then_error() {
+        let mut controller = detached_controller();
+
+        let err = controller
+            .set_hook_filter(&["MyKit".to_string()], &[])
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Failed to set hook filter"));
+    }
+
+    #[test]
+    fn check_status__native_codes__then_typed_errors() {
+        assert!(check_status(0, "attach to process").is_ok());
+        assert!(matches!(
+            check_status(-1, "attach to process"),
+            Err(TracerError::Failed("attach to process"))
+        ));
+        assert!(matches!(
+            check_status(-2, "attach to process"),
+            Err(TracerError::AlreadyAttached)
+        ));
+        assert!(matches!(
+            check_status(-3, "attach to process"),
+            Err(TracerError::PermissionDenied)
+        ));
+        assert!(matches!(
+            check_status(-4, "attach to process"),
+            Err(TracerError::NoSuchProcess)
+        ));
+        assert!(matches!(
+            check_status(-5, "resume process"),
+            Err(TracerError::NotSuspended)
+        ));
+        assert!(matches!(
+            check_status(-42, "resume process"),
+            Err(TracerError::Unknown(-42))
+        ));
+    }
+
+    #[test]
+    fn tracer_error__native_failure__then_message_names_operation() {
+        let err = check_status(-1, "attach to process").unwrap_err();
+
+        assert_eq!(err.to_string(), "Failed to attach to process");
+    }
+
+    #[test]
+    fn tracer_error__into_anyhow__then_question_mark_compiles() {
+        fn run(controller: &mut TracerController) -> anyhow::Result<()> {
+            controller.attach(1)?;
+            Ok(())
+        }
+
+        let err = run(&mut detached_controller()).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<TracerError>(),
+            Some(TracerError::Failed(_))
+        ));
+    }
+
+    fn processes() -> Vec<(u32, String)> {
+        vec![
+            (10, "launchd".to_string()),
+            (42, "MyApp".to_string()),
+            (43, "MyAppHelper".to_string()),
+            (77, "worker".to_string()),
+            (78, "worker".to_string()),
+        ]
+    }
+
+    #[test]
+    fn resolve_process_name__unique_match__then_pid() {
+        assert_eq!(resolve_process_name(&processes(), "MyApp", 1).unwrap(), 42);
+    }
+
+    #[test]
+    fn resolve_process_name__no_match__then_not_found() {
+        let err = resolve_process_name(&processes(), "Missing", 1).unwrap_err();
+
+        assert!(matches!(err, TracerError::ProcessNotFound(_)));
+    }
+
+    #[test]
+    fn resolve_process_name__several_matches__then_error_lists_pids() {
+        let err = resolve_process_name(&processes(), "worker", 1).unwrap_err();
+
+        assert!(err.to_string().contains("PIDs 77, 78"), "{err}");
+    }
+
+    #[test]
+    fn resolve_process_name__own_process__then_ignored() {
+        assert_eq!(
+            resolve_process_name(&processes(), "worker", 77).unwrap(),
+            78
+        );
+    }
+
+    #[test]
+    fn running_processes__current_system__then_includes_self() {
+        let own_pid = std::process::id();
+
+        assert!(running_processes().iter().any(|(pid, _)| *pid == own_pid));
+    }
+
+    #[test]
+    fn check_transition__attached_operations_before_attach__then_invalid_state() {
+        for state in [ProcessState::Uninitialized, ProcessState::Initialized] {
+            for operation in [
+                "install_hooks",
+                "arm_trigger",
+                "fire_trigger",
+                "disarm_trigger",
+                "set_detail_enabled",
+                "start_session",
+                "resume",
+            ] {
+                let err = check_transition(operation, state).unwrap_err();
+                assert!(
+                    matches!(err, TracerError::InvalidState { actual, .. } if actual == state),
+                    "{operation} in {state:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn check_transition__spawn_sequence__then_hooks_before_resume_only() {
+        // spawn_suspended leaves the process suspended until attach
+        assert!(check_transition("install_hooks", ProcessState::Suspended).is_err());
+        assert!(check_transition("resume", ProcessState::Suspended).is_ok());
+
+        // attach -> install_hooks -> arm_trigger -> resume
+        assert!(check_transition("install_hooks", ProcessState::Attached).is_ok());
+        assert!(check_transition("arm_trigger", ProcessState::Attached).is_ok());
+        assert!(check_transition("resume", ProcessState::Attached).is_ok());
+
+        // Once running, the process can't be resumed again
+        assert!(check_transition("start_session", ProcessState::Running).is_ok());
+        assert!(check_transition("resume", ProcessState::Running).is_err());
+    }
+
+    #[test]
+    fn check_transition__unguarded_operation__then_allowed_in_any_state() {
+        assert!(check_transition("detach", ProcessState::Failed).is_ok());
+        assert!(check_transition("stop_session", ProcessState::Uninitialized).is_ok());
+    }
+
+    #[test]
+    fn tracer_error__invalid_state__then_message_names_expected_and_actual() {
+        let err = check_transition("resume", ProcessState::Running).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Cannot resume while the process is running (expected suspended or attached)"
+        );
+    }
+
+    #[test]
+    fn tracer_controller__fresh_controller__then_guarded_operations_rejected() {
+        let mut controller = detached_controller();
+
+        for result in [
+            controller.install_hooks(),
+            controller.arm_trigger(0, 0),
+            controller.start_session(),
+            controller.resume(),
+        ] {
+            let err = result.unwrap_err();
+            assert!(matches!(err, TracerError::InvalidState { .. }), "{err}");
+        }
+    }
 }
@@ -203,4 +203,17 @@ size_t ring_buffer_available_write_raw(RingBufferHeader* header) {
     return (read_pos - write_pos - 1u) & rb_mask_from_header(header);
 }
 
+size_t ring_buffer_evict_oldest_raw(RingBufferHeader* header, size_t keep) {
+    if (!header || header->capacity == 0) return 0;
+    uint32_t mask = rb_mask_from_header(header);
+    uint32_t read_pos = __atomic_load_n(&header->read_pos, __ATOMIC_ACQUIRE);
+    uint32_t write_pos = __atomic_load_n(&header->write_pos, __ATOMIC_ACQUIRE);
+    size_t available = (write_pos - read_pos) & mask;
+    if (available <= keep) return 0;
+    size_t evicted = available - keep;
+    uint32_t next_pos = (read_pos + static_cast<uint32_t>(evicted)) & mask;
+    __atomic_store_n(&header->read_pos, next_pos, __ATOMIC_RELEASE);
+    return evicted;
+}
+
 }
@@ -3,6 +3,10 @@
 //!
 //! This test is `#[ignore]`d by default — run explicitly:
 //!   cargo test --release -p tracer_backend --test bench_e2e_overhead -- --ignored --nocapture
+//!
+//! To compare two results files (e.g. a saved baseline against the latest run):
+//!   ADA_BENCH_BASELINE=path/to/baseline.json \
+//!   cargo test --release -p tracer_backend --test bench_e2e_overhead bench_compare -- --ignored --nocapture
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -14,6 +18,7 @@ use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::{Duration, Instant};
+use tracer_backend::ffi::TracerStats;
 
 // ========================== Configuration ==========================
 
@@ -60,7 +65,7 @@ struct CiOverrides {
 
 // ========================== Results ==========================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct BenchResults {
     timestamp: String,
     git_sha: String,
@@ -71,7 +76,7 @@ struct BenchResults {
     verdict: Verdict,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct LanguageResult {
     total_calls: u64,
     baseline_median_ms: f64,
@@ -91,7 +96,7 @@ struct LanguageResult {
     failures: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct Verdict {
     pass: bool,
     failures: Vec<String>,
@@ -112,6 +117,22 @@ struct TracerStatsFile {
     hooks_installed: u32,
     #[serde(default)]
     fallback_events: u64,
+    #[serde(default)]
+    preroll_evicted: u64,
+}
+
+impl From<&TracerStatsFile> for TracerStats {
+    fn from(file: &TracerStatsFile) -> Self {
+        TracerStats {
+            events_captured: file.events_captured,
+            events_dropped: file.events_dropped,
+            bytes_written: file.bytes_written,
+            active_threads: 0,
+            hooks_installed: file.hooks_installed,
+            fallback_events: file.fallback_events,
+            preroll_evicted: file.preroll_evicted,
+        }
+    }
 }
 
 fn parse_tracer_stats(output_dir: &Path) -> Option<TracerStatsFile> {
@@ -570,7 +591,7 @@ fn bench_config(
 
     let (events_captured, hooks_installed, fallback_events, estimated_fn_calls) =
         if let Some(ref ts) = last_tracer_stats {
-            let est = ts.events_captured / 3;
+            let est = TracerStats::from(ts).estimated_fn_calls();
             (ts.events_captured, ts.hooks_installed, ts.fallback_events, est)
         } else {
             (0, 0, 0, 0)
@@ -1051,3 +1072,276 @@ fn bench_combined_verdict() {
 
     assert!(pass, "Benchmark failures: {:?}", all_failures);
 }
+
+// ========================== Compare ==========================
+
+/// Default percentage a metric may move before the config is flagged.
+const DEFAULT_MAX_DELTA_PCT: f64 = 10.0;
+
+/// Baseline vs current values for one `platform/lang_config` entry.
+#[derive(Debug)]
+struct ConfigDelta {
+    key: String,
+    per_call_ns: (f64, f64),
+    memory_overhead_kb: (i64, i64),
+    trace_bytes: (u64, u64),
+    flagged: bool,
+}
+
+/// Relative change from `before` to `after`, in percent.
+/// Anything appearing from zero counts as an unbounded change.
+fn pct_change(before: f64, after: f64) -> f64 {
+    if before == 0.0 {
+        if after == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (after - before) / before.abs() * 100.0
+    }
+}
+
+fn load_bench_results(path: &Path) -> BenchResults {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&content)
+        .unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Pair up configs present in both results, sorted by key.
+/// Returns the deltas plus keys found on only one side.
+fn compare_results(
+    baseline: &BenchResults,
+    current: &BenchResults,
+    max_delta_pct: f64,
+) -> (Vec<ConfigDelta>, Vec<String>) {
+    let flatten = |results: &BenchResults| {
+        let mut flat: Vec<(String, LanguageResult)> = results
+            .results
+            .iter()
+            .flat_map(|(platform, configs)| {
+                configs
+                    .iter()
+                    .map(move |(key, r)| (format!("{}/{}", platform, key), r.clone()))
+            })
+            .collect();
+        flat.sort_by(|a, b| a.0.cmp(&b.0));
+        flat
+    };
+    let before: HashMap<String, LanguageResult> = flatten(baseline).into_iter().collect();
+    let after = flatten(current);
+
+    let mut deltas = Vec::new();
+    let mut unmatched: Vec<String> = before
+        .keys()
+        .filter(|key| !after.iter().any(|(k, _)| k == *key))
+        .cloned()
+        .collect();
+
+    for (key, cur) in &after {
+        let Some(base) = before.get(key) else {
+            unmatched.push(key.clone());
+            continue;
+        };
+        let moved = [
+            pct_change(base.per_call_ns, cur.per_call_ns),
+            pct_change(
+                base.memory_overhead_kb as f64,
+                cur.memory_overhead_kb as f64,
+            ),
+            pct_change(base.trace_bytes as f64, cur.trace_bytes as f64),
+        ]
+        .iter()
+        .any(|pct| pct.abs() > max_delta_pct);
+
+        deltas.push(ConfigDelta {
+            key: key.clone(),
+            per_call_ns: (base.per_call_ns, cur.per_call_ns),
+            memory_overhead_kb: (base.memory_overhead_kb, cur.memory_overhead_kb),
+            trace_bytes: (base.trace_bytes, cur.trace_bytes),
+            flagged: moved,
+        });
+    }
+
+    unmatched.sort();
+    (deltas, unmatched)
+}
+
+fn format_pct(before: f64, after: f64) -> String {
+    let pct = pct_change(before, after);
+    if pct.is_infinite() {
+        "new".to_string()
+    } else {
+        format!("{:+.1}%", pct)
+    }
+}
+
+fn print_compare_table(deltas: &[ConfigDelta], unmatched: &[String], max_delta_pct: f64) {
+    println!(
+        "  {:<32}  {:>20}  {:>22}  {:>22}   {:<6}",
+        "Config", "Per-call ns", "Memory KB", "Trace bytes", "Status"
+    );
+    println!(
+        "  {:<32}  {:>20}  {:>22}  {:>22}   {:<6}",
+        "\u{2500}".repeat(32),
+        "\u{2500}".repeat(20),
+        "\u{2500}".repeat(22),
+        "\u{2500}".repeat(22),
+        "\u{2500}".repeat(6)
+    );
+    for delta in deltas {
+        let (ns_before, ns_after) = delta.per_call_ns;
+        let (mem_before, mem_after) = delta.memory_overhead_kb;
+        let (trace_before, trace_after) = delta.trace_bytes;
+        println!(
+            "  {:<32}  {:>20}  {:>22}  {:>22}   {:<6}",
+            delta.key,
+            format!("{:.0} ({})", ns_after, format_pct(ns_before, ns_after)),
+            format!(
+                "{} ({})",
+                mem_after,
+                format_pct(mem_before as f64, mem_after as f64)
+            ),
+            format!(
+                "{} ({})",
+                trace_after,
+                format_pct(trace_before as f64, trace_after as f64)
+            ),
+            if delta.flagged { "DRIFT" } else { "OK" }
+        );
+    }
+    for key in unmatched {
+        println!("  {:<32}  only in one results file", key);
+    }
+    println!();
+    println!(
+        "{} of {} configs moved more than {:.1}%",
+        deltas.iter().filter(|d| d.flagged).count(),
+        deltas.len(),
+        max_delta_pct
+    );
+}
+
+/// Compare `ADA_BENCH_BASELINE` against `ADA_BENCH_CURRENT` (default:
+/// `target/bench_e2e_results.json`) and fail if any config drifted by more
+/// than `ADA_BENCH_MAX_DELTA_PCT` percent (default 10).
+#[test]
+#[ignore]
+fn bench_compare() {
+    let project_root = find_project_root();
+    let baseline_path = PathBuf::from(
+        env::var("ADA_BENCH_BASELINE")
+            .expect("set ADA_BENCH_BASELINE to the baseline results file"),
+    );
+    let current_path = env::var("ADA_BENCH_CURRENT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| project_root.join("target/bench_e2e_results.json"));
+    let max_delta_pct = env::var("ADA_BENCH_MAX_DELTA_PCT")
+        .ok()
+        .map(|v| {
+            v.parse::<f64>()
+                .unwrap_or_else(|e| panic!("Invalid ADA_BENCH_MAX_DELTA_PCT {:?}: {}", v, e))
+        })
+        .unwrap_or(DEFAULT_MAX_DELTA_PCT);
+
+    let baseline = load_bench_results(&baseline_path);
+    let current = load_bench_results(&current_path);
+
+    println!("\n=== ADA E2E Benchmark Comparison ===");
+    println!(
+        "Baseline: {} ({})",
+        baseline_path.display(),
+        baseline.git_sha
+    );
+    println!(
+        "Current:  {} ({})\n",
+        current_path.display(),
+        current.git_sha
+    );
+
+    let (deltas, unmatched) = compare_results(&baseline, &current, max_delta_pct);
+    print_compare_table(&deltas, &unmatched, max_delta_pct);
+
+    let drifted: Vec<&str> = deltas
+        .iter()
+        .filter(|d| d.flagged)
+        .map(|d| d.key.as_str())
+        .collect();
+    assert!(drifted.is_empty(), "Configs drifted: {:?}", drifted);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn compare_results__per_call_regression__then_config_flagged() {
+    fn result(per_call_ns: f64, memory_overhead_kb: i64, trace_bytes: u64) -> LanguageResult {
+        LanguageResult {
+            total_calls: 1_000_000,
+            baseline_median_ms: 10.0,
+            traced_median_ms: 20.0,
+            overhead_ratio: 2.0,
+            frida_startup_ms: 50.0,
+            per_call_ns,
+            memory_overhead_kb,
+            baseline_rss_kb: 2048,
+            traced_rss_kb: 4096,
+            trace_bytes,
+            events_captured: 2_000_000,
+            hooks_installed: 12,
+            fallback_events: 0,
+            estimated_fn_calls: 1_000_000,
+            threshold_pass: true,
+            failures: Vec::new(),
+        }
+    }
+    fn results(configs: Vec<(&str, LanguageResult)>) -> BenchResults {
+        let configs = configs
+            .into_iter()
+            .map(|(key, r)| (key.to_string(), r))
+            .collect();
+        BenchResults {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            git_sha: "abc1234".to_string(),
+            platform: "macos".to_string(),
+            arch: "arm64".to_string(),
+            results: HashMap::from([("macos".to_string(), configs)]),
+            verdict: Verdict {
+                pass: true,
+                failures: Vec::new(),
+                configs_tested: 2,
+            },
+        }
+    }
+
+    let baseline = results(vec![
+        ("c_app_debug", result(100.0, 1024, 4096)),
+        ("swift_app_release", result(200.0, 2048, 8192)),
+        ("objc_app_debug", result(150.0, 1024, 4096)),
+    ]);
+    let current = results(vec![
+        ("c_app_debug", result(105.0, 1000, 4096)),
+        ("swift_app_release", result(260.0, 2048, 8192)),
+        ("cpp_app_debug", result(90.0, 1024, 4096)),
+    ]);
+    // Round-trip through JSON, as the compare mode reads files
+    let baseline: BenchResults =
+        serde_json::from_str(&serde_json::to_string(&baseline).unwrap()).unwrap();
+
+    let (deltas, unmatched) = compare_results(&baseline, &current, DEFAULT_MAX_DELTA_PCT);
+
+    assert_eq!(deltas.len(), 2);
+    assert_eq!(deltas[0].key, "macos/c_app_debug");
+    assert!(!deltas[0].flagged);
+    assert_eq!(deltas[1].key, "macos/swift_app_release");
+    assert!(deltas[1].flagged);
+    assert_eq!(deltas[1].per_call_ns, (200.0, 260.0));
+    assert_eq!(
+        unmatched,
+        vec![
+            "macos/cpp_app_debug".to_string(),
+            "macos/objc_app_debug".to_string()
+        ]
+    );
+    assert_eq!(format_pct(200.0, 260.0), "+30.0%");
+    assert_eq!(format_pct(0.0, 1.0), "new");
+}
@@ -9,6 +9,7 @@ extern "C" {
 #include <tracer_backend/agent/hook_registry.h>
 #include <tracer_backend/agent/comprehensive_hooks.h>
 
+using ada::agent::HookFilter;
 using ada::agent::HookRegistry;
 using ada::agent::HookPlanEntry;
 using ada::agent::plan_module_hooks;
@@ -65,3 +66,39 @@ TEST(comprehensive_hooks__plan_comprehensive__then_combines_modules, unit) {
     ada_exclude_destroy(xs);
 }
 
+
+TEST(comprehensive_hooks__hook_filter_include__then_only_matching_module_or_symbol, unit) {
+    HookFilter filter;
+    ASSERT_TRUE(filter.add_include("^MyKit$"));
+    ASSERT_TRUE(filter.add_include("^app_"));
+
+    HookRegistry reg;
+    std::vector<std::string> exports = {"foo", "app_main"};
+    auto own = plan_module_hooks("/Apps/X.app/Frameworks/MyKit.framework/MyKit",
+                                 exports, nullptr, reg, &filter);
+    auto system = plan_module_hooks("/usr/lib/libSystem.B.dylib", exports, nullptr, reg, &filter);
+
+    // Every symbol of the included module, only matching symbols elsewhere
+    EXPECT_EQ(own.size(), 2u);
+    ASSERT_EQ(system.size(), 1u);
+    EXPECT_EQ(system[0].symbol, "app_main");
+}
+
+TEST(comprehensive_hooks__hook_filter_exclude__then_matches_skipped, unit) {
+    HookFilter filter;
+    ASSERT_TRUE(filter.add_exclude("^_swift_"));
+
+    HookRegistry reg;
+    std::vector<std::string> exports = {"_swift_retain", "render"};
+    auto plan = plan_module_hooks("/usr/bin/main", exports, nullptr, reg, &filter);
+
+    ASSERT_EQ(plan.size(), 1u);
+    EXPECT_EQ(plan[0].symbol, "render");
+}
+
+TEST(comprehensive_hooks__hook_filter_invalid_pattern__then_rejected, unit) {
+    HookFilter filter;
+
+    EXPECT_FALSE(filter.add_include("(unclosed"));
+    EXPECT_TRUE(filter.empty());
+}
@@ -204,6 +204,78 @@ TEST_F(RingBufferTest, ring_buffer__concurrent_writes__then_preserve_order) {
     EXPECT_TRUE(ring_buffer_is_empty(rb));
 }
 
+// Test: ring_buffer__evict_oldest_raw__then_keeps_newest_events
+TEST_F(RingBufferTest, ring_buffer__evict_oldest_raw__then_keeps_newest_events) {
+    printf("[RING] evict_oldest_raw → keeps newest events\n");
+
+    // Arrange
+    rb = ring_buffer_create(memory.get(), buffer_size, sizeof(TestEvent));
+    ASSERT_NE(rb, nullptr);
+    RingBufferHeader* hdr = ring_buffer_get_header(rb);
+    for (uint64_t i = 0; i < 10; i++) {
+        TestEvent event = {.id = i, .timestamp = i * 100};
+        ASSERT_TRUE(ring_buffer_write_raw(hdr, sizeof(TestEvent), &event));
+    }
+
+    // Act / Assert - nothing to drop while within the limit
+    EXPECT_EQ(ring_buffer_evict_oldest_raw(hdr, 10), 0u);
+    EXPECT_EQ(ring_buffer_evict_oldest_raw(hdr, 4), 6u);
+    EXPECT_EQ(ring_buffer_available_read_raw(hdr), 4u);
+
+    TestEvent event;
+    ASSERT_TRUE(ring_buffer_read_raw(hdr, sizeof(TestEvent), &event));
+    EXPECT_EQ(event.id, 6u);
+}
+
+// Test: ring_buffer__evict_with_concurrent_producer__then_each_event_read_or_evicted_once
+TEST_F(RingBufferTest, ring_buffer__evict_with_concurrent_producer__then_each_event_read_or_evicted_once) {
+    printf("[RING] evict_with_concurrent_producer → each event read or evicted once\n");
+
+    // Arrange
+    rb = ring_buffer_create(memory.get(), buffer_size, sizeof(TestEvent));
+    ASSERT_NE(rb, nullptr);
+    RingBufferHeader* hdr = ring_buffer_get_header(rb);
+
+    const uint64_t num_events = 20000;
+    const size_t keep = 8;
+    std::atomic<bool> producer_done{false};
+
+    // Act - Producer thread writes like the agent's hooks
+    std::thread producer([&]() {
+        for (uint64_t i = 0; i < num_events; i++) {
+            TestEvent event = {.id = i, .timestamp = i * 100};
+            while (!ring_buffer_write_raw(hdr, sizeof(TestEvent), &event)) {
+                std::this_thread::yield();
+            }
+        }
+        producer_done = true;
+    });
+
+    // Act - Consumer evicts then reads, as the drain thread does while armed
+    uint64_t events_read = 0;
+    uint64_t events_evicted = 0;
+    uint64_t next_min_id = 0;
+    bool corrupted = false;
+    while (!producer_done || ring_buffer_available_read_raw(hdr) > 0) {
+        events_evicted += ring_buffer_evict_oldest_raw(hdr, keep);
+        TestEvent event;
+        while (ring_buffer_read_raw(hdr, sizeof(TestEvent), &event)) {
+            if (event.timestamp != event.id * 100 || event.id < next_min_id) {
+                corrupted = true;
+            }
+            next_min_id = event.id + 1;
+            events_read++;
+        }
+        std::this_thread::yield();
+    }
+    producer.join();
+
+    // Assert - no event lost, duplicated or torn
+    EXPECT_FALSE(corrupted);
+    EXPECT_EQ(events_read + events_evicted, num_events);
+    EXPECT_TRUE(ring_buffer_is_empty(rb));
+}
+
 // Test: ring_buffer__batch_operations__then_efficient_transfer
 TEST_F(RingBufferTest, ring_buffer__batch_operations__then_efficient_transfer) {
     printf("[RING] batch_operations → efficient transfer\n");
@@ -0,0 +1,144 @@
+//! Shields-style coverage badge for READMEs and the dashboard
+//!
+//! The badge shows total line coverage, colored by the same pass/warn
+//! cutoffs the dashboard uses for the "total" component.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::dashboard::{get_status, parse_lcov_metrics};
+use crate::thresholds::{CoverageConfig, Threshold};
+
+/// Approximate advance of one character in 11px Verdana, in pixels
+const CHAR_WIDTH: usize = 7;
+
+/// Horizontal padding around each half of the badge, in pixels
+const PADDING: usize = 10;
+
+/// Write a badge for the total line coverage in `input` to `output`
+pub fn write_badge(input: &Path, output: &Path, workspace: &Path) -> Result<()> {
+    if !input.exists() {
+        anyhow::bail!("LCOV file not found: {}", input.display());
+    }
+
+    let metrics = parse_lcov_metrics(input)?;
+    let coverage = metrics
+        .get("total")
+        .map(|total| total.line_coverage)
+        .unwrap_or_default();
+    let config = CoverageConfig::load(workspace)?;
+    let svg = badge_svg(coverage, config.threshold("total"));
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, svg).with_context(|| format!("Failed to write {}", output.display()))?;
+    println!(
+        "Coverage badge ({:.1}%) written to: {}",
+        coverage,
+        output.display()
+    );
+    Ok(())
+}
+
+/// Badge color for a `get_status` class
+fn status_color(status: &str) -> &'static str {
+    match status {
+        "pass" => "#4c1",
+        "warning" => "#dfb317",
+        _ => "#e05d44",
+    }
+}
+
+/// Render the badge SVG for a coverage percentage
+fn badge_svg(coverage: f64, threshold: Threshold) -> String {
+    let label = "coverage";
+    let value = format!("{:.1}%", coverage);
+    let color = status_color(&get_status(coverage, threshold));
+
+    let label_width = label.len() * CHAR_WIDTH + PADDING;
+    let value_width = value.len() * CHAR_WIDTH + PADDING;
+    let width = label_width + value_width;
+    // Text positions are in the 10x-scaled coordinate space used below
+    let label_x = label_width * 5;
+    let value_x = (label_width * 2 + value_width) * 5;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+  <title>{label}: {value}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="110">
+    <text x="{label_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)">{label}</text>
+    <text x="{label_x}" y="140" transform="scale(.1)">{label}</text>
+    <text x="{value_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)">{value}</text>
+    <text x="{value_x}" y="140" transform="scale(.1)">{value}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    #[test]
+    fn badge_svg__passing_coverage__then_green_with_percentage() {
+        let svg = badge_svg(85.3, Threshold::default());
+
+        assert!(svg.contains(">85.3%</text>"));
+        assert!(svg.contains(r##"fill="#4c1""##));
+        assert!(svg.contains("aria-label=\"coverage: "));
+    }
+
+    #[test]
+    fn badge_svg__coverage_levels__then_color_follows_threshold() {
+        let threshold = Threshold {
+            pass: 70.0,
+            warn: 50.0,
+        };
+
+        assert!(badge_svg(72.0, threshold).contains(r##"fill="#4c1""##));
+        assert!(badge_svg(55.0, threshold).contains(r##"fill="#dfb317""##));
+        assert!(badge_svg(49.9, threshold).contains(r##"fill="#e05d44""##));
+    }
+
+    #[test]
+    fn badge_svg__longer_value__then_wider_badge() {
+        let narrow = badge_svg(5.0, Threshold::default());
+        let wide = badge_svg(100.0, Threshold::default());
+
+        assert!(narrow.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="104""#));
+        assert!(wide.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="118""#));
+    }
+
+    #[test]
+    fn write_badge__lcov_input__then_svg_with_total_line_coverage() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lcov = temp_dir.path().join("merged.lcov");
+        fs::write(
+            &lcov,
+            "SF:/ws/query_engine/src/lib.rs\nDA:1,1\nDA:2,1\nDA:3,0\nDA:4,0\nLF:4\nLH:2\nend_of_record\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("badges/coverage.svg");
+
+        write_badge(&lcov, &output, temp_dir.path()).unwrap();
+
+        let svg = fs::read_to_string(output).unwrap();
+        assert!(svg.contains(">50.0%</text>"));
+        assert!(svg.contains(r##"fill="#e05d44""##));
+    }
+}
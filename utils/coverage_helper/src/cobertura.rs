@@ -0,0 +1,266 @@
+//! Cobertura XML export for CI systems that don't read LCOV
+//!
+//! Each project component becomes a `<package>` and each source file a
+//! `<class>`, with line and branch rates computed from the `DA:`/`BRDA:`
+//! records. Dependency and test files are left out, as on the dashboard.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::dashboard::{detect_component, parse_lcov_records, FileRecord};
+
+/// Convert a merged LCOV file into a Cobertura XML report
+///
+/// Class filenames are written relative to `source_root`.
+pub fn write_cobertura(input: &Path, output: &Path, source_root: &Path) -> Result<()> {
+    let content = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read LCOV file {}", input.display()))?;
+    let records = parse_lcov_records(&content);
+    let xml = cobertura_xml(&records, source_root, chrono::Utc::now().timestamp_millis());
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, xml).with_context(|| format!("Failed to write {}", output.display()))?;
+    println!("Cobertura report written to: {}", output.display());
+    Ok(())
+}
+
+/// Line and branch counts for a class, package or the whole report
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    lines_valid: usize,
+    lines_covered: usize,
+    branches_valid: usize,
+    branches_covered: usize,
+}
+
+impl Counts {
+    fn of(file: &FileRecord) -> Self {
+        Counts {
+            lines_valid: file.line_hits.len(),
+            lines_covered: file.line_hits.values().filter(|hits| **hits > 0).count(),
+            branches_valid: file.line_branches.values().map(|b| b.0).sum(),
+            branches_covered: file.line_branches.values().map(|b| b.1).sum(),
+        }
+    }
+
+    fn add(&mut self, other: Counts) {
+        self.lines_valid += other.lines_valid;
+        self.lines_covered += other.lines_covered;
+        self.branches_valid += other.branches_valid;
+        self.branches_covered += other.branches_covered;
+    }
+
+    fn line_rate(&self) -> String {
+        rate(self.lines_covered, self.lines_valid)
+    }
+
+    fn branch_rate(&self) -> String {
+        rate(self.branches_covered, self.branches_valid)
+    }
+}
+
+/// Cobertura rates are fractions; nothing to cover counts as fully covered
+fn rate(covered: usize, valid: usize) -> String {
+    if valid == 0 {
+        "1".to_string()
+    } else {
+        format!("{:.4}", covered as f64 / valid as f64)
+    }
+}
+
+/// Render parsed LCOV records as a Cobertura document
+fn cobertura_xml(records: &[FileRecord], source_root: &Path, timestamp: i64) -> String {
+    // Component -> files, in a stable order
+    let mut packages: BTreeMap<String, Vec<&FileRecord>> = BTreeMap::new();
+    for file in records {
+        let component = detect_component(&file.path);
+        if component == "dependencies" || component == "test_files" {
+            continue;
+        }
+        packages.entry(component).or_default().push(file);
+    }
+    for files in packages.values_mut() {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    let mut total = Counts::default();
+    let mut body = String::new();
+    for (name, files) in &packages {
+        let mut package = Counts::default();
+        let mut classes = String::new();
+        for file in files {
+            let counts = Counts::of(file);
+            package.add(counts);
+            write_class(&mut classes, file, counts, source_root);
+        }
+        total.add(package);
+
+        let _ = writeln!(
+            body,
+            r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            escape(name),
+            package.line_rate(),
+            package.branch_rate()
+        );
+        body.push_str("      <classes>\n");
+        body.push_str(&classes);
+        body.push_str("      </classes>\n");
+        body.push_str("    </package>\n");
+    }
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" ?>\n");
+    xml.push_str(
+        "<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n",
+    );
+    let _ = writeln!(
+        xml,
+        r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="coverage_helper" timestamp="{}">"#,
+        total.line_rate(),
+        total.branch_rate(),
+        total.lines_covered,
+        total.lines_valid,
+        total.branches_covered,
+        total.branches_valid,
+        timestamp
+    );
+    xml.push_str("  <sources>\n");
+    let _ = writeln!(
+        xml,
+        "    <source>{}</source>",
+        escape(&source_root.display().to_string())
+    );
+    xml.push_str("  </sources>\n");
+    xml.push_str("  <packages>\n");
+    xml.push_str(&body);
+    xml.push_str("  </packages>\n");
+    xml.push_str("</coverage>\n");
+    xml
+}
+
+fn write_class(out: &mut String, file: &FileRecord, counts: Counts, source_root: &Path) {
+    let filename = Path::new(&file.path)
+        .strip_prefix(source_root)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| file.path.clone());
+    // "tracer_backend/src/ring.c" -> "tracer_backend.src.ring"
+    let class_name = Path::new(&filename)
+        .with_extension("")
+        .display()
+        .to_string()
+        .replace('/', ".");
+
+    let _ = writeln!(
+        out,
+        r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+        escape(&class_name),
+        escape(&filename),
+        counts.line_rate(),
+        counts.branch_rate()
+    );
+    out.push_str("          <methods/>\n");
+    out.push_str("          <lines>\n");
+    for (number, hits) in &file.line_hits {
+        match file.line_branches.get(number) {
+            Some(&(valid, covered)) if valid > 0 => {
+                let _ = writeln!(
+                    out,
+                    r#"            <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                    number,
+                    hits,
+                    covered * 100 / valid,
+                    covered,
+                    valid
+                );
+            }
+            _ => {
+                let _ = writeln!(
+                    out,
+                    r#"            <line number="{}" hits="{}" branch="false"/>"#,
+                    number, hits
+                );
+            }
+        }
+    }
+    out.push_str("          </lines>\n");
+    out.push_str("        </class>\n");
+}
+
+/// Escape text for use in an XML attribute or element
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    const FIXTURE_LCOV: &str = include_str!("../tests/fixtures/cobertura/merged.lcov");
+    const EXPECTED_XML: &str = include_str!("../tests/fixtures/cobertura/expected.xml");
+
+    /// Element name, attributes (order-insensitive) and child elements
+    #[derive(Debug, PartialEq)]
+    struct Node {
+        name: String,
+        attributes: BTreeMap<String, String>,
+        text: String,
+        children: Vec<Node>,
+    }
+
+    fn node(element: roxmltree::Node) -> Node {
+        Node {
+            name: element.tag_name().name().to_string(),
+            attributes: element
+                .attributes()
+                .map(|a| (a.name().to_string(), a.value().to_string()))
+                .collect(),
+            text: element
+                .children()
+                .filter(|c| c.is_text())
+                .filter_map(|c| c.text())
+                .collect::<String>()
+                .trim()
+                .to_string(),
+            children: element
+                .children()
+                .filter(|c| c.is_element())
+                .map(node)
+                .collect(),
+        }
+    }
+
+    fn parse(xml: &str) -> Node {
+        let options = roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        };
+        let document = roxmltree::Document::parse_with_options(xml, options).unwrap();
+        node(document.root_element())
+    }
+
+    #[test]
+    fn cobertura_xml__fixture_lcov__then_matches_golden_file() {
+        let records = parse_lcov_records(FIXTURE_LCOV);
+
+        let xml = cobertura_xml(&records, Path::new("/ws"), 1_700_000_000_000);
+
+        assert_eq!(parse(&xml), parse(EXPECTED_XML));
+    }
+
+    #[test]
+    fn rate__no_valid_items__then_fully_covered() {
+        assert_eq!(rate(0, 0), "1");
+        assert_eq!(rate(1, 3), "0.3333");
+    }
+}
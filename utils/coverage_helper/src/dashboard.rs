@@ -1,11 +1,14 @@
 //! HTML Dashboard generator for coverage reports
 
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use crate::diff_cover::DiffCoverSettings;
+use crate::thresholds::{CoverageConfig, Threshold};
+
 /// Coverage metrics for a component
 #[derive(Debug, Default, Clone)]
 pub struct ComponentMetrics {
@@ -20,6 +23,23 @@ pub struct ComponentMetrics {
     pub branches_total: usize,
 }
 
+impl ComponentMetrics {
+    /// Fill in the percentages from the counts (left at 0 when there is no data)
+    fn compute_percentages(&mut self) {
+        if self.lines_total > 0 {
+            self.line_coverage = (self.lines_covered as f64 / self.lines_total as f64) * 100.0;
+        }
+        if self.functions_total > 0 {
+            self.function_coverage =
+                (self.functions_covered as f64 / self.functions_total as f64) * 100.0;
+        }
+        if self.branches_total > 0 {
+            self.branch_coverage =
+                (self.branches_covered as f64 / self.branches_total as f64) * 100.0;
+        }
+    }
+}
+
 /// Generate the HTML dashboard
 pub fn generate_dashboard(workspace: &Path, report_dir: &Path, merged_lcov: &Path) -> Result<()> {
     println!("\nGenerating HTML dashboard...");
@@ -29,14 +49,17 @@ pub fn generate_dashboard(workspace: &Path, report_dir: &Path, merged_lcov: &Pat
 
     // Parse LCOV file for metrics
     let metrics = parse_lcov_metrics(merged_lcov)?;
+    let config = CoverageConfig::load(workspace)?;
 
     // Get git information
     let commit = get_git_commit()?;
     let branch = get_git_branch()?;
     let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-    // Check diff-cover results if available
-    let changed_lines_metrics = get_diff_cover_metrics(report_dir)?;
+    // Run diff-cover first so its JSON report feeds the changed-lines panel
+    let diff_cover = DiffCoverSettings::from_env()?;
+    generate_diff_coverage_report(workspace, merged_lcov, report_dir, &diff_cover)?;
+    let changed_lines_metrics = get_diff_cover_metrics(report_dir, diff_cover.fail_under)?;
 
     // Read template
     let template_path = workspace.join("utils/coverage_helper/dashboard_template.html");
@@ -51,6 +74,7 @@ pub fn generate_dashboard(workspace: &Path, report_dir: &Path, merged_lcov: &Pat
     let html = replace_placeholders(
         template,
         &metrics,
+        &config,
         &changed_lines_metrics,
         &commit,
         &branch,
@@ -67,9 +91,6 @@ pub fn generate_dashboard(workspace: &Path, report_dir: &Path, merged_lcov: &Pat
         generate_full_html_report(merged_lcov, report_dir)?;
     }
 
-    // Generate diff-coverage HTML report
-    generate_diff_coverage_report(workspace, merged_lcov, report_dir)?;
-
     // Generate uncovered lines text file
     generate_uncovered_lines_report(merged_lcov, report_dir)?;
 
@@ -79,16 +100,156 @@ pub fn generate_dashboard(workspace: &Path, report_dir: &Path, merged_lcov: &Pat
     Ok(())
 }
 
-/// Parse LCOV file for coverage metrics
-fn parse_lcov_metrics(lcov_path: &Path) -> Result<HashMap<String, ComponentMetrics>> {
-    let metrics = HashMap::new();
+/// Write per-component, total and per-file coverage metrics as pretty JSON
+///
+/// Uses the same parsing as the HTML dashboard, so the numbers match it.
+/// With `worst`, only that many of the least-covered files are listed.
+pub fn write_metrics_json(merged_lcov: &Path, output: &Path, worst: Option<usize>) -> Result<()> {
+    if !merged_lcov.exists() {
+        anyhow::bail!("LCOV file not found: {}", merged_lcov.display());
+    }
+
+    let content = fs::read_to_string(merged_lcov)?;
+    let metrics = parse_lcov_content(&content);
+    let files = parse_file_metrics(&content);
+    let json = serde_json::to_string_pretty(&metrics_json(&metrics, &files, worst))?;
 
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, json).with_context(|| format!("Failed to write {}", output.display()))?;
+    println!("Coverage metrics written to: {}", output.display());
+    Ok(())
+}
+
+/// Build the metrics JSON document:
+/// `{"total": {...}, "components": {name: {...}}, "files": [{"path": ..., ...}]}`
+///
+/// `files` keeps the order given (least covered first) and is cut to `worst`.
+fn metrics_json(
+    metrics: &HashMap<String, ComponentMetrics>,
+    files: &[(String, ComponentMetrics)],
+    worst: Option<usize>,
+) -> serde_json::Value {
+    let components: serde_json::Map<String, serde_json::Value> = metrics
+        .iter()
+        .filter(|(name, _)| name.as_str() != "total")
+        .map(|(name, m)| (name.clone(), component_json(m)))
+        .collect();
+
+    let files: Vec<serde_json::Value> = files
+        .iter()
+        .take(worst.unwrap_or(usize::MAX))
+        .map(|(path, m)| {
+            let mut json = component_json(m);
+            json["path"] = serde_json::Value::from(path.as_str());
+            json
+        })
+        .collect();
+
+    serde_json::json!({
+        "total": component_json(&metrics.get("total").cloned().unwrap_or_default()),
+        "components": components,
+        "files": files,
+    })
+}
+
+fn component_json(metrics: &ComponentMetrics) -> serde_json::Value {
+    serde_json::json!({
+        "lines": coverage_json(metrics.lines_covered, metrics.lines_total, metrics.line_coverage),
+        "functions": coverage_json(
+            metrics.functions_covered,
+            metrics.functions_total,
+            metrics.function_coverage,
+        ),
+        "branches": coverage_json(
+            metrics.branches_covered,
+            metrics.branches_total,
+            metrics.branch_coverage,
+        ),
+    })
+}
+
+/// Percentage is null when there is no data, mirroring the dashboard's "N/A"
+fn coverage_json(covered: usize, total: usize, percent: f64) -> serde_json::Value {
+    serde_json::json!({
+        "covered": covered,
+        "total": total,
+        "percent": if total > 0 { Some(percent) } else { None },
+    })
+}
+
+/// Parse LCOV file for coverage metrics
+pub fn parse_lcov_metrics(lcov_path: &Path) -> Result<HashMap<String, ComponentMetrics>> {
     if !lcov_path.exists() {
-        return Ok(metrics);
+        return Ok(HashMap::new());
     }
 
     let content = fs::read_to_string(lcov_path)?;
-    let mut current_file = String::new();
+    Ok(parse_lcov_content(&content))
+}
+
+/// Per-file counts collected between `SF:` and `end_of_record`
+#[derive(Debug, Default)]
+pub(crate) struct FileRecord {
+    pub(crate) path: String,
+    lines_found: usize,
+    lines_hit: usize,
+    /// Line number -> hit count, from `DA:`
+    pub(crate) line_hits: BTreeMap<u32, u64>,
+    /// Function name -> hit count, from `FN:`/`FNDA:`
+    functions: HashMap<String, u64>,
+    branches_found: usize,
+    branches_hit: usize,
+    /// Line number -> (branches, branches taken), from `BRDA:`
+    pub(crate) line_branches: BTreeMap<u32, (usize, usize)>,
+    /// `LF`/`LH`/`FNF`/`FNH`/`BRF`/`BRH` summaries, used when the file has no
+    /// detailed records of that kind
+    summary_lines: Option<(usize, usize)>,
+    summary_functions: Option<(usize, usize)>,
+    summary_branches: Option<(usize, usize)>,
+}
+
+impl FileRecord {
+    fn new(path: &str) -> Self {
+        FileRecord {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn lines(&self) -> (usize, usize) {
+        if self.lines_found > 0 {
+            (self.lines_found, self.lines_hit)
+        } else {
+            self.summary_lines.unwrap_or_default()
+        }
+    }
+
+    fn functions(&self) -> (usize, usize) {
+        if !self.functions.is_empty() {
+            let hit = self.functions.values().filter(|hits| **hits > 0).count();
+            (self.functions.len(), hit)
+        } else {
+            self.summary_functions.unwrap_or_default()
+        }
+    }
+
+    pub(crate) fn branches(&self) -> (usize, usize) {
+        if self.branches_found > 0 {
+            (self.branches_found, self.branches_hit)
+        } else {
+            self.summary_branches.unwrap_or_default()
+        }
+    }
+}
+
+/// Parse LCOV content into per-component metrics
+///
+/// Detailed records (`DA`, `FN`/`FNDA`, `BRDA`) are preferred; the summary
+/// records are only used for a file that carries no detailed data of that
+/// kind, so the two are never double-counted.
+fn parse_lcov_content(content: &str) -> HashMap<String, ComponentMetrics> {
     let mut component_data: HashMap<String, ComponentMetrics> = HashMap::new();
 
     // Initialize component metrics
@@ -97,239 +258,194 @@ fn parse_lcov_metrics(lcov_path: &Path) -> Result<HashMap<String, ComponentMetri
     component_data.insert("query_engine".to_string(), ComponentMetrics::default());
     component_data.insert("total".to_string(), ComponentMetrics::default());
 
-    for line in content.lines() {
-        if line.starts_with("SF:") {
-            current_file = line[3..].to_string();
-        } else if line.starts_with("DA:") {
-            // Line coverage data
-            let parts: Vec<&str> = line[3..].split(',').collect();
-            if parts.len() == 2 {
-                let component = detect_component(&current_file);
+    for file in parse_lcov_records(content) {
+        accumulate_file_record(&mut component_data, &file);
+    }
 
-                // Skip dependencies and test files from coverage metrics
-                if component == "dependencies" || component == "test_files" {
-                    continue;
-                }
+    // Calculate percentages for all coverage types
+    for (name, metrics) in component_data.iter_mut() {
+        metrics.compute_percentages();
+        if metrics.branches_total > 0 {
+            eprintln!(
+                "DEBUG: {} branch coverage: {}/{} = {:.1}%",
+                name, metrics.branches_covered, metrics.branches_total, metrics.branch_coverage
+            );
+        } else if name != "total" && name != "other" {
+            eprintln!("DEBUG: {} has no branch data (branches_total=0)", name);
+        }
+    }
 
-                let is_covered = parts[1] != "0";
+    component_data
+}
 
-                // Update component metrics
-                component_data.entry(component.clone()).and_modify(|m| {
-                    m.lines_total += 1;
-                    if is_covered {
-                        m.lines_covered += 1;
-                    }
-                });
-
-                // Update total metrics (only for actual project files)
-                if component != "other" {
-                    component_data.entry("total".to_string()).and_modify(|m| {
-                        m.lines_total += 1;
-                        if is_covered {
-                            m.lines_covered += 1;
-                        }
-                    });
-                }
-            }
-        } else if line.starts_with("FNDA:") {
-            // Function coverage data
-            let parts: Vec<&str> = line[5..].split(',').collect();
-            if parts.len() >= 2 {
-                let component = detect_component(&current_file);
-
-                // Skip dependencies and test files from coverage metrics
-                if component == "dependencies" || component == "test_files" {
-                    continue;
-                }
+/// Per-file metrics for project sources, least line-covered first
+///
+/// Dependencies and test files are skipped as in the component totals;
+/// files without line data sort last. Ties are broken by path.
+pub(crate) fn parse_file_metrics(content: &str) -> Vec<(String, ComponentMetrics)> {
+    let mut files: Vec<(String, ComponentMetrics)> = parse_lcov_records(content)
+        .into_iter()
+        .filter(|file| {
+            let component = detect_component(&file.path);
+            component != "dependencies" && component != "test_files"
+        })
+        .map(|file| {
+            let (lines_total, lines_covered) = file.lines();
+            let (functions_total, functions_covered) = file.functions();
+            let (branches_total, branches_covered) = file.branches();
+            let mut metrics = ComponentMetrics {
+                lines_covered,
+                lines_total,
+                functions_covered,
+                functions_total,
+                branches_covered,
+                branches_total,
+                ..Default::default()
+            };
+            metrics.compute_percentages();
+            (file.path, metrics)
+        })
+        .collect();
+
+    files.sort_by(|(a_path, a), (b_path, b)| {
+        (a.lines_total == 0)
+            .cmp(&(b.lines_total == 0))
+            .then(a.line_coverage.total_cmp(&b.line_coverage))
+            .then_with(|| a_path.cmp(b_path))
+    });
+    files
+}
 
-                if let Ok(hits) = parts[0].parse::<i32>() {
-                    let is_covered = hits > 0;
+/// Split LCOV content into per-file records
+pub(crate) fn parse_lcov_records(content: &str) -> Vec<FileRecord> {
+    let mut records = Vec::new();
+    let mut record: Option<FileRecord> = None;
 
-                    // Update component metrics
-                    component_data.entry(component.clone()).and_modify(|m| {
-                        m.functions_total += 1;
-                        if is_covered {
-                            m.functions_covered += 1;
-                        }
-                    });
-
-                    // Update total metrics (only for actual project files)
-                    if component != "other" {
-                        component_data.entry("total".to_string()).and_modify(|m| {
-                            m.functions_total += 1;
-                            if is_covered {
-                                m.functions_covered += 1;
-                            }
-                        });
-                    }
-                }
-            }
-        } else if line.starts_with("BRDA:") {
-            // Branch coverage data
-            let parts: Vec<&str> = line[5..].split(',').collect();
-            if parts.len() >= 4 {
-                let component = detect_component(&current_file);
+    for line in content.lines() {
+        if let Some(path) = line.strip_prefix("SF:") {
+            records.extend(record.take());
+            record = Some(FileRecord::new(path));
+            continue;
+        }
 
-                // Skip dependencies and test files from coverage metrics
-                if component == "dependencies" || component == "test_files" {
-                    continue;
-                }
+        if line == "end_of_record" {
+            records.extend(record.take());
+            continue;
+        }
 
-                let is_covered = parts[3] != "-" && parts[3] != "0";
+        let Some(file) = record.as_mut() else {
+            continue;
+        };
 
-                // Update component metrics
-                component_data.entry(component.clone()).and_modify(|m| {
-                    m.branches_total += 1;
-                    if is_covered {
-                        m.branches_covered += 1;
-                    }
-                });
-
-                // Update total metrics (only for actual project files)
-                if component != "other" {
-                    component_data.entry("total".to_string()).and_modify(|m| {
-                        m.branches_total += 1;
-                        if is_covered {
-                            m.branches_covered += 1;
-                        }
-                    });
+        if let Some(data) = line.strip_prefix("DA:") {
+            // DA:<line>,<hits>
+            let parts: Vec<&str> = data.split(',').collect();
+            if parts.len() == 2 {
+                file.lines_found += 1;
+                if parts[1] != "0" {
+                    file.lines_hit += 1;
+                }
+                let number = parts[0].parse::<u32>();
+                if let (Ok(number), Ok(hits)) = (number, parts[1].parse::<u64>()) {
+                    *file.line_hits.entry(number).or_default() += hits;
                 }
             }
-        } else if line.starts_with("LF:") {
-            // Summary line for lines found (use if more accurate than counting DA lines)
-            if let Ok(total) = line[3..].parse::<usize>() {
-                let component = detect_component(&current_file);
-                component_data.entry(component.clone()).and_modify(|m| {
-                    // Only update if we haven't counted DA lines yet or this is more accurate
-                    if m.lines_total == 0 {
-                        m.lines_total = total;
-                    }
-                });
+        } else if let Some(data) = line.strip_prefix("FNDA:") {
+            // FNDA:<hits>,<name>
+            if let Some((hits, name)) = data.split_once(',') {
+                if let Ok(hits) = hits.parse::<u64>() {
+                    *file.functions.entry(name.to_string()).or_default() += hits;
+                }
             }
-        } else if line.starts_with("LH:") {
-            // Summary line for lines hit
-            if let Ok(hit) = line[3..].parse::<usize>() {
-                let component = detect_component(&current_file);
-                component_data.entry(component.clone()).and_modify(|m| {
-                    if m.lines_covered == 0 {
-                        m.lines_covered = hit;
-                    }
-                });
+        } else if let Some(data) = line.strip_prefix("FN:") {
+            // FN:<line>,<name>
+            if let Some((_, name)) = data.split_once(',') {
+                file.functions.entry(name.to_string()).or_default();
             }
-        } else if line.starts_with("FNF:") {
-            // Summary line for functions found
-            if let Ok(total) = line[4..].parse::<usize>() {
-                let component = detect_component(&current_file);
-
-                // Skip dependencies and test files
-                if component == "dependencies" || component == "test_files" {
-                    continue;
+        } else if let Some(data) = line.strip_prefix("BRDA:") {
+            // BRDA:<line>,<block>,<branch>,<taken>
+            let parts: Vec<&str> = data.split(',').collect();
+            if parts.len() >= 4 {
+                let taken = parts[3] != "-" && parts[3] != "0";
+                file.branches_found += 1;
+                if taken {
+                    file.branches_hit += 1;
                 }
-
-                // Add to component totals (don't replace, accumulate from multiple files)
-                component_data.entry(component.clone()).and_modify(|m| {
-                    m.functions_total += total;
-                });
-
-                // Also add to overall total (only for actual project files)
-                if component != "other" {
-                    component_data.entry("total".to_string()).and_modify(|m| {
-                        m.functions_total += total;
-                    });
+                if let Ok(number) = parts[0].parse::<u32>() {
+                    let counts = file.line_branches.entry(number).or_default();
+                    counts.0 += 1;
+                    if taken {
+                        counts.1 += 1;
+                    }
                 }
             }
-        } else if line.starts_with("FNH:") {
-            // Summary line for functions hit
-            if let Ok(hit) = line[4..].parse::<usize>() {
-                let component = detect_component(&current_file);
-
-                // Skip dependencies and test files
-                if component == "dependencies" || component == "test_files" {
-                    continue;
-                }
+        } else if let Some(value) = line.strip_prefix("LF:") {
+            set_summary_total(&mut file.summary_lines, value);
+        } else if let Some(value) = line.strip_prefix("LH:") {
+            set_summary_hit(&mut file.summary_lines, value);
+        } else if let Some(value) = line.strip_prefix("FNF:") {
+            set_summary_total(&mut file.summary_functions, value);
+        } else if let Some(value) = line.strip_prefix("FNH:") {
+            set_summary_hit(&mut file.summary_functions, value);
+        } else if let Some(value) = line.strip_prefix("BRF:") {
+            set_summary_total(&mut file.summary_branches, value);
+        } else if let Some(value) = line.strip_prefix("BRH:") {
+            set_summary_hit(&mut file.summary_branches, value);
+        }
+    }
+
+    // Tolerate a missing trailing end_of_record
+    records.extend(record.take());
+    records
+}
 
-                // Add to component coverage (accumulate from multiple files)
-                component_data.entry(component.clone()).and_modify(|m| {
-                    m.functions_covered += hit;
-                });
+fn set_summary_total(summary: &mut Option<(usize, usize)>, value: &str) {
+    if let Ok(total) = value.trim().parse::<usize>() {
+        summary.get_or_insert((0, 0)).0 = total;
+    }
+}
 
-                // Also add to overall coverage (only for actual project files)
-                if component != "other" {
-                    component_data.entry("total".to_string()).and_modify(|m| {
-                        m.functions_covered += hit;
-                    });
-                }
-            }
-        } else if line.starts_with("BRF:") {
-            // Summary line for branches found
-            if let Ok(total) = line[4..].parse::<usize>() {
-                let component = detect_component(&current_file);
-
-                // Skip dependencies and test files
-                if component == "dependencies" || component == "test_files" {
-                    continue;
-                }
+fn set_summary_hit(summary: &mut Option<(usize, usize)>, value: &str) {
+    if let Ok(hit) = value.trim().parse::<usize>() {
+        summary.get_or_insert((0, 0)).1 = hit;
+    }
+}
 
-                // Add to component totals (accumulate from multiple files)
-                component_data.entry(component.clone()).and_modify(|m| {
-                    m.branches_total += total;
-                });
+/// Add a finished file record to its component and, for project files, to the total
+fn accumulate_file_record(
+    component_data: &mut HashMap<String, ComponentMetrics>,
+    file: &FileRecord,
+) {
+    let component = detect_component(&file.path);
 
-                // Also add to overall total (only for actual project files)
-                if component != "other" {
-                    component_data.entry("total".to_string()).and_modify(|m| {
-                        m.branches_total += total;
-                    });
-                }
-            }
-        } else if line.starts_with("BRH:") {
-            // Summary line for branches hit
-            if let Ok(hit) = line[4..].parse::<usize>() {
-                let component = detect_component(&current_file);
-
-                // Skip dependencies and test files
-                if component == "dependencies" || component == "test_files" {
-                    continue;
-                }
+    // Skip dependencies and test files from coverage metrics
+    if component == "dependencies" || component == "test_files" {
+        return;
+    }
 
-                // Add to component coverage (accumulate from multiple files)
-                component_data.entry(component.clone()).and_modify(|m| {
-                    m.branches_covered += hit;
-                });
+    let (lines_total, lines_covered) = file.lines();
+    let (functions_total, functions_covered) = file.functions();
+    let (branches_total, branches_covered) = file.branches();
+
+    let add = |m: &mut ComponentMetrics| {
+        m.lines_total += lines_total;
+        m.lines_covered += lines_covered;
+        m.functions_total += functions_total;
+        m.functions_covered += functions_covered;
+        m.branches_total += branches_total;
+        m.branches_covered += branches_covered;
+    };
 
-                // Also add to overall coverage (only for actual project files)
-                if component != "other" {
-                    component_data.entry("total".to_string()).and_modify(|m| {
-                        m.branches_covered += hit;
-                    });
-                }
-            }
-        }
+    if let Some(m) = component_data.get_mut(&component) {
+        add(m);
     }
 
-    // Calculate percentages for all coverage types
-    for (name, metrics) in component_data.iter_mut() {
-        if metrics.lines_total > 0 {
-            metrics.line_coverage =
-                (metrics.lines_covered as f64 / metrics.lines_total as f64) * 100.0;
-        }
-        if metrics.functions_total > 0 {
-            metrics.function_coverage =
-                (metrics.functions_covered as f64 / metrics.functions_total as f64) * 100.0;
-        }
-        if metrics.branches_total > 0 {
-            metrics.branch_coverage =
-                (metrics.branches_covered as f64 / metrics.branches_total as f64) * 100.0;
-            eprintln!(
-                "DEBUG: {} branch coverage: {}/{} = {:.1}%",
-                name, metrics.branches_covered, metrics.branches_total, metrics.branch_coverage
-            );
-        } else if name != "total" && name != "other" {
-            eprintln!("DEBUG: {} has no branch data (branches_total=0)", name);
+    // Update total metrics (only for actual project files)
+    if component != "other" {
+        if let Some(m) = component_data.get_mut("total") {
+            add(m);
         }
     }
-
-    Ok(component_data)
 }
 
 /// Check if a file should be excluded from coverage metrics
@@ -367,7 +483,7 @@ pub fn should_exclude_from_coverage(file_path: &str) -> bool {
 }
 
 /// Detect which component a file belongs to
-fn detect_component(file_path: &str) -> String {
+pub(crate) fn detect_component(file_path: &str) -> String {
     // Use centralized exclusion logic
     if should_exclude_from_coverage(file_path) {
         // Determine the specific type of exclusion for categorization
@@ -401,20 +517,68 @@ fn detect_component(file_path: &str) -> String {
     }
 }
 
-/// Get diff-cover metrics if available
-fn get_diff_cover_metrics(_report_dir: &Path) -> Result<HashMap<String, String>> {
-    let mut metrics = HashMap::new();
+/// diff-cover JSON report written next to the HTML report
+const DIFF_COVER_JSON: &str = "diff-coverage.json";
+
+/// Get diff-cover metrics if available, passing at `fail_under` percent
+fn get_diff_cover_metrics(report_dir: &Path, fail_under: f64) -> Result<HashMap<String, String>> {
+    let json_path = report_dir.join(DIFF_COVER_JSON);
+    if !json_path.exists() {
+        return Ok(changed_lines_metrics("N/A", "warning", 0, 0, "Not Run"));
+    }
+
+    let content = fs::read_to_string(&json_path)?;
+    parse_diff_cover_json(&content, fail_under)
+}
 
-    // Set defaults
-    metrics.insert("CHANGED_LINES_COVERAGE".to_string(), "N/A".to_string());
-    metrics.insert("CHANGED_LINES_STATUS".to_string(), "warning".to_string());
-    metrics.insert("CHANGED_LINES_COVERED".to_string(), "0".to_string());
-    metrics.insert("CHANGED_LINES_TOTAL".to_string(), "0".to_string());
-    metrics.insert("CHANGED_LINES_RESULT".to_string(), "No Changes".to_string());
+/// Convert a diff-cover JSON report into `CHANGED_LINES_*` placeholders
+///
+/// Coverage at or above `fail_under` percent passes. A diff with no coverable
+/// changed lines is reported as "No Changes" rather than as 0% coverage.
+fn parse_diff_cover_json(content: &str, fail_under: f64) -> Result<HashMap<String, String>> {
+    let report: serde_json::Value =
+        serde_json::from_str(content).context("Failed to parse diff-cover JSON report")?;
+
+    let total = report["total_num_lines"].as_u64().unwrap_or(0) as usize;
+    if total == 0 {
+        return Ok(changed_lines_metrics("N/A", "pass", 0, 0, "No Changes"));
+    }
 
-    // TODO: Parse diff-cover output if available
+    let violations = report["total_num_violations"].as_u64().unwrap_or(0) as usize;
+    let covered = total.saturating_sub(violations);
+    let percent = report["total_percent_covered"]
+        .as_f64()
+        .unwrap_or((covered as f64 / total as f64) * 100.0);
 
-    Ok(metrics)
+    let (status, result) = if percent >= fail_under {
+        ("pass", "Passed")
+    } else {
+        ("fail", "Failed")
+    };
+
+    Ok(changed_lines_metrics(
+        &format!("{:.1}", percent),
+        status,
+        covered,
+        total,
+        result,
+    ))
+}
+
+fn changed_lines_metrics(
+    coverage: &str,
+    status: &str,
+    covered: usize,
+    total: usize,
+    result: &str,
+) -> HashMap<String, String> {
+    HashMap::from([
+        ("CHANGED_LINES_COVERAGE".to_string(), coverage.to_string()),
+        ("CHANGED_LINES_STATUS".to_string(), status.to_string()),
+        ("CHANGED_LINES_COVERED".to_string(), covered.to_string()),
+        ("CHANGED_LINES_TOTAL".to_string(), total.to_string()),
+        ("CHANGED_LINES_RESULT".to_string(), result.to_string()),
+    ])
 }
 
 /// Get current git commit
@@ -441,6 +605,7 @@ fn get_git_branch() -> Result<String> {
 fn replace_placeholders(
     mut template: String,
     metrics: &HashMap<String, ComponentMetrics>,
+    config: &CoverageConfig,
     changed_lines: &HashMap<String, String>,
     commit: &str,
     branch: &str,
@@ -481,7 +646,8 @@ fn replace_placeholders(
             0.0
         };
 
-        let status = get_status(overall_coverage);
+        let threshold = config.threshold("total");
+        let status = get_status(overall_coverage, threshold);
         template = template.replace("{{TOTAL_COVERAGE}}", &format!("{:.1}", overall_coverage));
         template = template.replace("{{TOTAL_STATUS}}", &status);
         template = template.replace("{{TOTAL_LINES_COVERED}}", &total.lines_covered.to_string());
@@ -493,7 +659,10 @@ fn replace_placeholders(
                 "{{FUNC_COVERAGE}}",
                 &format!("{:.1}", total.function_coverage),
             );
-            template = template.replace("{{FUNC_STATUS}}", &get_status(total.function_coverage));
+            template = template.replace(
+                "{{FUNC_STATUS}}",
+                &get_status(total.function_coverage, threshold),
+            );
             template = template.replace("{{FUNC_COVERED}}", &total.functions_covered.to_string());
             template = template.replace("{{FUNC_TOTAL}}", &total.functions_total.to_string());
         } else {
@@ -509,7 +678,10 @@ fn replace_placeholders(
                 "{{BRANCH_COVERAGE}}",
                 &format!("{:.1}", total.branch_coverage),
             );
-            template = template.replace("{{BRANCH_STATUS}}", &get_status(total.branch_coverage));
+            template = template.replace(
+                "{{BRANCH_STATUS}}",
+                &get_status(total.branch_coverage, threshold),
+            );
             template = template.replace("{{BRANCH_COVERED}}", &total.branches_covered.to_string());
             template = template.replace("{{BRANCH_TOTAL}}", &total.branches_total.to_string());
         } else {
@@ -524,8 +696,9 @@ fn replace_placeholders(
     for component in &["tracer", "tracer_backend", "query_engine"] {
         let prefix = component.to_uppercase().replace("_", "_");
         let comp_metrics = metrics.get(*component).cloned().unwrap_or_default();
-        let status = get_status(comp_metrics.line_coverage);
-        let health = get_health(&comp_metrics);
+        let threshold = config.threshold(component);
+        let status = get_status(comp_metrics.line_coverage, threshold);
+        let health = get_health(&comp_metrics, threshold);
 
         // Special handling for component prefixes
         let template_prefix = match *component {
@@ -568,10 +741,10 @@ fn replace_placeholders(
 }
 
 /// Get status class based on coverage percentage
-fn get_status(coverage: f64) -> String {
-    if coverage >= 80.0 {
+pub(crate) fn get_status(coverage: f64, threshold: Threshold) -> String {
+    if coverage >= threshold.pass {
         "pass".to_string()
-    } else if coverage >= 60.0 {
+    } else if coverage >= threshold.warn {
         "warning".to_string()
     } else {
         "fail".to_string()
@@ -579,10 +752,10 @@ fn get_status(coverage: f64) -> String {
 }
 
 /// Get health status for a component
-fn get_health(metrics: &ComponentMetrics) -> String {
-    if metrics.line_coverage >= 80.0 {
+fn get_health(metrics: &ComponentMetrics, threshold: Threshold) -> String {
+    if metrics.line_coverage >= threshold.pass {
         "Healthy".to_string()
-    } else if metrics.line_coverage >= 60.0 {
+    } else if metrics.line_coverage >= threshold.warn {
         "Needs Work".to_string()
     } else {
         "Critical".to_string()
@@ -630,6 +803,7 @@ fn generate_diff_coverage_report(
     workspace: &Path,
     lcov_path: &Path,
     report_dir: &Path,
+    diff_cover: &DiffCoverSettings,
 ) -> Result<()> {
     println!("  Generating diff-coverage HTML report...");
 
@@ -640,14 +814,21 @@ fn generate_diff_coverage_report(
     }
 
     let diff_report_path = report_dir.join("diff-coverage.html");
+    let diff_json_path = report_dir.join(DIFF_COVER_JSON);
+
+    // Don't let a stale JSON report from an earlier run feed the dashboard
+    let _ = fs::remove_file(&diff_json_path);
 
-    // Run diff-cover with HTML output
+    // Run diff-cover with HTML output plus a JSON report for the dashboard
+    let compare_branch = diff_cover.compare_branch_arg("main");
     let output = Command::new("diff-cover")
         .args(&[
             lcov_path.to_str().unwrap(),
             "--html-report",
             diff_report_path.to_str().unwrap(),
-            "--compare-branch=main",
+            "--json-report",
+            diff_json_path.to_str().unwrap(),
+            compare_branch.as_str(),
             "--ignore-errors",
         ])
         .current_dir(workspace)
@@ -958,3 +1139,270 @@ fn generate_history_report(workspace: &Path, report_dir: &Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::diff_cover::DEFAULT_FAIL_UNDER;
+
+    const SYNTHETIC_LCOV: &str = "\
+SF:/repo/tracer_backend/src/controller/frida_controller.cpp
+FN:10,attach
+FN:20,detach
+FN:30,resume
+FN:40,pause
+FNDA:5,attach
+FNDA:0,detach
+FNDA:1,resume
+FNF:4
+FNH:2
+DA:10,5
+DA:11,0
+BRDA:12,0,0,3
+BRDA:12,0,1,0
+BRDA:14,0,0,-
+BRDA:14,0,1,7
+BRF:4
+BRH:2
+LF:2
+LH:1
+end_of_record
+SF:/repo/query_engine/src/handlers/spans.rs
+FNF:5
+FNH:4
+BRF:10
+BRH:3
+DA:1,1
+end_of_record
+";
+
+    #[test]
+    fn parse_lcov_content__fn_and_brda_records__then_computes_function_and_branch_percentages() {
+        let metrics = parse_lcov_content(SYNTHETIC_LCOV);
+
+        let backend = &metrics["tracer_backend"];
+        // FN defines 4 functions, 2 with FNDA hits > 0; summaries are not added on top
+        assert_eq!((backend.functions_covered, backend.functions_total), (2, 4));
+        assert!((backend.function_coverage - 50.0).abs() < f64::EPSILON);
+        // 4 BRDA records, 2 taken ("-" and "0" are not)
+        assert_eq!((backend.branches_covered, backend.branches_total), (2, 4));
+        assert!((backend.branch_coverage - 50.0).abs() < f64::EPSILON);
+        assert_eq!((backend.lines_covered, backend.lines_total), (1, 2));
+    }
+
+    #[test]
+    fn parse_lcov_content__summary_only_file__then_falls_back_to_summaries() {
+        let metrics = parse_lcov_content(SYNTHETIC_LCOV);
+
+        let query = &metrics["query_engine"];
+        assert_eq!((query.functions_covered, query.functions_total), (4, 5));
+        assert!((query.function_coverage - 80.0).abs() < f64::EPSILON);
+        assert_eq!((query.branches_covered, query.branches_total), (3, 10));
+        assert!((query.branch_coverage - 30.0).abs() < f64::EPSILON);
+
+        let total = &metrics["total"];
+        assert_eq!((total.functions_covered, total.functions_total), (6, 9));
+        assert_eq!((total.branches_covered, total.branches_total), (5, 14));
+    }
+
+    #[test]
+    fn metrics_json__synthetic_lcov__then_has_expected_keys_per_component() {
+        let json = metrics_json(&parse_lcov_content(SYNTHETIC_LCOV), &[], None);
+
+        for name in ["tracer", "tracer_backend", "query_engine"] {
+            let component = &json["components"][name];
+            for kind in ["lines", "functions", "branches"] {
+                for key in ["covered", "total", "percent"] {
+                    assert!(
+                        component[kind].get(key).is_some(),
+                        "missing {}.{}.{}",
+                        name,
+                        kind,
+                        key
+                    );
+                }
+            }
+        }
+        assert!(json["components"].get("total").is_none());
+
+        assert_eq!(
+            json["components"]["tracer_backend"]["functions"]["covered"],
+            2
+        );
+        assert_eq!(
+            json["components"]["tracer_backend"]["functions"]["percent"],
+            50.0
+        );
+        assert_eq!(json["total"]["branches"]["total"], 14);
+        // No data is reported as null rather than 0%
+        assert!(json["components"]["tracer"]["lines"]["percent"].is_null());
+    }
+
+    const MULTI_FILE_LCOV: &str = "\
+SF:/repo/query_engine/src/server/server.rs
+DA:1,1
+DA:2,1
+DA:3,1
+DA:4,0
+end_of_record
+SF:/repo/tracer_backend/src/agent/hooks.cpp
+DA:1,1
+DA:2,0
+DA:3,0
+DA:4,0
+end_of_record
+SF:/repo/query_engine/src/lib.rs
+DA:1,1
+DA:2,1
+end_of_record
+SF:/repo/tracer_backend/src/controller.c
+DA:1,1
+DA:2,0
+end_of_record
+SF:/repo/query_engine/tests/test_server.rs
+DA:1,0
+end_of_record
+";
+
+    #[test]
+    fn metrics_json__multi_file_lcov__then_files_listed_worst_first() {
+        let files = parse_file_metrics(MULTI_FILE_LCOV);
+        let json = metrics_json(&parse_lcov_content(MULTI_FILE_LCOV), &files, None);
+
+        let paths: Vec<&str> = json["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|file| file["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/repo/tracer_backend/src/agent/hooks.cpp",
+                "/repo/tracer_backend/src/controller.c",
+                "/repo/query_engine/src/server/server.rs",
+                "/repo/query_engine/src/lib.rs",
+            ]
+        );
+        assert_eq!(json["files"][0]["lines"]["covered"], 1);
+        assert_eq!(json["files"][0]["lines"]["percent"], 25.0);
+    }
+
+    #[test]
+    fn metrics_json__worst_limit__then_files_truncated() {
+        let files = parse_file_metrics(MULTI_FILE_LCOV);
+        let json = metrics_json(&parse_lcov_content(MULTI_FILE_LCOV), &files, Some(2));
+
+        let listed = json["files"].as_array().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1]["path"], "/repo/tracer_backend/src/controller.c");
+    }
+
+    /// Captured from `diff-cover merged.lcov --json-report ...`
+    const DIFF_COVER_JSON_FIXTURE: &str = r#"{
+        "report_name": "merged.lcov",
+        "diff_name": "origin/main...HEAD, staged and unstaged changes",
+        "src_stats": {
+            "query_engine/src/handlers/spans.rs": {
+                "percent_covered": 75.0,
+                "violation_lines": [42, 57],
+                "covered_lines": [40, 41, 43, 44, 45, 46]
+            }
+        },
+        "total_num_lines": 8,
+        "total_num_violations": 2,
+        "total_percent_covered": 75,
+        "num_changed_lines": 31
+    }"#;
+
+    #[test]
+    fn parse_diff_cover_json__partial_coverage__then_counts_and_fail_verdict() {
+        let metrics = parse_diff_cover_json(DIFF_COVER_JSON_FIXTURE, DEFAULT_FAIL_UNDER).unwrap();
+
+        assert_eq!(metrics["CHANGED_LINES_COVERAGE"], "75.0");
+        assert_eq!(metrics["CHANGED_LINES_COVERED"], "6");
+        assert_eq!(metrics["CHANGED_LINES_TOTAL"], "8");
+        assert_eq!(metrics["CHANGED_LINES_STATUS"], "fail");
+        assert_eq!(metrics["CHANGED_LINES_RESULT"], "Failed");
+    }
+
+    #[test]
+    fn parse_diff_cover_json__no_changed_lines__then_no_changes_not_zero_percent() {
+        let json = r#"{"src_stats": {}, "total_num_lines": 0,
+            "total_num_violations": 0, "num_changed_lines": 0}"#;
+
+        let metrics = parse_diff_cover_json(json, DEFAULT_FAIL_UNDER).unwrap();
+
+        assert_eq!(metrics["CHANGED_LINES_COVERAGE"], "N/A");
+        assert_eq!(metrics["CHANGED_LINES_RESULT"], "No Changes");
+        assert_eq!(metrics["CHANGED_LINES_STATUS"], "pass");
+    }
+
+    #[test]
+    fn parse_diff_cover_json__fully_covered__then_pass_verdict() {
+        let json = r#"{"total_num_lines": 4, "total_num_violations": 0,
+            "total_percent_covered": 100}"#;
+
+        let metrics = parse_diff_cover_json(json, DEFAULT_FAIL_UNDER).unwrap();
+
+        assert_eq!(metrics["CHANGED_LINES_COVERAGE"], "100.0");
+        assert_eq!(metrics["CHANGED_LINES_RESULT"], "Passed");
+    }
+
+    #[test]
+    fn parse_diff_cover_json__coverage_above_lowered_threshold__then_pass_verdict() {
+        let metrics = parse_diff_cover_json(DIFF_COVER_JSON_FIXTURE, 70.0).unwrap();
+
+        assert_eq!(metrics["CHANGED_LINES_STATUS"], "pass");
+        assert_eq!(metrics["CHANGED_LINES_RESULT"], "Passed");
+    }
+
+    #[test]
+    fn replace_placeholders__function_and_branch_data__then_no_na_for_covered_components() {
+        let metrics = parse_lcov_content(SYNTHETIC_LCOV);
+        let template = "{{FUNC_COVERAGE}}|{{BRANCH_COVERAGE}}|{{BACKEND_FUNC_COV}}|\
+{{BACKEND_BRANCH_COV}}|{{QUERY_FUNC_COV}}|{{TRACER_FUNC_COV}}|{{TRACER_BRANCH_COV}}"
+            .to_string();
+
+        let html = replace_placeholders(
+            template,
+            &metrics,
+            &CoverageConfig::default(),
+            &HashMap::new(),
+            "c",
+            "b",
+            "t",
+        );
+
+        assert_eq!(html, "66.7|35.7|50.0|50.0|80.0|N/A|N/A");
+    }
+
+    #[test]
+    fn get_status__custom_threshold__then_uses_component_cutoffs() {
+        let threshold = Threshold {
+            pass: 70.0,
+            warn: 50.0,
+        };
+
+        assert_eq!(get_status(72.0, threshold), "pass");
+        assert_eq!(get_status(55.0, threshold), "warning");
+        assert_eq!(get_status(49.9, threshold), "fail");
+        // Same coverage under the default cutoffs
+        assert_eq!(get_status(72.0, Threshold::default()), "warning");
+    }
+
+    #[test]
+    fn replace_placeholders__configured_component__then_status_and_health_follow_config() {
+        let metrics = parse_lcov_content(SYNTHETIC_LCOV);
+        let config = CoverageConfig::parse("[components.tracer_backend]\npass = 50.0").unwrap();
+        let template = "{{BACKEND_STATUS}}|{{BACKEND_HEALTH}}|{{QUERY_STATUS}}".to_string();
+
+        let html =
+            replace_placeholders(template, &metrics, &config, &HashMap::new(), "c", "b", "t");
+
+        // Backend lines are 1/2 = 50%; query_engine 1/1 = 100%
+        assert_eq!(html, "pass|Healthy|pass");
+    }
+}
@@ -0,0 +1,141 @@
+//! diff-cover settings shared by the dashboard and the coverage scripts
+//!
+//! `ADA_COVERAGE_COMPARE_BRANCH` pins the branch changed lines are measured
+//! against and `ADA_COVERAGE_FAIL_UNDER` sets the coverage they must reach
+//! (0-100, default 100). `coverage_helper diff-cover-args` prints the
+//! resulting flags so `utils/run_coverage.sh` and
+//! `utils/metrics/integration_quality.sh` build them the same way.
+
+use anyhow::{Context, Result};
+use std::env;
+
+/// Branch to compare changed lines against
+pub const COMPARE_BRANCH_ENV: &str = "ADA_COVERAGE_COMPARE_BRANCH";
+
+/// Changed-line coverage percentage required to pass
+pub const FAIL_UNDER_ENV: &str = "ADA_COVERAGE_FAIL_UNDER";
+
+/// Threshold used when `ADA_COVERAGE_FAIL_UNDER` is unset
+pub const DEFAULT_FAIL_UNDER: f64 = 100.0;
+
+/// Resolved `ADA_COVERAGE_*` settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffCoverSettings {
+    /// Explicit compare branch; `None` leaves the choice to the caller
+    pub compare_branch: Option<String>,
+    /// Changed-line coverage required to pass, in percent
+    pub fail_under: f64,
+}
+
+impl DiffCoverSettings {
+    /// Read the settings from the environment
+    pub fn from_env() -> Result<Self> {
+        Self::from_values(
+            env::var(COMPARE_BRANCH_ENV).ok().as_deref(),
+            env::var(FAIL_UNDER_ENV).ok().as_deref(),
+        )
+    }
+
+    /// Settings from raw variable values; empty values count as unset
+    pub fn from_values(compare_branch: Option<&str>, fail_under: Option<&str>) -> Result<Self> {
+        let fail_under = match fail_under.filter(|raw| !raw.is_empty()) {
+            Some(raw) => parse_fail_under(raw)?,
+            None => DEFAULT_FAIL_UNDER,
+        };
+
+        Ok(DiffCoverSettings {
+            compare_branch: compare_branch
+                .filter(|branch| !branch.is_empty())
+                .map(str::to_string),
+            fail_under,
+        })
+    }
+
+    /// `--compare-branch` flag, using `default_branch` unless one was pinned
+    pub fn compare_branch_arg(&self, default_branch: &str) -> String {
+        let branch = self.compare_branch.as_deref().unwrap_or(default_branch);
+        format!("--compare-branch={}", branch)
+    }
+
+    /// `--fail-under` flag
+    pub fn fail_under_arg(&self) -> String {
+        format!("--fail-under={}", self.fail_under)
+    }
+
+    /// Both diff-cover flags, in the order the scripts pass them
+    pub fn args(&self, default_branch: &str) -> Vec<String> {
+        vec![
+            self.compare_branch_arg(default_branch),
+            self.fail_under_arg(),
+        ]
+    }
+}
+
+/// Parse a threshold, rejecting anything outside 0-100
+fn parse_fail_under(raw: &str) -> Result<f64> {
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| (0.0..=100.0).contains(value))
+        .with_context(|| {
+            format!(
+                "{} must be a number from 0 to 100, got '{}'",
+                FAIL_UNDER_ENV, raw
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    #[test]
+    fn from_values__unset__then_defaults() {
+        let settings = DiffCoverSettings::from_values(None, None).unwrap();
+
+        assert_eq!(settings.compare_branch, None);
+        assert_eq!(settings.fail_under, DEFAULT_FAIL_UNDER);
+        assert_eq!(
+            settings.args("main"),
+            vec!["--compare-branch=main", "--fail-under=100"]
+        );
+    }
+
+    #[test]
+    fn from_values__empty_strings__then_treated_as_unset() {
+        let settings = DiffCoverSettings::from_values(Some(""), Some("")).unwrap();
+
+        assert_eq!(
+            settings.args("HEAD~1"),
+            vec!["--compare-branch=HEAD~1", "--fail-under=100"]
+        );
+    }
+
+    #[test]
+    fn from_values__overrides__then_used_in_args() {
+        let settings = DiffCoverSettings::from_values(Some("origin/master"), Some("87.5")).unwrap();
+
+        assert_eq!(
+            settings.args("main"),
+            vec!["--compare-branch=origin/master", "--fail-under=87.5"]
+        );
+    }
+
+    #[test]
+    fn from_values__fail_under_bounds__then_accepted() {
+        for (raw, expected) in [("0", 0.0), ("100", 100.0), ("100.0", 100.0)] {
+            let settings = DiffCoverSettings::from_values(None, Some(raw)).unwrap();
+            assert_eq!(settings.fail_under, expected, "{raw}");
+        }
+    }
+
+    #[test]
+    fn from_values__fail_under_out_of_range_or_not_a_number__then_error() {
+        for raw in ["-1", "100.5", "abc", "NaN", "inf", "80%"] {
+            let err = DiffCoverSettings::from_values(None, Some(raw)).unwrap_err();
+            assert!(err.to_string().contains(FAIL_UNDER_ENV), "{raw}: {err}");
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Source-path exclusion for LCOV files
+//!
+//! Generated files and vendored sources drag the numbers down without being
+//! actionable. Whole file records (from `TN:`/`SF:` through `end_of_record`)
+//! whose `SF:` path matches an exclusion glob are dropped while streaming, so
+//! merged LCOV files are never held in memory.
+
+use anyhow::{Context, Result};
+use glob::Pattern;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Compile exclusion globs, e.g. `*/generated/*`
+///
+/// `*` also matches `/`, so a leading `*/` matches any absolute prefix.
+pub fn compile_patterns(globs: &[String]) -> Result<Vec<Pattern>> {
+    globs
+        .iter()
+        .map(|g| Pattern::new(g).with_context(|| format!("Invalid exclude glob: {}", g)))
+        .collect()
+}
+
+/// Copy LCOV from `reader` to `writer`, dropping excluded file records
+///
+/// Returns the number of file records dropped.
+pub fn filter_lcov<R: BufRead, W: Write>(
+    reader: R,
+    mut writer: W,
+    patterns: &[Pattern],
+) -> Result<usize> {
+    // Lines seen before the record's SF: (e.g. TN:), held until we know
+    // whether the record is kept
+    let mut pending: Vec<String> = Vec::new();
+    let mut skipping = false;
+    let mut dropped = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+
+        if skipping {
+            if line == "end_of_record" {
+                skipping = false;
+            }
+            continue;
+        }
+
+        if let Some(path) = line.strip_prefix("SF:") {
+            if patterns.iter().any(|p| p.matches(path)) {
+                pending.clear();
+                skipping = true;
+                dropped += 1;
+                continue;
+            }
+            for held in pending.drain(..) {
+                writeln!(writer, "{}", held)?;
+            }
+            writeln!(writer, "{}", line)?;
+        } else if line.starts_with("TN:") {
+            pending.push(line);
+        } else {
+            for held in pending.drain(..) {
+                writeln!(writer, "{}", held)?;
+            }
+            writeln!(writer, "{}", line)?;
+        }
+    }
+
+    for held in pending {
+        writeln!(writer, "{}", held)?;
+    }
+    writer.flush()?;
+    Ok(dropped)
+}
+
+/// Filter an LCOV file in place
+pub fn filter_lcov_file(path: &Path, patterns: &[Pattern]) -> Result<usize> {
+    if patterns.is_empty() {
+        return Ok(0);
+    }
+
+    let filtered_path = path.with_extension("lcov.filtered");
+    let reader = BufReader::new(
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?,
+    );
+    let writer = BufWriter::new(
+        File::create(&filtered_path)
+            .with_context(|| format!("Failed to create {}", filtered_path.display()))?,
+    );
+
+    let dropped = filter_lcov(reader, writer, patterns)?;
+    fs::rename(&filtered_path, path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+
+    if dropped > 0 {
+        println!(
+            "  Excluded {} file record(s) from {}",
+            dropped,
+            path.display()
+        );
+    }
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    const LCOV: &str = "\
+TN:
+SF:/ws/tracer_backend/src/ring_buffer.c
+DA:1,1
+end_of_record
+TN:
+SF:/ws/tracer_backend/generated/tracer_types.c
+DA:1,0
+DA:2,0
+BRDA:2,0,0,0
+end_of_record
+SF:/ws/query_engine/src/server.rs
+FN:3,serve
+DA:3,1
+end_of_record
+";
+
+    fn filter(globs: &[&str]) -> (String, usize) {
+        let globs: Vec<String> = globs.iter().map(|g| g.to_string()).collect();
+        let patterns = compile_patterns(&globs).unwrap();
+        let mut output = Vec::new();
+        let dropped = filter_lcov(LCOV.as_bytes(), &mut output, &patterns).unwrap();
+        (String::from_utf8(output).unwrap(), dropped)
+    }
+
+    #[test]
+    fn filter_lcov__generated_glob__then_whole_record_dropped() {
+        let (output, dropped) = filter(&["*/generated/*"]);
+
+        assert_eq!(dropped, 1);
+        assert!(!output.contains("generated"));
+        assert!(!output.contains("DA:2,0"));
+        assert!(!output.contains("BRDA"));
+        assert_eq!(
+            output,
+            "TN:\nSF:/ws/tracer_backend/src/ring_buffer.c\nDA:1,1\nend_of_record\n\
+             SF:/ws/query_engine/src/server.rs\nFN:3,serve\nDA:3,1\nend_of_record\n"
+        );
+    }
+
+    #[test]
+    fn filter_lcov__no_patterns__then_unchanged() {
+        let (output, dropped) = filter(&[]);
+
+        assert_eq!(dropped, 0);
+        assert_eq!(output, LCOV);
+    }
+
+    #[test]
+    fn compile_patterns__invalid_glob__then_error() {
+        assert!(compile_patterns(&["[unclosed".to_string()]).is_err());
+    }
+}
@@ -20,6 +20,27 @@
 //! # Run full coverage workflow
 //! coverage_helper full --format lcov
 //!
+//! # Fail if any component is below its threshold (coverage_thresholds.toml)
+//! coverage_helper full --format html --enforce
+//!
+//! # Export per-component metrics for CI dashboards
+//! coverage_helper metrics --output target/coverage_report/metrics.json
+//!
+//! # Same, listing the 20 least-covered files
+//! coverage_helper metrics --output metrics.json --worst 20
+//!
+//! # Leave generated and vendored sources out of the numbers
+//! coverage_helper collect --exclude '*/generated/*' --exclude '*/vendor/*'
+//!
+//! # Coverage badge for the README
+//! coverage_helper badge --output target/coverage_report/coverage.svg
+//!
+//! # Re-run coverage whenever sources change
+//! coverage_helper watch --debounce-ms 1000
+//!
+//! # Convert to Cobertura XML for GitLab/Azure
+//! coverage_helper cobertura --output target/coverage_report/cobertura.xml
+//!
 //! # Or step by step:
 //! coverage_helper clean
 //! # ... run tests with LLVM_PROFILE_FILE set ...
@@ -57,6 +78,7 @@
 //! If "llvm-profdata not found":
 //! - Install LLVM: `brew install llvm` (macOS) or `apt install llvm` (Linux)
 //! - Or install cargo-llvm-cov: `cargo install cargo-llvm-cov`
+//! - Or point at a custom build: set both `ADA_LLVM_PROFDATA` and `ADA_LLVM_COV`
 //!
 //! If no coverage data generated:
 //! - Ensure code was compiled with coverage flags
@@ -71,8 +93,15 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
 
+mod badge;
+mod cobertura;
 mod dashboard;
+mod diff_cover;
+mod lcov_filter;
+mod test_binaries;
+mod thresholds;
 mod toolchains;
+mod watch;
 
 #[derive(Parser)]
 #[command(name = "coverage_helper")]
@@ -80,6 +109,15 @@ mod toolchains;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Drop LCOV records whose source path matches this glob (repeatable),
+    /// in addition to `exclude` in coverage_thresholds.toml
+    #[arg(long, global = true, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Re-detect LLVM tools instead of using target/coverage/toolchain_cache.json
+    #[arg(long, global = true)]
+    refresh_toolchain: bool,
 }
 
 #[derive(Subcommand)]
@@ -94,11 +132,61 @@ enum Commands {
         #[arg(short, long, default_value = "lcov")]
         format: String,
     },
+    /// Write per-component coverage metrics as JSON
+    Metrics {
+        /// LCOV file to read (defaults to target/coverage_report/merged.lcov)
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// JSON file to write
+        #[arg(short, long)]
+        output: PathBuf,
+        /// List only the N least-covered files
+        #[arg(long, value_name = "N")]
+        worst: Option<usize>,
+    },
+    /// Convert merged LCOV into Cobertura XML for CI ingestion
+    Cobertura {
+        /// LCOV file to read (defaults to target/coverage_report/merged.lcov)
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// XML file to write
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Render a shields-style SVG badge of total line coverage
+    Badge {
+        /// LCOV file to read (defaults to target/coverage_report/merged.lcov)
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// SVG file to write
+        #[arg(short, long)]
+        output: PathBuf,
+    },
     /// Run full coverage workflow (clean, test with coverage, collect, report)
     Full {
         /// Output format for final report
         #[arg(short, long, default_value = "lcov")]
         format: String,
+        /// Exit nonzero if any component is below its pass threshold
+        /// (see coverage_thresholds.toml)
+        #[arg(long)]
+        enforce: bool,
+    },
+    /// Re-run incremental coverage whenever Rust, C++ or Python sources change
+    Watch {
+        /// Wait this long after the last change before running (milliseconds)
+        #[arg(long, default_value = "500")]
+        debounce_ms: u64,
+        /// Exit status of each run also reflects coverage_thresholds.toml
+        #[arg(long)]
+        enforce: bool,
+    },
+    /// Print diff-cover's --compare-branch and --fail-under flags, one per
+    /// line, from ADA_COVERAGE_COMPARE_BRANCH and ADA_COVERAGE_FAIL_UNDER
+    DiffCoverArgs {
+        /// Branch to compare against when ADA_COVERAGE_COMPARE_BRANCH is unset
+        #[arg(long, default_value = "main")]
+        default_branch: String,
     },
 }
 
@@ -107,9 +195,18 @@ fn main() -> Result<()> {
 
     match cli.command {
         Commands::Clean => clean_coverage(),
-        Commands::Collect => collect_coverage(),
-        Commands::Report { format } => generate_report(&format),
-        Commands::Full { format } => {
+        Commands::Collect => collect_coverage(&cli.exclude, cli.refresh_toolchain),
+        Commands::Report { format } => {
+            generate_report(&format, &cli.exclude, cli.refresh_toolchain)
+        }
+        Commands::Metrics {
+            input,
+            output,
+            worst,
+        } => write_metrics(input, &output, worst),
+        Commands::Cobertura { input, output } => write_cobertura(input, &output),
+        Commands::Badge { input, output } => write_badge(input, &output),
+        Commands::Full { format, enforce } => {
             let start = std::time::Instant::now();
 
             println!("[TIMING] Starting full coverage workflow");
@@ -129,14 +226,14 @@ fn main() -> Result<()> {
             );
 
             let collect_start = std::time::Instant::now();
-            collect_coverage()?;
+            collect_coverage(&cli.exclude, cli.refresh_toolchain)?;
             println!(
                 "[TIMING] Coverage collection completed in {:.2}s",
                 collect_start.elapsed().as_secs_f32()
             );
 
             let report_start = std::time::Instant::now();
-            generate_report(&format)?;
+            generate_report(&format, &cli.exclude, cli.refresh_toolchain)?;
             println!(
                 "[TIMING] Report generation completed in {:.2}s",
                 report_start.elapsed().as_secs_f32()
@@ -147,9 +244,48 @@ fn main() -> Result<()> {
                 start.elapsed().as_secs_f32()
             );
 
+            if enforce {
+                enforce_thresholds()?;
+            }
+
             Ok(())
         }
+        Commands::Watch {
+            debounce_ms,
+            enforce,
+        } => {
+            let workspace = get_workspace_root()?;
+            watch::watch(
+                &workspace,
+                std::time::Duration::from_millis(debounce_ms),
+                || run_incremental_coverage(&cli.exclude, cli.refresh_toolchain, enforce),
+            )
+        }
+        Commands::DiffCoverArgs { default_branch } => {
+            for arg in diff_cover::DiffCoverSettings::from_env()?.args(&default_branch) {
+                println!("{}", arg);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Test and collect without rebuilding from scratch
+///
+/// Only profile data is cleaned between runs; build artifacts are kept so
+/// cargo recompiles just what changed.
+fn run_incremental_coverage(
+    exclude: &[String],
+    refresh_toolchain: bool,
+    enforce: bool,
+) -> Result<()> {
+    clean_coverage()?;
+    run_tests_with_coverage()?;
+    collect_coverage(exclude, refresh_toolchain)?;
+    if enforce {
+        enforce_thresholds()?;
     }
+    Ok(())
 }
 
 fn get_workspace_root() -> Result<PathBuf> {
@@ -169,7 +305,19 @@ fn clean_coverage() -> Result<()> {
     let coverage_dir = workspace.join("target").join("coverage");
 
     if coverage_dir.exists() {
-        fs::remove_dir_all(&coverage_dir).context("Failed to remove coverage directory")?;
+        // Keep the toolchain cache; it only depends on the installed tools
+        for entry in fs::read_dir(&coverage_dir)?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if entry.file_name() == toolchains::TOOLCHAIN_CACHE {
+                continue;
+            }
+            if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            }
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
     }
 
     fs::create_dir_all(&coverage_dir).context("Failed to create coverage directory")?;
@@ -209,12 +357,7 @@ fn run_tests_with_coverage() -> Result<()> {
 
     // Run Rust and C/C++ tests with coverage
     let status = Command::new("cargo")
-        .args(&[
-            "test",
-            "--all",
-            "--features",
-            "tracer_backend/coverage,query_engine/coverage",
-        ])
+        .args(test_binaries::COVERAGE_TEST_ARGS)
         .env("CARGO_FEATURE_COVERAGE", "1")
         .status()
         .context("Failed to run tests with coverage")?;
@@ -248,12 +391,20 @@ fn run_tests_with_coverage() -> Result<()> {
     Ok(())
 }
 
-fn collect_coverage() -> Result<()> {
+/// Exclusion globs from the command line plus the thresholds config
+fn exclusion_patterns(workspace: &Path, cli_globs: &[String]) -> Result<Vec<glob::Pattern>> {
+    let config = thresholds::CoverageConfig::load(workspace)?;
+    let globs: Vec<String> = cli_globs.iter().chain(&config.exclude).cloned().collect();
+    lcov_filter::compile_patterns(&globs)
+}
+
+fn collect_coverage(exclude: &[String], refresh_toolchain: bool) -> Result<()> {
     println!("Collecting coverage data...");
 
     let workspace = get_workspace_root()?;
     let coverage_dir = workspace.join("target").join("coverage");
     let report_dir = workspace.join("target").join("coverage_report");
+    let patterns = exclusion_patterns(&workspace, exclude)?;
 
     // Ensure directories exist
     std::fs::create_dir_all(&coverage_dir)?;
@@ -263,7 +414,7 @@ fn collect_coverage() -> Result<()> {
 
     // Collect unified Rust + C++ coverage (they run together via cargo test)
     let unified_lcov = report_dir.join("unified.lcov");
-    match collect_unified_coverage(&workspace, &coverage_dir, &unified_lcov) {
+    match collect_unified_coverage(&workspace, &coverage_dir, &unified_lcov, refresh_toolchain) {
         Ok(_) => {
             lcov_files.push(unified_lcov);
         }
@@ -278,6 +429,11 @@ fn collect_coverage() -> Result<()> {
         lcov_files.push(python_lcov);
     }
 
+    // Drop excluded sources before merging
+    for lcov_file in &lcov_files {
+        lcov_filter::filter_lcov_file(lcov_file, &patterns)?;
+    }
+
     // Always create merged.lcov for consistency (even if just one file)
     let merged_lcov = report_dir.join("merged.lcov");
     if lcov_files.len() > 1 {
@@ -453,6 +609,7 @@ fn collect_unified_coverage(
     workspace: &Path,
     coverage_dir: &Path,
     output_lcov: &Path,
+    refresh_toolchain: bool,
 ) -> Result<()> {
     println!("\nCollecting unified Rust + C++ coverage...");
 
@@ -478,31 +635,15 @@ fn collect_unified_coverage(
     );
 
     // Use Rust toolchain (works for both Rust and C++ with LLVM coverage)
-    let toolchain = toolchains::detect_rust_toolchain()?;
+    let toolchain = toolchains::detect_rust_toolchain(
+        &coverage_dir.join(toolchains::TOOLCHAIN_CACHE),
+        refresh_toolchain,
+    )?;
 
     // Merge all profraw files into a single profdata
     let profdata_path = coverage_dir.join("unified.profdata");
     toolchains::merge_profdata(&toolchain, &profraw_files, &profdata_path)?;
 
-    // Collect all test binaries (both Rust and C++)
-    let mut test_binaries = Vec::new();
-
-    // Find Rust test binaries in deps
-    for entry in WalkDir::new(workspace.join("target"))
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file()
-            && path
-                .to_str()
-                .map_or(false, |s| s.contains("/deps/") && !s.contains("."))
-        {
-            test_binaries.push(path.to_path_buf());
-        }
-    }
-
-    // Find C++ test binaries in the predictable location
     let profile = if workspace
         .join("target/release/tracer_backend/test")
         .exists()
@@ -512,6 +653,39 @@ fn collect_unified_coverage(
         "debug"
     };
 
+    // Collect all test binaries (both Rust and C++), asking cargo which
+    // test executables it built
+    let release = profile == "release";
+    let mut test_binaries = match test_binaries::cargo_test_executables(workspace, release) {
+        Ok(executables) => executables,
+        Err(e) => {
+            eprintln!("Warning: Failed to list test executables: {}", e);
+            Vec::new()
+        }
+    };
+    println!(
+        "  Found {} test executables reported by cargo",
+        test_binaries.len()
+    );
+
+    // Fall back to guessing from file names in deps
+    if test_binaries.is_empty() {
+        for entry in WalkDir::new(workspace.join("target"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if path.is_file()
+                && path
+                    .to_str()
+                    .map_or(false, |s| s.contains("/deps/") && !s.contains("."))
+            {
+                test_binaries.push(path.to_path_buf());
+            }
+        }
+    }
+
+    // Find C++ test binaries in the predictable location
     let cpp_test_dir = workspace
         .join("target")
         .join(profile)
@@ -624,7 +798,7 @@ fn collect_unified_coverage(
 // Note: C++ coverage is now collected as part of unified coverage since
 // C++ tests run via Rust wrappers during 'cargo test'
 
-fn generate_report(format: &str) -> Result<()> {
+fn generate_report(format: &str, exclude: &[String], refresh_toolchain: bool) -> Result<()> {
     println!("Generating {} coverage report...", format);
 
     let workspace = get_workspace_root()?;
@@ -647,6 +821,11 @@ fn generate_report(format: &str) -> Result<()> {
                 return Ok(());
             }
 
+            let patterns = exclusion_patterns(&workspace, exclude)?;
+            for lcov_file in &lcov_files {
+                lcov_filter::filter_lcov_file(lcov_file, &patterns)?;
+            }
+
             let merged_lcov = coverage_dir.join("coverage.lcov");
 
             // Merge lcov files
@@ -678,7 +857,7 @@ fn generate_report(format: &str) -> Result<()> {
             // First ensure we have merged LCOV
             if !merged_lcov.exists() {
                 println!("No merged LCOV found. Running collection first...");
-                collect_coverage()?;
+                collect_coverage(exclude, refresh_toolchain)?;
             }
 
             // Generate dashboard
@@ -723,6 +902,66 @@ fn generate_report(format: &str) -> Result<()> {
     Ok(())
 }
 
+fn enforce_thresholds() -> Result<()> {
+    let workspace = get_workspace_root()?;
+    let merged_lcov = workspace
+        .join("target")
+        .join("coverage_report")
+        .join("merged.lcov");
+    let config = thresholds::CoverageConfig::load(&workspace)?;
+    let metrics = dashboard::parse_lcov_metrics(&merged_lcov)?;
+
+    let failing = thresholds::failing_components(&metrics, &config);
+    if failing.is_empty() {
+        println!("All components meet their coverage thresholds.");
+        return Ok(());
+    }
+
+    for (component, coverage, pass) in &failing {
+        eprintln!(
+            "  {}: {:.1}% line coverage is below the {:.1}% threshold",
+            component, coverage, pass
+        );
+    }
+    anyhow::bail!("{} component(s) below coverage threshold", failing.len())
+}
+
+fn write_metrics(input: Option<PathBuf>, output: &Path, worst: Option<usize>) -> Result<()> {
+    let merged_lcov = match input {
+        Some(path) => path,
+        None => get_workspace_root()?
+            .join("target")
+            .join("coverage_report")
+            .join("merged.lcov"),
+    };
+
+    dashboard::write_metrics_json(&merged_lcov, output, worst)
+}
+
+fn write_cobertura(input: Option<PathBuf>, output: &Path) -> Result<()> {
+    let workspace = get_workspace_root()?;
+    let merged_lcov = input.unwrap_or_else(|| {
+        workspace
+            .join("target")
+            .join("coverage_report")
+            .join("merged.lcov")
+    });
+
+    cobertura::write_cobertura(&merged_lcov, output, &workspace)
+}
+
+fn write_badge(input: Option<PathBuf>, output: &Path) -> Result<()> {
+    let workspace = get_workspace_root()?;
+    let merged_lcov = input.unwrap_or_else(|| {
+        workspace
+            .join("target")
+            .join("coverage_report")
+            .join("merged.lcov")
+    });
+
+    badge::write_badge(&merged_lcov, output, &workspace)
+}
+
 fn calculate_coverage_percentage(lcov_file: &Path) -> Result<()> {
     let content = fs::read_to_string(lcov_file)?;
 
@@ -0,0 +1,117 @@
+//! Discovery of the test executables built by `cargo test`
+//!
+//! Asks cargo itself (`cargo test --no-run --message-format=json`) which
+//! test executables it built for the instrumented targets, instead of
+//! guessing from file names under `target/`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Arguments shared by the coverage test run and discovery, so discovery
+/// resolves to the executables the run already built
+pub const COVERAGE_TEST_ARGS: &[&str] = &[
+    "test",
+    "--all",
+    "--features",
+    "tracer_backend/coverage,query_engine/coverage",
+];
+
+/// The parts of a cargo JSON message needed to find test executables
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    #[serde(default)]
+    profile: Option<ArtifactProfile>,
+    #[serde(default)]
+    executable: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtifactProfile {
+    test: bool,
+}
+
+/// Test executables listed in a cargo `--message-format=json` stream
+///
+/// Lines that aren't JSON (cargo may interleave build script output) are
+/// skipped.
+pub fn parse_test_executables(messages: &str) -> Vec<PathBuf> {
+    let mut executables: Vec<PathBuf> = messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|message| {
+            message.reason == "compiler-artifact"
+                && message.profile.as_ref().is_some_and(|p| p.test)
+        })
+        .filter_map(|message| message.executable)
+        .collect();
+    executables.sort();
+    executables.dedup();
+    executables
+}
+
+/// Build (if needed) and list the workspace's instrumented test executables
+/// for the debug or release profile
+pub fn cargo_test_executables(workspace: &Path, release: bool) -> Result<Vec<PathBuf>> {
+    let mut command = Command::new("cargo");
+    command.args(COVERAGE_TEST_ARGS);
+    if release {
+        command.arg("--release");
+    }
+    let output = command
+        .args(["--no-run", "--message-format=json"])
+        .env("CARGO_FEATURE_COVERAGE", "1")
+        .env("RUSTFLAGS", "-C instrument-coverage")
+        .current_dir(workspace)
+        .output()
+        .context("Failed to run cargo test --no-run")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "cargo test --no-run failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(parse_test_executables(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    const MESSAGES: &str = r#"{"reason":"compiler-artifact","package_id":"path+file:///ws/query_engine#0.1.0","target":{"kind":["lib"],"name":"query_engine","test":true},"profile":{"opt_level":"0","test":false},"executable":null,"fresh":true}
+{"reason":"build-script-executed","package_id":"path+file:///ws/tracer_backend#0.1.0","out_dir":"/ws/target/debug/build/tracer_backend-1/out"}
+{"reason":"compiler-artifact","package_id":"path+file:///ws/query_engine#0.1.0","target":{"kind":["lib"],"name":"query_engine","test":true},"profile":{"opt_level":"0","test":true},"executable":"/ws/target/debug/deps/query_engine-3f2a","fresh":false}
+{"reason":"compiler-artifact","package_id":"path+file:///ws/tracer_backend#0.1.0","target":{"kind":["test"],"name":"test_ring_buffer","test":true},"profile":{"opt_level":"0","test":true},"executable":"/ws/target/debug/deps/test_ring_buffer-9c1d","fresh":false}
+{"reason":"compiler-artifact","package_id":"path+file:///ws/ada-cli#0.1.0","target":{"kind":["bin"],"name":"ada","test":true},"profile":{"opt_level":"0","test":false},"executable":"/ws/target/debug/ada","fresh":false}
+cmake output that is not JSON
+{"reason":"build-finished","success":true}
+"#;
+
+    #[test]
+    fn parse_test_executables__cargo_messages__then_only_test_executables() {
+        let executables = parse_test_executables(MESSAGES);
+
+        assert_eq!(
+            executables,
+            vec![
+                PathBuf::from("/ws/target/debug/deps/query_engine-3f2a"),
+                PathBuf::from("/ws/target/debug/deps/test_ring_buffer-9c1d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_test_executables__no_test_artifacts__then_empty() {
+        let messages = r#"{"reason":"build-finished","success":true}"#;
+
+        assert!(parse_test_executables(messages).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+//! Per-component coverage thresholds
+//!
+//! Thresholds are read from an optional `coverage_thresholds.toml` at the
+//! workspace root. Components without an entry use the default 80% pass /
+//! 60% warn cutoffs:
+//!
+//! ```toml
+//! [components.tracer_backend]
+//! pass = 70.0
+//! warn = 50.0
+//!
+//! [components.total]
+//! pass = 75.0
+//! ```
+//!
+//! The same file can list source globs to leave out of collection, merged
+//! with any `--exclude` options:
+//!
+//! ```toml
+//! exclude = ["*/generated/*", "*/vendor/*"]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::dashboard::ComponentMetrics;
+
+/// Config file name, looked up at the workspace root
+pub const CONFIG_FILE: &str = "coverage_thresholds.toml";
+
+/// Pass/warn cutoffs in percent
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Threshold {
+    /// Coverage at or above this passes
+    pub pass: f64,
+    /// Coverage at or above this (but below `pass`) is a warning
+    pub warn: f64,
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Threshold {
+            pass: 80.0,
+            warn: 60.0,
+        }
+    }
+}
+
+/// Contents of `coverage_thresholds.toml`
+#[derive(Debug, Default, Deserialize)]
+pub struct CoverageConfig {
+    /// Component name -> threshold ("total" applies to the overall numbers)
+    #[serde(default)]
+    pub components: HashMap<String, Threshold>,
+    /// Source path globs dropped from the LCOV before merging
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl CoverageConfig {
+    /// Load the config from the workspace root, falling back to defaults
+    pub fn load(workspace: &Path) -> Result<Self> {
+        let path = workspace.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(CoverageConfig::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Parse config file contents
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Threshold for a component, or the default when not configured
+    pub fn threshold(&self, component: &str) -> Threshold {
+        self.components.get(component).copied().unwrap_or_default()
+    }
+}
+
+/// Components whose line coverage is below their pass threshold
+///
+/// Returns `(component, coverage, pass)` sorted by component name.
+/// Components without any line data are skipped rather than failed.
+pub fn failing_components(
+    metrics: &HashMap<String, ComponentMetrics>,
+    config: &CoverageConfig,
+) -> Vec<(String, f64, f64)> {
+    let mut failing: Vec<(String, f64, f64)> = metrics
+        .iter()
+        .filter(|(name, m)| name.as_str() != "total" && m.lines_total > 0)
+        .filter_map(|(name, m)| {
+            let pass = config.threshold(name).pass;
+            (m.line_coverage < pass).then(|| (name.clone(), m.line_coverage, pass))
+        })
+        .collect();
+
+    failing.sort_by(|a, b| a.0.cmp(&b.0));
+    failing
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    fn component(line_coverage: f64, lines_total: usize) -> ComponentMetrics {
+        ComponentMetrics {
+            line_coverage,
+            lines_total,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse__component_overrides__then_merged_with_defaults() {
+        let config = CoverageConfig::parse(
+            r#"
+            [components.tracer_backend]
+            pass = 70.0
+            warn = 50.0
+
+            [components.query_engine]
+            pass = 90.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.threshold("tracer_backend"),
+            Threshold {
+                pass: 70.0,
+                warn: 50.0
+            }
+        );
+        // Missing warn keeps its default
+        assert_eq!(
+            config.threshold("query_engine"),
+            Threshold {
+                pass: 90.0,
+                warn: 60.0
+            }
+        );
+        assert_eq!(config.threshold("tracer"), Threshold::default());
+    }
+
+    #[test]
+    fn parse__empty__then_defaults() {
+        let config = CoverageConfig::parse("").unwrap();
+        assert_eq!(config.threshold("total"), Threshold::default());
+    }
+
+    #[test]
+    fn parse__exclude_list__then_loaded_alongside_components() {
+        let config = CoverageConfig::parse(
+            r#"
+            exclude = ["*/generated/*"]
+
+            [components.tracer]
+            pass = 50.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.exclude, vec!["*/generated/*"]);
+        assert_eq!(config.threshold("tracer").pass, 50.0);
+    }
+
+    #[test]
+    fn parse__wrong_type__then_error() {
+        assert!(CoverageConfig::parse("[components.tracer]\npass = \"high\"").is_err());
+    }
+
+    #[test]
+    fn failing_components__below_pass__then_reported_without_empty_components() {
+        let config = CoverageConfig::parse("[components.tracer_backend]\npass = 70.0").unwrap();
+        let metrics = HashMap::from([
+            ("tracer_backend".to_string(), component(72.0, 100)),
+            ("query_engine".to_string(), component(79.9, 100)),
+            ("tracer".to_string(), component(0.0, 0)),
+            ("total".to_string(), component(10.0, 200)),
+        ]);
+
+        let failing = failing_components(&metrics, &config);
+
+        assert_eq!(failing, vec![("query_engine".to_string(), 79.9, 80.0)]);
+    }
+}
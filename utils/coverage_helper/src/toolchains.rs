@@ -5,26 +5,44 @@
 //! - C/C++ on macOS: Uses Xcode's LLVM tools via xcrun
 //! - C/C++ on Linux: Uses system LLVM tools
 //! - Python: Delegates to pytest-cov
+//!
+//! Setting both `ADA_LLVM_PROFDATA` and `ADA_LLVM_COV` (e.g. for a custom
+//! LLVM build) bypasses detection entirely.
+//!
+//! Detection results are cached in `target/coverage/toolchain_cache.json`,
+//! keyed by the active rustup toolchain, so repeated runs skip the
+//! rustup/xcrun/which calls while the cached binaries still exist.
 
 use anyhow::{Context, Result};
 use glob;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Cache file name, placed in the coverage data directory
+pub const TOOLCHAIN_CACHE: &str = "toolchain_cache.json";
+
+/// Environment variable overriding the llvm-profdata path
+pub const PROFDATA_ENV: &str = "ADA_LLVM_PROFDATA";
+
+/// Environment variable overriding the llvm-cov path
+pub const COV_ENV: &str = "ADA_LLVM_COV";
+
 /// Represents the detected LLVM toolchain
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LlvmToolchain {
     pub profdata: PathBuf,
     pub cov: PathBuf,
     pub source: ToolchainSource,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ToolchainSource {
     Rustup,   // Rust's bundled LLVM tools
     Xcode,    // macOS Xcode Command Line Tools
     System,   // System-installed LLVM
     Homebrew, // Homebrew-installed LLVM
+    Override, // ADA_LLVM_PROFDATA / ADA_LLVM_COV
 }
 
 impl std::fmt::Display for ToolchainSource {
@@ -34,34 +52,179 @@ impl std::fmt::Display for ToolchainSource {
             Self::Xcode => write!(f, "Xcode Command Line Tools"),
             Self::System => write!(f, "System LLVM"),
             Self::Homebrew => write!(f, "Homebrew LLVM"),
+            Self::Override => write!(f, "{}/{} override", PROFDATA_ENV, COV_ENV),
         }
     }
 }
 
-/// Detect the appropriate LLVM toolchain for Rust code
-pub fn detect_rust_toolchain() -> Result<LlvmToolchain> {
-    println!("Detecting Rust LLVM toolchain...");
+/// Which detection a cache entry belongs to
+#[derive(Debug, Clone, Copy)]
+enum ToolchainKind {
+    Rust,
+    Cpp,
+}
 
-    // Find rustup's LLVM tools
-    let rustup_home = std::env::var("RUSTUP_HOME")
-        .unwrap_or_else(|_| format!("{}/.rustup", std::env::var("HOME").unwrap()));
+/// Detected toolchains for one rustup toolchain
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ToolchainCache {
+    /// Active rustup toolchain the entries were detected under
+    rustup_toolchain: String,
+    rust: Option<LlvmToolchain>,
+    cpp: Option<LlvmToolchain>,
+}
+
+impl ToolchainCache {
+    /// Read the cache, treating a missing or unreadable file as empty
+    fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn entry(&mut self, kind: ToolchainKind) -> &mut Option<LlvmToolchain> {
+        match kind {
+            ToolchainKind::Rust => &mut self.rust,
+            ToolchainKind::Cpp => &mut self.cpp,
+        }
+    }
+
+    /// Cached toolchain, if detected under `rustup_toolchain` and its
+    /// binaries still exist
+    fn lookup(&mut self, rustup_toolchain: &str, kind: ToolchainKind) -> Option<LlvmToolchain> {
+        if self.rustup_toolchain != rustup_toolchain {
+            return None;
+        }
+        self.entry(kind)
+            .clone()
+            .filter(|t| t.profdata.exists() && t.cov.exists())
+    }
+}
+
+/// Return the cached toolchain or run `detect` and record its result
+///
+/// `refresh` ignores the cache. Failing to write the cache is not fatal.
+fn cached_or_detect(
+    cache_path: &Path,
+    rustup_toolchain: &str,
+    kind: ToolchainKind,
+    refresh: bool,
+    detect: impl FnOnce() -> Result<LlvmToolchain>,
+) -> Result<LlvmToolchain> {
+    let mut cache = ToolchainCache::load(cache_path).unwrap_or_default();
+    if !refresh {
+        if let Some(toolchain) = cache.lookup(rustup_toolchain, kind) {
+            println!(
+                "  Using cached {} ({})",
+                toolchain.source,
+                toolchain.cov.display()
+            );
+            return Ok(toolchain);
+        }
+    }
+
+    let toolchain = detect()?;
+
+    if cache.rustup_toolchain != rustup_toolchain {
+        cache = ToolchainCache {
+            rustup_toolchain: rustup_toolchain.to_string(),
+            ..Default::default()
+        };
+    }
+    *cache.entry(kind) = Some(toolchain.clone());
+    if let Err(e) = cache.save(cache_path) {
+        eprintln!("  Warning: Failed to cache toolchain: {}", e);
+    }
+
+    Ok(toolchain)
+}
+
+/// Toolchain from `ADA_LLVM_PROFDATA`/`ADA_LLVM_COV`, when both are set
+/// and exist
+fn override_toolchain() -> Option<LlvmToolchain> {
+    let profdata = PathBuf::from(std::env::var_os(PROFDATA_ENV)?);
+    let cov = PathBuf::from(std::env::var_os(COV_ENV)?);
+
+    for path in [&profdata, &cov] {
+        if !path.exists() {
+            println!(
+                "  Warning: {} does not exist; ignoring {}/{}",
+                path.display(),
+                PROFDATA_ENV,
+                COV_ENV
+            );
+            return None;
+        }
+    }
+
+    Some(LlvmToolchain {
+        profdata,
+        cov,
+        source: ToolchainSource::Override,
+    })
+}
+
+/// Use the environment override if present, otherwise run `detect`
+fn detect_with_override(detect: impl FnOnce() -> Result<LlvmToolchain>) -> Result<LlvmToolchain> {
+    match override_toolchain() {
+        Some(toolchain) => {
+            println!("  Using {}:", toolchain.source);
+            println!("    llvm-profdata: {}", toolchain.profdata.display());
+            println!("    llvm-cov: {}", toolchain.cov.display());
+            Ok(toolchain)
+        }
+        None => detect(),
+    }
+}
 
-    // Use the active toolchain (which should be stable per rust-toolchain.toml)
+/// Name of the active rustup toolchain (should be stable per rust-toolchain.toml)
+fn active_rustup_toolchain() -> Result<String> {
     let output = Command::new("rustup")
         .args(&["show", "active-toolchain"])
         .output()
         .context("Failed to run rustup")?;
 
     let toolchain = String::from_utf8_lossy(&output.stdout);
-    let toolchain_name = toolchain
+    Ok(toolchain
         .split_whitespace()
         .next()
         .context("Failed to parse rustup toolchain")?
-        .to_string();
+        .to_string())
+}
+
+/// Detect the appropriate LLVM toolchain for Rust code
+///
+/// Reuses the entry in `cache_path` unless `refresh` is set.
+pub fn detect_rust_toolchain(cache_path: &Path, refresh: bool) -> Result<LlvmToolchain> {
+    println!("Detecting Rust LLVM toolchain...");
+
+    detect_with_override(|| {
+        let toolchain_name = active_rustup_toolchain()?;
+        cached_or_detect(
+            cache_path,
+            &toolchain_name,
+            ToolchainKind::Rust,
+            refresh,
+            || find_rustup_toolchain(&toolchain_name),
+        )
+    })
+}
+
+/// Locate the LLVM tools bundled with a rustup toolchain
+fn find_rustup_toolchain(toolchain_name: &str) -> Result<LlvmToolchain> {
+    // Find rustup's LLVM tools
+    let rustup_home = std::env::var("RUSTUP_HOME")
+        .unwrap_or_else(|_| format!("{}/.rustup", std::env::var("HOME").unwrap()));
 
     // Verify we have llvm-tools-preview component
     let check_component = Command::new("rustup")
-        .args(&["component", "list", "--toolchain", &toolchain_name])
+        .args(&["component", "list", "--toolchain", toolchain_name])
         .output()
         .context("Failed to check components")?;
 
@@ -77,7 +240,7 @@ pub fn detect_rust_toolchain() -> Result<LlvmToolchain> {
                 "add",
                 "llvm-tools-preview",
                 "--toolchain",
-                &toolchain_name,
+                toolchain_name,
             ])
             .status()
             .context("Failed to install llvm-tools-preview")?;
@@ -119,10 +282,27 @@ pub fn detect_rust_toolchain() -> Result<LlvmToolchain> {
 }
 
 /// Detect the appropriate LLVM toolchain for C/C++ code
+///
+/// Reuses the entry in `cache_path` unless `refresh` is set.
 #[allow(dead_code)]
-pub fn detect_cpp_toolchain() -> Result<LlvmToolchain> {
+pub fn detect_cpp_toolchain(cache_path: &Path, refresh: bool) -> Result<LlvmToolchain> {
     println!("Detecting C/C++ LLVM toolchain...");
 
+    detect_with_override(|| {
+        // Without rustup the cache is still usable, keyed by an empty name
+        let toolchain_name = active_rustup_toolchain().unwrap_or_default();
+        cached_or_detect(
+            cache_path,
+            &toolchain_name,
+            ToolchainKind::Cpp,
+            refresh,
+            find_cpp_toolchain,
+        )
+    })
+}
+
+#[allow(dead_code)]
+fn find_cpp_toolchain() -> Result<LlvmToolchain> {
     // On macOS, prefer Xcode's LLVM tools
     #[cfg(target_os = "macos")]
     {
@@ -602,3 +782,180 @@ fn enhance_lcov_with_coverage_stats(path: &Path) -> Result<()> {
     println!("  Enhanced LCOV with function and branch coverage statistics");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use std::cell::Cell;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Serializes tests that mutate the process-global environment
+    static ENV_MUTEX: Mutex<()> = Mutex::new(());
+
+    fn with_override_env<F, R>(profdata: Option<&Path>, cov: Option<&Path>, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let _guard = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        let original = [PROFDATA_ENV, COV_ENV].map(std::env::var_os);
+
+        for (key, value) in [(PROFDATA_ENV, profdata), (COV_ENV, cov)] {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+
+        let result = f();
+
+        for (key, value) in [PROFDATA_ENV, COV_ENV].into_iter().zip(original) {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+
+        result
+    }
+
+    /// A toolchain whose binaries exist as empty files in `dir`
+    fn fake_toolchain(dir: &Path) -> LlvmToolchain {
+        let profdata = dir.join("llvm-profdata");
+        let cov = dir.join("llvm-cov");
+        std::fs::write(&profdata, b"").unwrap();
+        std::fs::write(&cov, b"").unwrap();
+        LlvmToolchain {
+            profdata,
+            cov,
+            source: ToolchainSource::Rustup,
+        }
+    }
+
+    #[test]
+    fn cached_or_detect__cache_round_trip__then_second_call_skips_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join(TOOLCHAIN_CACHE);
+        let toolchain = fake_toolchain(temp_dir.path());
+        let detections = Cell::new(0);
+        let detect = || {
+            detections.set(detections.get() + 1);
+            Ok(toolchain.clone())
+        };
+
+        let first =
+            cached_or_detect(&cache_path, "stable", ToolchainKind::Rust, false, detect).unwrap();
+        let second =
+            cached_or_detect(&cache_path, "stable", ToolchainKind::Rust, false, detect).unwrap();
+
+        assert_eq!(detections.get(), 1);
+        assert_eq!(first, toolchain);
+        assert_eq!(second, toolchain);
+        let cache = ToolchainCache::load(&cache_path).unwrap();
+        assert_eq!(cache.rustup_toolchain, "stable");
+        assert_eq!(cache.rust, Some(toolchain));
+        assert!(cache.cpp.is_none());
+    }
+
+    #[test]
+    fn cached_or_detect__cached_binary_removed__then_redetects() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join(TOOLCHAIN_CACHE);
+        let stale = fake_toolchain(temp_dir.path());
+        ToolchainCache {
+            rustup_toolchain: "stable".to_string(),
+            rust: Some(stale.clone()),
+            cpp: None,
+        }
+        .save(&cache_path)
+        .unwrap();
+        std::fs::remove_file(&stale.cov).unwrap();
+
+        let fresh_dir = temp_dir.path().join("fresh");
+        std::fs::create_dir(&fresh_dir).unwrap();
+        let fresh = fake_toolchain(&fresh_dir);
+        let detected = cached_or_detect(&cache_path, "stable", ToolchainKind::Rust, false, || {
+            Ok(fresh.clone())
+        })
+        .unwrap();
+
+        assert_eq!(detected, fresh);
+        assert_eq!(ToolchainCache::load(&cache_path).unwrap().rust, Some(fresh));
+    }
+
+    #[test]
+    fn cached_or_detect__different_rustup_toolchain_or_refresh__then_redetects() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join(TOOLCHAIN_CACHE);
+        let toolchain = fake_toolchain(temp_dir.path());
+        let detections = Cell::new(0);
+        let detect = || {
+            detections.set(detections.get() + 1);
+            Ok(toolchain.clone())
+        };
+
+        cached_or_detect(&cache_path, "stable", ToolchainKind::Cpp, false, detect).unwrap();
+        cached_or_detect(&cache_path, "nightly", ToolchainKind::Cpp, false, detect).unwrap();
+        cached_or_detect(&cache_path, "nightly", ToolchainKind::Cpp, true, detect).unwrap();
+
+        assert_eq!(detections.get(), 3);
+        assert_eq!(
+            ToolchainCache::load(&cache_path).unwrap().rustup_toolchain,
+            "nightly"
+        );
+    }
+
+    #[test]
+    fn toolchain_cache_load__corrupt_file__then_none() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join(TOOLCHAIN_CACHE);
+        std::fs::write(&cache_path, "not json").unwrap();
+
+        assert!(ToolchainCache::load(&cache_path).is_none());
+    }
+
+    #[test]
+    fn detect_with_override__both_paths_exist__then_override_skips_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let custom = fake_toolchain(temp_dir.path());
+
+        let detected = with_override_env(Some(&custom.profdata), Some(&custom.cov), || {
+            detect_with_override(|| panic!("detection must not run"))
+        })
+        .unwrap();
+
+        assert_eq!(detected.source, ToolchainSource::Override);
+        assert_eq!(detected.profdata, custom.profdata);
+        assert_eq!(detected.cov, custom.cov);
+    }
+
+    #[test]
+    fn detect_with_override__override_file_missing__then_falls_through_to_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let detected_toolchain = fake_toolchain(temp_dir.path());
+        let missing = temp_dir.path().join("missing-llvm-cov");
+
+        let detected =
+            with_override_env(Some(&detected_toolchain.profdata), Some(&missing), || {
+                detect_with_override(|| Ok(detected_toolchain.clone()))
+            })
+            .unwrap();
+
+        assert_eq!(detected, detected_toolchain);
+    }
+
+    #[test]
+    fn detect_with_override__only_one_variable_set__then_falls_through_to_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let detected_toolchain = fake_toolchain(temp_dir.path());
+
+        let detected = with_override_env(Some(&detected_toolchain.profdata), None, || {
+            detect_with_override(|| Ok(detected_toolchain.clone()))
+        })
+        .unwrap();
+
+        assert_eq!(detected.source, ToolchainSource::Rustup);
+    }
+}
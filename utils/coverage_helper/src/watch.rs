@@ -0,0 +1,233 @@
+//! Re-run coverage when sources change
+//!
+//! `coverage_helper watch` watches the workspace source directories and,
+//! once edits have settled for the debounce window, runs the incremental
+//! coverage workflow and prints a one-line pass/fail. Only Rust, C++ and
+//! Python sources trigger a run, and anything under `target/` (where the
+//! run itself writes) is ignored.
+
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// File extensions whose changes trigger a coverage run
+pub const WATCHED_EXTENSIONS: &[&str] = &["rs", "cpp", "py"];
+
+/// Whether a change to `path` under `workspace` should trigger a coverage run
+///
+/// Only the part of `path` below `workspace` is checked for `target` and
+/// hidden directories, so a workspace that itself lives under one (such as
+/// `~/.worktrees/ada`) still sees its changes.
+pub fn is_relevant(workspace: &Path, path: &Path) -> bool {
+    let relative = path.strip_prefix(workspace).unwrap_or(path);
+    let ignored_dir = relative.components().any(|component| {
+        let name = component.as_os_str();
+        name == "target" || name.to_string_lossy().starts_with('.')
+    });
+    if ignored_dir {
+        return false;
+    }
+
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| WATCHED_EXTENSIONS.contains(&ext))
+}
+
+/// Collapses a burst of changes into a single run once no change has been
+/// seen for `quiet`
+#[derive(Debug)]
+pub struct Debouncer {
+    quiet: Duration,
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(quiet: Duration) -> Self {
+        Self {
+            quiet,
+            last_change: None,
+        }
+    }
+
+    /// Note a relevant change seen at `now`
+    pub fn record(&mut self, now: Instant) {
+        self.last_change = Some(now);
+    }
+
+    /// Whether a run is due at `now`; a due run is consumed
+    pub fn take_due(&mut self, now: Instant) -> bool {
+        match self.last_change {
+            Some(last) if now.duration_since(last) >= self.quiet => {
+                self.last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// How long to wait for further changes before checking again
+    pub fn wait_time(&self, now: Instant) -> Duration {
+        match self.last_change {
+            Some(last) => self.quiet.saturating_sub(now.duration_since(last)),
+            None => self.quiet,
+        }
+    }
+}
+
+/// Top-level workspace directories to watch (everything except `target/`
+/// and hidden directories)
+fn watch_roots(workspace: &Path) -> Result<Vec<PathBuf>> {
+    let mut roots = Vec::new();
+    for entry in fs::read_dir(workspace)
+        .with_context(|| format!("Failed to read {}", workspace.display()))?
+    {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() && name != "target" && !name.starts_with('.') {
+            roots.push(path);
+        }
+    }
+    roots.sort();
+    Ok(roots)
+}
+
+/// Watch `workspace` and call `run` after each settled burst of relevant
+/// changes; runs until the watcher fails or the process is interrupted
+pub fn watch(workspace: &Path, quiet: Duration, mut run: impl FnMut() -> Result<()>) -> Result<()> {
+    // Event paths are canonical, so compare them against a canonical root
+    let workspace = &workspace
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.to_path_buf());
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to create file watcher")?;
+    let roots = watch_roots(workspace)?;
+    for root in &roots {
+        watcher
+            .watch(root, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", root.display()))?;
+    }
+
+    println!(
+        "[WATCH] Watching {} directories for .{} changes (Ctrl-C to stop)",
+        roots.len(),
+        WATCHED_EXTENSIONS.join("/.")
+    );
+
+    let mut debouncer = Debouncer::new(quiet);
+    loop {
+        match rx.recv_timeout(debouncer.wait_time(Instant::now())) {
+            Ok(Ok(event)) => {
+                if event.paths.iter().any(|path| is_relevant(workspace, path)) {
+                    debouncer.record(Instant::now());
+                }
+            }
+            Ok(Err(e)) => eprintln!("Warning: file watcher error: {}", e),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("File watcher stopped unexpectedly")
+            }
+        }
+
+        if debouncer.take_due(Instant::now()) {
+            let start = Instant::now();
+            match run() {
+                Ok(()) => println!("[WATCH] PASS in {:.2}s", start.elapsed().as_secs_f32()),
+                Err(e) => println!(
+                    "[WATCH] FAIL in {:.2}s: {:#}",
+                    start.elapsed().as_secs_f32(),
+                    e
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    #[test]
+    fn is_relevant__source_extensions__then_true() {
+        let ws = Path::new("/ws");
+
+        assert!(is_relevant(ws, Path::new("/ws/ada-cli/src/main.rs")));
+        assert!(is_relevant(
+            ws,
+            Path::new("/ws/tracer_backend/src/agent/hooks.cpp")
+        ));
+        assert!(is_relevant(
+            ws,
+            Path::new("/ws/query_engine/tests/test_api.py")
+        ));
+    }
+
+    #[test]
+    fn is_relevant__other_extensions__then_false() {
+        let ws = Path::new("/ws");
+
+        assert!(!is_relevant(ws, Path::new("/ws/README.md")));
+        assert!(!is_relevant(ws, Path::new("/ws/ada-cli/Cargo.toml")));
+        assert!(!is_relevant(ws, Path::new("/ws/ada-cli/src/main.rs.swp")));
+        assert!(!is_relevant(ws, Path::new("/ws/Makefile")));
+    }
+
+    #[test]
+    fn is_relevant__under_target_or_hidden_dir__then_false() {
+        let ws = Path::new("/ws");
+
+        assert!(!is_relevant(ws, Path::new("/ws/target/debug/build/out.rs")));
+        assert!(!is_relevant(
+            ws,
+            Path::new("/ws/query_engine/target/gen.py")
+        ));
+        assert!(!is_relevant(ws, Path::new("/ws/.git/hooks/pre-commit.py")));
+    }
+
+    #[test]
+    fn is_relevant__workspace_under_hidden_dir__then_only_relative_path_checked() {
+        let ws = Path::new("/Users/dev/.worktrees/target/ada");
+
+        assert!(is_relevant(
+            ws,
+            Path::new("/Users/dev/.worktrees/target/ada/ada-cli/src/main.rs")
+        ));
+        assert!(!is_relevant(
+            ws,
+            Path::new("/Users/dev/.worktrees/target/ada/target/debug/out.rs")
+        ));
+        assert!(!is_relevant(
+            ws,
+            Path::new("/Users/dev/.worktrees/target/ada/.git/hooks/pre-commit.py")
+        ));
+    }
+
+    #[test]
+    fn debouncer__burst_of_changes__then_single_run_after_quiet() {
+        let quiet = Duration::from_millis(300);
+        let mut debouncer = Debouncer::new(quiet);
+        let t0 = Instant::now();
+
+        debouncer.record(t0);
+        debouncer.record(t0 + Duration::from_millis(200));
+
+        assert!(!debouncer.take_due(t0 + Duration::from_millis(400)));
+        assert_eq!(
+            debouncer.wait_time(t0 + Duration::from_millis(400)),
+            Duration::from_millis(100)
+        );
+        assert!(debouncer.take_due(t0 + Duration::from_millis(500)));
+        assert!(!debouncer.take_due(t0 + Duration::from_millis(900)));
+    }
+
+    #[test]
+    fn debouncer__no_changes__then_never_due() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(300));
+
+        assert!(!debouncer.take_due(Instant::now() + Duration::from_secs(10)));
+    }
+}
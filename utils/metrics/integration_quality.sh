@@ -17,6 +17,11 @@ VERBOSE="${VERBOSE:-false}"
 COVERAGE_DIR="${REPO_ROOT}/target/coverage"
 COVERAGE_REPORT_DIR="${REPO_ROOT}/target/coverage_report"
 
+# diff-cover settings
+# Empty compare branch: pick HEAD, HEAD~1 or origin/main from context
+COVERAGE_COMPARE_BRANCH="${ADA_COVERAGE_COMPARE_BRANCH:-}"
+COVERAGE_FAIL_UNDER="${ADA_COVERAGE_FAIL_UNDER:-100}"
+
 # Colors for human output
 RED='\033[0;31m'
 GREEN='\033[0;32m'
@@ -609,6 +614,23 @@ check_incremental_coverage() {
         fi
     fi
 
+    # An explicit branch (e.g. origin/master) overrides the choice above
+    if [[ -n "$COVERAGE_COMPARE_BRANCH" ]]; then
+        compare_branch="$COVERAGE_COMPARE_BRANCH"
+        log_info "Checking coverage against $compare_branch (ADA_COVERAGE_COMPARE_BRANCH)"
+    fi
+
+    # coverage_helper builds the diff-cover flags and rejects a threshold
+    # outside 0-100
+    local diff_cover_flags
+    if ! diff_cover_flags=$(cargo run --quiet --manifest-path "${REPO_ROOT}/utils/coverage_helper/Cargo.toml" \
+            -- diff-cover-args --default-branch "$compare_branch"); then
+        deduct_points 100 "Invalid coverage threshold"
+        return 1
+    fi
+    local diff_cover_args
+    mapfile -t diff_cover_args <<< "$diff_cover_flags"
+
     # Get list of changed production files (excluding test files)
     # Use --cached to check only staged files when checking uncommitted changes
     local diff_command="git diff --name-only"
@@ -629,9 +651,7 @@ check_incremental_coverage() {
 
     # Run diff-cover on the filtered LCOV file
     # Capture output but don't immediately fail
-    diff-cover "$filtered_lcov" \
-               --fail-under=100 \
-               --compare-branch="$compare_branch" 2>&1 | tee "$COVERAGE_OUTPUT"
+    diff-cover "$filtered_lcov" "${diff_cover_args[@]}" 2>&1 | tee "$COVERAGE_OUTPUT"
     local diff_cover_result=${PIPESTATUS[0]}
 
     # Parse diff-cover output to check if only test files are missing coverage
@@ -656,16 +676,17 @@ check_incremental_coverage() {
         fi
     done < "$COVERAGE_OUTPUT"
 
-    if [[ "$production_files_missing_coverage" == "true" ]]; then
+    # Below 100%, files listed with missing lines may still meet the threshold
+    if [[ "$production_files_missing_coverage" == "true" ]] && [[ $diff_cover_result -ne 0 ]]; then
         log_error "Production files lack coverage:"
         echo -e "$coverage_issues"
-        deduct_points 100 "Production code must have 100% test coverage"
+        deduct_points 100 "Production code must have ${COVERAGE_FAIL_UNDER}% test coverage"
         return 1
     elif [[ $diff_cover_result -ne 0 ]]; then
         log_warning "Test files lack coverage (ignored for quality gate)"
     fi
 
-    log_success "All changed lines have 100% coverage"
+    log_success "Changed lines meet the ${COVERAGE_FAIL_UNDER}% coverage threshold"
 }
 
 
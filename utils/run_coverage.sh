@@ -52,9 +52,12 @@ fi
 echo
 echo "Checking coverage on changed lines..."
 if which diff-cover > /dev/null 2>&1; then
+    # Flags come from ADA_COVERAGE_COMPARE_BRANCH and ADA_COVERAGE_FAIL_UNDER;
+    # an out-of-range threshold stops the run here
+    DIFF_COVER_FLAGS=$("$WORKSPACE_ROOT/target/release/coverage_helper" diff-cover-args)
+    mapfile -t DIFF_COVER_ARGS <<< "$DIFF_COVER_FLAGS"
     diff-cover "$WORKSPACE_ROOT/target/coverage_report/merged.lcov" \
-               --compare-branch=main \
-               --fail-under=100 || {
+               "${DIFF_COVER_ARGS[@]}" || {
         echo "Warning: Some changed lines lack coverage"
     }
 else